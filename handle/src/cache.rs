@@ -0,0 +1,172 @@
+use std::{
+    collections::HashMap,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+/// Entries beyond this count trigger a sweep of stale entries
+const CACHE_SWEEP_THRESHOLD: usize = 4096;
+
+/// Most entries kept after a sweep; the oldest go first, even while paused
+const CACHE_MAX_ENTRIES: usize = CACHE_SWEEP_THRESHOLD / 2;
+
+/// Failed reads of an address in a row before it is skipped
+const FAILURE_THRESHOLD: u32 = 3;
+
+/// How long a repeatedly failing address is skipped before it is tried again
+const FAILURE_COOLDOWN: Duration = Duration::from_secs(2);
+
+/// Time keyed cache of process reads, keyed by address and size.
+/// Entries are served until they are older than `interval`, or while paused until the cache
+/// grows past its cap and they are among the oldest.
+/// Addresses whose reads keep failing are throttled so they do not stall every frame.
+pub(crate) struct ReadCache {
+    interval: Duration,
+    paused: bool,
+    entries: HashMap<(u64, usize), (Instant, Vec<u8>)>,
+    // Failures in a row and the time of the last one, per address
+    failures: HashMap<u64, (u32, Instant)>,
+}
+
+impl ReadCache {
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            paused: false,
+            entries: HashMap::new(),
+            failures: HashMap::new(),
+        }
+    }
+
+    pub fn set_interval(&mut self, interval: Duration) {
+        self.interval = interval;
+    }
+
+    pub fn set_paused(&mut self, paused: bool) {
+        self.paused = paused;
+    }
+
+    fn is_fresh(&self, read_at: Instant, now: Instant) -> bool {
+        self.paused || now.duration_since(read_at) < self.interval
+    }
+
+    pub fn lookup(&self, address: u64, len: usize, now: Instant) -> Option<&[u8]> {
+        let (read_at, data) = self.entries.get(&(address, len))?;
+        self.is_fresh(*read_at, now).then_some(data.as_slice())
+    }
+
+    pub fn store(&mut self, address: u64, data: &[u8], now: Instant) {
+        if self.interval.is_zero() && !self.paused {
+            return;
+        }
+        if self.entries.len() >= CACHE_SWEEP_THRESHOLD {
+            let interval = self.interval;
+            let paused = self.paused;
+            self.entries
+                .retain(|_, (read_at, _)| paused || now.duration_since(*read_at) < interval);
+            self.evict_oldest(CACHE_MAX_ENTRIES);
+        }
+        self.entries
+            .insert((address, data.len()), (now, data.to_vec()));
+    }
+
+    /// Drop the oldest entries until at most `max` remain
+    fn evict_oldest(&mut self, max: usize) {
+        let excess = self.entries.len().saturating_sub(max);
+        if excess == 0 {
+            return;
+        }
+        let mut read_times: Vec<Instant> =
+            self.entries.values().map(|(read_at, _)| *read_at).collect();
+        let (_, cutoff, _) = read_times.select_nth_unstable(excess - 1);
+        let cutoff = *cutoff;
+        let mut remaining = excess;
+        self.entries.retain(|_, (read_at, _)| {
+            if remaining > 0 && *read_at <= cutoff {
+                remaining -= 1;
+                return false;
+            }
+            true
+        });
+    }
+
+    /// Drop every entry overlapping `[address, address + len)`
+    pub fn invalidate_range(&mut self, address: u64, len: usize) {
+        let end = address.saturating_add(len as u64);
+        self.entries.retain(|(entry_address, entry_len), _| {
+            let entry_end = entry_address.saturating_add(*entry_len as u64);
+            entry_end <= address || *entry_address >= end
+        });
+    }
+
+    /// Whether `address` failed too often in a row and its cooldown has not passed yet
+    pub fn is_throttled(&self, address: u64, now: Instant) -> bool {
+        self.failures
+            .get(&address)
+            .is_some_and(|(count, failed_at)| {
+                *count >= FAILURE_THRESHOLD && now.duration_since(*failed_at) < FAILURE_COOLDOWN
+            })
+    }
+
+    pub fn record_failure(&mut self, address: u64, now: Instant) {
+        if self.failures.len() >= CACHE_SWEEP_THRESHOLD {
+            self.failures
+                .retain(|_, (_, failed_at)| now.duration_since(*failed_at) < FAILURE_COOLDOWN);
+        }
+        let (count, failed_at) = self.failures.entry(address).or_insert((0, now));
+        *count = count.saturating_add(1);
+        *failed_at = now;
+    }
+
+    pub fn record_success(&mut self, address: u64) {
+        self.failures.remove(&address);
+    }
+
+    /// Number of addresses currently skipped because their reads keep failing
+    pub fn throttled_count(&self, now: Instant) -> usize {
+        self.failures
+            .keys()
+            .filter(|address| self.is_throttled(**address, now))
+            .count()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.failures.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_paused_cache_is_capped() {
+        let mut cache = ReadCache::new(Duration::from_millis(100));
+        cache.set_paused(true);
+        let start = Instant::now();
+        for address in 0..=CACHE_SWEEP_THRESHOLD as u64 {
+            cache.store(address, &[1], start + Duration::from_millis(address));
+        }
+        assert!(cache.entries.len() <= CACHE_MAX_ENTRIES + 1);
+        let later = start + Duration::from_secs(60);
+        // The newest reads survive the sweep and stay frozen while paused
+        assert!(cache
+            .lookup(CACHE_SWEEP_THRESHOLD as u64, 1, later)
+            .is_some());
+        assert!(cache.lookup(0, 1, later).is_none());
+    }
+
+    #[test]
+    fn test_entries_expire_when_running() {
+        let mut cache = ReadCache::new(Duration::from_millis(100));
+        let start = Instant::now();
+        cache.store(0x1000, &[1, 2], start);
+        assert_eq!(cache.lookup(0x1000, 2, start), Some(&[1u8, 2][..]));
+        assert!(cache
+            .lookup(0x1000, 2, start + Duration::from_millis(200))
+            .is_none());
+    }
+}
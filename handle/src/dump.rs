@@ -0,0 +1,329 @@
+use std::{
+    fs::File,
+    io::{
+        Read,
+        Seek,
+        SeekFrom,
+    },
+    path::Path,
+    sync::Mutex,
+};
+
+use anyhow::Context;
+
+const MINIDUMP_SIGNATURE: &[u8; 4] = b"MDMP";
+const MEMORY_LIST_STREAM: u32 = 5;
+const SYSTEM_INFO_STREAM: u32 = 7;
+const MEMORY64_LIST_STREAM: u32 = 9;
+
+const PROCESSOR_ARCHITECTURE_INTEL: u16 = 0;
+const PROCESSOR_ARCHITECTURE_ARM: u16 = 5;
+
+/// Range of process memory stored in a dump file
+#[derive(Debug, Clone, Copy)]
+struct DumpRegion {
+    address: u64,
+    size: u64,
+    file_offset: u64,
+}
+
+/// Readable and seekable bytes of a dump, a file outside of tests
+trait DumpReader: Read + Seek + Send {}
+
+impl<T: Read + Seek + Send> DumpReader for T {}
+
+/// Process memory captured in a file. Addresses are translated to file offsets through the
+/// dump's region table; the file is never written.
+pub struct FileHandle {
+    file: Mutex<Box<dyn DumpReader>>,
+    // Sorted by address, not overlapping
+    regions: Vec<DumpRegion>,
+    pointer_size: u64,
+}
+
+impl FileHandle {
+    /// Open a minidump, or a raw dump whose first byte was at `base_address`
+    pub fn open(path: &Path, base_address: u64, pointer_size: u64) -> anyhow::Result<Self> {
+        let mut file =
+            File::open(path).with_context(|| format!("failed to open {}", path.display()))?;
+        let mut signature = [0u8; 4];
+        let is_minidump =
+            file.read_exact(&mut signature).is_ok() && &signature == MINIDUMP_SIGNATURE;
+        if is_minidump {
+            let (regions, pointer_size) = minidump_layout(&mut file).context("invalid minidump")?;
+            return Ok(Self::new(file, regions, pointer_size));
+        }
+        let size = file.metadata()?.len();
+        let region = DumpRegion {
+            address: base_address,
+            size,
+            file_offset: 0,
+        };
+        Ok(Self::new(file, vec![region], pointer_size))
+    }
+
+    fn new(
+        file: impl Read + Seek + Send + 'static,
+        mut regions: Vec<DumpRegion>,
+        pointer_size: u64,
+    ) -> Self {
+        regions.retain(|region| region.size > 0);
+        regions.sort_by_key(|region| region.address);
+        Self {
+            file: Mutex::new(Box::new(file)),
+            regions,
+            pointer_size,
+        }
+    }
+
+    /// Pointer size of the dumped process: from the minidump's system info, else as given
+    pub fn pointer_size(&self) -> u64 {
+        self.pointer_size
+    }
+
+    /// `(address, size)` of every memory range in the dump
+    pub fn regions(&self) -> Vec<(u64, u64)> {
+        self.regions.iter().map(|r| (r.address, r.size)).collect()
+    }
+
+    /// Fill `buffer` from `address`; reads may span adjacent regions but fail on any gap
+    pub fn read(&self, address: u64, buffer: &mut [u8]) -> anyhow::Result<()> {
+        let mut file = self
+            .file
+            .lock()
+            .map_err(|_| anyhow::anyhow!("dump file lock poisoned"))?;
+        let mut done = 0usize;
+        while done < buffer.len() {
+            let at = address
+                .checked_add(done as u64)
+                .context("address out of range")?;
+            let index = self.regions.partition_point(|r| r.address <= at);
+            let region = index
+                .checked_sub(1)
+                .map(|i| self.regions[i])
+                .filter(|r| at - r.address < r.size)
+                .with_context(|| format!("0x{at:X} is not in the dump"))?;
+            let within = at - region.address;
+            let len = (region.size - within).min((buffer.len() - done) as u64) as usize;
+            let offset = region
+                .file_offset
+                .checked_add(within)
+                .with_context(|| format!("0x{at:X} lies past the end of the dump file"))?;
+            file.seek(SeekFrom::Start(offset))?;
+            file.read_exact(&mut buffer[done..done + len])?;
+            done += len;
+        }
+        Ok(())
+    }
+}
+
+fn read_u16<R: Read>(reader: &mut R) -> anyhow::Result<u16> {
+    let mut bytes = [0u8; 2];
+    reader.read_exact(&mut bytes)?;
+    Ok(u16::from_le_bytes(bytes))
+}
+
+fn read_u32<R: Read>(reader: &mut R) -> anyhow::Result<u32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_u64<R: Read>(reader: &mut R) -> anyhow::Result<u64> {
+    let mut bytes = [0u8; 8];
+    reader.read_exact(&mut bytes)?;
+    Ok(u64::from_le_bytes(bytes))
+}
+
+/// Memory ranges and pointer size of a minidump, from its memory list streams and its system
+/// info stream. Full-memory dumps use the 64-bit list, whose ranges follow each other in the
+/// file from a single base offset.
+fn minidump_layout<R: Read + Seek>(reader: &mut R) -> anyhow::Result<(Vec<DumpRegion>, u64)> {
+    reader.seek(SeekFrom::Start(8))?;
+    let stream_count = read_u32(reader)?;
+    let directory = read_u32(reader)?;
+
+    let mut streams = Vec::new();
+    reader.seek(SeekFrom::Start(u64::from(directory)))?;
+    for _ in 0..stream_count {
+        let stream_type = read_u32(reader)?;
+        let _size = read_u32(reader)?;
+        let rva = read_u32(reader)?;
+        streams.push((stream_type, u64::from(rva)));
+    }
+
+    let mut regions = Vec::new();
+    let mut pointer_size = 8;
+    for (stream_type, rva) in streams {
+        reader.seek(SeekFrom::Start(rva))?;
+        match stream_type {
+            MEMORY_LIST_STREAM => {
+                let count = read_u32(reader)?;
+                for _ in 0..count {
+                    let address = read_u64(reader)?;
+                    let size = read_u32(reader)?;
+                    let file_offset = read_u32(reader)?;
+                    regions.push(DumpRegion {
+                        address,
+                        size: u64::from(size),
+                        file_offset: u64::from(file_offset),
+                    });
+                }
+            }
+            MEMORY64_LIST_STREAM => {
+                let count = read_u64(reader)?;
+                let mut file_offset = read_u64(reader)?;
+                for _ in 0..count {
+                    let address = read_u64(reader)?;
+                    let size = read_u64(reader)?;
+                    regions.push(DumpRegion {
+                        address,
+                        size,
+                        file_offset,
+                    });
+                    file_offset = file_offset
+                        .checked_add(size)
+                        .context("memory ranges run past the end of the file")?;
+                }
+            }
+            SYSTEM_INFO_STREAM => {
+                pointer_size = match read_u16(reader)? {
+                    PROCESSOR_ARCHITECTURE_INTEL | PROCESSOR_ARCHITECTURE_ARM => 4,
+                    _ => 8,
+                };
+            }
+            _ => {}
+        }
+    }
+    if regions.is_empty() {
+        anyhow::bail!("the dump holds no memory");
+    }
+    Ok((regions, pointer_size))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Cursor;
+
+    use super::*;
+
+    /// A minidump with a 32-bit memory list range at 0x5000, two adjacent 64-bit list ranges at
+    /// 0x1000 and 0x1004 and an x86 system info stream; memory bytes count up from 0xA0
+    fn minidump_bytes() -> Vec<u8> {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(MINIDUMP_SIGNATURE);
+        bytes.extend_from_slice(&0u32.to_le_bytes());
+        bytes.extend_from_slice(&3u32.to_le_bytes());
+        bytes.extend_from_slice(&32u32.to_le_bytes());
+        bytes.resize(32, 0);
+        for (stream_type, rva) in [
+            (MEMORY_LIST_STREAM, 68u32),
+            (MEMORY64_LIST_STREAM, 88),
+            (SYSTEM_INFO_STREAM, 136),
+        ] {
+            bytes.extend_from_slice(&stream_type.to_le_bytes());
+            bytes.extend_from_slice(&0u32.to_le_bytes());
+            bytes.extend_from_slice(&rva.to_le_bytes());
+        }
+        bytes.extend_from_slice(&1u32.to_le_bytes());
+        bytes.extend_from_slice(&0x5000u64.to_le_bytes());
+        bytes.extend_from_slice(&4u32.to_le_bytes());
+        bytes.extend_from_slice(&140u32.to_le_bytes());
+        bytes.extend_from_slice(&2u64.to_le_bytes());
+        bytes.extend_from_slice(&144u64.to_le_bytes());
+        for address in [0x1000u64, 0x1004] {
+            bytes.extend_from_slice(&address.to_le_bytes());
+            bytes.extend_from_slice(&4u64.to_le_bytes());
+        }
+        bytes.extend_from_slice(&PROCESSOR_ARCHITECTURE_INTEL.to_le_bytes());
+        bytes.resize(140, 0);
+        bytes.extend(0xA0u8..0xAC);
+        bytes
+    }
+
+    fn dump_handle() -> FileHandle {
+        let mut reader = Cursor::new(minidump_bytes());
+        let (regions, pointer_size) = minidump_layout(&mut reader).unwrap();
+        FileHandle::new(reader, regions, pointer_size)
+    }
+
+    #[test]
+    fn test_minidump_layout() {
+        let (regions, pointer_size) = minidump_layout(&mut Cursor::new(minidump_bytes())).unwrap();
+        assert_eq!(pointer_size, 4);
+        let layout: Vec<(u64, u64, u64)> = regions
+            .iter()
+            .map(|r| (r.address, r.size, r.file_offset))
+            .collect();
+        assert_eq!(
+            layout,
+            vec![(0x5000, 4, 140), (0x1000, 4, 144), (0x1004, 4, 148)]
+        );
+    }
+
+    #[test]
+    fn test_minidump_without_memory_is_rejected() {
+        let mut bytes = minidump_bytes();
+        // Only the system info stream remains in the directory
+        bytes[8..12].copy_from_slice(&1u32.to_le_bytes());
+        bytes[32..44].copy_from_slice(&[7, 0, 0, 0, 0, 0, 0, 0, 136, 0, 0, 0]);
+        assert!(minidump_layout(&mut Cursor::new(bytes)).is_err());
+    }
+
+    #[test]
+    fn test_minidump_with_oversized_range_is_rejected() {
+        let mut bytes = minidump_bytes();
+        // Size of the second 64-bit list range, pushing the next file offset past u64::MAX
+        bytes[128..136].copy_from_slice(&u64::MAX.to_le_bytes());
+        assert!(minidump_layout(&mut Cursor::new(bytes)).is_err());
+    }
+
+    #[test]
+    fn test_read_spans_adjacent_regions() {
+        let handle = dump_handle();
+        assert_eq!(
+            handle.regions(),
+            vec![(0x1000, 4), (0x1004, 4), (0x5000, 4)]
+        );
+        let mut buffer = [0u8; 4];
+        handle.read(0x1002, &mut buffer).unwrap();
+        assert_eq!(buffer, [0xA6, 0xA7, 0xA8, 0xA9]);
+        handle.read(0x5000, &mut buffer).unwrap();
+        assert_eq!(buffer, [0xA0, 0xA1, 0xA2, 0xA3]);
+    }
+
+    #[test]
+    fn test_read_fails_on_gaps() {
+        let handle = dump_handle();
+        let mut buffer = [0u8; 4];
+        assert!(handle.read(0x1006, &mut buffer).is_err());
+        assert!(handle.read(0xFFE, &mut buffer).is_err());
+        assert!(handle.read(0x3000, &mut buffer).is_err());
+    }
+
+    #[test]
+    fn test_raw_dump_starts_at_base_address() {
+        let region = DumpRegion {
+            address: 0x40_0000,
+            size: 3,
+            file_offset: 0,
+        };
+        let handle = FileHandle::new(Cursor::new(vec![1u8, 2, 3]), vec![region], 8);
+        let mut buffer = [0u8; 2];
+        handle.read(0x40_0001, &mut buffer).unwrap();
+        assert_eq!(buffer, [2, 3]);
+        assert!(handle.read(0x40_0002, &mut buffer).is_err());
+    }
+
+    #[test]
+    fn test_read_fails_on_file_offset_overflow() {
+        let region = DumpRegion {
+            address: 0x1000,
+            size: 4,
+            file_offset: u64::MAX - 1,
+        };
+        let handle = FileHandle::new(Cursor::new(vec![0u8; 4]), vec![region], 8);
+        let mut buffer = [0u8; 2];
+        assert!(handle.read(0x1002, &mut buffer).is_err());
+    }
+}
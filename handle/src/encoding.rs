@@ -0,0 +1,171 @@
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+/// Character encoding of a string in process memory
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum TextEncoding {
+    /// Also reads plain ASCII; invalid sequences show as U+FFFD
+    #[default]
+    Utf8,
+    Ascii,
+    Utf16Le,
+    Utf16Be,
+    /// Windows code page 932, common in Japanese games and legacy applications
+    ShiftJis,
+}
+
+impl TextEncoding {
+    pub const ALL: [TextEncoding; 5] = [
+        TextEncoding::Utf8,
+        TextEncoding::Ascii,
+        TextEncoding::Utf16Le,
+        TextEncoding::Utf16Be,
+        TextEncoding::ShiftJis,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Ascii => "ASCII",
+            TextEncoding::Utf16Le => "UTF-16 LE",
+            TextEncoding::Utf16Be => "UTF-16 BE",
+            TextEncoding::ShiftJis => "Shift-JIS",
+        }
+    }
+
+    /// Bytes per code unit; the null terminator is one unit wide
+    pub fn unit_size(self) -> usize {
+        match self {
+            TextEncoding::Utf16Le | TextEncoding::Utf16Be => 2,
+            _ => 1,
+        }
+    }
+
+    /// Decode `bytes` up to the first null terminator. Bytes that do not form a character
+    /// become U+FFFD.
+    pub fn decode(self, bytes: &[u8]) -> String {
+        match self {
+            TextEncoding::Utf16Le | TextEncoding::Utf16Be => {
+                let units: Vec<u16> = bytes
+                    .chunks_exact(2)
+                    .map(|pair| match self {
+                        TextEncoding::Utf16Be => u16::from_be_bytes([pair[0], pair[1]]),
+                        _ => u16::from_le_bytes([pair[0], pair[1]]),
+                    })
+                    .take_while(|unit| *unit != 0)
+                    .collect();
+                String::from_utf16_lossy(&units)
+            }
+            _ => {
+                // No multi-byte sequence of these encodings contains a zero byte
+                let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+                let bytes = &bytes[..end];
+                match self {
+                    TextEncoding::Ascii => bytes
+                        .iter()
+                        .map(|b| match b {
+                            0..=0x7F => char::from(*b),
+                            _ => char::REPLACEMENT_CHARACTER,
+                        })
+                        .collect(),
+                    TextEncoding::ShiftJis => decode_shift_jis(bytes),
+                    _ => String::from_utf8_lossy(bytes).into_owned(),
+                }
+            }
+        }
+    }
+}
+
+/// Code page 932 through the OS conversion table, which covers every double-byte character
+#[cfg(windows)]
+fn decode_shift_jis(bytes: &[u8]) -> String {
+    use windows_sys::Win32::Globalization::MultiByteToWideChar;
+
+    const CP_SHIFT_JIS: u32 = 932;
+    let Ok(len) = i32::try_from(bytes.len()) else {
+        return decode_shift_jis_single_byte(bytes);
+    };
+    if len == 0 {
+        return String::new();
+    }
+    // SAFETY: the input slice is `len` bytes long and the output buffer holds the number of
+    // UTF-16 units the first call asked for
+    let units = unsafe {
+        let needed = MultiByteToWideChar(
+            CP_SHIFT_JIS,
+            0,
+            bytes.as_ptr(),
+            len,
+            std::ptr::null_mut(),
+            0,
+        );
+        if needed <= 0 {
+            return decode_shift_jis_single_byte(bytes);
+        }
+        let mut units = vec![0u16; needed as usize];
+        let written = MultiByteToWideChar(
+            CP_SHIFT_JIS,
+            0,
+            bytes.as_ptr(),
+            len,
+            units.as_mut_ptr(),
+            needed,
+        );
+        units.truncate(written.max(0) as usize);
+        units
+    };
+    String::from_utf16_lossy(&units)
+}
+
+#[cfg(not(windows))]
+fn decode_shift_jis(bytes: &[u8]) -> String {
+    decode_shift_jis_single_byte(bytes)
+}
+
+/// ASCII and half-width katakana; double-byte characters need the OS table and show as U+FFFD
+fn decode_shift_jis_single_byte(bytes: &[u8]) -> String {
+    let mut out = String::with_capacity(bytes.len());
+    let mut iter = bytes.iter().copied();
+    while let Some(byte) = iter.next() {
+        match byte {
+            0..=0x7F => out.push(char::from(byte)),
+            0xA1..=0xDF => {
+                out.push(char::from_u32(0xFF61 + u32::from(byte - 0xA1)).unwrap_or_default())
+            }
+            0x81..=0x9F | 0xE0..=0xFC => {
+                // Lead byte: the trail byte belongs to the same character
+                iter.next();
+                out.push(char::REPLACEMENT_CHARACTER);
+            }
+            _ => out.push(char::REPLACEMENT_CHARACTER),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_text_encoding_decodes_until_terminator() {
+        assert_eq!(TextEncoding::Utf8.decode("héllo\0junk".as_bytes()), "héllo");
+        assert_eq!(TextEncoding::Ascii.decode(b"ab\xE9c\0d"), "ab\u{FFFD}c");
+        assert_eq!(
+            TextEncoding::Utf16Le.decode(&[b'h', 0, b'i', 0, 0, 0, b'x', 0]),
+            "hi"
+        );
+        assert_eq!(
+            TextEncoding::Utf16Be.decode(&[0, b'h', 0x30, 0x42, 0, 0]),
+            "h\u{3042}"
+        );
+        // Half-width katakana are single bytes
+        assert_eq!(
+            TextEncoding::ShiftJis.decode(b"A\xB1\xB2\0"),
+            "A\u{FF71}\u{FF72}"
+        );
+        assert_eq!(TextEncoding::ShiftJis.decode(b""), "");
+    }
+}
@@ -0,0 +1,223 @@
+use crate::AppHandle;
+
+/// Largest export directory we are willing to read in one go
+const MAX_EXPORT_DIRECTORY_SIZE: u32 = 16 * 1024 * 1024;
+
+/// Longest export name kept; longer ones are cut
+const MAX_EXPORT_NAME: usize = 256;
+
+/// Addresses further than this past the closest export are not attributed to it
+const MAX_EXPORT_DISPLACEMENT: u64 = 0x1000;
+
+/// `IMAGE_OPTIONAL_HEADER32.Magic`
+const OPTIONAL_HEADER_MAGIC_PE32: u16 = 0x10B;
+/// `IMAGE_OPTIONAL_HEADER64.Magic`
+const OPTIONAL_HEADER_MAGIC_PE32_PLUS: u16 = 0x20B;
+
+/// Named exports of a module, sorted by image relative address
+#[derive(Debug, Default)]
+pub struct ModuleExports {
+    symbols: Vec<(u32, String)>,
+}
+
+impl ModuleExports {
+    /// Export at or right before `rva` and the distance to it
+    pub fn lookup(&self, rva: u64) -> Option<(&str, u64)> {
+        let index = self
+            .symbols
+            .partition_point(|(symbol_rva, _)| u64::from(*symbol_rva) <= rva)
+            .checked_sub(1)?;
+        let (symbol_rva, name) = &self.symbols[index];
+        let displacement = rva - u64::from(*symbol_rva);
+        (displacement <= MAX_EXPORT_DISPLACEMENT).then_some((name.as_str(), displacement))
+    }
+
+    /// Parse the export directory of the PE image mapped at `base`. Every offset comes from
+    /// process memory, so anything out of range ends the parse instead of being trusted.
+    fn read(handle: &AppHandle, base: u64, module_size: u64) -> Option<Self> {
+        let mut dos_header = [0u8; 0x40];
+        handle.read_uncached(base, &mut dos_header).ok()?;
+        if &dos_header[..2] != b"MZ" {
+            return None;
+        }
+        let nt_offset = u64::from(u32_at(&dos_header, 0x3C)?);
+        // Signature, COFF header and the optional header up to the end of the export entry
+        let mut nt_headers = [0u8; 0x18 + 0x70 + 8];
+        if nt_offset.checked_add(nt_headers.len() as u64)? > module_size {
+            return None;
+        }
+        handle
+            .read_uncached(base + nt_offset, &mut nt_headers)
+            .ok()?;
+        if &nt_headers[..4] != b"PE\0\0" {
+            return None;
+        }
+        // The data directories follow the fixed part of the optional header
+        let export_entry = match u16_at(&nt_headers, 0x18)? {
+            OPTIONAL_HEADER_MAGIC_PE32 => 0x18 + 0x60,
+            OPTIONAL_HEADER_MAGIC_PE32_PLUS => 0x18 + 0x70,
+            _ => return None,
+        };
+        let directory_rva = u32_at(&nt_headers, export_entry)?;
+        let directory_size = u32_at(&nt_headers, export_entry + 4)?;
+        // The fixed part of `IMAGE_EXPORT_DIRECTORY` is 40 bytes
+        if directory_rva == 0 || !(40..=MAX_EXPORT_DIRECTORY_SIZE).contains(&directory_size) {
+            return Some(Self::default());
+        }
+        if u64::from(directory_rva) + u64::from(directory_size) > module_size {
+            return None;
+        }
+
+        // The directory, its tables and the name strings normally all lie within the range the
+        // data directory entry covers, so one read gets everything
+        let mut directory = vec![0u8; directory_size as usize];
+        handle
+            .read_uncached(base + u64::from(directory_rva), &mut directory)
+            .ok()?;
+        Self::parse(&directory, directory_rva)
+    }
+
+    /// Parse an `IMAGE_EXPORT_DIRECTORY` read from `directory_rva`, together with the tables
+    /// and names that follow it. Entries pointing outside `directory` are skipped and a table
+    /// cut short ends the parse, keeping the exports found so far.
+    fn parse(directory: &[u8], directory_rva: u32) -> Option<Self> {
+        let at = |rva: u32| rva.checked_sub(directory_rva).map(|offset| offset as usize);
+        let directory_end = directory_rva.saturating_add(directory.len() as u32);
+
+        let function_count = u32_at(directory, 0x14)?;
+        let name_count = u32_at(directory, 0x18)?;
+        let functions = at(u32_at(directory, 0x1C)?)?;
+        let names = at(u32_at(directory, 0x20)?)?;
+        let ordinals = at(u32_at(directory, 0x24)?)?;
+
+        let mut symbols = Vec::new();
+        for index in 0..name_count as usize {
+            let Some(ordinal) = u16_at(directory, ordinals + index * 2) else {
+                break;
+            };
+            let Some(name_rva) = u32_at(directory, names + index * 4) else {
+                break;
+            };
+            if u32::from(ordinal) >= function_count {
+                continue;
+            }
+            let Some(function_rva) = u32_at(directory, functions + usize::from(ordinal) * 4) else {
+                continue;
+            };
+            // Forwarded exports point at a "module.function" string inside the directory
+            if (directory_rva..directory_end).contains(&function_rva) {
+                continue;
+            }
+            let Some(name) = at(name_rva).and_then(|offset| c_string_at(directory, offset)) else {
+                continue;
+            };
+            symbols.push((function_rva, name));
+        }
+        symbols.sort_by_key(|(rva, _)| *rva);
+        Some(Self { symbols })
+    }
+}
+
+fn u16_at(bytes: &[u8], offset: usize) -> Option<u16> {
+    let bytes = bytes.get(offset..offset.checked_add(2)?)?;
+    Some(u16::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn u32_at(bytes: &[u8], offset: usize) -> Option<u32> {
+    let bytes = bytes.get(offset..offset.checked_add(4)?)?;
+    Some(u32::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn c_string_at(bytes: &[u8], offset: usize) -> Option<String> {
+    let bytes = bytes.get(offset..)?;
+    let bytes = &bytes[..bytes.len().min(MAX_EXPORT_NAME)];
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    (end > 0).then(|| String::from_utf8_lossy(&bytes[..end]).into_owned())
+}
+
+impl AppHandle {
+    /// Export table of the module mapped at `base`; empty when it cannot be read
+    pub fn read_module_exports(&self, base: u64, module_size: u64) -> ModuleExports {
+        ModuleExports::read(self, base, module_size).unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DIRECTORY_RVA: u32 = 0x2000;
+
+    /// Export directory at `DIRECTORY_RVA` with the function table, the name and ordinal
+    /// tables and the name strings right behind the fixed part
+    fn directory(functions: &[u32], names: &[(&str, u16)]) -> Vec<u8> {
+        let functions_at = 40;
+        let names_at = functions_at + functions.len() * 4;
+        let ordinals_at = names_at + names.len() * 4;
+        let mut strings_at = ordinals_at + names.len() * 2;
+        let mut bytes = vec![0u8; strings_at];
+        let rva = |offset: usize| DIRECTORY_RVA + offset as u32;
+        bytes[0x14..0x18].copy_from_slice(&(functions.len() as u32).to_le_bytes());
+        bytes[0x18..0x1C].copy_from_slice(&(names.len() as u32).to_le_bytes());
+        bytes[0x1C..0x20].copy_from_slice(&rva(functions_at).to_le_bytes());
+        bytes[0x20..0x24].copy_from_slice(&rva(names_at).to_le_bytes());
+        bytes[0x24..0x28].copy_from_slice(&rva(ordinals_at).to_le_bytes());
+        for (i, function) in functions.iter().enumerate() {
+            let at = functions_at + i * 4;
+            bytes[at..at + 4].copy_from_slice(&function.to_le_bytes());
+        }
+        for (i, (name, ordinal)) in names.iter().enumerate() {
+            let at = names_at + i * 4;
+            bytes[at..at + 4].copy_from_slice(&rva(strings_at).to_le_bytes());
+            let at = ordinals_at + i * 2;
+            bytes[at..at + 2].copy_from_slice(&ordinal.to_le_bytes());
+            bytes.extend(name.as_bytes());
+            bytes.push(0);
+            strings_at += name.len() + 1;
+        }
+        bytes
+    }
+
+    #[test]
+    fn test_exports_sorted_and_looked_up() {
+        let bytes = directory(&[0x1200, 0x1000], &[("Second", 0), ("First", 1)]);
+        let exports = ModuleExports::parse(&bytes, DIRECTORY_RVA).unwrap();
+        assert_eq!(exports.lookup(0x1000), Some(("First", 0)));
+        assert_eq!(exports.lookup(0x1204), Some(("Second", 4)));
+        assert_eq!(exports.lookup(0xFFF), None);
+        assert_eq!(exports.lookup(0x1200 + MAX_EXPORT_DISPLACEMENT + 1), None);
+    }
+
+    #[test]
+    fn test_forwarded_exports_are_skipped() {
+        // The second function points into the directory, at a "module.function" string
+        let bytes = directory(
+            &[0x1000, DIRECTORY_RVA + 0x30],
+            &[("Real", 0), ("Forwarded", 1)],
+        );
+        let exports = ModuleExports::parse(&bytes, DIRECTORY_RVA).unwrap();
+        assert_eq!(exports.symbols, vec![(0x1000, String::from("Real"))]);
+    }
+
+    #[test]
+    fn test_out_of_range_ordinals_are_skipped() {
+        let bytes = directory(&[0x1000], &[("Broken", 7), ("Real", 0)]);
+        let exports = ModuleExports::parse(&bytes, DIRECTORY_RVA).unwrap();
+        assert_eq!(exports.symbols, vec![(0x1000, String::from("Real"))]);
+    }
+
+    #[test]
+    fn test_truncated_tables() {
+        let mut bytes = directory(&[0x1000, 0x1100], &[("A", 0), ("B", 1)]);
+        // Claim more names than the tables hold; the parse stops at the end of the buffer
+        bytes[0x18..0x1C].copy_from_slice(&1000u32.to_le_bytes());
+        let exports = ModuleExports::parse(&bytes, DIRECTORY_RVA).unwrap();
+        assert_eq!(exports.symbols.len(), 2);
+
+        // Cut inside the fixed part of the directory
+        assert!(ModuleExports::parse(&bytes[..0x20], DIRECTORY_RVA).is_none());
+        // Cut before the name strings
+        let exports = ModuleExports::parse(&bytes[..40 + 8 + 8 + 4], DIRECTORY_RVA).unwrap();
+        assert!(exports.symbols.is_empty());
+    }
+}
@@ -3,10 +3,20 @@
 use std::{
     error::Error,
     ffi::CStr,
+    mem::MaybeUninit,
     sync::{
+        atomic::{
+            AtomicBool,
+            Ordering,
+        },
         Arc,
+        Mutex,
         Weak,
     },
+    time::{
+        Duration,
+        Instant,
+    },
 };
 
 use anyhow::Context;
@@ -31,11 +41,17 @@ use vtd_libum::{
 };
 
 use crate::{
+    cache::ReadCache,
+    FileHandle,
     SearchPattern,
     Signature,
     SignatureType,
+    TextEncoding,
 };
 
+/// Strings are read in chunks that do not cross pages of this size
+const STRING_READ_PAGE_SIZE: u64 = 0x1000;
+
 struct AppMemoryView {
     handle: Weak<AppHandle>,
 }
@@ -54,14 +70,105 @@ impl MemoryView for AppMemoryView {
     }
 }
 
+/// Where the memory shown through a handle comes from
+enum MemorySource {
+    Process {
+        interface: Arc<DriverInterface>,
+        process_id: ProcessId,
+    },
+    /// Read-only memory captured in a file
+    Dump(FileHandle),
+}
+
+impl MemorySource {
+    fn read(&self, address: u64, buffer: &mut [u8]) -> anyhow::Result<()> {
+        match self {
+            Self::Process {
+                interface,
+                process_id,
+            } => {
+                interface.read_slice(*process_id, DirectoryTableType::Default, address, buffer)?;
+                Ok(())
+            }
+            Self::Dump(file) => file.read(address, buffer),
+        }
+    }
+
+    fn interface(&self) -> anyhow::Result<(&DriverInterface, ProcessId)> {
+        match self {
+            Self::Process {
+                interface,
+                process_id,
+            } => Ok((interface, *process_id)),
+            Self::Dump(_) => anyhow::bail!("{}", obfstr!("memory dumps are read-only")),
+        }
+    }
+}
+
+/// Plain data that can be filled straight from process memory
+///
+/// # Safety
+/// Every bit pattern must be a valid value of the type, and it must hold no padding.
+pub unsafe trait Pod: Copy + 'static {}
+
+macro_rules! impl_pod {
+    ($($t:ty),*) => {
+        $(unsafe impl Pod for $t {})*
+    };
+}
+
+impl_pod!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, f32, f64);
+
+unsafe impl<T: Pod, const N: usize> Pod for [T; N] {}
+
 /// Handle to the process
 pub struct AppHandle {
     weak_self: Weak<Self>,
     metrics: bool,
 
     modules: Vec<ProcessModuleInfo>,
+    source: MemorySource,
+    read_cache: Mutex<ReadCache>,
+    read_failed: AtomicBool,
+    // 4 for 32-bit processes, 8 otherwise
+    pointer_size: u64,
+}
+
+/// Pointer size of the PE image mapped at `base`, from the machine type of its COFF header
+fn image_pointer_size(
+    interface: &DriverInterface,
     process_id: ProcessId,
-    ke_interface: Arc<DriverInterface>,
+    base: u64,
+) -> Option<u64> {
+    const IMAGE_FILE_MACHINE_I386: u16 = 0x014C;
+    const IMAGE_FILE_MACHINE_AMD64: u16 = 0x8664;
+    const IMAGE_FILE_MACHINE_ARM64: u16 = 0xAA64;
+
+    let mut e_lfanew = [0u8; 4];
+    interface
+        .read_slice(
+            process_id,
+            DirectoryTableType::Default,
+            base + 0x3C,
+            &mut e_lfanew,
+        )
+        .ok()?;
+    // The machine type follows the 4-byte "PE\0\0" signature
+    let mut machine = [0u8; 2];
+    interface
+        .read_slice(
+            process_id,
+            DirectoryTableType::Default,
+            base + u64::from(u32::from_le_bytes(e_lfanew)) + 4,
+            &mut machine,
+        )
+        .ok()?;
+    let machine = u16::from_le_bytes(machine);
+    match machine {
+        IMAGE_FILE_MACHINE_I386 => Some(4),
+        IMAGE_FILE_MACHINE_AMD64 | IMAGE_FILE_MACHINE_ARM64 => Some(8),
+        _ => None,
+    }
 }
 
 impl AppHandle {
@@ -70,28 +177,82 @@ impl AppHandle {
         process_id: ProcessId,
     ) -> anyhow::Result<Arc<Self>> {
         let modules = interface.list_modules(process_id, DirectoryTableType::Default)?;
+        // The first module is the process image; assume 64-bit if its header is unreadable
+        let pointer_size = modules
+            .first()
+            .and_then(|image| image_pointer_size(&interface, process_id, image.base_address))
+            .unwrap_or(8);
         log::debug!(
             "{}. Process id {}",
             obfstr!("Successfully initialized handle"),
             process_id
         );
 
-        let ke_interface = interface;
-        let handle = Arc::new_cyclic(|weak| Self {
+        Ok(Self::with_source(
+            MemorySource::Process {
+                interface,
+                process_id,
+            },
+            modules,
+            pointer_size,
+        ))
+    }
+
+    /// Handle reading from a memory dump instead of a live process. Dumps carry no module list.
+    pub fn create_from_dump(file: FileHandle) -> Arc<Self> {
+        let pointer_size = file.pointer_size();
+        Self::with_source(MemorySource::Dump(file), Vec::new(), pointer_size)
+    }
+
+    fn with_source(
+        source: MemorySource,
+        modules: Vec<ProcessModuleInfo>,
+        pointer_size: u64,
+    ) -> Arc<Self> {
+        Arc::new_cyclic(|weak| Self {
             weak_self: weak.clone(),
             metrics: false,
             modules,
-            process_id,
-            ke_interface,
-        });
-
-        Ok(handle)
+            source,
+            read_cache: Mutex::new(ReadCache::new(Duration::ZERO)),
+            read_failed: AtomicBool::new(false),
+            pointer_size,
+        })
     }
 
     pub fn get_all_modules(&self) -> &[ProcessModuleInfo] {
         &self.modules
     }
 
+    /// `(address, size)` of the memory scans walk: the ranges stored in a dump, or the loaded
+    /// modules of a process
+    pub fn scan_regions(&self) -> Vec<(u64, u64)> {
+        match &self.source {
+            MemorySource::Dump(file) => file.regions(),
+            MemorySource::Process { .. } => self
+                .modules
+                .iter()
+                .map(|m| (m.base_address, m.module_size))
+                .collect(),
+        }
+    }
+
+    /// `(address, size)` of all readable memory: the ranges stored in a dump, or every
+    /// committed region of a process, heaps and stacks included. Falls back to the loaded
+    /// modules when the process cannot be queried.
+    pub fn readable_regions(&self) -> Vec<(u64, u64)> {
+        let MemorySource::Process { process_id, .. } = &self.source else {
+            return self.scan_regions();
+        };
+        match crate::regions::committed_regions(*process_id) {
+            Ok(regions) => regions,
+            Err(err) => {
+                log::warn!("{err:#}, searching the loaded modules only");
+                self.scan_regions()
+            }
+        }
+    }
+
     pub fn get_module_by_name(&self, module_name: &str) -> Option<&ProcessModuleInfo> {
         self.modules.iter().find(|module| {
             module
@@ -107,17 +268,31 @@ impl AppHandle {
         })
     }
 
-    pub fn process_id(&self) -> ProcessId {
-        self.process_id
+    /// Size of a pointer in the process: 4 for 32-bit processes, 8 otherwise
+    pub fn pointer_size(&self) -> u64 {
+        self.pointer_size
+    }
+
+    /// Id of the attached process; none when reading from a dump
+    pub fn process_id(&self) -> Option<ProcessId> {
+        match &self.source {
+            MemorySource::Process { process_id, .. } => Some(*process_id),
+            MemorySource::Dump(_) => None,
+        }
+    }
+
+    /// Whether memory comes from a dump file rather than a live process
+    pub fn is_dump(&self) -> bool {
+        matches!(self.source, MemorySource::Dump(_))
     }
 
     pub fn send_keyboard_state(&self, states: &[KeyboardState]) -> anyhow::Result<()> {
-        self.ke_interface.send_keyboard_state(states)?;
+        self.source.interface()?.0.send_keyboard_state(states)?;
         Ok(())
     }
 
     pub fn send_mouse_state(&self, states: &[MouseState]) -> anyhow::Result<()> {
-        self.ke_interface.send_mouse_state(states)?;
+        self.source.interface()?.0.send_mouse_state(states)?;
         Ok(())
     }
 
@@ -127,9 +302,9 @@ impl AppHandle {
             return;
         }
 
-        let _ = self
-            .ke_interface
-            .add_metrics_record(record_type, record_payload);
+        if let Ok((interface, _)) = self.source.interface() {
+            let _ = interface.add_metrics_record(record_type, record_payload);
+        }
     }
 
     pub fn module_address(&self, module_name: &str, address: u64) -> Option<u64> {
@@ -156,19 +331,156 @@ impl AppHandle {
             .module_size)
     }
 
-    pub fn read_sized<T: Copy>(&self, address: u64) -> anyhow::Result<T> {
-        Ok(self
-            .ke_interface
-            .read(self.process_id, DirectoryTableType::Default, address)?)
+    /// Serve repeated reads of the same address and size from a cache for `interval`.
+    /// A zero interval disables caching.
+    pub fn set_read_interval(&self, interval: Duration) {
+        if let Ok(mut cache) = self.read_cache.lock() {
+            cache.set_interval(interval);
+        }
     }
 
-    pub fn read_slice<T: Copy>(&self, address: u64, buffer: &mut [T]) -> anyhow::Result<()> {
-        Ok(self.ke_interface.read_slice(
-            self.process_id,
-            DirectoryTableType::Default,
-            address,
-            buffer,
-        )?)
+    /// While paused, cached reads never expire so the displayed values stay frozen
+    pub fn set_reads_paused(&self, paused: bool) {
+        if let Ok(mut cache) = self.read_cache.lock() {
+            cache.set_paused(paused);
+        }
+    }
+
+    pub fn clear_read_cache(&self) {
+        if let Ok(mut cache) = self.read_cache.lock() {
+            cache.clear();
+        }
+    }
+
+    pub fn read_sized<T: Pod>(&self, address: u64) -> anyhow::Result<T> {
+        let mut value = MaybeUninit::<T>::zeroed();
+        // SAFETY: the byte view covers exactly the value
+        let bytes = unsafe {
+            std::slice::from_raw_parts_mut(value.as_mut_ptr() as *mut u8, std::mem::size_of::<T>())
+        };
+        self.read_bytes(address, bytes)?;
+        // SAFETY: `Pod` types are valid for any bit pattern
+        Ok(unsafe { value.assume_init() })
+    }
+
+    pub fn read_slice<T: Pod>(&self, address: u64, buffer: &mut [T]) -> anyhow::Result<()> {
+        // SAFETY: the byte view covers exactly the buffer, and any bytes written through it
+        // leave valid `Pod` values
+        let bytes = unsafe {
+            std::slice::from_raw_parts_mut(
+                buffer.as_mut_ptr() as *mut u8,
+                std::mem::size_of_val(buffer),
+            )
+        };
+        self.read_bytes(address, bytes)
+    }
+
+    fn read_bytes(&self, address: u64, buffer: &mut [u8]) -> anyhow::Result<()> {
+        let now = Instant::now();
+        if let Ok(cache) = self.read_cache.lock() {
+            if let Some(data) = cache.lookup(address, buffer.len(), now) {
+                buffer.copy_from_slice(data);
+                return Ok(());
+            }
+            if cache.is_throttled(address, now) {
+                return Err(anyhow::anyhow!(
+                    "{} {:X}",
+                    obfstr!("reads throttled at"),
+                    address
+                ));
+            }
+        }
+        if let Err(err) = self.source.read(address, buffer) {
+            self.read_failed.store(true, Ordering::Relaxed);
+            if let Ok(mut cache) = self.read_cache.lock() {
+                cache.record_failure(address, now);
+            }
+            return Err(err);
+        }
+        if let Ok(mut cache) = self.read_cache.lock() {
+            cache.record_success(address);
+            cache.store(address, buffer, now);
+        }
+        Ok(())
+    }
+
+    /// Number of addresses whose reads failed repeatedly and are skipped for a cooldown,
+    /// so the values shown for them are stale
+    pub fn throttled_read_count(&self) -> usize {
+        self.read_cache
+            .lock()
+            .map(|cache| cache.throttled_count(Instant::now()))
+            .unwrap_or(0)
+    }
+
+    /// Whether a driver read failed since the last call. Bad pointers fail too, so this is only
+    /// a hint to check if the process is still alive.
+    pub fn take_read_failure(&self) -> bool {
+        self.read_failed.swap(false, Ordering::Relaxed)
+    }
+
+    /// Ask the driver for the module list of the process; this fails once the process exited.
+    /// A dump never goes away.
+    pub fn is_process_alive(&self) -> bool {
+        match &self.source {
+            MemorySource::Process {
+                interface,
+                process_id,
+            } => interface
+                .list_modules(*process_id, DirectoryTableType::Default)
+                .is_ok(),
+            MemorySource::Dump(_) => true,
+        }
+    }
+
+    /// Read straight from the process without going through the read cache.
+    /// Meant for bulk reads such as scans that would otherwise flood the cache.
+    pub fn read_uncached(&self, address: u64, buffer: &mut [u8]) -> anyhow::Result<()> {
+        self.source.read(address, buffer)
+    }
+
+    /// Heuristic check whether `value` looks like an address in the process: it lies inside a
+    /// loaded module or points to readable user space memory
+    pub fn is_probably_pointer(&self, value: u64) -> bool {
+        const USER_SPACE: std::ops::RangeInclusive<u64> = 0x10000..=0x7FFF_FFFF_FFFF;
+        if !USER_SPACE.contains(&value) {
+            return false;
+        }
+        if self.get_module_by_address(value).is_some() {
+            return true;
+        }
+        let mut probe = [0u8; 1];
+        self.read_uncached(value, &mut probe).is_ok()
+    }
+
+    /// Whether `address` lies in a loaded module or in readable memory of the process. Probes go
+    /// through the read cache, so this is cheap enough to ask for every pointer on every frame.
+    pub fn is_address_mapped(&self, address: u64) -> bool {
+        if self.get_module_by_address(address).is_some() {
+            return true;
+        }
+        let mut probe = [0u8; 1];
+        self.read_slice(address, &mut probe).is_ok()
+    }
+
+    fn invalidate_cached(&self, address: u64, len: usize) {
+        if let Ok(mut cache) = self.read_cache.lock() {
+            cache.invalidate_range(address, len);
+        }
+    }
+
+    pub fn write_sized<T: Copy>(&self, address: u64, value: T) -> anyhow::Result<()> {
+        let (interface, process_id) = self.source.interface()?;
+        interface.write(process_id, DirectoryTableType::Default, address, &value)?;
+        self.invalidate_cached(address, std::mem::size_of::<T>());
+        Ok(())
+    }
+
+    pub fn write_slice(&self, address: u64, data: &[u8]) -> anyhow::Result<()> {
+        let (interface, process_id) = self.source.interface()?;
+        interface.write_slice(process_id, DirectoryTableType::Default, address, data)?;
+        self.invalidate_cached(address, data.len());
+        Ok(())
     }
 
     pub fn read_string(
@@ -195,6 +507,41 @@ impl AppHandle {
         }
     }
 
+    /// Read at most `max_length` bytes at `address` as a string in `encoding`, cut at the
+    /// first null terminator. The bytes are read a page at a time and reading stops at the
+    /// terminator, so a short string right before an unreadable page still reads; a string
+    /// running into one is cut there.
+    pub fn read_string_enc(
+        &self,
+        address: u64,
+        max_length: usize,
+        encoding: TextEncoding,
+    ) -> anyhow::Result<String> {
+        let unit = encoding.unit_size();
+        let mut buffer = Vec::with_capacity(max_length);
+        while buffer.len() < max_length {
+            let start = buffer.len();
+            let chunk_address = address.wrapping_add(start as u64);
+            let to_page_end = STRING_READ_PAGE_SIZE - chunk_address % STRING_READ_PAGE_SIZE;
+            let chunk = (max_length - start).min(to_page_end as usize);
+            buffer.resize(start + chunk, 0);
+            if let Err(err) = self.read_slice(chunk_address, &mut buffer[start..]) {
+                if start == 0 {
+                    return Err(err).context("read_string_enc");
+                }
+                buffer.truncate(start);
+                break;
+            }
+            if buffer
+                .chunks_exact(unit)
+                .any(|code_unit| code_unit.iter().all(|b| *b == 0))
+            {
+                break;
+            }
+        }
+        Ok(encoding.decode(&buffer))
+    }
+
     pub fn create_memory_view(&self) -> Arc<dyn MemoryView + Send + Sync> {
         Arc::new(AppMemoryView {
             handle: self.weak_self.clone(),
@@ -213,12 +560,7 @@ impl AppHandle {
         }
 
         let mut buffer = vec![0; length];
-        self.ke_interface.read_slice(
-            self.process_id,
-            DirectoryTableType::Default,
-            address,
-            &mut buffer,
-        )?;
+        self.source.read(address, &mut buffer)?;
 
         for (index, window) in buffer.windows(pattern.length()).enumerate() {
             if !pattern.is_matching(window) {
@@ -231,6 +573,45 @@ impl AppHandle {
         Ok(None)
     }
 
+    /// Find every match of `pattern` in `[address, address + length)`, up to `limit` results.
+    /// Memory is read in chunks so that unreadable regions only skip their own chunk.
+    pub fn find_all_patterns(
+        &self,
+        address: u64,
+        length: usize,
+        pattern: &dyn SearchPattern,
+        limit: usize,
+    ) -> Vec<u64> {
+        const CHUNK_SIZE: usize = 0x10_0000;
+
+        let pattern_len = pattern.length();
+        let mut matches = Vec::new();
+        if pattern_len == 0 || pattern_len > length {
+            return matches;
+        }
+
+        let mut buffer = vec![0u8; CHUNK_SIZE + pattern_len - 1];
+        let mut chunk_start = 0;
+        while chunk_start + pattern_len <= length && matches.len() < limit {
+            // Overlap chunks so matches crossing a chunk boundary are still found
+            let read_len = (CHUNK_SIZE + pattern_len - 1).min(length - chunk_start);
+            let chunk = &mut buffer[..read_len];
+            let chunk_address = address + chunk_start as u64;
+            if self.source.read(chunk_address, chunk).is_ok() {
+                for (index, window) in chunk.windows(pattern_len).enumerate() {
+                    if pattern.is_matching(window) {
+                        matches.push(chunk_address + index as u64);
+                        if matches.len() >= limit {
+                            break;
+                        }
+                    }
+                }
+            }
+            chunk_start += CHUNK_SIZE;
+        }
+        matches
+    }
+
     pub fn resolve_signature(
         &self,
         module_name: &str,
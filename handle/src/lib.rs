@@ -1,6 +1,17 @@
 #![feature(array_try_from_fn)]
 #![feature(sync_unsafe_cell)]
 
+mod cache;
+
+mod dump;
+pub use dump::*;
+
+mod encoding;
+pub use encoding::*;
+
+mod exports;
+pub use exports::ModuleExports;
+
 mod handle;
 pub use handle::*;
 
@@ -9,6 +20,10 @@ pub use signature::*;
 
 mod pattern;
 
+mod regions;
+
+mod rtti;
+
 pub use pattern::*;
 pub use vtd_libum::{
     protocol::command::{
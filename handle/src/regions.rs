@@ -0,0 +1,103 @@
+/// Committed, readable memory of a process as `(address, size)`, sorted and with touching
+/// ranges merged. Queried from the OS, which needs the process to be openable for queries.
+#[cfg(windows)]
+pub(crate) fn committed_regions(process_id: u32) -> anyhow::Result<Vec<(u64, u64)>> {
+    use windows_sys::Win32::{
+        Foundation::CloseHandle,
+        System::{
+            Memory::{
+                VirtualQueryEx,
+                MEMORY_BASIC_INFORMATION,
+                MEM_COMMIT,
+                PAGE_GUARD,
+                PAGE_NOACCESS,
+            },
+            Threading::{
+                OpenProcess,
+                PROCESS_QUERY_LIMITED_INFORMATION,
+            },
+        },
+    };
+
+    // SAFETY: plain calls with a handle we own; the information struct is written by the OS
+    // and only read after a successful query
+    unsafe {
+        let process = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, process_id);
+        if process.is_null() {
+            anyhow::bail!("failed to open process {process_id} for memory queries");
+        }
+        let mut regions = Vec::new();
+        let mut address = 0u64;
+        loop {
+            let mut info = std::mem::zeroed::<MEMORY_BASIC_INFORMATION>();
+            let written = VirtualQueryEx(
+                process,
+                address as *const core::ffi::c_void,
+                &mut info,
+                std::mem::size_of::<MEMORY_BASIC_INFORMATION>(),
+            );
+            if written == 0 || info.RegionSize == 0 {
+                break;
+            }
+            let base = info.BaseAddress as u64;
+            let size = info.RegionSize as u64;
+            if info.State == MEM_COMMIT && info.Protect & (PAGE_NOACCESS | PAGE_GUARD) == 0 {
+                regions.push((base, size));
+            }
+            match base.checked_add(size) {
+                Some(next) if next > address => address = next,
+                _ => break,
+            }
+        }
+        CloseHandle(process);
+        Ok(merge_regions(regions))
+    }
+}
+
+#[cfg(not(windows))]
+pub(crate) fn committed_regions(_process_id: u32) -> anyhow::Result<Vec<(u64, u64)>> {
+    anyhow::bail!("memory queries are only supported on Windows")
+}
+
+/// Sort `regions` and merge the ones that touch or overlap
+#[cfg_attr(not(windows), allow(dead_code))]
+fn merge_regions(mut regions: Vec<(u64, u64)>) -> Vec<(u64, u64)> {
+    regions.sort_by_key(|(address, _)| *address);
+    let mut merged: Vec<(u64, u64)> = Vec::with_capacity(regions.len());
+    for (address, size) in regions {
+        if let Some((last_address, last_size)) = merged.last_mut() {
+            let last_end = last_address.saturating_add(*last_size);
+            if address <= last_end {
+                *last_size = address.saturating_add(size).max(last_end) - *last_address;
+                continue;
+            }
+        }
+        merged.push((address, size));
+    }
+    merged
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_merge_regions() {
+        let regions = vec![
+            (0x3000, 0x1000),
+            (0x1000, 0x1000),
+            (0x2000, 0x800),
+            (0x2400, 0x100),
+        ];
+        assert_eq!(
+            merge_regions(regions),
+            vec![(0x1000, 0x1800), (0x3000, 0x1000)]
+        );
+    }
+
+    #[test]
+    fn test_merge_regions_overlapping() {
+        let regions = vec![(0x1000, 0x3000), (0x2000, 0x1000), (0x3800, 0x1000)];
+        assert_eq!(merge_regions(regions), vec![(0x1000, 0x3800)]);
+    }
+}
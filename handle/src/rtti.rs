@@ -0,0 +1,201 @@
+use crate::{
+    AppHandle,
+    Pod,
+};
+
+/// `RTTICompleteObjectLocator.signature` of 32-bit images, all pointers are absolute
+const COL_SIGNATURE_ABSOLUTE: u32 = 0;
+/// `RTTICompleteObjectLocator.signature` of 64-bit images, pointers are image relative
+const COL_SIGNATURE_RELATIVE: u32 = 1;
+
+/// Longest decorated name we are willing to read from a type descriptor
+const MAX_DECORATED_NAME: usize = 256;
+
+/// Mirrors `RTTICompleteObjectLocator` from the MSVC runtime
+#[derive(Clone, Copy, Default)]
+#[repr(C)]
+struct CompleteObjectLocator {
+    signature: u32,
+    offset: u32,
+    cd_offset: u32,
+    type_descriptor: u32,
+    class_descriptor: u32,
+    /// Image relative address of this locator (64-bit images only)
+    self_rva: u32,
+}
+
+// SAFETY: `repr(C)` with only `u32` fields, so no padding and no invalid bit patterns
+unsafe impl Pod for CompleteObjectLocator {}
+
+/// Turn a decorated type descriptor name like `.?AVPlayer@game@@` into `game::Player`
+fn demangle_type_name(decorated: &str) -> Option<String> {
+    let body = [".?AV", ".?AU", ".?AW4"]
+        .iter()
+        .find_map(|prefix| decorated.strip_prefix(prefix))?;
+    let body = body.strip_suffix("@@").unwrap_or(body);
+    if body.is_empty() {
+        return None;
+    }
+    // Templates and other special names keep their decorated form
+    if body.starts_with('?') {
+        return Some(decorated.to_string());
+    }
+    let mut parts: Vec<&str> = body.split('@').filter(|p| !p.is_empty()).collect();
+    parts.reverse();
+    Some(parts.join("::"))
+}
+
+impl AppHandle {
+    /// Pointer of the process's width at `address`, little-endian as on every MSVC target
+    fn read_target_pointer(&self, address: u64) -> Option<u64> {
+        if self.pointer_size() == 4 {
+            self.read_sized::<u32>(address).ok().map(u64::from)
+        } else {
+            self.read_sized::<u64>(address).ok()
+        }
+    }
+
+    /// Recover the MSVC RTTI class name of the polymorphic object at `object_address`
+    /// by following its vtable to the complete object locator and type descriptor.
+    /// Returns `None` if any step cannot be read or does not look like RTTI.
+    pub fn resolve_rtti_name(&self, object_address: u64) -> Option<String> {
+        let pointer_size = self.pointer_size();
+        let vtable = self.read_target_pointer(object_address)?;
+        if vtable == 0 {
+            return None;
+        }
+        // The locator pointer sits right before the first virtual function
+        let locator_address = self.read_target_pointer(vtable.checked_sub(pointer_size)?)?;
+        if locator_address == 0 {
+            return None;
+        }
+        let locator = self
+            .read_sized::<CompleteObjectLocator>(locator_address)
+            .ok()?;
+
+        let type_descriptor = match locator.signature {
+            COL_SIGNATURE_RELATIVE => {
+                let image_base = match self.get_module_by_address(locator_address) {
+                    Some(module) => module.base_address,
+                    None => locator_address.checked_sub(u64::from(locator.self_rva))?,
+                };
+                image_base.checked_add(u64::from(locator.type_descriptor))?
+            }
+            COL_SIGNATURE_ABSOLUTE => u64::from(locator.type_descriptor),
+            _ => return None,
+        };
+        if type_descriptor == 0 {
+            return None;
+        }
+
+        // The name follows pVFTable and spare, both pointer sized
+        let mut name = [0u8; MAX_DECORATED_NAME];
+        self.read_slice(type_descriptor.checked_add(2 * pointer_size)?, &mut name)
+            .ok()?;
+        let len = name.iter().position(|b| *b == 0)?;
+        let decorated = std::str::from_utf8(&name[..len]).ok()?;
+        demangle_type_name(decorated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::*;
+    use crate::FileHandle;
+
+    const DUMP_BASE: u64 = 0x40_0000;
+
+    /// Handle over a raw dump of `bytes` mapped at `DUMP_BASE`
+    fn dump_handle(name: &str, bytes: &[u8], pointer_size: u64) -> std::sync::Arc<AppHandle> {
+        let path: PathBuf =
+            std::env::temp_dir().join(format!("reclass-rtti-{name}-{}.bin", std::process::id()));
+        std::fs::write(&path, bytes).unwrap();
+        let file = FileHandle::open(&path, DUMP_BASE, pointer_size).unwrap();
+        let _ = std::fs::remove_file(&path);
+        AppHandle::create_from_dump(file)
+    }
+
+    fn put(bytes: &mut [u8], offset: usize, value: &[u8]) {
+        bytes[offset..offset + value.len()].copy_from_slice(value);
+    }
+
+    fn locator_bytes(signature: u32, type_descriptor: u32, self_rva: u32) -> Vec<u8> {
+        [signature, 0, 0, type_descriptor, 0, self_rva]
+            .iter()
+            .flat_map(|value| value.to_le_bytes())
+            .collect()
+    }
+
+    #[test]
+    fn test_demangle_type_name() {
+        assert_eq!(
+            demangle_type_name(".?AVPlayer@@").as_deref(),
+            Some("Player")
+        );
+        assert_eq!(
+            demangle_type_name(".?AUVector3@@").as_deref(),
+            Some("Vector3")
+        );
+        assert_eq!(
+            demangle_type_name(".?AW4Team@game@@").as_deref(),
+            Some("game::Team")
+        );
+        assert_eq!(
+            demangle_type_name(".?AVInventory@Player@game@@").as_deref(),
+            Some("game::Player::Inventory")
+        );
+        // Templates keep their decorated form
+        let template = ".?AV?$vector@HV?$allocator@H@std@@@std@@";
+        assert_eq!(demangle_type_name(template).as_deref(), Some(template));
+        assert_eq!(demangle_type_name(".?AV@@"), None);
+        assert_eq!(demangle_type_name("Player"), None);
+    }
+
+    #[test]
+    fn test_relative_locator_of_64_bit_image() {
+        // object at +0x10, vtable at +0x108 with the locator pointer before it, locator at
+        // +0x200 (image relative 0x200), type descriptor at +0x300
+        let mut bytes = vec![0u8; 0x500];
+        put(&mut bytes, 0x10, &(DUMP_BASE + 0x108).to_le_bytes());
+        put(&mut bytes, 0x100, &(DUMP_BASE + 0x200).to_le_bytes());
+        put(&mut bytes, 0x200, &locator_bytes(1, 0x300, 0x200));
+        put(&mut bytes, 0x310, b".?AVPlayer@game@@\0");
+        let handle = dump_handle("relative", &bytes, 8);
+        assert_eq!(
+            handle.resolve_rtti_name(DUMP_BASE + 0x10).as_deref(),
+            Some("game::Player")
+        );
+    }
+
+    #[test]
+    fn test_absolute_locator_of_32_bit_image() {
+        let mut bytes = vec![0u8; 0x500];
+        let base = DUMP_BASE as u32;
+        put(&mut bytes, 0x10, &(base + 0x104).to_le_bytes());
+        put(&mut bytes, 0x100, &(base + 0x200).to_le_bytes());
+        put(&mut bytes, 0x200, &locator_bytes(0, base + 0x300, 0));
+        put(&mut bytes, 0x308, b".?AUEntity@@\0");
+        let handle = dump_handle("absolute", &bytes, 4);
+        assert_eq!(
+            handle.resolve_rtti_name(DUMP_BASE + 0x10).as_deref(),
+            Some("Entity")
+        );
+    }
+
+    #[test]
+    fn test_bad_locator_returns_none() {
+        let mut bytes = vec![0u8; 0x500];
+        put(&mut bytes, 0x10, &(DUMP_BASE + 0x108).to_le_bytes());
+        put(&mut bytes, 0x100, &(DUMP_BASE + 0x200).to_le_bytes());
+        // A self offset larger than the locator address
+        put(&mut bytes, 0x200, &locator_bytes(1, u32::MAX, u32::MAX));
+        let handle = dump_handle("bad", &bytes, 8);
+        assert_eq!(handle.resolve_rtti_name(DUMP_BASE + 0x10), None);
+        // Unknown signature
+        put(&mut bytes, 0x200, &locator_bytes(7, 0x300, 0x200));
+        let handle = dump_handle("signature", &bytes, 8);
+        assert_eq!(handle.resolve_rtti_name(DUMP_BASE + 0x10), None);
+    }
+}
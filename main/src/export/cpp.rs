@@ -0,0 +1,432 @@
+use std::{
+    collections::HashSet,
+    fmt::Write,
+};
+
+use crate::memory::{
+    ClassDefinition,
+    EnumDefinition,
+    FieldDefinition,
+    FieldType,
+    MemoryStructure,
+    PointerTarget,
+};
+
+/// Generate a C++ header containing every enum and class of the memory structure.
+/// Structs are ordered so that embedded types are defined before their users; all
+/// structs are forward declared up front so pointers may reference any of them.
+pub fn generate_cpp_header(ms: &MemoryStructure) -> String {
+    let mut out = String::new();
+    out.push_str("#pragma once\n\n#include <cstdint>\n\n");
+
+    for id in enum_order(ms) {
+        let Some(ed) = ms.enum_registry.get(id) else {
+            continue;
+        };
+        let _ = writeln!(
+            out,
+            "enum class {} : {} {{",
+            sanitize_identifier(&ed.name),
+            enum_backing_type(ed)
+        );
+        for variant in &ed.variants {
+            if ed.is_flags {
+                let _ = writeln!(
+                    out,
+                    "    {} = 0x{:X},",
+                    sanitize_identifier(&variant.name),
+                    variant.value
+                );
+            } else {
+                let _ = writeln!(
+                    out,
+                    "    {} = {},",
+                    sanitize_identifier(&variant.name),
+                    variant.value
+                );
+            }
+        }
+        out.push_str("};\n\n");
+    }
+
+    let order = dependency_order(ms);
+    if order.is_empty() {
+        return out;
+    }
+    for id in &order {
+        let _ = writeln!(out, "struct {};", class_name(ms, *id));
+    }
+    out.push_str("\n#pragma pack(push, 1)\n\n");
+    for id in &order {
+        if let Some(def) = ms.class_registry.get(*id) {
+            write_struct(ms, def, &mut out);
+        }
+    }
+    out.push_str("#pragma pack(pop)\n");
+    out
+}
+
+fn write_struct(ms: &MemoryStructure, def: &ClassDefinition, out: &mut String) {
+    let name = sanitize_identifier(&def.name);
+    match def
+        .base_class_id
+        .filter(|base_id| ms.class_registry.contains(*base_id))
+    {
+        Some(base_id) => {
+            let _ = writeln!(out, "struct {name} : {} {{", class_name(ms, base_id));
+        }
+        None => {
+            let _ = writeln!(out, "struct {name} {{");
+        }
+    }
+
+    // Members of a derived struct start after its base
+    let mut offset = ms.base_class_size(def.id);
+    let mut pad: Option<(u64, u64)> = None;
+    for fd in &def.fields {
+        let size = ms.field_size(fd, &mut vec![def.id]);
+        if fd.field_type.is_hex_type() {
+            if fd.comment.is_some() {
+                // Split the padding so the comment stays next to the bytes it describes
+                if let Some((start, len)) = pad.take() {
+                    write_padding(out, start, len);
+                }
+                write_comment(out, fd);
+            }
+            pad.get_or_insert((offset, 0)).1 += size;
+            offset += size;
+            continue;
+        }
+        if let Some((start, len)) = pad.take() {
+            write_padding(out, start, len);
+        }
+        write_comment(out, fd);
+        let field_name = fd
+            .name
+            .as_deref()
+            .map(sanitize_identifier)
+            .unwrap_or_else(|| format!("var_{offset:X}"));
+        match member_declaration(ms, fd, &field_name) {
+            Some(decl) => {
+                let _ = writeln!(out, "    {decl}; // 0x{offset:04X}");
+            }
+            None => {
+                let _ = writeln!(
+                    out,
+                    "    // {field_name}: {} without a resolvable layout // 0x{offset:04X}",
+                    fd.field_type
+                );
+            }
+        }
+        offset += size;
+    }
+    if let Some((start, len)) = pad.take() {
+        write_padding(out, start, len);
+    }
+
+    out.push_str("};\n");
+    let _ = writeln!(
+        out,
+        "static_assert(sizeof({name}) == 0x{offset:X}, \"{name} has an unexpected size\");\n"
+    );
+}
+
+/// Member declarations of the fields of `class_id` listed in `field_ids`, in class order, for
+/// pasting into a struct body. Hex fields and gaps between the selected fields collapse into
+/// `char pad` arrays so the members keep their offsets; each line ends with `// +0xNN`.
+pub fn generate_cpp_snippet(
+    ms: &MemoryStructure,
+    class_id: u64,
+    field_ids: &HashSet<u64>,
+) -> String {
+    let mut out = String::new();
+    let Some(def) = ms.class_registry.get(class_id) else {
+        return out;
+    };
+    let mut offset = ms.base_class_size(class_id);
+    // End of the last selected field; bytes from here to the next one are padding
+    let mut written_to = offset;
+    let mut pad: Option<(u64, u64)> = None;
+    for fd in &def.fields {
+        let size = ms.field_size(fd, &mut vec![def.id]);
+        if !field_ids.contains(&fd.id) {
+            offset += size;
+            continue;
+        }
+        if offset > written_to {
+            pad.get_or_insert((written_to, 0)).1 += offset - written_to;
+        }
+        written_to = offset + size;
+        if fd.field_type.is_hex_type() {
+            pad.get_or_insert((offset, 0)).1 += size;
+            offset += size;
+            continue;
+        }
+        if let Some((start, len)) = pad.take() {
+            write_snippet_padding(&mut out, start, len);
+        }
+        write_comment(&mut out, fd);
+        let field_name = fd
+            .name
+            .as_deref()
+            .map(sanitize_identifier)
+            .unwrap_or_else(|| format!("var_{offset:X}"));
+        match member_declaration(ms, fd, &field_name) {
+            Some(decl) => {
+                let _ = writeln!(out, "    {decl}; // +0x{offset:04X}");
+            }
+            None => {
+                let _ = writeln!(
+                    out,
+                    "    // {field_name}: {} without a resolvable layout // +0x{offset:04X}",
+                    fd.field_type
+                );
+            }
+        }
+        offset += size;
+    }
+    if let Some((start, len)) = pad.take() {
+        write_snippet_padding(&mut out, start, len);
+    }
+    out
+}
+
+fn write_snippet_padding(out: &mut String, start: u64, len: u64) {
+    if len > 0 {
+        let _ = writeln!(out, "    char pad{start:04X}[0x{len:X}]; // +0x{start:04X}");
+    }
+}
+
+pub(super) fn write_comment(out: &mut String, fd: &FieldDefinition) {
+    for line in fd.comment.iter().flat_map(|c| c.lines()) {
+        let _ = writeln!(out, "    // {}", line.trim_end());
+    }
+}
+
+fn write_padding(out: &mut String, start: u64, len: u64) {
+    if len > 0 {
+        let _ = writeln!(out, "    char pad{start:04X}[0x{len:X}]; // 0x{start:04X}");
+    }
+}
+
+fn member_declaration(ms: &MemoryStructure, fd: &FieldDefinition, name: &str) -> Option<String> {
+    match fd.field_type {
+        FieldType::ClassInstance => {
+            let cid = fd.class_id.filter(|cid| ms.class_registry.contains(*cid))?;
+            Some(format!("{} {name}", class_name(ms, cid)))
+        }
+        FieldType::Array => {
+            let len = fd.array_length.unwrap_or(0);
+            if len == 0 {
+                return None;
+            }
+            let element_size = ms.array_element_size(fd, &mut Vec::new());
+            let step = fd.array_step(element_size);
+            if step != element_size {
+                // Elements with a stride have no array type; keep the bytes so offsets hold
+                return Some(format!("char {name}[0x{:X}]", step * u64::from(len)));
+            }
+            if let Some(cid) = fd
+                .array_pointee_class()
+                .filter(|cid| ms.class_registry.contains(*cid))
+            {
+                return Some(format!("{}* {name}[{len}]", class_name(ms, cid)));
+            }
+            let (ty, suffix) = inline_type(ms, fd.array_element.as_ref()?)?;
+            Some(format!("{ty} {name}[{len}]{suffix}"))
+        }
+        FieldType::Enum => match fd.enum_id.and_then(|eid| ms.enum_registry.get(eid)) {
+            Some(ed) => Some(format!("{} {name}", sanitize_identifier(&ed.name))),
+            None => Some(format!("uint32_t {name}")),
+        },
+        FieldType::Bitfield => {
+            // Pad the unit with unnamed bits so the next field starts in a fresh unit
+            let (size, bit_offset, width) = fd.bitfield_layout();
+            let ty = unsigned_type(size as u64);
+            let trailing = size * 8 - bit_offset - width;
+            let mut parts = Vec::with_capacity(3);
+            if bit_offset > 0 {
+                parts.push(format!("{ty} : {bit_offset}"));
+            }
+            parts.push(format!("{ty} {name} : {width}"));
+            if trailing > 0 {
+                parts.push(format!("{ty} : {trailing}"));
+            }
+            Some(parts.join("; "))
+        }
+        FieldType::Pointer => Some(format!(
+            "{}* {name}",
+            pointee_type(ms, fd.pointer_target.as_ref())
+        )),
+        FieldType::Text => Some(format!("char {name}[{}]", fd.get_size())),
+        _ => {
+            let (ty, suffix) = primitive_type(&fd.field_type)?;
+            Some(format!("{ty} {name}{suffix}"))
+        }
+    }
+}
+
+/// C++ type and declarator suffix for a value stored inline (field or array element)
+fn inline_type(ms: &MemoryStructure, target: &PointerTarget) -> Option<(String, String)> {
+    match target {
+        PointerTarget::FieldType(t) => primitive_type(t),
+        PointerTarget::ClassId(cid) => ms
+            .class_registry
+            .contains(*cid)
+            .then(|| (class_name(ms, *cid), String::new())),
+        PointerTarget::EnumId(eid) => ms
+            .enum_registry
+            .get(*eid)
+            .map(|ed| (sanitize_identifier(&ed.name), String::new())),
+        PointerTarget::Array { .. } | PointerTarget::Chain { .. } => None,
+    }
+}
+
+fn pointee_type(ms: &MemoryStructure, target: Option<&PointerTarget>) -> String {
+    match target {
+        Some(PointerTarget::Array { element, .. }) => pointee_type(ms, Some(element)),
+        Some(PointerTarget::Chain { .. }) | None => "void".to_string(),
+        Some(other) => inline_type(ms, other)
+            .map(|(ty, _)| ty)
+            .unwrap_or_else(|| "void".to_string()),
+    }
+}
+
+fn primitive_type(t: &FieldType) -> Option<(String, String)> {
+    let (ty, suffix) = match t {
+        FieldType::Hex64 | FieldType::UInt64 => ("uint64_t", String::new()),
+        FieldType::Hex32 | FieldType::UInt32 => ("uint32_t", String::new()),
+        FieldType::Hex16 | FieldType::UInt16 => ("uint16_t", String::new()),
+        FieldType::Hex8 | FieldType::UInt8 => ("uint8_t", String::new()),
+        FieldType::Int64 => ("int64_t", String::new()),
+        FieldType::Int32 => ("int32_t", String::new()),
+        FieldType::Int16 => ("int16_t", String::new()),
+        FieldType::Int8 => ("int8_t", String::new()),
+        FieldType::Bool => ("bool", String::new()),
+        FieldType::Float => ("float", String::new()),
+        FieldType::Double => ("double", String::new()),
+        FieldType::Vector4 | FieldType::Vector3 | FieldType::Vector2 | FieldType::Quaternion => {
+            ("float", format!("[{}]", t.get_size() / 4))
+        }
+        FieldType::Matrix4x4 => ("float", "[4][4]".to_string()),
+        FieldType::Guid => ("uint8_t", "[16]".to_string()),
+        FieldType::UnixTime32 => ("uint32_t", String::new()),
+        FieldType::UnixTime64 => ("int64_t", String::new()),
+        FieldType::Win32FileTime => ("uint64_t", String::new()),
+        FieldType::Text => ("char", format!("[{}]", t.get_size())),
+        FieldType::TextPointer => ("char*", String::new()),
+        FieldType::WideText => ("wchar_t", format!("[{}]", t.get_size() / 2)),
+        FieldType::WideTextPointer => ("wchar_t*", String::new()),
+        FieldType::Pointer => ("void*", String::new()),
+        FieldType::Enum => ("uint32_t", String::new()),
+        FieldType::ClassInstance | FieldType::Array | FieldType::Bitfield => return None,
+    };
+    Some((ty.to_string(), suffix))
+}
+
+fn enum_backing_type(ed: &EnumDefinition) -> &'static str {
+    if ed.is_signed {
+        match ed.default_size {
+            1 => "int8_t",
+            2 => "int16_t",
+            8 => "int64_t",
+            _ => "int32_t",
+        }
+    } else {
+        unsigned_type(ed.default_size as u64)
+    }
+}
+
+fn unsigned_type(size: u64) -> &'static str {
+    match size {
+        1 => "uint8_t",
+        2 => "uint16_t",
+        8 => "uint64_t",
+        _ => "uint32_t",
+    }
+}
+
+/// Classes that must be complete before `def` can be defined (base class, embedded instances
+/// and arrays)
+fn embedded_dependencies(def: &ClassDefinition) -> Vec<u64> {
+    def.base_class_id
+        .into_iter()
+        .chain(def.fields.iter().filter_map(|fd| match fd.field_type {
+            FieldType::ClassInstance => fd.class_id,
+            FieldType::Array => match fd.array_element {
+                Some(PointerTarget::ClassId(cid)) => Some(cid),
+                _ => None,
+            },
+            _ => None,
+        }))
+        .collect()
+}
+
+/// Enum ids ordered by name
+pub(super) fn enum_order(ms: &MemoryStructure) -> Vec<u64> {
+    let mut enum_ids = ms.enum_registry.get_enum_ids();
+    enum_ids.sort_by_key(|id| {
+        let name = ms
+            .enum_registry
+            .get(*id)
+            .map(|ed| ed.name.clone())
+            .unwrap_or_default();
+        (name, *id)
+    });
+    enum_ids
+}
+
+/// Class ids ordered so that every embedded dependency precedes its user
+pub(super) fn dependency_order(ms: &MemoryStructure) -> Vec<u64> {
+    let mut ids = ms.class_registry.get_class_ids();
+    ids.sort_by_key(|id| (class_name(ms, *id), *id));
+
+    fn visit(
+        ms: &MemoryStructure,
+        id: u64,
+        visiting: &mut HashSet<u64>,
+        done: &mut HashSet<u64>,
+        order: &mut Vec<u64>,
+    ) {
+        if done.contains(&id) || !visiting.insert(id) {
+            return;
+        }
+        if let Some(def) = ms.class_registry.get(id) {
+            for dep in embedded_dependencies(def) {
+                if ms.class_registry.contains(dep) {
+                    visit(ms, dep, visiting, done, order);
+                }
+            }
+        }
+        visiting.remove(&id);
+        done.insert(id);
+        order.push(id);
+    }
+
+    let mut visiting = HashSet::new();
+    let mut done = HashSet::new();
+    let mut order = Vec::with_capacity(ids.len());
+    for id in ids {
+        visit(ms, id, &mut visiting, &mut done, &mut order);
+    }
+    order
+}
+
+pub(super) fn class_name(ms: &MemoryStructure, id: u64) -> String {
+    ms.class_registry
+        .get(id)
+        .map(|def| sanitize_identifier(&def.name))
+        .unwrap_or_else(|| format!("Class_{id}"))
+}
+
+/// Turn an arbitrary name into a valid C/C++ identifier
+pub(super) fn sanitize_identifier(name: &str) -> String {
+    let mut ident: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    if ident.is_empty() || ident.starts_with(|c: char| c.is_ascii_digit()) {
+        ident.insert(0, '_');
+    }
+    ident
+}
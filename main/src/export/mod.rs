@@ -0,0 +1,6 @@
+pub mod cpp;
+pub mod offsets;
+pub mod rust;
+
+#[cfg(test)]
+mod tests;
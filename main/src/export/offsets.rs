@@ -0,0 +1,122 @@
+use std::{
+    collections::HashSet,
+    fmt::Write,
+};
+
+use super::cpp::sanitize_identifier;
+use crate::memory::{
+    FieldType,
+    MemoryStructure,
+};
+
+/// A non-hex field reachable from the root without following pointers, with its offset from
+/// the root address. `path` starts with the root class name and ends with the field name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RootOffset {
+    pub path: Vec<String>,
+    pub offset: u64,
+}
+
+/// Every non-hex field of the root class, its base classes and its embedded instances, in
+/// layout order. Embedded instances are listed before their own fields.
+pub fn collect_root_offsets(ms: &MemoryStructure) -> Vec<RootOffset> {
+    let root_id = ms.root_class.class_id;
+    let Some(def) = ms.class_registry.get(root_id) else {
+        return Vec::new();
+    };
+    let mut out = Vec::new();
+    walk_class(
+        ms,
+        root_id,
+        0,
+        std::slice::from_ref(&def.name),
+        &mut Vec::new(),
+        &mut out,
+    );
+    out
+}
+
+fn walk_class(
+    ms: &MemoryStructure,
+    class_id: u64,
+    base: u64,
+    prefix: &[String],
+    stack: &mut Vec<u64>,
+    out: &mut Vec<RootOffset>,
+) {
+    let Some(def) = ms.class_registry.get(class_id) else {
+        return;
+    };
+    if stack.contains(&class_id) {
+        return;
+    }
+    stack.push(class_id);
+    // Inherited fields are members of the derived class itself
+    if let Some(base_id) = def
+        .base_class_id
+        .filter(|id| ms.class_registry.contains(*id))
+    {
+        walk_class(ms, base_id, base, prefix, stack, out);
+    }
+    for (fd, offset) in def.fields.iter().zip(ms.field_offsets(class_id)) {
+        if fd.field_type.is_hex_type() {
+            continue;
+        }
+        let name = fd.name.clone().unwrap_or_else(|| format!("var_{offset:X}"));
+        let mut path = prefix.to_vec();
+        path.push(name);
+        let offset = base.saturating_add(offset);
+        if let (FieldType::ClassInstance, Some(cid)) = (&fd.field_type, fd.class_id) {
+            out.push(RootOffset {
+                path: path.clone(),
+                offset,
+            });
+            walk_class(ms, cid, offset, &path, stack, out);
+        } else {
+            out.push(RootOffset { path, offset });
+        }
+    }
+    stack.pop();
+}
+
+/// C++ header with one `constexpr` per field in `namespace Offsets`, named after the path
+/// from the root, e.g. `Player_inventory_count`
+pub fn generate_offsets_cpp(ms: &MemoryStructure) -> String {
+    let mut out = String::new();
+    out.push_str("#pragma once\n\nnamespace Offsets {\n");
+    let mut used: HashSet<String> = HashSet::new();
+    for entry in collect_root_offsets(ms) {
+        let joined = entry
+            .path
+            .iter()
+            .map(|segment| sanitize_identifier(segment))
+            .collect::<Vec<_>>()
+            .join("_");
+        // Names that only differ in characters C++ does not allow would collide
+        let mut ident = joined.clone();
+        let mut n = 1;
+        while !used.insert(ident.clone()) {
+            n += 1;
+            ident = format!("{joined}_{n}");
+        }
+        let _ = writeln!(out, "    constexpr auto {ident} = 0x{:X};", entry.offset);
+    }
+    out.push_str("}\n");
+    out
+}
+
+/// JSON object mapping the dotted path of every field to its offset from the root, in
+/// layout order
+pub fn generate_offsets_json(ms: &MemoryStructure) -> String {
+    let lines: Vec<String> = collect_root_offsets(ms)
+        .into_iter()
+        .map(|entry| {
+            let key = serde_json::to_string(&entry.path.join(".")).unwrap_or_default();
+            format!("  {key}: {}", entry.offset)
+        })
+        .collect();
+    if lines.is_empty() {
+        return String::from("{}\n");
+    }
+    format!("{{\n{}\n}}\n", lines.join(",\n"))
+}
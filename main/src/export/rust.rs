@@ -0,0 +1,376 @@
+use std::{
+    collections::HashSet,
+    fmt::Write,
+};
+
+use super::cpp::{
+    class_name,
+    dependency_order,
+    enum_order,
+    sanitize_identifier,
+    write_comment,
+};
+use crate::memory::{
+    ClassDefinition,
+    EnumDefinition,
+    FieldDefinition,
+    FieldType,
+    MemoryStructure,
+    PointerTarget,
+};
+
+const RUST_KEYWORDS: &[&str] = &[
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl", "in",
+    "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+/// Generate Rust definitions for every enum and class of the memory structure.
+/// Structs are `#[repr(C)]`, or `#[repr(C, packed)]` when a member is not naturally aligned,
+/// and their size is checked at compile time. Embedded types are defined before their users;
+/// pointers are raw, so recursive types need no special treatment.
+pub fn generate_rust_struct(ms: &MemoryStructure) -> String {
+    let mut out = String::new();
+    out.push_str("#![allow(non_camel_case_types, non_snake_case, dead_code)]\n\n");
+    for id in enum_order(ms) {
+        if let Some(ed) = ms.enum_registry.get(id) {
+            write_enum(ed, &mut out);
+        }
+    }
+    for id in dependency_order(ms) {
+        if let Some(def) = ms.class_registry.get(id) {
+            write_struct(ms, def, &mut out);
+        }
+    }
+    out
+}
+
+/// Flag enums become a transparent integer wrapper with one constant per flag, since a Rust
+/// enum cannot hold combinations of its variants
+fn write_enum(ed: &EnumDefinition, out: &mut String) {
+    let name = rust_identifier(&ed.name);
+    let repr = if ed.is_signed {
+        signed_type(ed.default_size as u64)
+    } else {
+        unsigned_type(ed.default_size as u64)
+    };
+    if ed.is_flags {
+        let _ = writeln!(out, "#[repr(transparent)]");
+        let _ = writeln!(out, "#[derive(Debug, Clone, Copy, PartialEq, Eq)]");
+        let _ = writeln!(out, "pub struct {name}(pub {repr});\n");
+        let _ = writeln!(out, "impl {name} {{");
+        for variant in &ed.variants {
+            let _ = writeln!(
+                out,
+                "    pub const {}: Self = Self(0x{:X});",
+                rust_identifier(&variant.name),
+                variant.value
+            );
+        }
+        out.push_str("}\n\n");
+        return;
+    }
+    if ed.variants.is_empty() {
+        let _ = writeln!(out, "pub type {name} = {repr};\n");
+        return;
+    }
+    let _ = writeln!(out, "#[repr({repr})]");
+    let _ = writeln!(out, "#[derive(Debug, Clone, Copy, PartialEq, Eq)]");
+    let _ = writeln!(out, "pub enum {name} {{");
+    let mut seen = HashSet::new();
+    for variant in &ed.variants {
+        let variant_name = rust_identifier(&variant.name);
+        if seen.insert(variant.value) {
+            let _ = writeln!(out, "    {variant_name} = {},", variant.value);
+        } else {
+            // Rust enums cannot repeat a discriminant
+            let _ = writeln!(
+                out,
+                "    // {variant_name} = {} (duplicate value)",
+                variant.value
+            );
+        }
+    }
+    out.push_str("}\n\n");
+}
+
+fn write_struct(ms: &MemoryStructure, def: &ClassDefinition, out: &mut String) {
+    let name = rust_class_name(ms, def.id);
+    let (aligned, _) = class_layout(ms, def.id, &mut Vec::new());
+    let _ = writeln!(out, "#[repr({})]", if aligned { "C" } else { "C, packed" });
+    let _ = writeln!(out, "pub struct {name} {{");
+
+    // Rust has no inheritance; the base class becomes the first member
+    let mut offset: u64 = 0;
+    if let Some(base_id) = base_class(ms, def) {
+        let _ = writeln!(
+            out,
+            "    pub base: {}, // 0x0000",
+            rust_class_name(ms, base_id)
+        );
+        offset = ms.base_class_size(def.id);
+    }
+    let mut pad: Option<(u64, u64)> = None;
+    for fd in &def.fields {
+        let size = ms.field_size(fd, &mut vec![def.id]);
+        let field_name = fd
+            .name
+            .as_deref()
+            .map(rust_identifier)
+            .unwrap_or_else(|| format!("var_{offset:X}"));
+        let member = (!fd.field_type.is_hex_type())
+            .then(|| member_type(ms, def, fd))
+            .flatten();
+        let Some(ty) = member else {
+            if fd.comment.is_some() || !fd.field_type.is_hex_type() {
+                // Split the padding so the comment stays next to the bytes it describes
+                if let Some((start, len)) = pad.take() {
+                    write_padding(out, start, len);
+                }
+                write_comment(out, fd);
+            }
+            if !fd.field_type.is_hex_type() {
+                let _ = writeln!(
+                    out,
+                    "    // {field_name}: {} without a resolvable layout",
+                    fd.field_type
+                );
+            }
+            pad.get_or_insert((offset, 0)).1 += size;
+            offset += size;
+            continue;
+        };
+        if let Some((start, len)) = pad.take() {
+            write_padding(out, start, len);
+        }
+        write_comment(out, fd);
+        if fd.field_type == FieldType::Bitfield {
+            let (_, bit_offset, width) = fd.bitfield_layout();
+            let _ = writeln!(
+                out,
+                "    pub {field_name}: {ty}, // 0x{offset:04X} bits {bit_offset}..{}",
+                bit_offset + width
+            );
+        } else {
+            let _ = writeln!(out, "    pub {field_name}: {ty}, // 0x{offset:04X}");
+        }
+        offset += size;
+    }
+    if let Some((start, len)) = pad.take() {
+        write_padding(out, start, len);
+    }
+
+    out.push_str("}\n");
+    let _ = writeln!(
+        out,
+        "const _: () = assert!(core::mem::size_of::<{name}>() == 0x{offset:X});\n"
+    );
+}
+
+fn write_padding(out: &mut String, start: u64, len: u64) {
+    if len > 0 {
+        let _ = writeln!(
+            out,
+            "    _pad{start:04X}: [u8; 0x{len:X}], // 0x{start:04X}"
+        );
+    }
+}
+
+/// Rust type of a member, or `None` if it has no resolvable layout
+fn member_type(
+    ms: &MemoryStructure,
+    owner: &ClassDefinition,
+    fd: &FieldDefinition,
+) -> Option<String> {
+    match fd.field_type {
+        FieldType::ClassInstance => {
+            let cid = fd.class_id.filter(|cid| ms.class_registry.contains(*cid))?;
+            // A class embedding itself has no finite size
+            (!ms.would_create_cycle(owner.id, cid)).then(|| rust_class_name(ms, cid))
+        }
+        FieldType::Array => {
+            let len = fd.array_length.unwrap_or(0);
+            if len == 0 {
+                return None;
+            }
+            let element_size = ms.array_element_size(fd, &mut Vec::new());
+            let step = fd.array_step(element_size);
+            if step != element_size {
+                // Elements with a stride have no array type; keep the bytes so offsets hold
+                return Some(format!("[u8; 0x{:X}]", step * u64::from(len)));
+            }
+            if let Some(cid) = fd
+                .array_pointee_class()
+                .filter(|cid| ms.class_registry.contains(*cid))
+            {
+                return Some(format!("[*mut {}; {len}]", rust_class_name(ms, cid)));
+            }
+            let ty = inline_type(ms, fd.array_element.as_ref()?)?;
+            Some(format!("[{ty}; {len}]"))
+        }
+        FieldType::Enum => match fd.enum_id.and_then(|eid| ms.enum_registry.get(eid)) {
+            Some(ed) => Some(rust_identifier(&ed.name)),
+            None => Some("u32".to_string()),
+        },
+        FieldType::Bitfield => Some(unsigned_type(fd.bitfield_layout().0 as u64).to_string()),
+        FieldType::Pointer => Some(format!(
+            "*mut {}",
+            pointee_type(ms, fd.pointer_target.as_ref())
+        )),
+        FieldType::Text => Some(format!("[u8; {}]", fd.get_size())),
+        _ => primitive_type(&fd.field_type),
+    }
+}
+
+/// Rust type of a value stored inline (field or array element)
+fn inline_type(ms: &MemoryStructure, target: &PointerTarget) -> Option<String> {
+    match target {
+        PointerTarget::FieldType(t) => primitive_type(t),
+        PointerTarget::ClassId(cid) => ms
+            .class_registry
+            .contains(*cid)
+            .then(|| rust_class_name(ms, *cid)),
+        PointerTarget::EnumId(eid) => ms
+            .enum_registry
+            .get(*eid)
+            .map(|ed| rust_identifier(&ed.name)),
+        PointerTarget::Array { .. } | PointerTarget::Chain { .. } => None,
+    }
+}
+
+fn pointee_type(ms: &MemoryStructure, target: Option<&PointerTarget>) -> String {
+    match target {
+        Some(PointerTarget::Array { element, .. }) => pointee_type(ms, Some(element)),
+        Some(PointerTarget::Chain { .. }) | None => "core::ffi::c_void".to_string(),
+        Some(other) => inline_type(ms, other).unwrap_or_else(|| "core::ffi::c_void".to_string()),
+    }
+}
+
+fn primitive_type(t: &FieldType) -> Option<String> {
+    let ty = match t {
+        FieldType::Hex64 | FieldType::UInt64 => "u64".to_string(),
+        FieldType::Hex32 | FieldType::UInt32 => "u32".to_string(),
+        FieldType::Hex16 | FieldType::UInt16 => "u16".to_string(),
+        FieldType::Hex8 | FieldType::UInt8 => "u8".to_string(),
+        FieldType::Int64 => "i64".to_string(),
+        FieldType::Int32 => "i32".to_string(),
+        FieldType::Int16 => "i16".to_string(),
+        FieldType::Int8 => "i8".to_string(),
+        FieldType::Bool => "bool".to_string(),
+        FieldType::Float => "f32".to_string(),
+        FieldType::Double => "f64".to_string(),
+        FieldType::Vector4 | FieldType::Vector3 | FieldType::Vector2 | FieldType::Quaternion => {
+            format!("[f32; {}]", t.get_size() / 4)
+        }
+        FieldType::Matrix4x4 => "[[f32; 4]; 4]".to_string(),
+        FieldType::Guid => "[u8; 16]".to_string(),
+        FieldType::UnixTime32 => "u32".to_string(),
+        FieldType::UnixTime64 => "i64".to_string(),
+        FieldType::Win32FileTime => "u64".to_string(),
+        FieldType::Text => format!("[u8; {}]", t.get_size()),
+        FieldType::TextPointer => "*mut u8".to_string(),
+        FieldType::WideText => format!("[u16; {}]", t.get_size() / 2),
+        FieldType::WideTextPointer => "*mut u16".to_string(),
+        FieldType::Pointer => "*mut core::ffi::c_void".to_string(),
+        FieldType::Enum => "u32".to_string(),
+        FieldType::ClassInstance | FieldType::Array | FieldType::Bitfield => return None,
+    };
+    Some(ty)
+}
+
+fn field_align(ms: &MemoryStructure, fd: &FieldDefinition, stack: &mut Vec<u64>) -> u64 {
+    let enum_align = |eid: u64| {
+        ms.enum_registry
+            .get(eid)
+            .map(|ed| ed.default_size as u64)
+            .unwrap_or(4)
+    };
+    match fd.field_type {
+        FieldType::ClassInstance => fd
+            .class_id
+            .map(|cid| class_layout(ms, cid, stack).1)
+            .unwrap_or(1),
+        FieldType::Array => match &fd.array_element {
+            Some(PointerTarget::FieldType(t)) => t.natural_alignment(ms.pointer_size()),
+            Some(PointerTarget::ClassId(cid)) => class_layout(ms, *cid, stack).1,
+            Some(PointerTarget::EnumId(eid)) => enum_align(*eid),
+            _ => 1,
+        },
+        FieldType::Enum => fd.enum_id.map(enum_align).unwrap_or(4),
+        FieldType::Bitfield => fd.bitfield_layout().0 as u64,
+        _ => fd.field_type.natural_alignment(ms.pointer_size()),
+    }
+}
+
+/// Whether `#[repr(C)]` reproduces the class layout without inserting padding, i.e. every
+/// member sits at a multiple of its alignment and the size is a multiple of the largest one,
+/// together with the alignment of the emitted struct (1 when it has to be packed)
+fn class_layout(ms: &MemoryStructure, class_id: u64, stack: &mut Vec<u64>) -> (bool, u64) {
+    let Some(def) = ms.class_registry.get(class_id) else {
+        return (true, 1);
+    };
+    if stack.contains(&class_id) {
+        return (true, 1);
+    }
+    stack.push(class_id);
+    let mut offset: u64 = 0;
+    let mut max_align: u64 = 1;
+    let mut aligned = true;
+    if let Some(base_id) = base_class(ms, def) {
+        max_align = class_layout(ms, base_id, stack).1;
+        offset = ms.base_class_size(class_id);
+    }
+    for fd in &def.fields {
+        if !fd.field_type.is_hex_type() && member_type(ms, def, fd).is_some() {
+            let align = field_align(ms, fd, stack);
+            aligned &= offset.is_multiple_of(align);
+            max_align = max_align.max(align);
+        }
+        offset += ms.field_size(fd, stack);
+    }
+    stack.pop();
+    aligned &= offset.is_multiple_of(max_align);
+    (aligned, if aligned { max_align } else { 1 })
+}
+
+/// Base class of `def` that can be embedded as its first member
+fn base_class(ms: &MemoryStructure, def: &ClassDefinition) -> Option<u64> {
+    def.base_class_id.filter(|base_id| {
+        ms.class_registry.contains(*base_id) && !ms.would_create_cycle(def.id, *base_id)
+    })
+}
+
+fn unsigned_type(size: u64) -> &'static str {
+    match size {
+        1 => "u8",
+        2 => "u16",
+        8 => "u64",
+        _ => "u32",
+    }
+}
+
+fn signed_type(size: u64) -> &'static str {
+    match size {
+        1 => "i8",
+        2 => "i16",
+        8 => "i64",
+        _ => "i32",
+    }
+}
+
+fn rust_class_name(ms: &MemoryStructure, id: u64) -> String {
+    escape_keyword(class_name(ms, id))
+}
+
+fn rust_identifier(name: &str) -> String {
+    escape_keyword(sanitize_identifier(name))
+}
+
+fn escape_keyword(mut ident: String) -> String {
+    if RUST_KEYWORDS.contains(&ident.as_str()) {
+        ident.push('_');
+    }
+    ident
+}
@@ -0,0 +1,381 @@
+use crate::{
+    export::{
+        cpp::{
+            generate_cpp_header,
+            generate_cpp_snippet,
+        },
+        offsets::{
+            collect_root_offsets,
+            generate_offsets_cpp,
+            generate_offsets_json,
+        },
+        rust::generate_rust_struct,
+    },
+    memory::{
+        ClassDefinition,
+        EnumDefinition,
+        EnumVariant,
+        FieldType,
+        MemoryStructure,
+        PointerTarget,
+    },
+};
+
+#[cfg(test)]
+mod cpp_export_tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_hex_runs_become_padding() {
+        let mut root = ClassDefinition::new("Player".to_string());
+        root.add_hex_field(FieldType::Hex64);
+        root.add_hex_field(FieldType::Hex32);
+        root.add_named_field("health".to_string(), FieldType::Int32);
+        root.add_named_field("speed".to_string(), FieldType::Float);
+        root.add_hex_field(FieldType::Hex8);
+        let ms = MemoryStructure::new("root".to_string(), 0, root);
+
+        let header = generate_cpp_header(&ms);
+        assert!(header.contains("#pragma pack(push, 1)"));
+        assert!(header.contains("#pragma pack(pop)"));
+        assert!(header.contains("struct Player {"));
+        assert!(header.contains("    char pad0000[0xC]; // 0x0000"));
+        assert!(header.contains("    int32_t health; // 0x000C"));
+        assert!(header.contains("    float speed; // 0x0010"));
+        assert!(header.contains("    char pad0014[0x1]; // 0x0014"));
+        assert!(header.contains("static_assert(sizeof(Player) == 0x15"));
+    }
+
+    #[test]
+    fn test_dependencies_emitted_first() {
+        let mut inner = ClassDefinition::new("AInner".to_string());
+        inner.add_named_field("x".to_string(), FieldType::Double);
+
+        let mut root = ClassDefinition::new("Outer".to_string());
+        root.add_class_instance("inner".to_string(), &inner);
+        root.add_named_field("next".to_string(), FieldType::Int64);
+        root.set_field_type_at(1, FieldType::Pointer);
+        root.fields[1].pointer_target = Some(PointerTarget::ClassId(root.id));
+
+        // Name sorts before its dependency to make sure ordering is by dependency
+        let mut first = ClassDefinition::new("0First".to_string());
+        first.add_class_instance("outer".to_string(), &root);
+
+        let mut ms = MemoryStructure::new("root".to_string(), 0, root);
+        ms.register_class(inner);
+        ms.register_class(first);
+
+        let header = generate_cpp_header(&ms);
+        let pos = |needle: &str| header.find(needle).expect(needle);
+        assert!(pos("struct Outer;") < pos("#pragma pack(push, 1)"));
+        assert!(pos("struct AInner {") < pos("struct Outer {"));
+        assert!(pos("struct Outer {") < pos("struct _0First {"));
+        assert!(header.contains("    AInner inner; // 0x0000"));
+        assert!(header.contains("    Outer* next; // 0x0008"));
+        assert!(header.contains("    Outer outer; // 0x0000"));
+        assert!(header.contains("static_assert(sizeof(_0First) == 0x10"));
+    }
+
+    #[test]
+    fn test_array_of_class_pointers() {
+        let entity = ClassDefinition::new("Entity".to_string());
+        let mut root = ClassDefinition::new("EntityList".to_string());
+        root.add_named_field("entities".to_string(), FieldType::Int32);
+        root.set_field_type_at(0, FieldType::Array);
+        root.fields[0].array_element = Some(PointerTarget::FieldType(FieldType::Pointer));
+        root.fields[0].pointer_target = Some(PointerTarget::ClassId(entity.id));
+        root.fields[0].array_length = Some(4);
+        let mut ms = MemoryStructure::new("root".to_string(), 0, root);
+        ms.register_class(entity);
+
+        let header = generate_cpp_header(&ms);
+        assert!(header.contains("    Entity* entities[4]; // 0x0000"));
+        let rust = crate::export::rust::generate_rust_struct(&ms);
+        assert!(rust.contains("    pub entities: [*mut Entity; 4], // 0x0000"));
+    }
+
+    #[test]
+    fn test_strided_array_keeps_its_bytes() {
+        let mut root = ClassDefinition::new("Slots".to_string());
+        root.add_named_field("slots".to_string(), FieldType::Int32);
+        root.set_field_type_at(0, FieldType::Array);
+        root.fields[0].array_element = Some(PointerTarget::FieldType(FieldType::Int32));
+        root.fields[0].array_length = Some(4);
+        root.set_array_stride_at(0, Some(8));
+        root.add_named_field("count".to_string(), FieldType::Int32);
+        let ms = MemoryStructure::new("root".to_string(), 0, root);
+
+        let header = generate_cpp_header(&ms);
+        assert!(header.contains("    char slots[0x20]; // 0x0000"));
+        assert!(header.contains("    int32_t count; // 0x0020"));
+        let rust = crate::export::rust::generate_rust_struct(&ms);
+        assert!(rust.contains("    pub slots: [u8; 0x20], // 0x0000"));
+    }
+
+    #[test]
+    fn test_signed_enum_backing_type() {
+        let mut en = EnumDefinition::new("Team".to_string());
+        en.is_signed = true;
+        en.variants.push(EnumVariant {
+            name: "None".to_string(),
+            value: -1,
+        });
+        let mut ms = MemoryStructure::new(
+            "root".to_string(),
+            0,
+            ClassDefinition::new("Root".to_string()),
+        );
+        ms.enum_registry.register(en);
+
+        let header = generate_cpp_header(&ms);
+        assert!(header.contains("enum class Team : int32_t {"));
+        assert!(header.contains("    None = -1,"));
+    }
+
+    #[test]
+    fn test_enums_and_arrays() {
+        let mut en = EnumDefinition::new("State".to_string());
+        en.default_size = 2;
+        en.variants.push(EnumVariant {
+            name: "Idle".to_string(),
+            value: 0,
+        });
+        en.variants.push(EnumVariant {
+            name: "Running".to_string(),
+            value: 3,
+        });
+
+        let mut root = ClassDefinition::new("Root".to_string());
+        root.add_named_field("state".to_string(), FieldType::Int32);
+        root.set_field_type_at(0, FieldType::Enum);
+        root.fields[0].enum_id = Some(en.id);
+        root.add_named_field("values".to_string(), FieldType::Int32);
+        root.set_field_type_at(1, FieldType::Array);
+        root.fields[1].array_element = Some(PointerTarget::FieldType(FieldType::Vector3));
+        root.fields[1].array_length = Some(4);
+
+        let mut ms = MemoryStructure::new("root".to_string(), 0, root);
+        ms.enum_registry.register(en);
+
+        let header = generate_cpp_header(&ms);
+        assert!(header.contains("enum class State : uint16_t {"));
+        assert!(header.contains("    Running = 3,"));
+        assert!(header.contains("    State state; // 0x0000"));
+        assert!(header.contains("    float values[4][3]; // 0x0002"));
+        assert!(header.contains("static_assert(sizeof(Root) == 0x32"));
+    }
+
+    #[test]
+    fn test_bitfields_fill_their_unit() {
+        let mut root = ClassDefinition::new("Flags".to_string());
+        root.add_named_field("visible".to_string(), FieldType::Int32);
+        root.set_field_type_at(0, FieldType::Bitfield);
+        root.set_bitfield_at(0, 1, 2, 1);
+        root.add_named_field("mode".to_string(), FieldType::Int32);
+        root.set_field_type_at(1, FieldType::Bitfield);
+        root.set_bitfield_at(1, 2, 0, 16);
+        let ms = MemoryStructure::new("root".to_string(), 0, root);
+
+        let header = generate_cpp_header(&ms);
+        assert!(header.contains("    uint8_t : 2; uint8_t visible : 1; uint8_t : 5; // 0x0000"));
+        assert!(header.contains("    uint16_t mode : 16; // 0x0001"));
+        assert!(header.contains("static_assert(sizeof(Flags) == 0x3"));
+    }
+
+    #[test]
+    fn test_comments_are_emitted() {
+        let mut root = ClassDefinition::new("Player".to_string());
+        root.add_hex_field(FieldType::Hex32);
+        root.add_hex_field(FieldType::Hex32);
+        root.add_named_field("health".to_string(), FieldType::Int32);
+        root.set_comment_at(1, "vtable index?");
+        root.set_comment_at(2, "current hp\nmax is at +0x10");
+        let ms = MemoryStructure::new("root".to_string(), 0, root);
+
+        let header = generate_cpp_header(&ms);
+        assert!(header.contains(
+            "    char pad0000[0x4]; // 0x0000\n    // vtable index?\n    char pad0004[0x4]; // 0x0004\n"
+        ));
+        assert!(header.contains(
+            "    // current hp\n    // max is at +0x10\n    int32_t health; // 0x0008\n"
+        ));
+    }
+
+    #[test]
+    fn test_snippet_keeps_offsets_of_selected_fields() {
+        let mut root = ClassDefinition::new("Player".to_string());
+        root.add_hex_field(FieldType::Hex64);
+        root.add_named_field("health".to_string(), FieldType::Int32);
+        root.add_named_field("armor".to_string(), FieldType::Int32);
+        root.add_hex_field(FieldType::Hex32);
+        root.add_named_field("speed".to_string(), FieldType::Float);
+        let selected: HashSet<u64> = [1, 3, 4].iter().map(|i| root.fields[*i].id).collect();
+        let class_id = root.id;
+        let ms = MemoryStructure::new("root".to_string(), 0, root);
+
+        let snippet = generate_cpp_snippet(&ms, class_id, &selected);
+        assert!(snippet.contains("    char pad0000[0x8]; // +0x0000"));
+        assert!(snippet.contains("    int32_t health; // +0x0008"));
+        // The unselected armor field and the Hex32 after it share one pad
+        assert!(snippet.contains("    char pad000C[0x8]; // +0x000C"));
+        assert!(snippet.contains("    float speed; // +0x0014"));
+        assert!(!snippet.contains("armor"));
+        assert!(!snippet.contains("struct"));
+    }
+
+    #[test]
+    fn test_base_class_is_inherited() {
+        let mut base = ClassDefinition::new("Entity".to_string());
+        base.add_named_field("id".to_string(), FieldType::Int64);
+        let mut root = ClassDefinition::new("Player".to_string());
+        root.add_named_field("health".to_string(), FieldType::Float);
+        let (base_id, root_id) = (base.id, root.id);
+        let mut ms = MemoryStructure::new("root".to_string(), 0, root);
+        ms.register_class(base);
+        assert!(ms.set_base_class(root_id, Some(base_id)));
+
+        let header = generate_cpp_header(&ms);
+        assert!(header.contains("struct Player : Entity {"));
+        assert!(header.contains("    float health; // 0x0008"));
+        assert!(header.contains("static_assert(sizeof(Player) == 0xC"));
+        assert!(header.find("struct Entity {") < header.find("struct Player : Entity {"));
+
+        let rust = generate_rust_struct(&ms);
+        assert!(rust.contains("    pub base: Entity, // 0x0000"));
+        assert!(rust.contains("    pub health: f32, // 0x0008"));
+        assert!(rust.contains("size_of::<Player>() == 0xC"));
+    }
+}
+
+#[cfg(test)]
+mod rust_export_tests {
+    use super::*;
+
+    #[test]
+    fn test_aligned_struct_is_repr_c() {
+        let mut root = ClassDefinition::new("Player".to_string());
+        root.add_hex_field(FieldType::Hex64);
+        root.add_named_field("health".to_string(), FieldType::Int32);
+        root.add_named_field("speed".to_string(), FieldType::Float);
+        root.add_named_field("type".to_string(), FieldType::Pointer);
+        let ms = MemoryStructure::new("root".to_string(), 0, root);
+
+        let out = generate_rust_struct(&ms);
+        assert!(out.contains("#[repr(C)]\npub struct Player {"));
+        assert!(out.contains("    _pad0000: [u8; 0x8], // 0x0000"));
+        assert!(out.contains("    pub health: i32, // 0x0008"));
+        assert!(out.contains("    pub speed: f32, // 0x000C"));
+        assert!(out.contains("    pub type_: *mut core::ffi::c_void, // 0x0010"));
+        assert!(out.contains("const _: () = assert!(core::mem::size_of::<Player>() == 0x18);"));
+    }
+
+    #[test]
+    fn test_misaligned_struct_is_packed() {
+        let mut root = ClassDefinition::new("Packed".to_string());
+        root.add_named_field("flag".to_string(), FieldType::Bool);
+        root.add_named_field("value".to_string(), FieldType::Double);
+        let ms = MemoryStructure::new("root".to_string(), 0, root);
+
+        let out = generate_rust_struct(&ms);
+        assert!(out.contains("#[repr(C, packed)]\npub struct Packed {"));
+        assert!(out.contains("    pub value: f64, // 0x0001"));
+        assert!(out.contains("size_of::<Packed>() == 0x9"));
+    }
+
+    #[test]
+    fn test_enums_embedded_classes_and_pointers() {
+        let mut state = EnumDefinition::new("State".to_string());
+        state.default_size = 2;
+        state.variants = vec![
+            EnumVariant {
+                name: "Idle".to_string(),
+                value: 0,
+            },
+            EnumVariant {
+                name: "Also idle".to_string(),
+                value: 0,
+            },
+        ];
+        let mut flags = EnumDefinition::new("Flags".to_string());
+        flags.is_flags = true;
+        flags.variants = vec![EnumVariant {
+            name: "Visible".to_string(),
+            value: 4,
+        }];
+
+        let mut inner = ClassDefinition::new("Inner".to_string());
+        inner.add_named_field("x".to_string(), FieldType::Vector3);
+        let mut root = ClassDefinition::new("Root".to_string());
+        root.add_named_field("next".to_string(), FieldType::Pointer);
+        root.fields[0].pointer_target = Some(PointerTarget::ClassId(root.id));
+        root.add_named_field("state".to_string(), FieldType::Enum);
+        root.fields[1].enum_id = Some(state.id);
+        root.add_hex_field(FieldType::Hex16);
+        root.add_class_instance("inner".to_string(), &inner);
+
+        let mut ms = MemoryStructure::new("root".to_string(), 0, root);
+        ms.register_class(inner);
+        ms.enum_registry.register(state);
+        ms.enum_registry.register(flags);
+
+        let out = generate_rust_struct(&ms);
+        assert!(out.contains("#[repr(u16)]"));
+        assert!(out.contains(
+            "pub enum State {\n    Idle = 0,\n    // Also_idle = 0 (duplicate value)\n}"
+        ));
+        assert!(out.contains("pub struct Flags(pub u32);"));
+        assert!(out.contains("    pub const Visible: Self = Self(0x4);"));
+        assert!(out.find("pub struct Inner").unwrap() < out.find("pub struct Root").unwrap());
+        assert!(out.contains("    pub next: *mut Root, // 0x0000"));
+        assert!(out.contains("    pub state: State, // 0x0008"));
+        assert!(out.contains("    _pad000A: [u8; 0x2], // 0x000A"));
+        assert!(out.contains("    pub inner: Inner, // 0x000C"));
+        assert!(out.contains("    pub x: [f32; 3], // 0x0000"));
+        assert!(out.contains("size_of::<Root>() == 0x18"));
+    }
+}
+
+#[cfg(test)]
+mod offsets_export_tests {
+    use super::*;
+
+    #[test]
+    fn test_offsets_follow_embedded_instances_and_bases() {
+        let mut base = ClassDefinition::new("Entity".to_string());
+        base.add_named_field("id".to_string(), FieldType::Int64);
+        let mut stats = ClassDefinition::new("Stats".to_string());
+        stats.add_hex_field(FieldType::Hex32);
+        stats.add_named_field("health".to_string(), FieldType::Float);
+        let mut root = ClassDefinition::new("Player".to_string());
+        root.add_class_instance("stats".to_string(), &stats);
+        root.add_named_field("target".to_string(), FieldType::Pointer);
+        root.fields[1].pointer_target = Some(PointerTarget::ClassId(stats.id));
+        let (base_id, root_id) = (base.id, root.id);
+        let mut ms = MemoryStructure::new("root".to_string(), 0, root);
+        ms.register_class(base);
+        ms.register_class(stats);
+        assert!(ms.set_base_class(root_id, Some(base_id)));
+
+        let offsets: Vec<(String, u64)> = collect_root_offsets(&ms)
+            .into_iter()
+            .map(|entry| (entry.path.join("."), entry.offset))
+            .collect();
+        assert_eq!(
+            offsets,
+            vec![
+                ("Player.id".to_string(), 0x0),
+                ("Player.stats".to_string(), 0x8),
+                ("Player.stats.health".to_string(), 0xC),
+                ("Player.target".to_string(), 0x10),
+            ]
+        );
+
+        let cpp = generate_offsets_cpp(&ms);
+        assert!(cpp.contains("namespace Offsets {"));
+        assert!(cpp.contains("    constexpr auto Player_stats_health = 0xC;"));
+        let json: serde_json::Value = serde_json::from_str(&generate_offsets_json(&ms)).unwrap();
+        assert_eq!(json["Player.target"], 16);
+    }
+}
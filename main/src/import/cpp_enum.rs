@@ -0,0 +1,292 @@
+use std::fmt;
+
+use crate::memory::EnumVariant;
+
+/// Why pasted C/C++ enum source could not be turned into variants
+#[derive(Debug, PartialEq, Eq)]
+pub enum EnumParseError {
+    /// An opening `{` without a closing `}`
+    UnterminatedBody,
+    /// The body does not declare any enumerator
+    NoVariants,
+    /// An enumerator name is not a C identifier
+    InvalidName(String),
+    /// The value of an enumerator is not an integer expression we understand
+    InvalidValue { name: String, value: String },
+}
+
+impl fmt::Display for EnumParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EnumParseError::UnterminatedBody => write!(f, "Missing closing '}}'"),
+            EnumParseError::NoVariants => write!(f, "No enumerators found"),
+            EnumParseError::InvalidName(name) => write!(f, "Invalid enumerator name '{name}'"),
+            EnumParseError::InvalidValue { name, value } => {
+                write!(f, "Cannot evaluate value of {name}: '{value}'")
+            }
+        }
+    }
+}
+
+impl std::error::Error for EnumParseError {}
+
+/// Enum parsed from C/C++ source
+#[derive(Debug, Clone)]
+pub struct ParsedEnum {
+    pub variants: Vec<EnumVariant>,
+    /// From the underlying type if given (`enum class Foo : uint8_t`), otherwise 4 bytes
+    /// unless a value needs 8
+    pub default_size: u8,
+    pub is_signed: bool,
+}
+
+/// Parse `enum Foo { A = 1, B, C = 0x10 };` (also `enum class`, `typedef enum { .. } Foo;` or
+/// just the enumerator list). Unspecified values continue from the previous one; values may be
+/// decimal, hex, octal or binary literals, earlier enumerators, and `|`, `<<`, `+`, `-`
+/// combinations of them.
+pub fn parse_cpp_enum(text: &str) -> Result<ParsedEnum, EnumParseError> {
+    let text = strip_comments(text);
+    let (header, body) = match text.find('{') {
+        Some(open) => {
+            let close = text[open..]
+                .rfind('}')
+                .map(|i| open + i)
+                .ok_or(EnumParseError::UnterminatedBody)?;
+            (&text[..open], &text[open + 1..close])
+        }
+        None => ("", text.as_str()),
+    };
+    // `enum class Foo : uint8_t`
+    let underlying = header
+        .split_once(':')
+        .and_then(|(_, ty)| underlying_type(ty));
+
+    let mut variants: Vec<EnumVariant> = Vec::new();
+    let mut next: i64 = 0;
+    for entry in body.split(',') {
+        let entry = entry.trim().trim_end_matches(';').trim();
+        if entry.is_empty() {
+            continue;
+        }
+        let (variant_name, expr) = match entry.split_once('=') {
+            Some((n, e)) => (n.trim(), Some(e.trim())),
+            None => (entry, None),
+        };
+        if !is_identifier(variant_name) {
+            return Err(EnumParseError::InvalidName(variant_name.to_string()));
+        }
+        let value = match expr {
+            Some(expr) => ExprParser::new(expr, &variants).parse().ok_or_else(|| {
+                EnumParseError::InvalidValue {
+                    name: variant_name.to_string(),
+                    value: expr.to_string(),
+                }
+            })?,
+            None => next,
+        };
+        variants.push(EnumVariant {
+            name: variant_name.to_string(),
+            value,
+        });
+        next = value.wrapping_add(1);
+    }
+    if variants.is_empty() {
+        return Err(EnumParseError::NoVariants);
+    }
+
+    let has_negative = variants.iter().any(|v| v.value < 0);
+    let (default_size, is_signed) = underlying.unwrap_or_else(|| {
+        let fits_32 = variants
+            .iter()
+            .all(|v| v.value >= i64::from(i32::MIN) && v.value <= i64::from(u32::MAX));
+        (if fits_32 { 4 } else { 8 }, has_negative)
+    });
+    Ok(ParsedEnum {
+        variants,
+        default_size,
+        is_signed,
+    })
+}
+
+fn strip_comments(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut rest = text;
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix("//") {
+            rest = after.find('\n').map(|i| &after[i..]).unwrap_or("");
+        } else if let Some(after) = rest.strip_prefix("/*") {
+            rest = after.find("*/").map(|i| &after[i + 2..]).unwrap_or("");
+            out.push(' ');
+        } else {
+            let ch = rest.chars().next().unwrap_or_default();
+            out.push(ch);
+            rest = &rest[ch.len_utf8()..];
+        }
+    }
+    out
+}
+
+/// Size and signedness of an explicit underlying type
+fn underlying_type(ty: &str) -> Option<(u8, bool)> {
+    let words: Vec<&str> = ty
+        .split_whitespace()
+        .map(|w| w.trim_start_matches("std::"))
+        .collect();
+    let unsigned = words.contains(&"unsigned");
+    let base = words
+        .iter()
+        .copied()
+        .rfind(|w| !matches!(*w, "unsigned" | "signed" | "int"))
+        .unwrap_or("int");
+    let size_and_sign = match base {
+        "uint8_t" | "BYTE" | "UCHAR" => (1, false),
+        "int8_t" | "char" => (1, !unsigned),
+        "uint16_t" | "WORD" | "USHORT" => (2, false),
+        "int16_t" | "short" => (2, !unsigned),
+        "uint32_t" | "DWORD" | "UINT" | "ULONG" => (4, false),
+        "int32_t" | "int" | "INT" | "LONG" => (4, !unsigned),
+        // `long long` is 8 bytes, a single `long` is 4 on Windows
+        "long" if words.iter().filter(|w| **w == "long").count() == 2 => (8, !unsigned),
+        "long" => (4, !unsigned),
+        "uint64_t" | "QWORD" | "ULONGLONG" | "DWORD64" | "UINT64" => (8, false),
+        "int64_t" | "__int64" | "LONGLONG" | "INT64" => (8, !unsigned),
+        _ => return None,
+    };
+    Some(size_and_sign)
+}
+
+fn is_identifier(token: &str) -> bool {
+    let mut chars = token.chars();
+    chars
+        .next()
+        .map(|c| c == '_' || c.is_ascii_alphabetic())
+        .unwrap_or(false)
+        && chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+}
+
+/// Integer expression of an enumerator value, in C precedence: `|` < `<<`/`>>` < `+`/`-` < unary
+struct ExprParser<'a> {
+    s: &'a [u8],
+    i: usize,
+    previous: &'a [EnumVariant],
+}
+
+impl<'a> ExprParser<'a> {
+    fn new(s: &'a str, previous: &'a [EnumVariant]) -> Self {
+        Self {
+            s: s.as_bytes(),
+            i: 0,
+            previous,
+        }
+    }
+
+    fn parse(mut self) -> Option<i64> {
+        let value = self.bit_or()?;
+        self.skip_ws();
+        (self.i == self.s.len()).then_some(value)
+    }
+
+    fn skip_ws(&mut self) {
+        while self.s.get(self.i).is_some_and(u8::is_ascii_whitespace) {
+            self.i += 1;
+        }
+    }
+
+    fn eat(&mut self, token: &str) -> bool {
+        self.skip_ws();
+        if self.s[self.i..].starts_with(token.as_bytes()) {
+            self.i += token.len();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn bit_or(&mut self) -> Option<i64> {
+        let mut value = self.shift()?;
+        while self.eat("|") {
+            value |= self.shift()?;
+        }
+        Some(value)
+    }
+
+    fn shift(&mut self) -> Option<i64> {
+        let mut value = self.additive()?;
+        loop {
+            if self.eat("<<") {
+                value = value.checked_shl(u32::try_from(self.additive()?).ok()?)?;
+            } else if self.eat(">>") {
+                value = value.checked_shr(u32::try_from(self.additive()?).ok()?)?;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    fn additive(&mut self) -> Option<i64> {
+        let mut value = self.unary()?;
+        loop {
+            if self.eat("+") {
+                value = value.wrapping_add(self.unary()?);
+            } else if self.eat("-") {
+                value = value.wrapping_sub(self.unary()?);
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    fn unary(&mut self) -> Option<i64> {
+        if self.eat("-") {
+            return self.unary().map(i64::wrapping_neg);
+        }
+        if self.eat("~") {
+            return self.unary().map(|v| !v);
+        }
+        if self.eat("+") {
+            return self.unary();
+        }
+        if self.eat("(") {
+            let value = self.bit_or()?;
+            return self.eat(")").then_some(value);
+        }
+        self.skip_ws();
+        let start = self.i;
+        while self
+            .s
+            .get(self.i)
+            .is_some_and(|c| *c == b'_' || c.is_ascii_alphanumeric())
+        {
+            self.i += 1;
+        }
+        let token = std::str::from_utf8(&self.s[start..self.i]).ok()?;
+        if token.starts_with(|c: char| c.is_ascii_digit()) {
+            parse_literal(token)
+        } else {
+            self.previous
+                .iter()
+                .rev()
+                .find(|v| v.name == token)
+                .map(|v| v.value)
+        }
+    }
+}
+
+/// Decimal, `0x` hex, `0b` binary or leading-zero octal literal with optional `u`/`l` suffixes
+fn parse_literal(token: &str) -> Option<i64> {
+    let lower = token.to_ascii_lowercase();
+    let (digits, radix) = if let Some(hex) = lower.strip_prefix("0x") {
+        (hex.trim_end_matches(['u', 'l']), 16)
+    } else if let Some(bin) = lower.strip_prefix("0b") {
+        (bin.trim_end_matches(['u', 'l']), 2)
+    } else {
+        let dec = lower.trim_end_matches(['u', 'l']);
+        if dec.len() > 1 && dec.starts_with('0') {
+            (&dec[1..], 8)
+        } else {
+            (dec, 10)
+        }
+    };
+    // Literals above i64::MAX keep their bit pattern, like an unsigned 64-bit enum
+    u64::from_str_radix(digits, radix).ok().map(|v| v as i64)
+}
@@ -0,0 +1,5 @@
+pub mod cpp_enum;
+pub mod reclass_net;
+
+#[cfg(test)]
+mod tests;
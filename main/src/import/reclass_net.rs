@@ -0,0 +1,391 @@
+use std::{
+    collections::HashMap,
+    fmt,
+};
+
+use roxmltree::{
+    Document,
+    Node,
+};
+
+use crate::memory::{
+    ClassDefinition,
+    EnumDefinition,
+    EnumVariant,
+    FieldDefinition,
+    FieldType,
+    MemoryStructure,
+    PointerTarget,
+    TEXT_MAX_LENGTH,
+};
+
+/// Errors that abort a ReClass.NET import
+#[derive(Debug)]
+pub enum ImportError {
+    /// The input is not well-formed XML
+    Xml(String),
+    /// The document does not contain any class definitions
+    NoClasses,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ImportError::Xml(err) => write!(f, "Invalid XML: {err}"),
+            ImportError::NoClasses => write!(f, "The file does not contain any classes."),
+        }
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+/// Lookup tables from ReClass.NET references into our id space
+struct ImportContext {
+    class_ids: HashMap<String, u64>,
+    enums: HashMap<String, (u64, u8)>,
+}
+
+/// Embedded class instance whose class id is assigned once all classes are registered,
+/// so that cyclic layouts can be detected and degraded to pointers
+struct PendingInstance {
+    owner_id: u64,
+    field_id: u64,
+    target_id: u64,
+}
+
+/// Import a ReClass.NET project (`<reclass><classes><class><node .../>`) into a memory structure.
+/// The first class becomes the root class. Unsupported node types are replaced by hex fields
+/// of the same size.
+pub fn import_reclass_net(xml: &str) -> Result<MemoryStructure, ImportError> {
+    let doc = Document::parse(xml).map_err(|err| ImportError::Xml(err.to_string()))?;
+    let root = doc.root_element();
+
+    let mut ctx = ImportContext {
+        class_ids: HashMap::new(),
+        enums: HashMap::new(),
+    };
+
+    let mut enum_defs = Vec::new();
+    for enum_node in elements_named(root, "enums").flat_map(|n| children_named(n, "enum")) {
+        let name = attr(enum_node, "name").unwrap_or("Enum").to_string();
+        let mut def = EnumDefinition::new(name.clone());
+        def.default_size = attr(enum_node, "size")
+            .and_then(|s| s.parse::<u8>().ok())
+            .filter(|size| matches!(size, 1 | 2 | 4 | 8))
+            .unwrap_or(4);
+        def.is_flags = attr(enum_node, "use_flags_mode")
+            .map(|v| v.eq_ignore_ascii_case("true"))
+            .unwrap_or(false);
+        for item in children_named(enum_node, "item") {
+            let value = attr(item, "value")
+                .and_then(|v| v.parse::<i64>().ok())
+                .unwrap_or(0);
+            def.is_signed |= value < 0;
+            def.variants.push(EnumVariant {
+                name: attr(item, "name").unwrap_or("Value").to_string(),
+                value,
+            });
+        }
+        ctx.enums.insert(name, (def.id, def.default_size));
+        enum_defs.push(def);
+    }
+
+    // First pass: allocate class ids so references can be resolved in any order
+    let class_nodes: Vec<Node> = elements_named(root, "classes")
+        .flat_map(|n| children_named(n, "class"))
+        .collect();
+    if class_nodes.is_empty() {
+        return Err(ImportError::NoClasses);
+    }
+    let mut class_defs: Vec<ClassDefinition> = Vec::with_capacity(class_nodes.len());
+    for class_node in &class_nodes {
+        let name = attr(*class_node, "name").unwrap_or("Class").to_string();
+        let def = ClassDefinition::new(name.clone());
+        if let Some(uuid) = attr(*class_node, "uuid") {
+            ctx.class_ids.insert(uuid.to_string(), def.id);
+        }
+        ctx.class_ids.entry(name).or_insert(def.id);
+        class_defs.push(def);
+    }
+
+    // Second pass: convert the nodes of every class
+    let mut pending = Vec::new();
+    for (class_node, def) in class_nodes.iter().zip(class_defs.iter_mut()) {
+        for node in children_named(*class_node, "node") {
+            convert_node(&ctx, node, def, &mut pending);
+        }
+    }
+
+    let mut defs = class_defs.into_iter();
+    let root_def = defs.next().ok_or(ImportError::NoClasses)?;
+    let mut ms = MemoryStructure::new("root".to_string(), 0, root_def);
+    for def in defs {
+        ms.class_registry.register(def);
+    }
+    for def in enum_defs {
+        ms.enum_registry.register(def);
+    }
+
+    for p in pending {
+        let cyclic = ms.would_create_cycle(p.owner_id, p.target_id);
+        let Some(def) = ms.class_registry.get_mut(p.owner_id) else {
+            continue;
+        };
+        let Some(index) = def.fields.iter().position(|f| f.id == p.field_id) else {
+            continue;
+        };
+        if cyclic {
+            def.set_field_type_at(index, FieldType::Pointer);
+            def.fields[index].pointer_target = Some(PointerTarget::ClassId(p.target_id));
+        } else {
+            def.fields[index].class_id = Some(p.target_id);
+        }
+    }
+
+    ms.rebuild_root_from_registry();
+    Ok(ms)
+}
+
+fn convert_node(
+    ctx: &ImportContext,
+    node: Node,
+    def: &mut ClassDefinition,
+    pending: &mut Vec<PendingInstance>,
+) {
+    let kind = node_kind(node);
+    let name = attr(node, "name")
+        .filter(|n| !n.is_empty())
+        .map(str::to_string);
+
+    if let Some(field_type) = primitive_type(kind) {
+        if field_type.is_hex_type() {
+            def.add_hex_field(field_type);
+        } else {
+            push_field(def, name, field_type);
+        }
+        return;
+    }
+
+    match kind {
+        "Utf8Text" => {
+            let length = attr_u64(node, "length").unwrap_or(0);
+            if (1..=TEXT_MAX_LENGTH as u64).contains(&length) {
+                push_field(def, name, FieldType::Text);
+                if length != FieldType::Text.get_size() {
+                    let index = def.fields.len() - 1;
+                    def.set_text_length_at(index, Some(length as usize));
+                }
+            } else {
+                push_hex_bytes(def, length);
+            }
+        }
+        "Utf16Text" => {
+            // `length` counts characters, our inline wide text holds 32 of them
+            let length = attr_u64(node, "length").unwrap_or(0);
+            if length.saturating_mul(2) == FieldType::WideText.get_size() {
+                push_field(def, name, FieldType::WideText);
+            } else {
+                push_hex_bytes(def, length.saturating_mul(2));
+            }
+        }
+        "ClassInstance" => match reference(ctx, node) {
+            Some(target_id) => {
+                let field_id = push_field(def, name, FieldType::ClassInstance);
+                pending.push(PendingInstance {
+                    owner_id: def.id,
+                    field_id,
+                    target_id,
+                });
+            }
+            None => push_hex_bytes(def, attr_u64(node, "size").unwrap_or(0)),
+        },
+        "Pointer" | "ClassPtr" => {
+            let target = match reference(ctx, node) {
+                Some(cid) => Some(PointerTarget::ClassId(cid)),
+                None => node
+                    .children()
+                    .find(|c| c.is_element())
+                    .and_then(|inner| element_target(ctx, inner)),
+            };
+            push_pointer(def, name, target);
+        }
+        "Enum" => match attr(node, "reference").and_then(|r| ctx.enums.get(r)) {
+            Some((enum_id, enum_size)) => {
+                let field_id = push_field(def, name, FieldType::Enum);
+                if let Some(fd) = def.fields.iter_mut().find(|f| f.id == field_id) {
+                    fd.enum_id = Some(*enum_id);
+                    fd.enum_size = Some(*enum_size);
+                }
+            }
+            None => push_hex_bytes(def, attr_u64(node, "size").unwrap_or(4)),
+        },
+        "Array" | "ClassInstanceArray" => {
+            let count = attr_u64(node, "count").unwrap_or(0);
+            let inner = node.children().find(|c| c.is_element());
+            let element = match reference(ctx, node) {
+                Some(cid) => Some(PointerTarget::ClassId(cid)),
+                None => inner.and_then(|inner| element_target(ctx, inner)),
+            };
+            match element {
+                Some(element) if count > 0 => {
+                    let field_id = push_field(def, name, FieldType::Array);
+                    if let Some(fd) = def.fields.iter_mut().find(|f| f.id == field_id) {
+                        fd.array_element = Some(element);
+                        fd.array_length = Some(count.min(u32::MAX as u64) as u32);
+                    }
+                }
+                _ => {
+                    let element_size = inner
+                        .map(|inner| unknown_node_size(inner, node_kind(inner)))
+                        .unwrap_or(0);
+                    push_hex_bytes(def, count.saturating_mul(element_size));
+                }
+            }
+        }
+        other => {
+            // Typed pointer shorthand such as `Int32Ptr`
+            if let Some(pointee) = other.strip_suffix("Ptr").and_then(primitive_type) {
+                push_pointer(def, name, Some(PointerTarget::FieldType(pointee)));
+                return;
+            }
+            push_hex_bytes(def, unknown_node_size(node, other));
+        }
+    }
+}
+
+/// Describe a nested node (pointer target or array element) as a `PointerTarget`
+fn element_target(ctx: &ImportContext, node: Node) -> Option<PointerTarget> {
+    let kind = node_kind(node);
+    if let Some(field_type) = primitive_type(kind) {
+        return Some(PointerTarget::FieldType(field_type));
+    }
+    match kind {
+        "ClassInstance" => reference(ctx, node).map(PointerTarget::ClassId),
+        "Enum" => attr(node, "reference")
+            .and_then(|r| ctx.enums.get(r))
+            .map(|(enum_id, _)| PointerTarget::EnumId(*enum_id)),
+        "Pointer" | "ClassPtr" => Some(PointerTarget::FieldType(FieldType::Pointer)),
+        "Utf8TextPtr" => Some(PointerTarget::FieldType(FieldType::TextPointer)),
+        "Utf16TextPtr" => Some(PointerTarget::FieldType(FieldType::WideTextPointer)),
+        _ => None,
+    }
+}
+
+fn primitive_type(kind: &str) -> Option<FieldType> {
+    Some(match kind {
+        "Hex64" => FieldType::Hex64,
+        "Hex32" => FieldType::Hex32,
+        "Hex16" => FieldType::Hex16,
+        "Hex8" => FieldType::Hex8,
+        "Int64" | "NInt" => FieldType::Int64,
+        "Int32" => FieldType::Int32,
+        "Int16" => FieldType::Int16,
+        "Int8" => FieldType::Int8,
+        "UInt64" | "NUInt" => FieldType::UInt64,
+        "UInt32" => FieldType::UInt32,
+        "UInt16" => FieldType::UInt16,
+        "UInt8" => FieldType::UInt8,
+        "Bool" => FieldType::Bool,
+        "Float" => FieldType::Float,
+        "Double" => FieldType::Double,
+        "Vector2" => FieldType::Vector2,
+        "Vector3" => FieldType::Vector3,
+        "Vector4" => FieldType::Vector4,
+        "Matrix4x4" => FieldType::Matrix4x4,
+        "Utf8TextPtr" => FieldType::TextPointer,
+        "Utf16TextPtr" => FieldType::WideTextPointer,
+        _ => return None,
+    })
+}
+
+/// Byte size of node types we do not model, so they can be kept as hex
+fn unknown_node_size(node: Node, kind: &str) -> u64 {
+    if let Some(size) = attr_u64(node, "size") {
+        return size;
+    }
+    let length = attr_u64(node, "length").unwrap_or(0);
+    match kind {
+        "Matrix3x3" => 36,
+        "Matrix3x4" => 48,
+        "Utf32Text" => length.saturating_mul(4),
+        "BitField" => attr_u64(node, "bits").unwrap_or(8).div_ceil(8),
+        _ => 8,
+    }
+}
+
+fn push_field(def: &mut ClassDefinition, name: Option<String>, field_type: FieldType) -> u64 {
+    let name = name.unwrap_or_else(|| format!("var_{}", def.fields.len()));
+    let field = FieldDefinition::new(Some(name), field_type, def.total_size);
+    let id = field.id;
+    def.add_field(field);
+    id
+}
+
+fn push_pointer(def: &mut ClassDefinition, name: Option<String>, target: Option<PointerTarget>) {
+    let field_id = push_field(def, name, FieldType::Pointer);
+    if let Some(fd) = def.fields.iter_mut().find(|f| f.id == field_id) {
+        fd.pointer_target = target;
+    }
+}
+
+/// Fill `size` bytes with the widest hex fields that fit
+fn push_hex_bytes(def: &mut ClassDefinition, mut size: u64) {
+    while size > 0 {
+        let field_type = match size {
+            8.. => FieldType::Hex64,
+            4..=7 => FieldType::Hex32,
+            2..=3 => FieldType::Hex16,
+            _ => FieldType::Hex8,
+        };
+        size -= field_type.get_size();
+        def.add_hex_field(field_type);
+    }
+}
+
+fn reference(ctx: &ImportContext, node: Node) -> Option<u64> {
+    attr(node, "reference").and_then(|r| ctx.class_ids.get(r).copied())
+}
+
+/// Node type without the `Node` suffix used by ReClass.NET (`Hex64Node` -> `Hex64`)
+fn node_kind<'a>(node: Node<'a, '_>) -> &'a str {
+    let kind = attr(node, "type").unwrap_or_default();
+    kind.strip_suffix("Node").unwrap_or(kind)
+}
+
+fn attr<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    node.attributes()
+        .find(|a| a.name().eq_ignore_ascii_case(name))
+        .map(|a| a.value())
+}
+
+fn attr_u64(node: Node, name: &str) -> Option<u64> {
+    let value = attr(node, name)?.trim();
+    match value
+        .strip_prefix("0x")
+        .or_else(|| value.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16).ok(),
+        None => value.parse().ok(),
+    }
+}
+
+fn children_named<'a, 'input: 'a>(
+    node: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    node.children()
+        .filter(move |c| c.is_element() && c.tag_name().name().eq_ignore_ascii_case(name))
+}
+
+/// The named container either directly below the document element or the element itself
+fn elements_named<'a, 'input: 'a>(
+    root: Node<'a, 'input>,
+    name: &'a str,
+) -> impl Iterator<Item = Node<'a, 'input>> + 'a {
+    let this = root
+        .tag_name()
+        .name()
+        .eq_ignore_ascii_case(name)
+        .then_some(root);
+    this.into_iter().chain(children_named(root, name))
+}
@@ -0,0 +1,224 @@
+use crate::{
+    import::reclass_net::{
+        import_reclass_net,
+        ImportError,
+    },
+    memory::{
+        FieldType,
+        PointerTarget,
+    },
+};
+
+#[cfg(test)]
+mod reclass_net_tests {
+    use super::*;
+
+    const PROJECT: &str = r#"<?xml version="1.0" encoding="utf-8"?>
+<reclass version="65537" type="x64">
+  <enums>
+    <enum name="State" use_flags_mode="false" size="2">
+      <item name="Idle" value="0" />
+      <item name="Running" value="3" />
+      <item name="Dead" value="-1" />
+    </enum>
+  </enums>
+  <classes>
+    <class uuid="AAAA" name="Player" comment="" address="0">
+      <node type="Hex64Node" name="N1" comment="" hidden="false" />
+      <node type="Int32Node" name="health" comment="" hidden="false" />
+      <node type="EnumNode" name="state" reference="State" />
+      <node type="ClassInstanceNode" name="pos" reference="BBBB" />
+      <node type="PointerNode" name="next">
+        <node type="ClassInstanceNode" name="" reference="AAAA" />
+      </node>
+      <node type="ArrayNode" name="ammo" count="4">
+        <node type="UInt16Node" name="" />
+      </node>
+      <node type="Matrix4x4Node" name="view" />
+      <node type="Int32Ptr" name="counter" />
+    </class>
+    <class uuid="BBBB" name="Position" comment="" address="0">
+      <node type="FloatNode" name="x" />
+      <node type="FloatNode" name="y" />
+      <node type="ClassInstanceNode" name="owner" reference="AAAA" />
+    </class>
+  </classes>
+</reclass>"#;
+
+    #[test]
+    fn test_import_maps_node_types() {
+        let ms = import_reclass_net(PROJECT).expect("import");
+        let player = ms
+            .class_registry
+            .get(ms.root_class.class_id)
+            .expect("root class");
+        assert_eq!(player.name, "Player");
+
+        let types: Vec<FieldType> = player.fields.iter().map(|f| f.field_type.clone()).collect();
+        assert_eq!(
+            types,
+            &[
+                FieldType::Hex64,
+                FieldType::Int32,
+                FieldType::Enum,
+                FieldType::ClassInstance,
+                FieldType::Pointer,
+                FieldType::Array,
+                FieldType::Matrix4x4,
+                FieldType::Pointer,
+            ]
+        );
+        assert_eq!(player.fields[6].get_size(), 64);
+        assert!(player.fields[0].name.is_none());
+        assert_eq!(player.fields[1].name.as_deref(), Some("health"));
+
+        let state = ms
+            .enum_registry
+            .get(player.fields[2].enum_id.unwrap())
+            .unwrap();
+        assert_eq!(state.name, "State");
+        assert_eq!(state.default_size, 2);
+        assert_eq!(state.variants[1].value, 3);
+        // A negative constant makes the backing integer signed
+        assert!(state.is_signed);
+        assert_eq!(state.variants[2].value, -1);
+
+        let position_id = player.fields[3].class_id.expect("instance class");
+        assert_eq!(ms.class_registry.get(position_id).unwrap().name, "Position");
+        assert_eq!(
+            player.fields[4].pointer_target,
+            Some(PointerTarget::ClassId(player.id))
+        );
+        assert_eq!(
+            player.fields[5].array_element,
+            Some(PointerTarget::FieldType(FieldType::UInt16))
+        );
+        assert_eq!(player.fields[5].array_length, Some(4));
+        assert_eq!(
+            player.fields.last().unwrap().pointer_target,
+            Some(PointerTarget::FieldType(FieldType::Int32))
+        );
+    }
+
+    #[test]
+    fn test_import_breaks_instance_cycles() {
+        let ms = import_reclass_net(PROJECT).expect("import");
+        let position = ms
+            .class_registry
+            .get_class_ids()
+            .into_iter()
+            .filter_map(|id| ms.class_registry.get(id))
+            .find(|d| d.name == "Position")
+            .unwrap()
+            .clone();
+        let owner = &position.fields[2];
+        assert_eq!(owner.field_type, FieldType::Pointer);
+        assert_eq!(
+            owner.pointer_target,
+            Some(PointerTarget::ClassId(ms.root_class.class_id))
+        );
+        // Hex64 + Int32 + Enum(2) + Position(8 + ptr) + ptr + 4 * u16 + 64 + ptr
+        assert_eq!(ms.root_class.total_size, 8 + 4 + 2 + 16 + 8 + 8 + 64 + 8);
+    }
+
+    #[test]
+    fn test_import_errors() {
+        assert!(matches!(
+            import_reclass_net("<reclass><classes>"),
+            Err(ImportError::Xml(_))
+        ));
+        assert!(matches!(
+            import_reclass_net("<reclass><classes></classes></reclass>"),
+            Err(ImportError::NoClasses)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod cpp_enum_tests {
+    use crate::import::cpp_enum::{
+        parse_cpp_enum,
+        EnumParseError,
+    };
+
+    fn values(text: &str) -> Vec<(String, i64)> {
+        parse_cpp_enum(text)
+            .unwrap()
+            .variants
+            .into_iter()
+            .map(|v| (v.name, v.value))
+            .collect()
+    }
+
+    #[test]
+    fn test_explicit_and_auto_increment_values() {
+        let parsed = parse_cpp_enum("enum Foo { A = 1, B, C = 0x10, D, E = 017 };").unwrap();
+        assert_eq!(parsed.default_size, 4);
+        assert!(!parsed.is_signed);
+        let pairs: Vec<(&str, i64)> = parsed
+            .variants
+            .iter()
+            .map(|v| (v.name.as_str(), v.value))
+            .collect();
+        assert_eq!(
+            pairs,
+            vec![("A", 1), ("B", 2), ("C", 0x10), ("D", 0x11), ("E", 0o17)]
+        );
+    }
+
+    #[test]
+    fn test_underlying_type_comments_and_expressions() {
+        let text = r#"
+            enum class Flags : std::uint8_t {
+                None = 0,        // nothing
+                Visible = 1 << 0,
+                /* hidden */ Solid = 1u << 1,
+                Both = Visible | Solid,
+            };
+        "#;
+        let parsed = parse_cpp_enum(text).unwrap();
+        assert_eq!(parsed.default_size, 1);
+        assert!(!parsed.is_signed);
+        assert_eq!(parsed.variants[3].value, 3);
+    }
+
+    #[test]
+    fn test_negative_and_wide_values_infer_backing() {
+        let parsed = parse_cpp_enum("typedef enum { Invalid = -1, Zero } Team;").unwrap();
+        assert!(parsed.is_signed);
+        assert_eq!(parsed.default_size, 4);
+        assert_eq!(parsed.variants[1].value, 0);
+
+        let wide = parse_cpp_enum("enum Big { Huge = 0x100000000 }").unwrap();
+        assert_eq!(wide.default_size, 8);
+
+        // A bare list without the declaration works as well
+        assert_eq!(
+            values("X = 5, Y"),
+            vec![("X".to_string(), 5), ("Y".to_string(), 6)]
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert_eq!(
+            parse_cpp_enum("enum Foo { A = 1,").unwrap_err(),
+            EnumParseError::UnterminatedBody
+        );
+        assert_eq!(
+            parse_cpp_enum("enum Foo { };").unwrap_err(),
+            EnumParseError::NoVariants
+        );
+        assert_eq!(
+            parse_cpp_enum("enum Foo { 1A };").unwrap_err(),
+            EnumParseError::InvalidName("1A".to_string())
+        );
+        assert_eq!(
+            parse_cpp_enum("enum Foo { A = sizeof(int) };").unwrap_err(),
+            EnumParseError::InvalidValue {
+                name: "A".to_string(),
+                value: "sizeof(int)".to_string(),
+            }
+        );
+    }
+}
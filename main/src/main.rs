@@ -3,8 +3,11 @@ use eframe::{
     NativeOptions,
 };
 
+mod export;
+mod import;
 mod memory;
 mod re_class_app;
+mod scan;
 
 fn main() -> Result<(), anyhow::Error> {
     let native_options = NativeOptions {
@@ -16,7 +19,7 @@ fn main() -> Result<(), anyhow::Error> {
     let res = eframe::run_native(
         "ReClass RS",
         native_options,
-        Box::new(|_cc| Box::new(re_class_app::ReClassGui::new().expect("init gui"))),
+        Box::new(|cc| Box::new(re_class_app::ReClassGui::new(cc.storage).expect("init gui"))),
     );
     match res {
         Ok(()) => Ok(()),
@@ -0,0 +1,260 @@
+use crate::memory::{
+    definitions::{
+        ClassDefinition,
+        FieldDefinition,
+    },
+    nodes::ClassInstance,
+    types::{
+        FieldType,
+        PointerTarget,
+    },
+};
+
+/// Floats outside of this magnitude range are more likely integers or garbage
+const PLAUSIBLE_FLOAT_RANGE: std::ops::RangeInclusive<f32> = 1e-3..=1e6;
+/// 8-byte slots covered by a Text field, all of which must look like ASCII
+const TEXT_SLOTS: usize = 4;
+
+/// A field of a proposed layout and its offset within the analyzed instance
+#[derive(Debug, Clone)]
+pub struct ProposedField {
+    pub offset: u64,
+    pub field: FieldDefinition,
+    /// Whether the type was detected by the analysis rather than kept from the definition
+    pub detected: bool,
+}
+
+fn is_plausible_float(bits: u32) -> bool {
+    let value = f32::from_bits(bits);
+    value.is_finite() && PLAUSIBLE_FLOAT_RANGE.contains(&value.abs())
+}
+
+fn is_text_slot(slot: &[u8]) -> bool {
+    slot.iter()
+        .all(|b| *b == 0 || b.is_ascii_graphic() || *b == b' ')
+}
+
+fn looks_like_text(bytes: &[u8]) -> bool {
+    bytes.len() >= TEXT_SLOTS * 8
+        && bytes[..4]
+            .iter()
+            .all(|b| b.is_ascii_graphic() || *b == b' ')
+        && bytes[..TEXT_SLOTS * 8].chunks(8).all(is_text_slot)
+}
+
+fn push_hex(out: &mut Vec<FieldType>, mut len: usize) {
+    for (field_type, size) in [
+        (FieldType::Hex64, 8),
+        (FieldType::Hex32, 4),
+        (FieldType::Hex16, 2),
+        (FieldType::Hex8, 1),
+    ] {
+        while len >= size {
+            out.push(field_type.clone());
+            len -= size;
+        }
+    }
+}
+
+/// Classify the bytes of a run of hex fields starting at `address`, one 8-byte aligned slot at
+/// a time: pointers, floats in a plausible range and ASCII text are detected, anything else
+/// stays hex.
+pub fn classify_bytes(
+    bytes: &[u8],
+    address: u64,
+    is_pointer: &dyn Fn(u64) -> bool,
+) -> Vec<FieldType> {
+    let mut out = Vec::new();
+    let lead = (((8 - address % 8) % 8) as usize).min(bytes.len());
+    // Unaligned leading bytes, smallest first so the rest ends up aligned
+    for (field_type, size) in [
+        (FieldType::Hex8, 1),
+        (FieldType::Hex16, 2),
+        (FieldType::Hex32, 4),
+    ] {
+        if lead & size != 0 {
+            out.push(field_type);
+        }
+    }
+
+    let mut i = lead;
+    while i + 8 <= bytes.len() {
+        if looks_like_text(&bytes[i..]) {
+            out.push(FieldType::Text);
+            i += TEXT_SLOTS * 8;
+            continue;
+        }
+        let slot: [u8; 8] = bytes[i..i + 8].try_into().unwrap_or_default();
+        let value = u64::from_le_bytes(slot);
+        let low = value as u32;
+        let high = (value >> 32) as u32;
+        if value != 0 && is_pointer(value) {
+            out.push(FieldType::Pointer);
+        } else if is_plausible_float(low) || is_plausible_float(high) {
+            for half in [low, high] {
+                out.push(if is_plausible_float(half) {
+                    FieldType::Float
+                } else {
+                    FieldType::Hex32
+                });
+            }
+        } else {
+            out.push(FieldType::Hex64);
+        }
+        i += 8;
+    }
+    push_hex(&mut out, bytes.len() - i);
+    out
+}
+
+/// Propose a new field list for `def` from the live `block` of `instance`. Typed fields are
+/// kept; every run of hex fields is replaced by the types `classify_bytes` detects.
+pub fn propose_layout(
+    def: &ClassDefinition,
+    instance: &ClassInstance,
+    block: &[u8],
+    is_pointer: &dyn Fn(u64) -> bool,
+) -> Vec<ProposedField> {
+    let mut out = Vec::with_capacity(def.fields.len());
+    let mut index = 0;
+    while index < def.fields.len() {
+        let Some(start) = instance
+            .fields
+            .get(index)
+            .map(|f| f.address - instance.address)
+        else {
+            break;
+        };
+        let fd = &def.fields[index];
+        if !fd.field_type.is_hex_type() {
+            out.push(ProposedField {
+                offset: start,
+                field: fd.clone(),
+                detected: false,
+            });
+            index += 1;
+            continue;
+        }
+
+        let run_len: u64 = def.fields[index..]
+            .iter()
+            .take_while(|f| f.field_type.is_hex_type())
+            .map(|f| f.get_size())
+            .sum();
+        let run_fields = def.fields[index..]
+            .iter()
+            .take_while(|f| f.field_type.is_hex_type())
+            .count();
+        let Some(bytes) = block.get(start as usize..(start + run_len) as usize) else {
+            // Not covered by the block, keep the hex fields as they are
+            for (i, fd) in def.fields[index..index + run_fields].iter().enumerate() {
+                out.push(ProposedField {
+                    offset: instance.fields[index + i].address - instance.address,
+                    field: fd.clone(),
+                    detected: false,
+                });
+            }
+            index += run_fields;
+            continue;
+        };
+
+        let mut offset = start;
+        for field_type in classify_bytes(bytes, instance.address + start, is_pointer) {
+            let size = field_type.get_size();
+            let detected = !field_type.is_hex_type();
+            let mut field = if detected {
+                FieldDefinition::new_named(format!("var_{offset:X}"), field_type.clone(), 0)
+            } else {
+                FieldDefinition::new_hex(field_type.clone(), 0)
+            };
+            if field_type == FieldType::Pointer {
+                field.pointer_target = Some(PointerTarget::FieldType(FieldType::Hex64));
+            }
+            out.push(ProposedField {
+                offset,
+                field,
+                detected,
+            });
+            offset += size;
+        }
+        index += run_fields;
+    }
+    out
+}
+
+/// Elements compared when guessing the stride of a class array
+const STRIDE_SAMPLE_ELEMENTS: u64 = 4;
+/// Strides up to this many bytes past the element size are tried
+const STRIDE_SEARCH_SPAN: u64 = 0x40;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SlotKind {
+    Pointer,
+    Float,
+}
+
+fn slot_kind(
+    bytes: &[u8],
+    pos: usize,
+    address: u64,
+    pointer_size: u64,
+    is_pointer: &dyn Fn(u64) -> bool,
+) -> Option<SlotKind> {
+    let ptr_len = pointer_size as usize;
+    let aligned = address
+        .checked_add(pos as u64)
+        .is_some_and(|slot| slot.is_multiple_of(pointer_size));
+    if aligned && pos + ptr_len <= bytes.len() {
+        let mut raw = [0u8; 8];
+        raw[..ptr_len].copy_from_slice(&bytes[pos..pos + ptr_len]);
+        let value = u64::from_le_bytes(raw);
+        if value != 0 && is_pointer(value) {
+            return Some(SlotKind::Pointer);
+        }
+    }
+    let half: [u8; 4] = bytes.get(pos..pos + 4)?.try_into().ok()?;
+    is_plausible_float(u32::from_le_bytes(half)).then_some(SlotKind::Float)
+}
+
+/// Byte count to read at the start of an array to try every stride for `element_size`
+pub fn stride_sample_len(element_size: u64) -> u64 {
+    STRIDE_SAMPLE_ELEMENTS * (element_size.max(4) + STRIDE_SEARCH_SPAN)
+}
+
+/// Guess the distance between the elements of a class array from the bytes at its start.
+/// Every 4-byte aligned stride from `element_size` up to `STRIDE_SEARCH_SPAN` past it is
+/// scored by the number of 4-byte slots within the element that hold a pointer or a plausible
+/// float in all sampled elements alike. The smallest best-scoring stride wins; `None` if no
+/// stride lines up any slot.
+pub fn detect_array_stride(
+    bytes: &[u8],
+    address: u64,
+    element_size: u64,
+    pointer_size: u64,
+    is_pointer: &dyn Fn(u64) -> bool,
+) -> Option<u64> {
+    let first = element_size.max(4).div_ceil(4) * 4;
+    let mut best: Option<(usize, u64)> = None;
+    for stride in (first..=element_size + STRIDE_SEARCH_SPAN).step_by(4) {
+        let elements = (bytes.len() as u64 / stride).min(STRIDE_SAMPLE_ELEMENTS);
+        if elements < 2 {
+            continue;
+        }
+        let score = (0..element_size)
+            .step_by(4)
+            .filter(|offset| {
+                let kinds: Vec<Option<SlotKind>> = (0..elements)
+                    .map(|i| {
+                        let pos = (i * stride + offset) as usize;
+                        slot_kind(bytes, pos, address, pointer_size, is_pointer)
+                    })
+                    .collect();
+                kinds[0].is_some() && kinds.iter().all(|kind| *kind == kinds[0])
+            })
+            .count();
+        if score > best.map_or(0, |(s, _)| s) {
+            best = Some((score, stride));
+        }
+    }
+    best.map(|(_, stride)| stride)
+}
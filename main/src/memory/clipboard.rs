@@ -0,0 +1,169 @@
+use std::collections::{
+    HashMap,
+    HashSet,
+};
+
+use crate::memory::{
+    definitions::FieldDefinition,
+    nodes::MemoryStructure,
+    types::{
+        FieldType,
+        PointerTarget,
+    },
+};
+
+/// Field definitions copied out of a class, together with the names of the classes and enums
+/// they reference so they can be remapped when pasted into another structure.
+#[derive(Debug, Clone, Default)]
+pub struct FieldClipboard {
+    fields: Vec<FieldDefinition>,
+    class_names: HashMap<u64, String>,
+    enum_names: HashMap<u64, String>,
+}
+
+fn collect_target_ids(target: &PointerTarget, class_ids: &mut Vec<u64>, enum_ids: &mut Vec<u64>) {
+    match target {
+        PointerTarget::ClassId(id) => class_ids.push(*id),
+        PointerTarget::EnumId(id) => enum_ids.push(*id),
+        PointerTarget::Array { element, .. } => collect_target_ids(element, class_ids, enum_ids),
+        PointerTarget::FieldType(_) | PointerTarget::Chain { .. } => {}
+    }
+}
+
+impl FieldClipboard {
+    /// Copy the fields of `class_id` whose ids are in `field_ids`, keeping their class order
+    pub fn copy(ms: &MemoryStructure, class_id: u64, field_ids: &HashSet<u64>) -> Option<Self> {
+        let def = ms.class_registry.get(class_id)?;
+        let fields: Vec<FieldDefinition> = def
+            .fields
+            .iter()
+            .filter(|f| field_ids.contains(&f.id))
+            .cloned()
+            .collect();
+        if fields.is_empty() {
+            return None;
+        }
+
+        let mut class_ids = Vec::new();
+        let mut enum_ids = Vec::new();
+        for f in &fields {
+            class_ids.extend(f.class_id);
+            enum_ids.extend(f.enum_id);
+            for target in [&f.pointer_target, &f.array_element].into_iter().flatten() {
+                collect_target_ids(target, &mut class_ids, &mut enum_ids);
+            }
+        }
+        let class_names = class_ids
+            .into_iter()
+            .filter_map(|id| ms.class_registry.get(id).map(|d| (id, d.name.clone())))
+            .collect();
+        let enum_names = enum_ids
+            .into_iter()
+            .filter_map(|id| ms.enum_registry.get(id).map(|d| (id, d.name.clone())))
+            .collect();
+        Some(Self {
+            fields,
+            class_names,
+            enum_names,
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.fields.len()
+    }
+
+    /// Find the class a copied id refers to in `ms`: the same id if its name still matches,
+    /// otherwise a class with the same name.
+    fn remap_class(&self, ms: &MemoryStructure, id: u64) -> Option<u64> {
+        let name = self.class_names.get(&id)?;
+        if ms.class_registry.get(id).is_some_and(|d| &d.name == name) {
+            return Some(id);
+        }
+        ms.class_registry
+            .get_class_ids()
+            .into_iter()
+            .find(|cid| ms.class_registry.get(*cid).is_some_and(|d| &d.name == name))
+    }
+
+    fn remap_enum(&self, ms: &MemoryStructure, id: u64) -> Option<u64> {
+        let name = self.enum_names.get(&id)?;
+        if ms.enum_registry.get(id).is_some_and(|d| &d.name == name) {
+            return Some(id);
+        }
+        ms.enum_registry
+            .get_enum_ids()
+            .into_iter()
+            .find(|eid| ms.enum_registry.get(*eid).is_some_and(|d| &d.name == name))
+    }
+
+    fn remap_target(&self, ms: &MemoryStructure, target: &PointerTarget) -> Option<PointerTarget> {
+        match target {
+            PointerTarget::ClassId(id) => self.remap_class(ms, *id).map(PointerTarget::ClassId),
+            PointerTarget::EnumId(id) => self.remap_enum(ms, *id).map(PointerTarget::EnumId),
+            PointerTarget::Array { element, length } => Some(PointerTarget::Array {
+                element: Box::new(
+                    self.remap_target(ms, element)
+                        .unwrap_or(PointerTarget::FieldType(FieldType::Hex8)),
+                ),
+                length: *length,
+            }),
+            other => Some(other.clone()),
+        }
+    }
+
+    /// Insert copies of the clipboard fields before `index` in `class_id`, returning how many
+    /// were inserted. References to classes or enums missing from `ms` are dropped, as are
+    /// embedded instances that would make the class contain itself.
+    pub fn paste_into(&self, ms: &mut MemoryStructure, class_id: u64, index: usize) -> usize {
+        if !ms.class_registry.contains(class_id) {
+            return 0;
+        }
+        let mut pasted = Vec::with_capacity(self.fields.len());
+        for source in &self.fields {
+            let mut field = FieldDefinition::new(source.name.clone(), source.field_type.clone(), 0);
+            field.enum_size = source.enum_size;
+            field.array_length = source.array_length;
+            field.array_stride = source.array_stride;
+            field.display_base = source.display_base;
+            field.bitfield_size = source.bitfield_size;
+            field.bit_offset = source.bit_offset;
+            field.bit_width = source.bit_width;
+            field.comment = source.comment.clone();
+            field.color = source.color;
+            field.text_length = source.text_length;
+            field.text_encoding = source.text_encoding;
+            field.enum_id = source.enum_id.and_then(|id| self.remap_enum(ms, id));
+            field.pointer_target = source.pointer_target.as_ref().map(|t| {
+                self.remap_target(ms, t)
+                    .unwrap_or(PointerTarget::FieldType(FieldType::Hex64))
+            });
+            field.array_element = source.array_element.as_ref().map(|t| {
+                self.remap_target(ms, t)
+                    .filter(|t| match t {
+                        PointerTarget::ClassId(cid) => !ms.would_create_cycle(class_id, *cid),
+                        _ => true,
+                    })
+                    .unwrap_or(PointerTarget::FieldType(FieldType::Hex8))
+            });
+            if field.field_type == FieldType::ClassInstance {
+                field.class_id = source
+                    .class_id
+                    .and_then(|id| self.remap_class(ms, id))
+                    .filter(|cid| !ms.would_create_cycle(class_id, *cid));
+                if field.class_id.is_none() {
+                    field.field_type = FieldType::Hex64;
+                    field.name = None;
+                }
+            }
+            pasted.push(field);
+        }
+
+        let count = pasted.len();
+        if let Some(def) = ms.class_registry.get_mut(class_id) {
+            for (i, field) in pasted.into_iter().enumerate() {
+                def.insert_field_at(index + i, field);
+            }
+        }
+        count
+    }
+}
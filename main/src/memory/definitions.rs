@@ -1,6 +1,9 @@
-use std::sync::atomic::{
-    AtomicU64,
-    Ordering,
+use std::{
+    ops::Range,
+    sync::atomic::{
+        AtomicU64,
+        Ordering,
+    },
 };
 
 use serde::{
@@ -9,8 +12,11 @@ use serde::{
 };
 
 use crate::memory::types::{
+    default_pointer_size,
     FieldType,
+    NumberBase,
     PointerTarget,
+    TextEncoding,
 };
 
 static FIELD_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
@@ -24,10 +30,29 @@ fn next_class_def_id() -> u64 {
     CLASS_DEF_ID_COUNTER.fetch_add(1, Ordering::Relaxed)
 }
 static ENUM_DEF_ID_COUNTER: AtomicU64 = AtomicU64::new(1);
+
+const AUTO_FIELD_NAME_PREFIX: &str = "field_0x";
+
+/// Whether `name` looks like one given by `ClassDefinition::auto_field_name`, e.g.
+/// `field_0x40` or `field_0x40_2`
+pub fn is_auto_field_name(name: &str) -> bool {
+    let Some(rest) = name.strip_prefix(AUTO_FIELD_NAME_PREFIX) else {
+        return false;
+    };
+    let (offset, suffix) = rest.split_once('_').unwrap_or((rest, "1"));
+    !offset.is_empty()
+        && offset.chars().all(|c| c.is_ascii_hexdigit())
+        && !suffix.is_empty()
+        && suffix.chars().all(|c| c.is_ascii_digit())
+}
+
 fn next_enum_def_id() -> u64 {
     ENUM_DEF_ID_COUNTER.fetch_add(1, Ordering::Relaxed)
 }
 
+/// Longest Text field, and the most bytes read behind a TextPointer
+pub const TEXT_MAX_LENGTH: usize = 1024;
+
 /// Represents a field in a class definition
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct FieldDefinition {
@@ -41,6 +66,15 @@ pub struct FieldDefinition {
     pub enum_size: Option<u8>, // For Enum fields, underlying size in bytes (1,2,4,8)
     pub array_element: Option<PointerTarget>, // For Array fields, element description
     pub array_length: Option<u32>, // For Array fields, number of elements
+    pub array_stride: Option<u64>, // For Array fields, bytes between elements; None packs them
+    pub display_base: Option<NumberBase>, // For integer and hex fields, base the value is shown in
+    pub bitfield_size: Option<u8>, // For Bitfield fields, backing integer size in bytes (1,2,4,8)
+    pub bit_offset: Option<u8>, // For Bitfield fields, lowest bit of the value
+    pub bit_width: Option<u8>, // For Bitfield fields, number of bits of the value
+    pub comment: Option<String>, // Free-form note shown at the end of the row
+    pub color: Option<[u8; 3]>, // Custom RGB tint of the type label
+    pub text_length: Option<usize>, // For Text/TextPointer fields, bytes of the string to read
+    pub text_encoding: Option<TextEncoding>, // For Text/TextPointer fields; None reads UTF-8
 }
 
 impl FieldDefinition {
@@ -57,6 +91,15 @@ impl FieldDefinition {
             enum_size: None,
             array_element: None,
             array_length: None,
+            array_stride: None,
+            display_base: None,
+            bitfield_size: None,
+            bit_offset: None,
+            bit_width: None,
+            comment: None,
+            color: None,
+            text_length: None,
+            text_encoding: None,
         }
     }
 
@@ -73,6 +116,15 @@ impl FieldDefinition {
             enum_size: None,
             array_element: None,
             array_length: None,
+            array_stride: None,
+            display_base: None,
+            bitfield_size: None,
+            bit_offset: None,
+            bit_width: None,
+            comment: None,
+            color: None,
+            text_length: None,
+            text_encoding: None,
         }
     }
 
@@ -88,15 +140,110 @@ impl FieldDefinition {
             enum_size: None,
             array_element: None,
             array_length: None,
+            array_stride: None,
+            display_base: None,
+            bitfield_size: None,
+            bit_offset: None,
+            bit_width: None,
+            comment: None,
+            color: None,
+            text_length: None,
+            text_encoding: None,
         }
     }
 
-    #[allow(dead_code)]
     pub fn get_size(&self) -> u64 {
-        self.field_type.get_size()
+        self.get_size_for(default_pointer_size())
+    }
+
+    /// Size of the field in a process whose pointers are `pointer_size` bytes
+    pub fn get_size_for(&self, pointer_size: u64) -> u64 {
+        match self.field_type {
+            FieldType::Bitfield => self.bitfield_layout().0 as u64,
+            FieldType::Text => self.text_read_length() as u64,
+            _ => self.field_type.get_size_for(pointer_size),
+        }
+    }
+
+    /// Bytes read for a Text field (its inline size) or behind a TextPointer; a TextPointer
+    /// without a length reads up to `TEXT_MAX_LENGTH` bytes
+    pub fn text_read_length(&self) -> usize {
+        match self.field_type {
+            FieldType::TextPointer => self.text_length.unwrap_or(TEXT_MAX_LENGTH),
+            _ => self
+                .text_length
+                .unwrap_or(FieldType::Text.get_size() as usize),
+        }
+    }
+
+    /// Bytes from the start of one array element to the next: the stride if one is set and
+    /// it is not smaller than `element_size`, else `element_size`
+    pub fn array_step(&self, element_size: u64) -> u64 {
+        self.array_stride
+            .filter(|stride| *stride >= element_size)
+            .unwrap_or(element_size)
+    }
+
+    /// Class the elements of an array of pointers point to. The element type stays
+    /// `FieldType::Pointer` and the class is kept in `pointer_target`, like a pointer field.
+    pub fn array_pointee_class(&self) -> Option<u64> {
+        match (&self.field_type, &self.array_element, &self.pointer_target) {
+            (
+                FieldType::Array,
+                Some(PointerTarget::FieldType(FieldType::Pointer)),
+                Some(PointerTarget::ClassId(cid)),
+            ) => Some(*cid),
+            _ => None,
+        }
+    }
+
+    /// Class whose layout this field embeds, points to or holds an array of
+    pub fn referenced_class(&self) -> Option<u64> {
+        match (&self.field_type, &self.pointer_target, &self.array_element) {
+            (FieldType::ClassInstance, ..) => self.class_id,
+            (FieldType::Pointer, Some(PointerTarget::ClassId(cid)), _)
+            | (FieldType::Array, _, Some(PointerTarget::ClassId(cid))) => Some(*cid),
+            (FieldType::Array, ..) => self.array_pointee_class(),
+            _ => None,
+        }
+    }
+
+    /// Backing size in bytes, bit offset and bit width of a Bitfield field, clamped so the
+    /// bits always fit inside the backing integer
+    pub fn bitfield_layout(&self) -> (u8, u8, u8) {
+        clamp_bitfield(
+            self.bitfield_size.unwrap_or(4),
+            self.bit_offset.unwrap_or(0),
+            self.bit_width.unwrap_or(1),
+        )
+    }
+
+    /// Extract the bits described by this field from the backing integer `raw`
+    pub fn extract_bits(&self, raw: u64) -> u64 {
+        let (_, offset, width) = self.bitfield_layout();
+        let mask = if width >= 64 {
+            u64::MAX
+        } else {
+            (1u64 << width) - 1
+        };
+        (raw >> offset) & mask
     }
 }
 
+/// Clamp a bitfield description: the size falls back to 4 bytes unless it is 1, 2, 4 or 8,
+/// the width is at least one bit and offset + width never exceeds the backing size
+pub fn clamp_bitfield(size: u8, offset: u8, width: u8) -> (u8, u8, u8) {
+    let size = if matches!(size, 1 | 2 | 4 | 8) {
+        size
+    } else {
+        4
+    };
+    let bits = size * 8;
+    let width = width.clamp(1, bits);
+    let offset = offset.min(bits - width);
+    (size, offset, width)
+}
+
 /// Represents a class definition that can be reused for multiple instances
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClassDefinition {
@@ -106,6 +253,12 @@ pub struct ClassDefinition {
     pub total_size: u64,
     #[serde(default)]
     pub entry_offset: Option<u64>,
+    /// Class whose layout comes first, like a C++ base class; the fields follow it
+    #[serde(default)]
+    pub base_class_id: Option<u64>,
+    /// Width of pointer fields in the offsets above, from the registry the class is part of
+    #[serde(skip, default = "default_pointer_size")]
+    pub pointer_size: u64,
 }
 
 impl ClassDefinition {
@@ -116,6 +269,8 @@ impl ClassDefinition {
             fields: Vec::new(),
             total_size: 0,
             entry_offset: None,
+            base_class_id: None,
+            pointer_size: default_pointer_size(),
         }
     }
 
@@ -131,10 +286,33 @@ impl ClassDefinition {
         self.add_field(field);
     }
 
-    pub fn add_hex_field(&mut self, field_type: FieldType) {
+    /// Append a hex field; returns its index as a range like the other hex helpers
+    pub fn add_hex_field(&mut self, field_type: FieldType) -> Range<usize> {
         let offset = self.total_size;
         let field = FieldDefinition::new_hex(field_type, offset);
         self.add_field(field);
+        self.fields.len() - 1..self.fields.len()
+    }
+
+    /// Append `num_bytes` of hex fields, using the widest hex types that fit. Returns the
+    /// indices of the new fields.
+    pub fn add_hex_bytes(&mut self, num_bytes: u64) -> Range<usize> {
+        let start = self.fields.len();
+        let mut remaining = num_bytes;
+        for (field_type, size) in [
+            (FieldType::Hex64, 8),
+            (FieldType::Hex32, 4),
+            (FieldType::Hex16, 2),
+            (FieldType::Hex8, 1),
+        ] {
+            while remaining >= size {
+                self.fields
+                    .push(FieldDefinition::new_hex(field_type.clone(), 0));
+                remaining -= size;
+            }
+        }
+        self.recalculate_size();
+        start..self.fields.len()
     }
 
     #[cfg(test)]
@@ -154,7 +332,8 @@ impl ClassDefinition {
         for field in &mut self.fields {
             field.offset = running_offset;
             if !field.field_type.is_dynamic_size() {
-                running_offset = running_offset.saturating_add(field.get_size());
+                running_offset =
+                    running_offset.saturating_add(field.get_size_for(self.pointer_size));
             }
         }
         self.total_size = running_offset;
@@ -172,13 +351,74 @@ impl ClassDefinition {
         self.fields.get(index)
     }
 
-    pub fn insert_hex_field_at(&mut self, index: usize, field_type: FieldType) {
-        let field = FieldDefinition::new_hex(field_type, 0);
+    /// Insert `num_bytes` of hex fields at `index`, widest first. Returns the indices of the
+    /// new fields.
+    pub fn insert_hex_bytes_at(&mut self, index: usize, num_bytes: u64) -> Range<usize> {
+        let mut remaining = num_bytes;
+        let start = index.min(self.fields.len());
+        let mut idx = start;
+        for (field_type, size) in [
+            (FieldType::Hex64, 8),
+            (FieldType::Hex32, 4),
+            (FieldType::Hex16, 2),
+            (FieldType::Hex8, 1),
+        ] {
+            while remaining >= size {
+                self.fields
+                    .insert(idx, FieldDefinition::new_hex(field_type.clone(), 0));
+                idx += 1;
+                remaining -= size;
+            }
+        }
+        self.recalculate_size();
+        start..idx
+    }
+
+    /// `field_0x40`-style name for a field at `offset`, with a `_2`, `_3`, ... suffix when
+    /// another field of the class already has it
+    pub fn auto_field_name(&self, offset: u64) -> String {
+        let base = format!("{AUTO_FIELD_NAME_PREFIX}{offset:X}");
+        let taken = |name: &str| self.fields.iter().any(|f| f.name.as_deref() == Some(name));
+        let mut name = base.clone();
+        let mut n = 1;
+        while taken(&name) {
+            n += 1;
+            name = format!("{base}_{n}");
+        }
+        name
+    }
+
+    /// Name the fields in `range` after their offset, e.g. the ones a hex helper just created
+    /// while the "Auto-name new fields" setting is on
+    pub fn name_fields_by_offset(&mut self, range: Range<usize>) {
+        for index in range {
+            let name = self.auto_field_name(self.fields[index].offset);
+            self.fields[index].name = Some(name);
+        }
+    }
+
+    /// A class holding nothing but the Hex64 it was created with, unnamed or auto-named
+    pub fn is_placeholder(&self) -> bool {
+        match self.fields.as_slice() {
+            [f] => {
+                f.field_type == FieldType::Hex64 && f.name.as_deref().is_none_or(is_auto_field_name)
+            }
+            _ => false,
+        }
+    }
+
+    pub fn insert_field_at(&mut self, index: usize, field: FieldDefinition) {
         let idx = index.min(self.fields.len());
         self.fields.insert(idx, field);
         self.recalculate_size();
     }
 
+    /// Replace all fields at once, e.g. with an accepted analysis proposal
+    pub fn replace_fields(&mut self, fields: Vec<FieldDefinition>) {
+        self.fields = fields;
+        self.recalculate_size();
+    }
+
     pub fn remove_field_at(&mut self, index: usize) {
         if index < self.fields.len() {
             self.fields.remove(index);
@@ -186,9 +426,101 @@ impl ClassDefinition {
         }
     }
 
+    /// Move the field at `from` so that it ends up at index `to`. Returns false if either index
+    /// is out of bounds.
+    pub fn move_field(&mut self, from: usize, to: usize) -> bool {
+        if from >= self.fields.len() || to >= self.fields.len() {
+            return false;
+        }
+        if from != to {
+            let field = self.fields.remove(from);
+            self.fields.insert(to, field);
+            self.recalculate_size();
+        }
+        true
+    }
+
+    /// Change the backing size and bit range of the Bitfield field at `index`, clamping the
+    /// range to the backing size
+    pub fn set_bitfield_at(&mut self, index: usize, size: u8, offset: u8, width: u8) {
+        if let Some(f) = self.fields.get_mut(index) {
+            if f.field_type != FieldType::Bitfield {
+                return;
+            }
+            let (size, offset, width) = clamp_bitfield(size, offset, width);
+            f.bitfield_size = Some(size);
+            f.bit_offset = Some(offset);
+            f.bit_width = Some(width);
+            self.recalculate_size();
+        }
+    }
+
+    /// Set how many bytes a Text or TextPointer field reads, clamped to 1..=`TEXT_MAX_LENGTH`.
+    /// `None` restores the default.
+    pub fn set_text_length_at(&mut self, index: usize, length: Option<usize>) {
+        if let Some(f) = self.fields.get_mut(index) {
+            if !matches!(f.field_type, FieldType::Text | FieldType::TextPointer) {
+                return;
+            }
+            f.text_length = length.map(|n| n.clamp(1, TEXT_MAX_LENGTH));
+            self.recalculate_size();
+        }
+    }
+
+    /// Set the encoding a Text or TextPointer field is decoded with; `None` restores UTF-8
+    pub fn set_text_encoding_at(&mut self, index: usize, encoding: Option<TextEncoding>) {
+        if let Some(f) = self.fields.get_mut(index) {
+            if matches!(f.field_type, FieldType::Text | FieldType::TextPointer) {
+                f.text_encoding = encoding.filter(|e| *e != TextEncoding::default());
+            }
+        }
+    }
+
+    /// Set the distance between the elements of an array field; `None` packs them
+    pub fn set_array_stride_at(&mut self, index: usize, stride: Option<u64>) {
+        if let Some(f) = self.fields.get_mut(index) {
+            if f.field_type == FieldType::Array {
+                f.array_stride = stride;
+            }
+        }
+    }
+
+    /// Show an integer or hex field in `base`; `None` goes back to the type's default
+    pub fn set_display_base_at(&mut self, index: usize, base: Option<NumberBase>) {
+        if let Some(f) = self.fields.get_mut(index) {
+            if f.field_type.default_base().is_some() {
+                f.display_base = base;
+            }
+        }
+    }
+
+    /// Give a field a fresh id, e.g. when it duplicates the id of another field
+    pub fn renew_field_id_at(&mut self, index: usize) {
+        if let Some(f) = self.fields.get_mut(index) {
+            f.id = next_field_id();
+        }
+    }
+
+    /// Set the comment of a field; blank text removes it
+    pub fn set_comment_at(&mut self, index: usize, comment: &str) {
+        if let Some(f) = self.fields.get_mut(index) {
+            let comment = comment.trim();
+            f.comment = (!comment.is_empty()).then(|| comment.to_string());
+        }
+    }
+
+    pub fn set_color_at(&mut self, index: usize, color: Option<[u8; 3]>) {
+        if let Some(f) = self.fields.get_mut(index) {
+            f.color = color;
+        }
+    }
+
     pub fn set_field_type_at(&mut self, index: usize, new_type: FieldType) {
         if let Some(f) = self.fields.get_mut(index) {
             f.field_type = new_type.clone();
+            if new_type.default_base().is_none() {
+                f.display_base = None;
+            }
             if new_type != FieldType::ClassInstance {
                 f.class_id = None;
             }
@@ -201,6 +533,7 @@ impl ClassDefinition {
             if new_type != FieldType::Array {
                 f.array_element = None;
                 f.array_length = None;
+                f.array_stride = None;
             } else {
                 if f.array_element.is_none() {
                     f.array_element = Some(PointerTarget::FieldType(FieldType::Hex8));
@@ -209,6 +542,20 @@ impl ClassDefinition {
                     f.array_length = Some(1);
                 }
             }
+            if !matches!(new_type, FieldType::Text | FieldType::TextPointer) {
+                f.text_length = None;
+                f.text_encoding = None;
+            }
+            if new_type != FieldType::Bitfield {
+                f.bitfield_size = None;
+                f.bit_offset = None;
+                f.bit_width = None;
+            } else {
+                let (size, offset, width) = f.bitfield_layout();
+                f.bitfield_size = Some(size);
+                f.bit_offset = Some(offset);
+                f.bit_width = Some(width);
+            }
             if !new_type.is_hex_type() && f.name.is_none() {
                 f.name = Some(format!("var_{index}"));
             } else if new_type.is_hex_type() {
@@ -225,6 +572,9 @@ pub struct EnumDefinition {
     pub id: u64,
     pub name: String,
     pub is_flags: bool,
+    /// Read the backing integer as two's complement; projects saved before this are unsigned
+    #[serde(default)]
+    pub is_signed: bool,
     pub default_size: u8, // 1,2,4,8 bytes
     pub variants: Vec<EnumVariant>,
 }
@@ -235,6 +585,7 @@ impl EnumDefinition {
             id: next_enum_def_id(),
             name,
             is_flags: false,
+            is_signed: false,
             default_size: 4,
             variants: Vec::new(),
         }
@@ -243,12 +594,114 @@ impl EnumDefinition {
     pub fn rename(&mut self, new_name: String) {
         self.name = new_name;
     }
+
+    /// Bytes of the backing integer; sizes other than 1, 2 and 8 are read as 4
+    pub fn backing_size(&self) -> u8 {
+        match self.default_size {
+            1 | 2 | 8 => self.default_size,
+            _ => 4,
+        }
+    }
+
+    /// Little-endian bytes of `value` as the backing integer, or None if it does not fit
+    pub fn encode_value(&self, value: i64) -> Option<Vec<u8>> {
+        let size = usize::from(self.backing_size());
+        let bits = size as u32 * 8;
+        if bits < 64 {
+            let fits = if self.is_signed {
+                let half = 1i64 << (bits - 1);
+                (-half..half).contains(&value)
+            } else {
+                (0..1i64 << bits).contains(&value)
+            };
+            if !fits {
+                return None;
+            }
+        }
+        Some(value.to_le_bytes()[..size].to_vec())
+    }
+
+    /// Value of the backing integer `raw` (read as `default_size` bytes), sign-extended for
+    /// signed enums
+    pub fn value_of(&self, raw: u64) -> i64 {
+        let bits = u32::from(self.default_size.clamp(1, 8)) * 8;
+        if self.is_signed && bits < 64 {
+            let shift = 64 - bits;
+            ((raw << shift) as i64) >> shift
+        } else {
+            raw as i64
+        }
+    }
+
+    /// Name of the variant matching the backing integer `raw`, or the number itself.
+    /// Flags enums list every set single-bit variant as `A | B`, leftover bits as hex.
+    pub fn format_value(&self, raw: u64) -> String {
+        if self.is_flags {
+            return self.format_flags(raw);
+        }
+        let value = self.value_of(raw);
+        match self.variants.iter().find(|variant| variant.value == value) {
+            Some(variant) => variant.name.clone(),
+            None if self.is_signed => value.to_string(),
+            None => raw.to_string(),
+        }
+    }
+
+    /// Swap the variant at `index` with the one above it (`up`) or below it. Returns false
+    /// when there is no neighbour in that direction.
+    pub fn move_variant(&mut self, index: usize, up: bool) -> bool {
+        let Some(other) = (if up {
+            index.checked_sub(1)
+        } else {
+            index.checked_add(1)
+        }) else {
+            return false;
+        };
+        if index >= self.variants.len() || other >= self.variants.len() {
+            return false;
+        }
+        self.variants.swap(index, other);
+        true
+    }
+
+    /// Order the variants by ascending value; variants with equal values keep their order.
+    /// Flags compare as unsigned bit patterns, so each bit keeps its variant and a high bit
+    /// such as `1 << 63` sorts after the low ones.
+    pub fn sort_variants_by_value(&mut self) {
+        if self.is_flags {
+            self.variants.sort_by_key(|variant| variant.value as u64);
+        } else {
+            self.variants.sort_by_key(|variant| variant.value);
+        }
+    }
+
+    fn format_flags(&self, raw: u64) -> String {
+        if let Some(variant) = self.variants.iter().find(|v| v.value as u64 == raw) {
+            return variant.name.clone();
+        }
+        if raw == 0 {
+            return "0".to_string();
+        }
+        let mut names = Vec::new();
+        let mut rest = raw;
+        for variant in &self.variants {
+            let bit = variant.value as u64;
+            if bit.is_power_of_two() && rest & bit != 0 {
+                names.push(variant.name.clone());
+                rest &= !bit;
+            }
+        }
+        if rest != 0 {
+            names.push(format!("0x{rest:X}"));
+        }
+        names.join(" | ")
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnumVariant {
     pub name: String,
-    pub value: u32,
+    pub value: i64,
 }
 
 /// Registry for enum definitions
@@ -315,19 +768,31 @@ impl Default for EnumDefinitionRegistry {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ClassDefinitionRegistry {
     definitions: HashMap<u64, ClassDefinition>,
+    /// Width of pointer fields, from the process the structure is shown for
+    #[serde(skip, default = "default_pointer_size")]
+    pointer_size: u64,
 }
 
 impl ClassDefinitionRegistry {
     pub fn new() -> Self {
         Self {
             definitions: HashMap::new(),
+            pointer_size: default_pointer_size(),
         }
     }
 
-    pub fn register(&mut self, class_def: ClassDefinition) {
+    pub fn register(&mut self, mut class_def: ClassDefinition) {
+        if class_def.pointer_size != self.pointer_size {
+            class_def.pointer_size = self.pointer_size;
+            class_def.recalculate_size();
+        }
         self.definitions.insert(class_def.id, class_def);
     }
 
+    pub fn pointer_size(&self) -> u64 {
+        self.pointer_size
+    }
+
     pub fn get(&self, id: u64) -> Option<&ClassDefinition> {
         self.definitions.get(&id)
     }
@@ -348,10 +813,49 @@ impl ClassDefinitionRegistry {
         self.definitions.values().map(|d| d.id).collect()
     }
 
+    /// `base` if no class uses it yet, otherwise the first free `base_N`
+    pub fn generate_unique_class_name(&self, base: &str) -> String {
+        let mut name = base.to_string();
+        let mut idx: usize = 1;
+        while self.contains_name(&name) {
+            name = format!("{base}_{idx}");
+            idx += 1;
+        }
+        name
+    }
+
+    /// Register a deep copy of class `id` under a fresh id and unique name. Fields get new ids;
+    /// their class, enum and pointer targets still point at the original targets.
+    pub fn duplicate(&mut self, id: u64) -> Option<u64> {
+        let mut copy = self.definitions.get(&id)?.clone();
+        copy.id = next_class_def_id();
+        copy.name = self.generate_unique_class_name(&copy.name);
+        for f in &mut copy.fields {
+            f.id = next_field_id();
+        }
+        let new_id = copy.id;
+        self.register(copy);
+        Some(new_id)
+    }
+
     pub fn remove(&mut self, id: u64) -> Option<ClassDefinition> {
         self.definitions.remove(&id)
     }
 
+    /// Recompute field offsets of every class, e.g. after references to them were repaired
+    pub fn relayout_all(&mut self) {
+        for def in self.definitions.values_mut() {
+            def.pointer_size = self.pointer_size;
+            def.recalculate_size();
+        }
+    }
+
+    /// Lay out every class with pointers of `pointer_size` bytes
+    pub fn set_pointer_size(&mut self, pointer_size: u64) {
+        self.pointer_size = pointer_size;
+        self.relayout_all();
+    }
+
     pub fn reseed_id_counters(&self) {
         let mut max_field_id: u64 = 1;
         let mut max_class_id: u64 = 1;
@@ -373,6 +877,16 @@ impl ClassDefinitionRegistry {
     pub fn get_by_id(&self, id: u64) -> Option<&ClassDefinition> {
         self.definitions.values().find(|d| d.id == id)
     }
+
+    /// Class id and index of the field with definition id `field_id`
+    pub fn find_field(&self, field_id: u64) -> Option<(u64, usize)> {
+        self.definitions.values().find_map(|d| {
+            d.fields
+                .iter()
+                .position(|f| f.id == field_id)
+                .map(|index| (d.id, index))
+        })
+    }
 }
 
 impl Default for ClassDefinitionRegistry {
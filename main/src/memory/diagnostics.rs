@@ -0,0 +1,165 @@
+use crate::memory::{
+    definitions::FieldDefinition,
+    nodes::{
+        ClassInstance,
+        MemoryStructure,
+    },
+    types::{
+        FieldType,
+        PointerTarget,
+    },
+};
+
+/// Layout problem of a class compared to what a C compiler would produce
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AlignmentWarning {
+    /// Field `index` sits at `offset`, which is not a multiple of its `alignment`
+    MisalignedField {
+        index: usize,
+        offset: u64,
+        alignment: u64,
+    },
+    /// The class size is not a multiple of its largest member alignment
+    UnalignedSize { size: u64, alignment: u64 },
+}
+
+impl AlignmentWarning {
+    /// Offset the warning is about: the field offset, or the end of the class
+    pub fn offset(&self) -> u64 {
+        match self {
+            AlignmentWarning::MisalignedField { offset, .. } => *offset,
+            AlignmentWarning::UnalignedSize { size, .. } => *size,
+        }
+    }
+
+    /// Hex bytes to insert before the field (or at the end of the class) to fix the warning
+    pub fn padding(&self) -> u64 {
+        let (offset, alignment) = match self {
+            AlignmentWarning::MisalignedField {
+                offset, alignment, ..
+            } => (*offset, *alignment),
+            AlignmentWarning::UnalignedSize { size, alignment } => (*size, *alignment),
+        };
+        (alignment - offset % alignment) % alignment
+    }
+}
+
+/// Natural alignment of a field, looking through enums, bitfields, arrays and embedded classes
+pub fn field_alignment(ms: &MemoryStructure, fd: &FieldDefinition, stack: &mut Vec<u64>) -> u64 {
+    let enum_alignment = |eid: u64| {
+        ms.enum_registry
+            .get(eid)
+            .map(|ed| ed.default_size as u64)
+            .unwrap_or(4)
+    };
+    let alignment = match fd.field_type {
+        FieldType::ClassInstance => fd
+            .class_id
+            .map(|cid| class_alignment(ms, cid, stack))
+            .unwrap_or(1),
+        FieldType::Array => match &fd.array_element {
+            Some(PointerTarget::FieldType(t)) => t.natural_alignment(ms.pointer_size()),
+            Some(PointerTarget::ClassId(cid)) => class_alignment(ms, *cid, stack),
+            Some(PointerTarget::EnumId(eid)) => enum_alignment(*eid),
+            _ => 1,
+        },
+        FieldType::Enum => fd.enum_id.map(enum_alignment).unwrap_or(4),
+        FieldType::Bitfield => fd.bitfield_layout().0 as u64,
+        _ => fd.field_type.natural_alignment(ms.pointer_size()),
+    };
+    alignment.max(1)
+}
+
+/// Largest alignment among the typed members of a class; hex bytes do not count
+pub fn class_alignment(ms: &MemoryStructure, class_id: u64, stack: &mut Vec<u64>) -> u64 {
+    let Some(def) = ms.class_registry.get(class_id) else {
+        return 1;
+    };
+    if stack.contains(&class_id) {
+        return 1;
+    }
+    stack.push(class_id);
+    let base_alignment = def
+        .base_class_id
+        .map_or(1, |base_id| class_alignment(ms, base_id, stack));
+    let alignment = def
+        .fields
+        .iter()
+        .filter(|fd| !fd.field_type.is_hex_type())
+        .map(|fd| field_alignment(ms, fd, stack))
+        .fold(base_alignment, u64::max);
+    stack.pop();
+    alignment
+}
+
+/// Check every typed field of `instance` against its natural alignment, and the instance size
+/// against the largest of them. Offsets come from the instance so dynamic-size fields count.
+pub fn alignment_warnings(ms: &MemoryStructure, instance: &ClassInstance) -> Vec<AlignmentWarning> {
+    let Some(def) = ms.class_registry.get(instance.class_id) else {
+        return Vec::new();
+    };
+    let mut warnings = Vec::new();
+    let mut stack = vec![def.id];
+    let mut max_alignment = def
+        .base_class_id
+        .map_or(1, |base_id| class_alignment(ms, base_id, &mut stack));
+    for (index, (fd, field)) in def.fields.iter().zip(&instance.fields).enumerate() {
+        if fd.field_type.is_hex_type() {
+            continue;
+        }
+        let alignment = field_alignment(ms, fd, &mut stack);
+        max_alignment = max_alignment.max(alignment);
+        let offset = field.address.wrapping_sub(instance.address);
+        if offset % alignment != 0 {
+            warnings.push(AlignmentWarning::MisalignedField {
+                index,
+                offset,
+                alignment,
+            });
+        }
+    }
+    let size = instance.get_size();
+    if !size.is_multiple_of(max_alignment) {
+        warnings.push(AlignmentWarning::UnalignedSize {
+            size,
+            alignment: max_alignment,
+        });
+    }
+    warnings
+}
+
+/// Field `index` at `offset` reuses the id of an earlier field, so both resolve to the first one
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DuplicateIdWarning {
+    pub index: usize,
+    pub offset: u64,
+    pub id: u64,
+}
+
+/// Look for fields of `instance` sharing an id, which makes edits and reads of one row land on
+/// the other
+pub fn duplicate_id_warnings(
+    ms: &MemoryStructure,
+    instance: &ClassInstance,
+) -> Vec<DuplicateIdWarning> {
+    let Some(def) = ms.class_registry.get(instance.class_id) else {
+        return Vec::new();
+    };
+    let mut warnings = Vec::new();
+    for (index, fd) in def.fields.iter().enumerate() {
+        if def.fields[..index]
+            .iter()
+            .any(|earlier| earlier.id == fd.id)
+        {
+            let offset = instance.fields.get(index).map_or(fd.offset, |field| {
+                field.address.wrapping_sub(instance.address)
+            });
+            warnings.push(DuplicateIdWarning {
+                index,
+                offset,
+                id: fd.id,
+            });
+        }
+    }
+    warnings
+}
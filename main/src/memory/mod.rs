@@ -1,6 +1,11 @@
+pub mod analyze;
+pub mod clipboard;
 pub mod definitions;
+pub mod diagnostics;
 pub mod nodes;
+pub mod snapshot;
 pub mod types;
+pub mod undo;
 
 pub use definitions::*;
 pub use nodes::*;
@@ -1,5 +1,9 @@
-use std::collections::HashSet;
+use std::{
+    collections::HashSet,
+    ops::Range,
+};
 
+use handle::AppHandle;
 use serde::{
     Deserialize,
     Serialize,
@@ -10,14 +14,27 @@ use crate::memory::{
         ClassDefinition,
         ClassDefinitionRegistry,
         EnumDefinitionRegistry,
+        FieldDefinition,
+    },
+    types::{
+        Endianness,
+        FieldType,
+        PointerTarget,
     },
-    types::FieldType,
 };
 
+/// Most bytes `ClassInstance::read_fields` reads at once
+const MAX_FIELDS_READ: u64 = 0x10000;
+
+/// Layout version of saved structures; files from before versioning are version 0
+pub const STRUCTURE_FORMAT_VERSION: u32 = 1;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryField {
     pub def_id: u64,
     pub address: u64,
+    // Bytes of this field from the owning instance's last block read; not persisted
+    #[serde(skip)]
     pub data: Option<Vec<u8>>,
     pub error: Option<String>,
     pub is_editing: bool,
@@ -44,6 +61,9 @@ pub struct ClassInstance {
     pub class_id: u64,
     pub fields: Vec<MemoryField>,
     pub total_size: u64,
+    // Instance of the base class at the same address; the fields start after it
+    #[serde(default)]
+    pub base: Option<Box<ClassInstance>>,
 }
 
 impl ClassInstance {
@@ -54,6 +74,7 @@ impl ClassInstance {
             class_id: class_definition.id,
             fields: Vec::new(),
             total_size: 0,
+            base: None,
         };
         instance.create_fields_from_definition(&class_definition);
         instance
@@ -71,7 +92,7 @@ impl ClassInstance {
             self.fields.push(memory_field);
 
             if !field_def.field_type.is_dynamic_size() {
-                current_offset += field_def.get_size();
+                current_offset += field_def.get_size_for(class_definition.pointer_size);
             }
         }
 
@@ -86,6 +107,90 @@ impl ClassInstance {
         self.total_size
     }
 
+    /// Instances embedded in this one: the base class instance followed by the class instances
+    /// and pointer targets held by its fields
+    pub fn nested_instances(&self) -> impl Iterator<Item = &ClassInstance> {
+        self.base.as_deref().into_iter().chain(
+            self.fields
+                .iter()
+                .filter_map(|field| field.nested_instance.as_ref()),
+        )
+    }
+
+    /// Read the whole instance `[address, address + total_size)` with a single read
+    pub fn read_block(&self, handle: &AppHandle) -> Option<Vec<u8>> {
+        if self.total_size == 0 {
+            return None;
+        }
+        let mut block = vec![0u8; self.total_size as usize];
+        handle.read_slice(self.address, block.as_mut_slice()).ok()?;
+        Some(block)
+    }
+
+    /// Distribute a block obtained by `read_block` onto the fields' `data`, including the
+    /// fields of embedded instances. Passing `None` clears the data of all fields.
+    pub fn apply_block(&mut self, block: Option<&[u8]>) {
+        if let Some(base) = self.base.as_mut() {
+            let len = base.total_size as usize;
+            base.apply_block(block.and_then(|b| b.get(..len)));
+        }
+        self.apply_fields_block(0..self.fields.len(), self.address, block);
+    }
+
+    /// End address of the field at `index`: the start of the next field, or the instance end
+    fn field_end(&self, index: usize) -> u64 {
+        self.fields
+            .get(index + 1)
+            .map(|f| f.address)
+            .unwrap_or_else(|| self.address.saturating_add(self.total_size))
+    }
+
+    /// Read only the fields in `range` with a single read, e.g. the rows in view, and
+    /// distribute the bytes onto them. The read stops short of `MAX_FIELDS_READ` bytes: fields
+    /// past that, and leading fields larger than it (long arrays), keep empty data so their
+    /// renderers read only what they show. Leaves the data empty when the read fails.
+    pub fn read_fields(&mut self, handle: &AppHandle, range: Range<usize>) {
+        let end = range.end.min(self.fields.len());
+        let Some(first) = (range.start..end).find(|&index| {
+            self.field_end(index)
+                .saturating_sub(self.fields[index].address)
+                <= MAX_FIELDS_READ
+        }) else {
+            return;
+        };
+        let start = self.fields[first].address;
+        let last = (first..end)
+            .take_while(|&index| self.field_end(index).saturating_sub(start) <= MAX_FIELDS_READ)
+            .last()
+            .unwrap_or(first);
+        let range = first..last + 1;
+        let len = self.field_end(last).saturating_sub(start) as usize;
+        let mut block = vec![0u8; len];
+        let read = handle.read_slice(start, block.as_mut_slice()).is_ok();
+        self.apply_fields_block(range, start, read.then_some(block.as_slice()));
+    }
+
+    /// Give the fields in `range` their bytes from `block`, which starts at `block_address`
+    fn apply_fields_block(
+        &mut self,
+        range: Range<usize>,
+        block_address: u64,
+        block: Option<&[u8]>,
+    ) {
+        let bounds: Vec<u64> = range.clone().map(|index| self.field_end(index)).collect();
+        for (field, next) in self.fields[range].iter_mut().zip(bounds) {
+            let start = field.address.saturating_sub(block_address) as usize;
+            let stop = next.saturating_sub(block_address) as usize;
+            field.data = block.and_then(|b| b.get(start..stop)).map(|d| d.to_vec());
+            if let Some(nested) = field.nested_instance.as_mut() {
+                // Pointer targets live elsewhere and are read on their own
+                if nested.address == field.address {
+                    nested.apply_block(field.data.as_deref());
+                }
+            }
+        }
+    }
+
     #[cfg(test)]
     pub fn get_display_name_with_registry(&self, reg: &ClassDefinitionRegistry) -> String {
         let cname = reg
@@ -96,6 +201,39 @@ impl ClassInstance {
     }
 }
 
+/// Labeled address saved with the project
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Bookmark {
+    pub name: String,
+    /// Address expression, evaluated again every time the bookmark is opened
+    pub expr: String,
+    /// Root class to switch to when the bookmark is opened
+    #[serde(default)]
+    pub class_id: Option<u64>,
+}
+
+/// Known entry points of one process image, saved with the project and applied when
+/// attaching to a process with that image name
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    /// Image base name of the process, e.g. `game.exe`; compared case-insensitively
+    pub image: String,
+    /// Added to the project's bookmarks on attach, skipping ones already there
+    #[serde(default)]
+    pub bookmarks: Vec<Bookmark>,
+    /// Opened as the root after attaching, like clicking a bookmark
+    #[serde(default)]
+    pub root: Option<Bookmark>,
+}
+
+impl Profile {
+    /// Whether the profile belongs to a process with image base name `image`
+    pub fn matches_image(&self, image: &str) -> bool {
+        self.image.trim().eq_ignore_ascii_case(image.trim())
+    }
+}
+
 /// Represents the root memory structure
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MemoryStructure {
@@ -103,6 +241,16 @@ pub struct MemoryStructure {
     pub class_registry: ClassDefinitionRegistry,
     #[serde(default)]
     pub enum_registry: EnumDefinitionRegistry,
+    #[serde(default)]
+    pub bookmarks: Vec<Bookmark>,
+    /// Byte order values are shown and written in; projects saved before this are little-endian
+    #[serde(default)]
+    pub endianness: Endianness,
+    #[serde(default)]
+    pub profiles: Vec<Profile>,
+    /// Layout version the structure was saved with, brought up to date by `migrate_format`
+    #[serde(default)]
+    pub format_version: u32,
 }
 
 impl MemoryStructure {
@@ -116,9 +264,64 @@ impl MemoryStructure {
             root_class,
             class_registry,
             enum_registry: EnumDefinitionRegistry::new(),
+            bookmarks: Vec::new(),
+            endianness: Endianness::Little,
+            profiles: Vec::new(),
+            format_version: STRUCTURE_FORMAT_VERSION,
         }
     }
 
+    /// Bring a structure saved by an older version to the current layout. Version 0 sized
+    /// Vector2 as 4 bytes; those fields and array elements become Float so the fields after
+    /// them keep their offsets. Returns the number of fields changed.
+    pub fn migrate_format(&mut self) -> usize {
+        let mut migrated = 0;
+        if self.format_version < 1 {
+            for cid in self.class_registry.get_class_ids() {
+                let Some(def) = self.class_registry.get_mut(cid) else {
+                    continue;
+                };
+                for field in &mut def.fields {
+                    let mut changed = field.field_type == FieldType::Vector2;
+                    if changed {
+                        field.field_type = FieldType::Float;
+                    }
+                    if let Some(element) = &mut field.array_element {
+                        changed |= narrow_vector2_elements(element);
+                    }
+                    if let Some(target @ PointerTarget::Array { .. }) = &mut field.pointer_target {
+                        changed |= narrow_vector2_elements(target);
+                    }
+                    migrated += changed as usize;
+                }
+            }
+        }
+        self.format_version = STRUCTURE_FORMAT_VERSION;
+        migrated
+    }
+
+    /// First profile for the process image `image`
+    pub fn profile_for_image(&self, image: &str) -> Option<&Profile> {
+        self.profiles.iter().find(|p| p.matches_image(image))
+    }
+
+    /// Add the profile's bookmarks that are not bookmarked yet (same name and expression).
+    /// Returns how many were added.
+    pub fn merge_profile_bookmarks(&mut self, profile: &Profile) -> usize {
+        let mut added = 0;
+        for bookmark in &profile.bookmarks {
+            let known = self
+                .bookmarks
+                .iter()
+                .any(|b| b.name == bookmark.name && b.expr == bookmark.expr);
+            if !known {
+                self.bookmarks.push(bookmark.clone());
+                added += 1;
+            }
+        }
+        added
+    }
+
     pub fn rename_class(&mut self, id: u64, new_name: &str) -> bool {
         if !self.class_registry.contains(id) {
             return false;
@@ -192,6 +395,105 @@ impl MemoryStructure {
         false
     }
 
+    /// Whether a pointer or array target names a class or enum that does not exist, also
+    /// through the element of a pointed-to array
+    pub fn is_target_missing(&self, target: &PointerTarget) -> bool {
+        match target {
+            PointerTarget::ClassId(id) => !self.class_registry.contains(*id),
+            PointerTarget::EnumId(id) => !self.enum_registry.contains(*id),
+            PointerTarget::Array { element, .. } => self.is_target_missing(element),
+            _ => false,
+        }
+    }
+
+    /// Clear references to classes and enums that do not exist, e.g. in a hand-edited file,
+    /// the way pasting does: embedded instances become Hex64, pointers to a missing class
+    /// or enum point to Hex64 and array elements become Hex8. Returns one line per repair.
+    pub fn validate_references(&mut self) -> Vec<String> {
+        let class_ids: HashSet<u64> = self.class_registry.get_class_ids().into_iter().collect();
+        let enum_ids: HashSet<u64> = self.enum_registry.get_enum_ids().into_iter().collect();
+        let mut repairs = Vec::new();
+        for cid in self.class_registry.get_class_ids() {
+            let loops = self.base_chain_loops(cid);
+            let Some(def) = self.class_registry.get_mut(cid) else {
+                continue;
+            };
+            if let Some(base) = def.base_class_id.filter(|id| !class_ids.contains(id)) {
+                repairs.push(format!("{}: base class {base} is missing", def.name));
+                def.base_class_id = None;
+            } else if loops {
+                repairs.push(format!(
+                    "{}: base classes lead back to it, base removed",
+                    def.name
+                ));
+                def.base_class_id = None;
+            }
+            for field in &mut def.fields {
+                let label = match &field.name {
+                    Some(name) => format!("{}.{name}", def.name),
+                    None => format!("{}+0x{:X}", def.name, field.offset),
+                };
+                if field.field_type == FieldType::ClassInstance {
+                    let missing = match field.class_id {
+                        Some(id) if class_ids.contains(&id) => None,
+                        Some(id) => Some(format!("class {id} is missing")),
+                        None => Some(String::from("no class is set")),
+                    };
+                    if let Some(missing) = missing {
+                        repairs.push(format!("{label}: {missing}, changed to Hex64"));
+                        field.field_type = FieldType::Hex64;
+                        field.class_id = None;
+                        field.name = None;
+                    }
+                }
+                if let Some(id) = field.enum_id.filter(|id| !enum_ids.contains(id)) {
+                    repairs.push(format!("{label}: enum {id} is missing"));
+                    field.enum_id = None;
+                }
+                if let Some(target) = field.pointer_target.as_mut() {
+                    if let Some(missing) =
+                        repair_target(target, &class_ids, &enum_ids, FieldType::Hex64)
+                    {
+                        repairs.push(format!("{label}: pointed-to {missing} is missing"));
+                    }
+                }
+                if let Some(element) = field.array_element.as_mut() {
+                    if let Some(missing) =
+                        repair_target(element, &class_ids, &enum_ids, FieldType::Hex8)
+                    {
+                        repairs.push(format!("{label}: element {missing} is missing"));
+                    }
+                }
+            }
+        }
+        if !repairs.is_empty() {
+            self.class_registry.relayout_all();
+        }
+        repairs
+    }
+
+    /// Whether following the base classes of `class_id` comes back to it
+    fn base_chain_loops(&self, class_id: u64) -> bool {
+        let mut seen = HashSet::new();
+        let mut current = self
+            .class_registry
+            .get(class_id)
+            .and_then(|def| def.base_class_id);
+        while let Some(id) = current {
+            if id == class_id {
+                return true;
+            }
+            if !seen.insert(id) {
+                return false;
+            }
+            current = self
+                .class_registry
+                .get(id)
+                .and_then(|def| def.base_class_id);
+        }
+        false
+    }
+
     #[cfg(test)]
     pub fn register_class(&mut self, class_def: ClassDefinition) {
         self.class_registry.register(class_def);
@@ -216,7 +518,7 @@ impl MemoryStructure {
 
     pub fn create_nested_instances(&mut self) {
         let registry = self.class_registry.clone();
-        Self::build_nested_for_instance(&registry, &mut self.root_class);
+        Self::build_nested_for_instance(&registry, &mut self.root_class, &mut Vec::new());
         Self::recalc_instance_layout(
             &self.enum_registry,
             &self.class_registry,
@@ -226,18 +528,35 @@ impl MemoryStructure {
 
     pub fn bind_nested_for_instance(&self, instance: &mut ClassInstance) {
         let registry = self.class_registry.clone();
-        Self::build_nested_for_instance(&registry, instance);
+        Self::build_nested_for_instance(&registry, instance, &mut Vec::new());
         Self::recalc_instance_layout(&self.enum_registry, &self.class_registry, instance);
     }
 
+    /// Width of pointer fields in the layout
+    pub fn pointer_size(&self) -> u64 {
+        self.class_registry.pointer_size()
+    }
+
+    /// Lay out the structure for a process whose pointers are `pointer_size` bytes, rebuilding
+    /// the instance tree when that moves any offsets
+    pub fn set_pointer_size(&mut self, pointer_size: u64) {
+        if pointer_size == self.pointer_size() {
+            return;
+        }
+        self.class_registry.set_pointer_size(pointer_size);
+        self.rebuild_root_from_registry();
+    }
+
     pub fn rebuild_root_from_registry(&mut self) {
         let root_type = self.root_class.class_id;
         if let Some(def) = self.class_registry.get(root_type).cloned() {
             let name = self.root_class.name.clone();
             let address = self.root_class.address;
-            self.root_class = ClassInstance::new(name, address, def);
+            let mut previous =
+                std::mem::replace(&mut self.root_class, ClassInstance::new(name, address, def));
+            Self::carry_pointer_instances(&mut previous, &mut self.root_class);
             let registry = self.class_registry.clone();
-            Self::build_nested_for_instance(&registry, &mut self.root_class);
+            Self::build_nested_for_instance(&registry, &mut self.root_class, &mut Vec::new());
             Self::recalc_instance_layout(
                 &self.enum_registry,
                 &self.class_registry,
@@ -246,7 +565,53 @@ impl MemoryStructure {
         }
     }
 
-    fn build_nested_for_instance(registry: &ClassDefinitionRegistry, instance: &mut ClassInstance) {
+    /// Hand the instances of expanded pointers in `previous` to the fields with the same
+    /// definition in `fresh`, so rebuilding the tree keeps them; embedded instances and the
+    /// base are searched too
+    fn carry_pointer_instances(previous: &mut ClassInstance, fresh: &mut ClassInstance) {
+        for field in &mut fresh.fields {
+            if let Some(old) = previous
+                .fields
+                .iter_mut()
+                .find(|f| f.def_id == field.def_id)
+            {
+                field.nested_instance = old.nested_instance.take();
+            }
+        }
+        fresh.base = previous.base.take();
+    }
+
+    /// Create the base and embedded instances of `instance`. `stack` holds the classes whose
+    /// base or embedded instances are being built, to stop on cyclic definitions; pointer
+    /// targets live elsewhere and start a stack of their own.
+    fn build_nested_for_instance(
+        registry: &ClassDefinitionRegistry,
+        instance: &mut ClassInstance,
+        stack: &mut Vec<u64>,
+    ) {
+        if stack.contains(&instance.class_id) {
+            instance.base = None;
+            for field in &mut instance.fields {
+                field.nested_instance = None;
+            }
+            return;
+        }
+        stack.push(instance.class_id);
+        let mut previous_base = instance.base.take();
+        instance.base = registry
+            .get_by_id(instance.class_id)
+            .and_then(|def| def.base_class_id)
+            .filter(|base_id| !stack.contains(base_id))
+            .and_then(|base_id| registry.get_by_id(base_id))
+            .map(|base_def| {
+                let mut base =
+                    ClassInstance::new(base_def.name.clone(), instance.address, base_def.clone());
+                if let Some(previous) = previous_base.as_deref_mut() {
+                    Self::carry_pointer_instances(previous, &mut base);
+                }
+                Self::build_nested_for_instance(registry, &mut base, stack);
+                Box::new(base)
+            });
         for field in &mut instance.fields {
             let field_def_opt = registry
                 .get_by_id(instance.class_id)
@@ -261,13 +626,16 @@ impl MemoryStructure {
                     };
                     if let Some(class_def) = class_def_opt {
                         // Always create a fresh instance and clear any stale nested linkage
-                        field.nested_instance = None;
+                        let previous = field.nested_instance.take();
                         let mut nested_instance = ClassInstance::new(
                             field_def.name.clone().unwrap_or_default(),
                             field.address,
                             class_def.clone(),
                         );
-                        Self::build_nested_for_instance(registry, &mut nested_instance);
+                        if let Some(mut previous) = previous {
+                            Self::carry_pointer_instances(&mut previous, &mut nested_instance);
+                        }
+                        Self::build_nested_for_instance(registry, &mut nested_instance, stack);
                         // Use default enum registry for nested; caller will re-run with real registry on rebuild
                         Self::recalc_instance_layout(
                             &EnumDefinitionRegistry::new(),
@@ -277,12 +645,33 @@ impl MemoryStructure {
                         field.nested_instance = Some(nested_instance);
                         continue;
                     }
+                } else if let (FieldType::Pointer, Some(PointerTarget::ClassId(cid))) =
+                    (&field_def.field_type, &field_def.pointer_target)
+                {
+                    // An expanded pointer keeps its instance, rebound to the current layout of
+                    // its class so edits show without collapsing the header
+                    field.nested_instance = field
+                        .nested_instance
+                        .take()
+                        .filter(|nested| nested.class_id == *cid)
+                        .and_then(|mut previous| {
+                            let class_def = registry.get_by_id(*cid)?;
+                            let mut nested = ClassInstance::new(
+                                previous.name.clone(),
+                                previous.address,
+                                class_def.clone(),
+                            );
+                            Self::carry_pointer_instances(&mut previous, &mut nested);
+                            Self::build_nested_for_instance(registry, &mut nested, &mut Vec::new());
+                            Some(nested)
+                        });
                 } else {
                     // Ensure primitive fields do not retain stale nested instances
                     field.nested_instance = None;
                 }
             }
         }
+        stack.pop();
         Self::recalc_instance_layout(&EnumDefinitionRegistry::new(), registry, instance);
     }
 
@@ -292,6 +681,11 @@ impl MemoryStructure {
         instance: &mut ClassInstance,
     ) {
         let mut current_offset: u64 = 0;
+        if let Some(base) = instance.base.as_mut() {
+            base.address = instance.address;
+            Self::recalc_instance_layout(enum_registry, class_registry, base);
+            current_offset = base.total_size;
+        }
         for field in &mut instance.fields {
             field.address = instance.address + current_offset;
             let fd_opt = class_registry
@@ -308,25 +702,20 @@ impl MemoryStructure {
                             0
                         }
                     }
+                    FieldType::Pointer => {
+                        // An expanded pointer's instance lives elsewhere; only its own layout
+                        if let Some(ref mut nested) = field.nested_instance {
+                            Self::recalc_instance_layout(enum_registry, class_registry, nested);
+                        }
+                        fd.get_size_for(class_registry.pointer_size())
+                    }
                     FieldType::Array => {
-                        // Look up field definition for element and length
-                        let len = fd.array_length.unwrap_or(0) as u64;
-                        let elem_size: u64 = match &fd.array_element {
-                            Some(crate::memory::types::PointerTarget::FieldType(t)) => t.get_size(),
-                            Some(crate::memory::types::PointerTarget::EnumId(eid)) => enum_registry
-                                .get_by_id(*eid)
-                                .map(|ed| ed.default_size as u64)
-                                .unwrap_or(0),
-                            Some(crate::memory::types::PointerTarget::ClassId(cid)) => {
-                                class_registry
-                                    .get_by_id(*cid)
-                                    .map(|cd| cd.total_size)
-                                    .unwrap_or(0)
-                            }
-                            Some(crate::memory::types::PointerTarget::Array { .. }) => 0,
-                            None => 0,
+                        // Elements of a class count its embedded classes and arrays too
+                        let layout = Layout {
+                            enums: enum_registry,
+                            classes: class_registry,
                         };
-                        elem_size.saturating_mul(len)
+                        layout.field_size(fd, &mut vec![instance.class_id])
                     }
                     FieldType::Enum => {
                         if let Some(eid) = fd.enum_id {
@@ -339,7 +728,7 @@ impl MemoryStructure {
                             4
                         }
                     }
-                    _ => fd.field_type.get_size(),
+                    _ => fd.get_size_for(class_registry.pointer_size()),
                 }
             } else {
                 0
@@ -366,7 +755,7 @@ impl MemoryStructure {
             let address = self.root_class.address;
             self.root_class = ClassInstance::new(name, address, def);
             let registry = self.class_registry.clone();
-            Self::build_nested_for_instance(&registry, &mut self.root_class);
+            Self::build_nested_for_instance(&registry, &mut self.root_class, &mut Vec::new());
             Self::recalc_instance_layout(
                 &self.enum_registry,
                 &self.class_registry,
@@ -378,6 +767,138 @@ impl MemoryStructure {
         }
     }
 
+    /// Change the type of a field with the defaults of "Change type": pointers point to Hex64,
+    /// enum fields use the first enum and arrays hold one Hex8
+    pub fn set_field_type_with_defaults(
+        &mut self,
+        class_id: u64,
+        index: usize,
+        field_type: FieldType,
+    ) {
+        let first_enum = self.enum_registry.get_enum_ids().into_iter().next();
+        let Some(def) = self.class_registry.get_mut(class_id) else {
+            return;
+        };
+        def.set_field_type_at(index, field_type.clone());
+        let Some(fd) = def.fields.get_mut(index) else {
+            return;
+        };
+        match field_type {
+            FieldType::Pointer => {
+                fd.pointer_target = Some(PointerTarget::FieldType(FieldType::Hex64));
+            }
+            FieldType::Enum => fd.enum_id = first_enum,
+            _ => {}
+        }
+    }
+
+    /// Indices of the fields of type `from` per class, in `class_id` or in every class
+    fn fields_of_type(&self, class_id: Option<u64>, from: &FieldType) -> Vec<(u64, Vec<usize>)> {
+        let class_ids = match class_id {
+            Some(id) => vec![id],
+            None => self.class_registry.get_class_ids(),
+        };
+        class_ids
+            .into_iter()
+            .filter_map(|id| {
+                let def = self.class_registry.get(id)?;
+                let indices: Vec<usize> = def
+                    .fields
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, fd)| fd.field_type == *from)
+                    .map(|(index, _)| index)
+                    .collect();
+                (!indices.is_empty()).then_some((id, indices))
+            })
+            .collect()
+    }
+
+    /// Number of fields of type `from` in `class_id`, or in every class when `None`
+    pub fn count_fields_of_type(&self, class_id: Option<u64>, from: &FieldType) -> usize {
+        self.fields_of_type(class_id, from)
+            .iter()
+            .map(|(_, indices)| indices.len())
+            .sum()
+    }
+
+    /// Change every field of type `from` in `class_id` (or in every class) to `to`, see
+    /// `set_field_type_with_defaults`. Returns how many fields changed.
+    pub fn replace_field_types(
+        &mut self,
+        class_id: Option<u64>,
+        from: &FieldType,
+        to: &FieldType,
+    ) -> usize {
+        if from == to {
+            return 0;
+        }
+        let mut count = 0;
+        for (id, indices) in self.fields_of_type(class_id, from) {
+            for index in indices {
+                self.set_field_type_with_defaults(id, index, to.clone());
+                count += 1;
+            }
+        }
+        count
+    }
+
+    /// One line per field among `field_ids` of `class_id` that embeds, points to or holds an
+    /// array of a class, e.g. `+0x10 stats: ClassInstance -> Stats`; removing such a field
+    /// drops that nested layout from the tree
+    pub fn class_referencing_fields(&self, class_id: u64, field_ids: &HashSet<u64>) -> Vec<String> {
+        let Some(def) = self.class_registry.get(class_id) else {
+            return Vec::new();
+        };
+        def.fields
+            .iter()
+            .zip(self.field_offsets(class_id))
+            .filter(|(fd, _)| field_ids.contains(&fd.id) && fd.referenced_class().is_some())
+            .map(|(fd, offset)| self.field_summary_line(fd, offset))
+            .collect()
+    }
+
+    /// Short description of a class for tooltips: laid-out size, field count and the first
+    /// `max_fields` fields with their offsets, e.g. `+0x8 stats: ClassInstance -> Stats`
+    pub fn class_summary(&self, class_id: u64, max_fields: usize) -> Option<String> {
+        let def = self.class_registry.get(class_id)?;
+        let size = self.layout().class_size(class_id, &mut Vec::new());
+        let mut text = format!("0x{size:X} bytes, {} field(s)", def.fields.len());
+        if let Some(base) = def.base_class_id.and_then(|id| self.class_registry.get(id)) {
+            text.push_str(&format!(", base {}", base.name));
+        }
+        for (fd, offset) in def
+            .fields
+            .iter()
+            .zip(self.field_offsets(class_id))
+            .take(max_fields)
+        {
+            text.push('\n');
+            text.push_str(&self.field_summary_line(fd, offset));
+        }
+        if def.fields.len() > max_fields {
+            text.push_str("\n…");
+        }
+        Some(text)
+    }
+
+    /// `+0xOFFSET name: Type`, followed by `-> Class` for fields that embed or point to one
+    fn field_summary_line(&self, fd: &FieldDefinition, offset: u64) -> String {
+        let mut line = format!(
+            "+0x{offset:X} {}: {}",
+            fd.name.as_deref().unwrap_or("<unnamed>"),
+            fd.field_type
+        );
+        if let Some(target) = fd.referenced_class() {
+            let target_name = self
+                .class_registry
+                .get(target)
+                .map_or_else(|| format!("#{target}"), |d| d.name.clone());
+            line.push_str(&format!(" -> {target_name}"));
+        }
+        line
+    }
+
     /// Check if assigning `target_class_id` to a field within `owner_class_id` would create a cycle
     pub fn would_create_cycle(&self, owner_class_id: u64, target_class_id: u64) -> bool {
         // If same class, direct self-cycle
@@ -396,6 +917,12 @@ impl MemoryStructure {
                 return false;
             }
             if let Some(def) = reg.get_by_id(current) {
+                // A base class is embedded like a class instance field
+                if let Some(base) = def.base_class_id {
+                    if base == target || dfs(reg, base, target, visited) {
+                        return true;
+                    }
+                }
                 for f in &def.fields {
                     if f.field_type == FieldType::ClassInstance {
                         if let Some(cid) = f.class_id {
@@ -419,6 +946,189 @@ impl MemoryStructure {
         )
     }
 
+    /// Make `base_id` the base class of `class_id`, or clear it with `None`. Refused when the
+    /// base does not exist or already embeds `class_id`.
+    pub fn set_base_class(&mut self, class_id: u64, base_id: Option<u64>) -> bool {
+        if let Some(base_id) = base_id {
+            if !self.class_registry.contains(base_id) || self.would_create_cycle(class_id, base_id)
+            {
+                return false;
+            }
+        }
+        match self.class_registry.get_mut(class_id) {
+            Some(def) => {
+                def.base_class_id = base_id;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Laid-out size of the base class of `class_id`, where its own fields start
+    pub fn base_class_size(&self, class_id: u64) -> u64 {
+        self.class_registry
+            .get(class_id)
+            .and_then(|def| def.base_class_id)
+            .map_or(0, |base_id| {
+                self.layout().class_size(base_id, &mut vec![class_id])
+            })
+    }
+
+    /// Find the current address of a field by its owning instance address and definition id
+    pub fn find_field_address(&self, instance_address: u64, def_id: u64) -> Option<u64> {
+        fn walk(instance: &ClassInstance, instance_address: u64, def_id: u64) -> Option<u64> {
+            if instance.address == instance_address {
+                if let Some(f) = instance.fields.iter().find(|f| f.def_id == def_id) {
+                    return Some(f.address);
+                }
+            }
+            instance
+                .nested_instances()
+                .find_map(|nested| walk(nested, instance_address, def_id))
+        }
+        walk(&self.root_class, instance_address, def_id)
+    }
+
+    /// Find the instance of `class_id` living at `instance_address` anywhere in the tree
+    pub fn find_instance(&self, instance_address: u64, class_id: u64) -> Option<&ClassInstance> {
+        fn walk(instance: &ClassInstance, address: u64, class_id: u64) -> Option<&ClassInstance> {
+            if instance.address == address && instance.class_id == class_id {
+                return Some(instance);
+            }
+            instance
+                .nested_instances()
+                .find_map(|nested| walk(nested, address, class_id))
+        }
+        walk(&self.root_class, instance_address, class_id)
+    }
+
+    /// Fields of every class whose name or comment contains `needle` (case-insensitive),
+    /// ordered by class name and field index
+    pub fn find_fields(&self, needle: &str) -> Vec<(u64, usize)> {
+        let needle = needle.trim().to_lowercase();
+        if needle.is_empty() {
+            return Vec::new();
+        }
+        let mut classes: Vec<&ClassDefinition> = self
+            .class_registry
+            .get_class_ids()
+            .into_iter()
+            .filter_map(|id| self.class_registry.get(id))
+            .collect();
+        classes.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+        let matches = |text: Option<&String>| {
+            text.map(|t| t.to_lowercase().contains(&needle))
+                .unwrap_or(false)
+        };
+        classes
+            .into_iter()
+            .flat_map(|def| {
+                def.fields
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, fd)| matches(fd.name.as_ref()) || matches(fd.comment.as_ref()))
+                    .map(move |(index, _)| (def.id, index))
+            })
+            .collect()
+    }
+
+    /// Size of a field as laid out in memory, following embedded classes and arrays.
+    /// `stack` holds the classes currently being measured to stop on cyclic definitions.
+    pub fn field_size(&self, fd: &FieldDefinition, stack: &mut Vec<u64>) -> u64 {
+        self.layout().field_size(fd, stack)
+    }
+
+    /// Size of one element of an array field, without the stride
+    pub fn array_element_size(&self, fd: &FieldDefinition, stack: &mut Vec<u64>) -> u64 {
+        self.layout().array_element_size(fd, stack)
+    }
+
+    /// Size of a class as laid out in memory, counting its base class, embedded classes and
+    /// arrays (unlike `ClassDefinition::total_size`)
+    pub fn class_size(&self, class_id: u64) -> u64 {
+        self.layout().class_size(class_id, &mut Vec::new())
+    }
+
+    fn layout(&self) -> Layout<'_> {
+        Layout {
+            enums: &self.enum_registry,
+            classes: &self.class_registry,
+        }
+    }
+
+    /// Offset of every field of a class from the cumulative laid-out sizes of the base class
+    /// and the fields before it (unlike `FieldDefinition::offset`, embedded classes and arrays
+    /// count)
+    pub fn field_offsets(&self, class_id: u64) -> Vec<u64> {
+        let Some(def) = self.class_registry.get(class_id) else {
+            return Vec::new();
+        };
+        let mut offset = self.base_class_size(class_id);
+        def.fields
+            .iter()
+            .map(|fd| {
+                let start = offset;
+                offset = offset.saturating_add(self.field_size(fd, &mut vec![class_id]));
+                start
+            })
+            .collect()
+    }
+
+    /// Byte ranges `(address, len)` covered by the fields `field_ids` of an instance of
+    /// `class_id` at `instance_address`, with adjacent fields merged into one range
+    pub fn field_byte_ranges(
+        &self,
+        instance_address: u64,
+        class_id: u64,
+        field_ids: &HashSet<u64>,
+    ) -> Vec<(u64, u64)> {
+        let Some(def) = self.class_registry.get(class_id) else {
+            return Vec::new();
+        };
+        let mut ranges: Vec<(u64, u64)> = Vec::new();
+        for (fd, offset) in def.fields.iter().zip(self.field_offsets(class_id)) {
+            if !field_ids.contains(&fd.id) {
+                continue;
+            }
+            let len = self.field_size(fd, &mut vec![class_id]);
+            if len == 0 {
+                continue;
+            }
+            let start = instance_address + offset;
+            match ranges.last_mut() {
+                Some((last_start, last_len)) if *last_start + *last_len == start => {
+                    *last_len += len;
+                }
+                _ => ranges.push((start, len)),
+            }
+        }
+        ranges
+    }
+
+    /// Classes with a field starting exactly at `offset`, optionally only of type `ty`,
+    /// ordered by class name
+    pub fn classes_with_field_at(&self, offset: u64, ty: Option<FieldType>) -> Vec<u64> {
+        let mut classes: Vec<&ClassDefinition> = self
+            .class_registry
+            .get_class_ids()
+            .into_iter()
+            .filter_map(|id| self.class_registry.get(id))
+            .collect();
+        classes.sort_by(|a, b| a.name.cmp(&b.name).then(a.id.cmp(&b.id)));
+        classes
+            .into_iter()
+            .filter(|def| {
+                self.field_offsets(def.id)
+                    .iter()
+                    .zip(&def.fields)
+                    .any(|(start, fd)| {
+                        *start == offset && ty.as_ref().is_none_or(|t| *t == fd.field_type)
+                    })
+            })
+            .map(|def| def.id)
+            .collect()
+    }
+
     #[allow(dead_code)]
     pub fn get_total_size(&self) -> u64 {
         self.root_class.get_size()
@@ -429,3 +1139,97 @@ impl MemoryStructure {
         self.class_registry.get_class_ids()
     }
 }
+
+/// Sizes of fields and classes computed from the registries alone, so instance layout and
+/// `MemoryStructure::field_size` agree
+struct Layout<'a> {
+    enums: &'a EnumDefinitionRegistry,
+    classes: &'a ClassDefinitionRegistry,
+}
+
+impl Layout<'_> {
+    fn field_size(&self, fd: &FieldDefinition, stack: &mut Vec<u64>) -> u64 {
+        match fd.field_type {
+            FieldType::ClassInstance => fd
+                .class_id
+                .map(|cid| self.class_size(cid, stack))
+                .unwrap_or(0),
+            FieldType::Array => {
+                let len = fd.array_length.unwrap_or(0) as u64;
+                let elem_size = self.array_element_size(fd, stack);
+                fd.array_step(elem_size).saturating_mul(len)
+            }
+            FieldType::Enum => fd
+                .enum_id
+                .and_then(|eid| self.enums.get(eid))
+                .map(|ed| ed.default_size as u64)
+                .unwrap_or(4),
+            _ => fd.get_size_for(self.classes.pointer_size()),
+        }
+    }
+
+    fn array_element_size(&self, fd: &FieldDefinition, stack: &mut Vec<u64>) -> u64 {
+        match &fd.array_element {
+            Some(PointerTarget::FieldType(t)) => t.get_size_for(self.classes.pointer_size()),
+            Some(PointerTarget::EnumId(eid)) => self
+                .enums
+                .get(*eid)
+                .map(|ed| ed.default_size as u64)
+                .unwrap_or(0),
+            Some(PointerTarget::ClassId(cid)) => self.class_size(*cid, stack),
+            Some(PointerTarget::Array { .. }) | Some(PointerTarget::Chain { .. }) | None => 0,
+        }
+    }
+
+    fn class_size(&self, class_id: u64, stack: &mut Vec<u64>) -> u64 {
+        if stack.contains(&class_id) {
+            return 0;
+        }
+        let Some(def) = self.classes.get(class_id) else {
+            return 0;
+        };
+        stack.push(class_id);
+        let base_size = def
+            .base_class_id
+            .map_or(0, |base_id| self.class_size(base_id, stack));
+        let size = def
+            .fields
+            .iter()
+            .map(|fd| self.field_size(fd, stack))
+            .fold(base_size, u64::saturating_add);
+        stack.pop();
+        size
+    }
+}
+
+/// Replace a class or enum id missing from the registries with `fallback`, also inside
+/// pointed-to arrays whose elements fall back to Hex8. Returns what was missing.
+fn repair_target(
+    target: &mut PointerTarget,
+    class_ids: &HashSet<u64>,
+    enum_ids: &HashSet<u64>,
+    fallback: FieldType,
+) -> Option<String> {
+    let missing = match target {
+        PointerTarget::ClassId(id) if !class_ids.contains(id) => format!("class {id}"),
+        PointerTarget::EnumId(id) if !enum_ids.contains(id) => format!("enum {id}"),
+        PointerTarget::Array { element, .. } => {
+            return repair_target(element, class_ids, enum_ids, FieldType::Hex8);
+        }
+        _ => return None,
+    };
+    *target = PointerTarget::FieldType(fallback);
+    Some(missing)
+}
+
+/// Turn version 0 Vector2 array elements, nested ones included, into Float
+fn narrow_vector2_elements(target: &mut PointerTarget) -> bool {
+    match target {
+        PointerTarget::FieldType(t) if *t == FieldType::Vector2 => {
+            *t = FieldType::Float;
+            true
+        }
+        PointerTarget::Array { element, .. } => narrow_vector2_elements(element),
+        _ => false,
+    }
+}
@@ -0,0 +1,25 @@
+/// Bytes of an instance captured at one moment, to find the fields that change afterwards
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Snapshot {
+    pub address: u64,
+    pub bytes: Vec<u8>,
+}
+
+impl Snapshot {
+    pub fn new(address: u64, bytes: Vec<u8>) -> Self {
+        Self { address, bytes }
+    }
+
+    /// Captured bytes of `[address, address + len)`, if the snapshot covers the whole range
+    pub fn slice(&self, address: u64, len: usize) -> Option<&[u8]> {
+        let start = usize::try_from(address.checked_sub(self.address)?).ok()?;
+        self.bytes.get(start..start.checked_add(len)?)
+    }
+
+    /// Whether `current`, read at `address`, differs from the captured bytes. Ranges the
+    /// snapshot does not cover never count as changed.
+    pub fn differs(&self, address: u64, current: &[u8]) -> bool {
+        self.slice(address, current.len())
+            .is_some_and(|captured| captured != current)
+    }
+}
@@ -1,15 +1,24 @@
 use crate::memory::{
     definitions::{
+        is_auto_field_name,
         ClassDefinition,
         ClassDefinitionRegistry,
         FieldDefinition,
+        TEXT_MAX_LENGTH,
     },
     nodes::{
+        Bookmark,
         ClassInstance,
         MemoryField,
         MemoryStructure,
+        Profile,
+        STRUCTURE_FORMAT_VERSION,
+    },
+    types::{
+        Endianness,
+        FieldType,
+        NumberBase,
     },
-    types::FieldType,
 };
 
 #[cfg(test)]
@@ -37,17 +46,33 @@ mod field_type_tests {
         assert_eq!(FieldType::Float.get_size(), 4);
         assert_eq!(FieldType::Double.get_size(), 8);
 
-        assert_eq!(FieldType::Vector2.get_size(), 4);
+        assert_eq!(FieldType::Vector2.get_size(), 8);
         assert_eq!(FieldType::Vector3.get_size(), 12);
         assert_eq!(FieldType::Vector4.get_size(), 16);
+        assert_eq!(FieldType::Guid.get_size(), 16);
+        assert_eq!(FieldType::Quaternion.get_size(), 16);
+        assert_eq!(FieldType::Matrix4x4.get_size(), 64);
 
         assert_eq!(FieldType::Text.get_size(), 32);
         assert_eq!(FieldType::TextPointer.get_size(), 8);
+        assert_eq!(FieldType::WideText.get_size(), 64);
+        assert_eq!(FieldType::WideTextPointer.get_size(), 8);
 
         assert_eq!(FieldType::ClassInstance.get_size(), 0); // Dynamic size
         assert_eq!(FieldType::Array.get_size(), 0); // Dynamic size
     }
 
+    #[test]
+    fn test_pointer_sizes_follow_process_width() {
+        assert_eq!(FieldType::Pointer.get_size_for(4), 4);
+        assert_eq!(FieldType::TextPointer.get_size_for(4), 4);
+        assert_eq!(FieldType::WideTextPointer.get_size_for(4), 4);
+        assert_eq!(FieldType::Pointer.get_size_for(8), 8);
+        // Fixed-width types do not depend on the process
+        assert_eq!(FieldType::Hex64.get_size_for(4), 8);
+        assert_eq!(FieldType::UInt32.get_size_for(8), 4);
+    }
+
     #[test]
     fn test_hex_type_detection() {
         assert!(FieldType::Hex64.is_hex_type());
@@ -86,6 +111,61 @@ mod field_type_tests {
         assert_eq!(FieldType::ClassInstance.to_string(), "ClassInstance");
         assert_eq!(FieldType::Array.to_string(), "Array");
     }
+
+    #[test]
+    fn test_timestamp_format() {
+        assert_eq!(FieldType::UnixTime32.get_size(), 4);
+        assert_eq!(FieldType::Win32FileTime.get_size(), 8);
+        assert_eq!(
+            FieldType::UnixTime32
+                .format_timestamp(1_709_641_696)
+                .as_deref(),
+            Some("2024-03-05 12:28:16 UTC")
+        );
+        assert_eq!(
+            FieldType::UnixTime64
+                .format_timestamp(951_782_400)
+                .as_deref(),
+            Some("2000-02-29 00:00:00 UTC")
+        );
+        // 2024-03-05 12:28:16 in 100ns ticks since 1601
+        assert_eq!(
+            FieldType::Win32FileTime
+                .format_timestamp(133_541_152_960_000_000)
+                .as_deref(),
+            Some("2024-03-05 12:28:16 UTC")
+        );
+        // Zero and values far in the future fall back to the number
+        assert_eq!(
+            FieldType::UnixTime64.format_timestamp(0).as_deref(),
+            Some("0")
+        );
+        assert_eq!(
+            FieldType::UnixTime64.format_timestamp(u64::MAX).as_deref(),
+            Some("18446744073709551615")
+        );
+        assert_eq!(FieldType::UInt32.format_timestamp(1), None);
+    }
+
+    #[test]
+    fn test_number_base_format() {
+        assert_eq!(NumberBase::Decimal.format(0xFF, 1, true), "-1");
+        assert_eq!(NumberBase::Decimal.format(0xFF, 1, false), "255");
+        assert_eq!(NumberBase::Decimal.format(0xFFFF_FFFE, 4, true), "-2");
+        assert_eq!(NumberBase::Hexadecimal.format(0x1A, 2, false), "0x001A");
+        assert_eq!(
+            NumberBase::Hexadecimal.format(u64::MAX, 8, true),
+            "0xFFFFFFFFFFFFFFFF"
+        );
+        assert_eq!(NumberBase::Binary.format(0x105, 1, false), "0b00000101");
+
+        assert_eq!(
+            FieldType::Hex32.default_base(),
+            Some(NumberBase::Hexadecimal)
+        );
+        assert_eq!(FieldType::UInt16.default_base(), Some(NumberBase::Decimal));
+        assert_eq!(FieldType::Float.default_base(), None);
+    }
 }
 
 #[cfg(test)]
@@ -106,12 +186,152 @@ mod field_definition_tests {
         assert_eq!(hex_field.offset, 8);
         assert_eq!(hex_field.get_size(), 8);
     }
+
+    #[test]
+    fn test_bitfield_extracts_bits() {
+        let mut field = FieldDefinition::new_named("flags".to_string(), FieldType::Bitfield, 0);
+        field.bitfield_size = Some(2);
+        field.bit_offset = Some(3);
+        field.bit_width = Some(3);
+        assert_eq!(field.get_size(), 2);
+        assert_eq!(field.extract_bits(0b1010_1000), 0b101);
+
+        field.bitfield_size = Some(8);
+        field.bit_offset = Some(0);
+        field.bit_width = Some(64);
+        assert_eq!(field.extract_bits(u64::MAX), u64::MAX);
+    }
+
+    #[test]
+    fn test_bitfield_layout_is_clamped() {
+        let mut field = FieldDefinition::new_named("flags".to_string(), FieldType::Bitfield, 0);
+        assert_eq!(field.bitfield_layout(), (4, 0, 1));
+
+        field.bitfield_size = Some(3);
+        field.bit_offset = Some(30);
+        field.bit_width = Some(8);
+        // Invalid sizes fall back to 4 bytes and the range is pulled inside the backing integer
+        assert_eq!(field.bitfield_layout(), (4, 24, 8));
+
+        field.bitfield_size = Some(1);
+        field.bit_width = Some(0);
+        assert_eq!(field.bitfield_layout(), (1, 7, 1));
+    }
+
+    #[test]
+    fn test_array_pointee_class() {
+        use crate::memory::types::PointerTarget;
+
+        let mut class = ClassDefinition::new("List".to_string());
+        class.add_named_field("items".to_string(), FieldType::Int32);
+        class.set_field_type_at(0, FieldType::Array);
+        class.fields[0].array_length = Some(3);
+        assert_eq!(class.fields[0].array_pointee_class(), None);
+
+        class.fields[0].array_element = Some(PointerTarget::FieldType(FieldType::Pointer));
+        assert_eq!(class.fields[0].array_pointee_class(), None);
+        class.fields[0].pointer_target = Some(PointerTarget::ClassId(42));
+        assert_eq!(class.fields[0].array_pointee_class(), Some(42));
+
+        // Each element is one pointer slot, whatever the target class
+        let mut structure = MemoryStructure::new("root".to_string(), 0x1000, class);
+        structure.rebuild_root_from_registry();
+        assert_eq!(structure.root_class.get_size(), 24);
+
+        let mut plain = structure
+            .class_registry
+            .get(structure.root_class.class_id)
+            .unwrap()
+            .clone();
+        plain.fields[0].array_element = Some(PointerTarget::ClassId(42));
+        assert_eq!(plain.fields[0].array_pointee_class(), None);
+    }
+
+    #[test]
+    fn test_array_stride_spaces_elements() {
+        use crate::memory::types::PointerTarget;
+
+        let mut class = ClassDefinition::new("Slots".to_string());
+        class.add_named_field("slots".to_string(), FieldType::Int32);
+        class.set_field_type_at(0, FieldType::Array);
+        class.fields[0].array_element = Some(PointerTarget::FieldType(FieldType::Int32));
+        class.fields[0].array_length = Some(4);
+        class.set_array_stride_at(0, Some(0x10));
+        assert_eq!(class.fields[0].array_step(4), 0x10);
+        // A stride below the element size is ignored
+        assert_eq!(class.fields[0].array_step(0x20), 0x20);
+
+        let mut structure = MemoryStructure::new("root".to_string(), 0x1000, class);
+        structure.rebuild_root_from_registry();
+        assert_eq!(structure.root_class.get_size(), 0x40);
+
+        let def = structure
+            .class_registry
+            .get_mut(structure.root_class.class_id)
+            .unwrap();
+        def.set_array_stride_at(0, None);
+        structure.rebuild_root_from_registry();
+        assert_eq!(structure.root_class.get_size(), 0x10);
+    }
 }
 
 #[cfg(test)]
 mod class_definition_tests {
     use super::*;
 
+    #[test]
+    fn test_field_color_roundtrip() {
+        let mut class = ClassDefinition::new("Colored".to_string());
+        class.add_named_field("health".to_string(), FieldType::Int32);
+        assert_eq!(class.fields[0].color, None);
+        class.set_color_at(0, Some([230, 90, 90]));
+
+        let json = serde_json::to_string(&class).unwrap();
+        let loaded: ClassDefinition = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.fields[0].color, Some([230, 90, 90]));
+
+        class.set_color_at(0, None);
+        assert_eq!(class.fields[0].color, None);
+    }
+
+    #[test]
+    fn test_display_base_roundtrip() {
+        let mut class = ClassDefinition::new("Counters".to_string());
+        class.add_named_field("flags".to_string(), FieldType::UInt32);
+        class.add_named_field("speed".to_string(), FieldType::Float);
+        class.set_display_base_at(0, Some(NumberBase::Binary));
+        // Only integer and hex fields have a base
+        class.set_display_base_at(1, Some(NumberBase::Hexadecimal));
+        assert_eq!(class.fields[1].display_base, None);
+
+        let json = serde_json::to_string(&class).unwrap();
+        let loaded: ClassDefinition = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.fields[0].display_base, Some(NumberBase::Binary));
+
+        class.set_field_type_at(0, FieldType::Hex32);
+        assert_eq!(class.fields[0].display_base, Some(NumberBase::Binary));
+        class.set_field_type_at(0, FieldType::Double);
+        assert_eq!(class.fields[0].display_base, None);
+    }
+
+    #[test]
+    fn test_field_comment_roundtrip() {
+        let mut class = ClassDefinition::new("Commented".to_string());
+        class.add_named_field("health".to_string(), FieldType::Int32);
+        class.set_comment_at(0, "  current hp\nclamped to max_health  ");
+        assert_eq!(
+            class.fields[0].comment.as_deref(),
+            Some("current hp\nclamped to max_health")
+        );
+
+        let json = serde_json::to_string(&class).unwrap();
+        let loaded: ClassDefinition = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.fields[0].comment, class.fields[0].comment);
+
+        class.set_comment_at(0, "   ");
+        assert_eq!(class.fields[0].comment, None);
+    }
+
     #[test]
     fn test_class_definition_creation() {
         let class = ClassDefinition::new("TestClass".to_string());
@@ -120,6 +340,83 @@ mod class_definition_tests {
         assert_eq!(class.total_size, 0);
     }
 
+    #[test]
+    fn test_bitfield_backing_size_drives_layout() {
+        let mut class = ClassDefinition::new("Flags".to_string());
+        class.add_named_field("bits".to_string(), FieldType::Int32);
+        class.add_named_field("after".to_string(), FieldType::Int32);
+        class.set_field_type_at(0, FieldType::Bitfield);
+        assert_eq!(class.fields[0].bitfield_layout(), (4, 0, 1));
+
+        class.set_bitfield_at(0, 1, 6, 4);
+        assert_eq!(class.fields[0].bitfield_layout(), (1, 4, 4));
+        assert_eq!(class.fields[1].offset, 1);
+        assert_eq!(class.total_size, 5);
+
+        class.set_field_type_at(0, FieldType::UInt16);
+        assert_eq!(class.fields[0].bit_width, None);
+        assert_eq!(class.total_size, 6);
+    }
+
+    #[test]
+    fn test_text_length_drives_layout() {
+        let mut class = ClassDefinition::new("Names".to_string());
+        class.add_named_field("name".to_string(), FieldType::Text);
+        class.add_named_field("tag".to_string(), FieldType::TextPointer);
+        class.add_named_field("after".to_string(), FieldType::Int32);
+        assert_eq!(class.fields[0].text_read_length(), 32);
+        assert_eq!(class.fields[1].text_read_length(), TEXT_MAX_LENGTH);
+
+        class.set_text_length_at(0, Some(128));
+        class.set_text_length_at(1, Some(64));
+        assert_eq!(class.fields[0].get_size(), 128);
+        assert_eq!(class.fields[1].get_size(), 8);
+        assert_eq!(class.fields[1].text_read_length(), 64);
+        assert_eq!(class.fields[2].offset, 136);
+
+        // Clamped, ignored for other types and cleared on retype
+        class.set_text_length_at(0, Some(0));
+        assert_eq!(class.fields[0].text_length, Some(1));
+        class.set_text_length_at(2, Some(16));
+        assert_eq!(class.fields[2].text_length, None);
+        class.set_field_type_at(0, FieldType::Hex64);
+        assert_eq!(class.fields[0].text_length, None);
+        assert_eq!(class.total_size, 20);
+    }
+
+    #[test]
+    fn test_text_encoding_is_kept_per_field() {
+        use crate::memory::TextEncoding;
+
+        let mut class = ClassDefinition::new("Names".to_string());
+        class.add_named_field("name".to_string(), FieldType::Text);
+        class.add_named_field("count".to_string(), FieldType::Int32);
+        class.set_text_encoding_at(0, Some(TextEncoding::ShiftJis));
+        class.set_text_encoding_at(1, Some(TextEncoding::Utf16Le));
+        assert_eq!(class.fields[0].text_encoding, Some(TextEncoding::ShiftJis));
+        assert_eq!(class.fields[1].text_encoding, None);
+
+        let json = serde_json::to_string(&class).unwrap();
+        let loaded: ClassDefinition = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.fields[0].text_encoding, Some(TextEncoding::ShiftJis));
+
+        // Projects saved before encodings existed read UTF-8
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value["fields"][0]
+            .as_object_mut()
+            .unwrap()
+            .remove("text_encoding");
+        let legacy: ClassDefinition = serde_json::from_value(value).unwrap();
+        assert_eq!(legacy.fields[0].text_encoding, None);
+
+        // The default is stored as None, and retyping drops the choice
+        class.set_text_encoding_at(0, Some(TextEncoding::Utf8));
+        assert_eq!(class.fields[0].text_encoding, None);
+        class.set_text_encoding_at(0, Some(TextEncoding::Ascii));
+        class.set_field_type_at(0, FieldType::Int32);
+        assert_eq!(class.fields[0].text_encoding, None);
+    }
+
     #[test]
     fn test_add_named_field() {
         let mut class = ClassDefinition::new("TestClass".to_string());
@@ -214,6 +511,88 @@ mod class_definition_tests {
         let out_of_bounds = class.get_field_by_index(2);
         assert!(out_of_bounds.is_none());
     }
+
+    #[test]
+    fn test_add_hex_bytes_uses_widest_types() {
+        let mut class = ClassDefinition::new("TestClass".to_string());
+        class.add_hex_field(FieldType::Hex8);
+        class.add_hex_bytes(15);
+        let types: Vec<FieldType> = class.fields.iter().map(|f| f.field_type.clone()).collect();
+        assert_eq!(
+            types,
+            [
+                FieldType::Hex8,
+                FieldType::Hex64,
+                FieldType::Hex32,
+                FieldType::Hex16,
+                FieldType::Hex8,
+            ]
+        );
+        assert_eq!(class.total_size, 16);
+        assert_eq!(class.fields[4].offset, 15);
+    }
+
+    #[test]
+    fn test_auto_field_names_are_unique_and_count_as_unnamed() {
+        let mut class = ClassDefinition::new("TestClass".to_string());
+        class.add_hex_bytes(8);
+        assert!(class.is_placeholder());
+        assert_eq!(class.auto_field_name(0x40), "field_0x40");
+
+        // A field that moved keeps its name, so the offset name may already be taken
+        class.fields[0].name = Some(class.auto_field_name(0));
+        assert!(class.is_placeholder());
+        assert_eq!(class.auto_field_name(0), "field_0x0_2");
+        assert!(is_auto_field_name("field_0x0_2"));
+        assert!(!is_auto_field_name("field_0xZ"));
+        assert!(!is_auto_field_name("health"));
+
+        class.fields[0].name = Some("health".to_string());
+        assert!(!class.is_placeholder());
+    }
+
+    #[test]
+    fn test_hex_helpers_report_new_fields_for_naming() {
+        let mut class = ClassDefinition::new("TestClass".to_string());
+        assert_eq!(class.add_hex_bytes(12), 0..2);
+        // Unnamed unless the caller asks for names
+        assert!(class.fields.iter().all(|f| f.name.is_none()));
+
+        let created = class.insert_hex_bytes_at(1, 6);
+        assert_eq!(created, 1..3);
+        class.name_fields_by_offset(created);
+        assert_eq!(class.fields[1].name.as_deref(), Some("field_0x8"));
+        assert_eq!(class.fields[2].name.as_deref(), Some("field_0xC"));
+        assert_eq!(class.fields[3].name, None);
+
+        let created = class.add_hex_field(FieldType::Hex8);
+        class.name_fields_by_offset(created);
+        assert_eq!(class.fields[4].name.as_deref(), Some("field_0x12"));
+    }
+
+    #[test]
+    fn test_move_field_recalculates_offsets() {
+        let mut class = ClassDefinition::new("TestClass".to_string());
+        class.add_named_field("a".to_string(), FieldType::Int64);
+        class.add_named_field("b".to_string(), FieldType::Int32);
+        class.add_named_field("c".to_string(), FieldType::Int8);
+
+        assert!(class.move_field(0, 2));
+        let names: Vec<_> = class
+            .fields
+            .iter()
+            .map(|f| f.name.clone().unwrap())
+            .collect();
+        assert_eq!(names, ["b", "c", "a"]);
+        assert_eq!(class.fields[1].offset, 4);
+        assert_eq!(class.fields[2].offset, 5);
+        assert_eq!(class.total_size, 13);
+
+        assert!(class.move_field(2, 0));
+        assert_eq!(class.fields[0].name.as_deref(), Some("a"));
+        assert!(!class.move_field(3, 0));
+        assert!(!class.move_field(0, 3));
+    }
 }
 
 #[cfg(test)]
@@ -271,6 +650,213 @@ mod class_registry_tests {
         assert!(!registry.contains(class.id));
         assert!(registry.get(class.id).is_none());
     }
+
+    #[test]
+    fn test_find_field() {
+        let mut registry = ClassDefinitionRegistry::new();
+        let mut class = ClassDefinition::new("Player".to_string());
+        class.add_hex_field(FieldType::Hex64);
+        class.add_named_field("health".to_string(), FieldType::Int32);
+        let health_id = class.fields[1].id;
+        let class_id = class.id;
+        registry.register(class);
+        registry.register(ClassDefinition::new("Other".to_string()));
+
+        assert_eq!(registry.find_field(health_id), Some((class_id, 1)));
+        assert_eq!(registry.find_field(u64::MAX), None);
+    }
+
+    #[test]
+    fn test_generate_unique_class_name() {
+        let mut registry = ClassDefinitionRegistry::new();
+        assert_eq!(registry.generate_unique_class_name("Player"), "Player");
+        registry.register(ClassDefinition::new("Player".to_string()));
+        registry.register(ClassDefinition::new("Player_1".to_string()));
+        assert_eq!(registry.generate_unique_class_name("Player"), "Player_2");
+    }
+
+    #[test]
+    fn test_duplicate_class() {
+        use crate::memory::types::PointerTarget;
+        let mut registry = ClassDefinitionRegistry::new();
+        let target = ClassDefinition::new("Target".to_string());
+        let mut class = ClassDefinition::new("Player".to_string());
+        class.add_named_field("health".to_string(), FieldType::Int32);
+        class.add_class_instance("target".to_string(), &target);
+        let mut ptr = FieldDefinition::new_named("next".to_string(), FieldType::Pointer, 0);
+        ptr.pointer_target = Some(PointerTarget::ClassId(class.id));
+        class.add_field(ptr);
+        let original_id = class.id;
+        registry.register(target.clone());
+        registry.register(class.clone());
+
+        let copy_id = registry.duplicate(original_id).expect("class exists");
+        assert_ne!(copy_id, original_id);
+        let copy = registry.get(copy_id).unwrap();
+        assert_eq!(copy.name, "Player_1");
+        assert_eq!(copy.total_size, class.total_size);
+        assert_eq!(copy.fields.len(), 3);
+        for (a, b) in copy.fields.iter().zip(&class.fields) {
+            assert_ne!(a.id, b.id);
+            assert_eq!(a.offset, b.offset);
+        }
+        assert_eq!(copy.fields[1].class_id, Some(target.id));
+        assert_eq!(
+            copy.fields[2].pointer_target,
+            Some(PointerTarget::ClassId(original_id))
+        );
+
+        // The original is untouched
+        assert_eq!(registry.get(original_id).unwrap().name, "Player");
+        assert!(registry.duplicate(9999).is_none());
+    }
+}
+
+#[cfg(test)]
+mod enum_definition_tests {
+    use crate::memory::definitions::{
+        EnumDefinition,
+        EnumVariant,
+    };
+
+    fn state_enum(size: u8, is_signed: bool) -> EnumDefinition {
+        let mut en = EnumDefinition::new("State".to_string());
+        en.default_size = size;
+        en.is_signed = is_signed;
+        en.variants = vec![
+            EnumVariant {
+                name: "Invalid".to_string(),
+                value: -1,
+            },
+            EnumVariant {
+                name: "Idle".to_string(),
+                value: 0,
+            },
+        ];
+        en
+    }
+
+    #[test]
+    fn test_signed_backing_matches_negative_variants() {
+        let en = state_enum(2, true);
+        assert_eq!(en.value_of(0xFFFF), -1);
+        assert_eq!(en.format_value(0xFFFF), "Invalid");
+        assert_eq!(en.format_value(0), "Idle");
+        assert_eq!(en.format_value(0xFFFE), "-2");
+
+        let wide = state_enum(8, true);
+        assert_eq!(wide.format_value(u64::MAX), "Invalid");
+    }
+
+    #[test]
+    fn test_unsigned_backing_stays_unsigned() {
+        let en = state_enum(1, false);
+        assert_eq!(en.value_of(0xFF), 255);
+        assert_eq!(en.format_value(0xFF), "255");
+        assert_eq!(en.format_value(0), "Idle");
+    }
+
+    #[test]
+    fn test_encode_value_uses_backing_size() {
+        let signed = state_enum(2, true);
+        assert_eq!(signed.encode_value(-1), Some(vec![0xFF, 0xFF]));
+        assert_eq!(signed.encode_value(0x7FFF), Some(vec![0xFF, 0x7F]));
+        assert_eq!(signed.encode_value(0x8000), None);
+
+        let unsigned = state_enum(1, false);
+        assert_eq!(unsigned.encode_value(255), Some(vec![0xFF]));
+        assert_eq!(unsigned.encode_value(-1), None);
+        assert_eq!(unsigned.encode_value(256), None);
+
+        // Unusual sizes are written as 4 bytes, like they are read
+        assert_eq!(state_enum(3, false).encode_value(1), Some(vec![1, 0, 0, 0]));
+        assert_eq!(
+            state_enum(8, true).encode_value(-2),
+            Some((-2i64).to_le_bytes().to_vec())
+        );
+    }
+
+    #[test]
+    fn test_flags_join_set_bits() {
+        let mut en = EnumDefinition::new("Access".to_string());
+        en.is_flags = true;
+        for (name, value) in [("None", 0), ("Read", 1), ("Write", 2), ("Exec", 4)] {
+            en.variants.push(EnumVariant {
+                name: name.to_string(),
+                value,
+            });
+        }
+        assert_eq!(en.format_value(0), "None");
+        assert_eq!(en.format_value(2), "Write");
+        assert_eq!(en.format_value(5), "Read | Exec");
+        assert_eq!(en.format_value(0x13), "Read | Write | 0x10");
+        assert_eq!(en.format_value(0x30), "0x30");
+
+        en.variants.remove(0);
+        assert_eq!(en.format_value(0), "0");
+    }
+
+    #[test]
+    fn test_move_variant_swaps_neighbours() {
+        let mut en = state_enum(4, true);
+        assert!(en.move_variant(1, true));
+        assert_eq!(en.variants[0].name, "Idle");
+        assert_eq!(en.variants[1].name, "Invalid");
+        assert!(!en.move_variant(0, true));
+        assert!(!en.move_variant(1, false));
+        assert!(!en.move_variant(5, true));
+        assert_eq!(en.variants[0].name, "Idle");
+    }
+
+    #[test]
+    fn test_sort_variants_by_value() {
+        let mut en = state_enum(4, true);
+        en.variants.insert(
+            0,
+            EnumVariant {
+                name: "Running".to_string(),
+                value: 3,
+            },
+        );
+        en.variants.push(EnumVariant {
+            name: "Alias".to_string(),
+            value: 0,
+        });
+        en.sort_variants_by_value();
+        let names: Vec<_> = en.variants.iter().map(|v| v.name.as_str()).collect();
+        assert_eq!(names, ["Invalid", "Idle", "Alias", "Running"]);
+
+        // Flags sort by bit pattern and keep their values
+        let mut flags = EnumDefinition::new("Access".to_string());
+        flags.is_flags = true;
+        for (name, value) in [("Exec", 4), ("High", i64::MIN), ("Read", 1), ("Write", 2)] {
+            flags.variants.push(EnumVariant {
+                name: name.to_string(),
+                value,
+            });
+        }
+        flags.sort_variants_by_value();
+        let sorted: Vec<_> = flags
+            .variants
+            .iter()
+            .map(|v| (v.name.as_str(), v.value))
+            .collect();
+        assert_eq!(
+            sorted,
+            [("Read", 1), ("Write", 2), ("Exec", 4), ("High", i64::MIN)]
+        );
+        assert_eq!(flags.format_value(5), "Read | Exec");
+    }
+
+    #[test]
+    fn test_sign_flag_defaults_to_unsigned() {
+        let en = state_enum(4, true);
+        let mut value = serde_json::to_value(&en).unwrap();
+        value.as_object_mut().unwrap().remove("is_signed");
+        let legacy: EnumDefinition = serde_json::from_value(value).unwrap();
+        assert!(!legacy.is_signed);
+        assert_eq!(legacy.variants[0].value, -1);
+    }
 }
 
 #[cfg(test)]
@@ -356,6 +942,91 @@ mod class_instance_tests {
         assert!(out_of_bounds.is_none());
     }
 
+    #[test]
+    fn test_apply_block_slices_fields_and_embedded_instances() {
+        let mut inner = ClassDefinition::new("Inner".to_string());
+        inner.add_named_field("a".to_string(), FieldType::UInt16);
+        inner.add_named_field("b".to_string(), FieldType::UInt16);
+
+        let mut outer = ClassDefinition::new("Outer".to_string());
+        outer.add_named_field("health".to_string(), FieldType::Int32);
+        outer.add_class_instance("inner".to_string(), &inner);
+        outer.add_hex_field(FieldType::Hex8);
+
+        let mut ms = MemoryStructure::new("root".to_string(), 0x1000, outer);
+        ms.register_class(inner);
+        ms.create_nested_instances();
+        assert_eq!(ms.root_class.total_size, 9);
+
+        let block: Vec<u8> = (0u8..9).collect();
+        ms.root_class.apply_block(Some(&block));
+        let fields = &ms.root_class.fields;
+        assert_eq!(fields[0].data.as_deref(), Some(&[0u8, 1, 2, 3][..]));
+        assert_eq!(fields[1].data.as_deref(), Some(&[4u8, 5, 6, 7][..]));
+        assert_eq!(fields[2].data.as_deref(), Some(&[8u8][..]));
+        let nested = fields[1].nested_instance.as_ref().unwrap();
+        assert_eq!(nested.fields[0].data.as_deref(), Some(&[4u8, 5][..]));
+        assert_eq!(nested.fields[1].data.as_deref(), Some(&[6u8, 7][..]));
+
+        // A short block leaves uncovered fields to be read from the process
+        ms.root_class.apply_block(Some(&block[..6]));
+        assert!(ms.root_class.fields[1].data.is_none());
+        ms.root_class.apply_block(None);
+        assert!(ms.root_class.fields.iter().all(|f| f.data.is_none()));
+    }
+
+    #[test]
+    fn test_read_fields_fills_only_the_range() {
+        let mut class_def = ClassDefinition::new("Rows".to_string());
+        class_def.add_named_field("a".to_string(), FieldType::UInt16);
+        class_def.add_named_field("b".to_string(), FieldType::UInt32);
+        class_def.add_named_field("c".to_string(), FieldType::UInt16);
+        let mut instance = ClassInstance::new("rows".to_string(), 0x1000, class_def);
+
+        let path = std::env::temp_dir().join(format!("reclass-rows-{}.bin", std::process::id()));
+        std::fs::write(&path, (0u8..8).collect::<Vec<u8>>()).unwrap();
+        let file = handle::FileHandle::open(&path, 0x1000, 8).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let handle = handle::AppHandle::create_from_dump(file);
+
+        instance.read_fields(&handle, 1..2);
+        assert!(instance.fields[0].data.is_none());
+        assert_eq!(
+            instance.fields[1].data.as_deref(),
+            Some(&[2u8, 3, 4, 5][..])
+        );
+        assert!(instance.fields[2].data.is_none());
+
+        // Ranges past the last field are clamped
+        instance.read_fields(&handle, 2..usize::MAX);
+        assert_eq!(instance.fields[2].data.as_deref(), Some(&[6u8, 7][..]));
+    }
+
+    #[test]
+    fn test_read_fields_skips_fields_larger_than_the_block_limit() {
+        use crate::memory::types::PointerTarget;
+
+        let mut class_def = ClassDefinition::new("Rows".to_string());
+        class_def.add_named_field("huge".to_string(), FieldType::Array);
+        class_def.add_named_field("after".to_string(), FieldType::UInt16);
+        class_def.fields[0].array_element = Some(PointerTarget::FieldType(FieldType::UInt64));
+        class_def.fields[0].array_length = Some(0x10_0000);
+        let mut ms = MemoryStructure::new("rows".to_string(), 0x1000, class_def);
+        ms.create_nested_instances();
+        let after = ms.root_class.fields[1].address;
+        assert_eq!(after, 0x1000 + 8 * 0x10_0000);
+
+        let path = std::env::temp_dir().join(format!("reclass-huge-{}.bin", std::process::id()));
+        std::fs::write(&path, [9u8, 8]).unwrap();
+        let file = handle::FileHandle::open(&path, after, 8).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let handle = handle::AppHandle::create_from_dump(file);
+
+        ms.root_class.read_fields(&handle, 0..2);
+        assert!(ms.root_class.fields[0].data.is_none());
+        assert_eq!(ms.root_class.fields[1].data.as_deref(), Some(&[9u8, 8][..]));
+    }
+
     #[test]
     fn test_display_name() {
         let class_def = ClassDefinition::new("TestClass".to_string());
@@ -387,6 +1058,206 @@ mod memory_structure_tests {
             "TestClass"
         );
         assert!(structure.class_registry.contains(class_def.id));
+        assert!(structure.bookmarks.is_empty());
+    }
+
+    #[test]
+    fn test_pointer_size_is_per_structure() {
+        let mut class_def = ClassDefinition::new("TestClass".to_string());
+        class_def.add_named_field("next".to_string(), FieldType::Pointer);
+        class_def.add_named_field("health".to_string(), FieldType::Int32);
+        let class_id = class_def.id;
+        let mut x86 = MemoryStructure::new("Root".to_string(), 0x1000, class_def.clone());
+        let x64 = MemoryStructure::new("Root".to_string(), 0x1000, class_def);
+
+        x86.set_pointer_size(4);
+        assert_eq!(x86.pointer_size(), 4);
+        assert_eq!(x86.class_registry.get(class_id).unwrap().total_size, 8);
+        assert_eq!(x86.root_class.fields[1].address, 0x1004);
+        assert_eq!(x86.root_class.get_size(), 8);
+        // Another structure keeps its own layout
+        assert_eq!(x64.class_registry.get(class_id).unwrap().total_size, 12);
+        assert_eq!(x64.root_class.fields[1].address, 0x1008);
+
+        // Classes registered later take the structure's pointer size
+        let mut other = ClassDefinition::new("Other".to_string());
+        other.add_named_field("ptr".to_string(), FieldType::Pointer);
+        let other_id = other.id;
+        x86.class_registry.register(other);
+        assert_eq!(x86.class_registry.get(other_id).unwrap().total_size, 4);
+    }
+
+    #[test]
+    fn test_bookmarks_roundtrip_and_default() {
+        let mut class_def = ClassDefinition::new("TestClass".to_string());
+        class_def.add_named_field("health".to_string(), FieldType::Int32);
+        let mut structure = MemoryStructure::new("RootInstance".to_string(), 0x1000, class_def);
+        structure.bookmarks.push(Bookmark {
+            name: "Entity list".to_string(),
+            expr: "<game.exe>+0x2000".to_string(),
+            class_id: None,
+        });
+
+        let json = serde_json::to_string(&structure).unwrap();
+        let loaded: MemoryStructure = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.bookmarks, structure.bookmarks);
+
+        // Files saved before bookmarks existed still load
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value.as_object_mut().unwrap().remove("bookmarks");
+        let legacy: MemoryStructure = serde_json::from_value(value).unwrap();
+        assert!(legacy.bookmarks.is_empty());
+    }
+
+    #[test]
+    fn test_profiles_match_image_and_merge_bookmarks() {
+        let class_def = ClassDefinition::new("TestClass".to_string());
+        let mut structure = MemoryStructure::new("RootInstance".to_string(), 0x1000, class_def);
+        let entity_list = Bookmark {
+            name: "Entity list".to_string(),
+            expr: "<game.exe>+0x2000".to_string(),
+            class_id: None,
+        };
+        let local_player = Bookmark {
+            name: "Local player".to_string(),
+            expr: "<game.exe>+0x3000".to_string(),
+            class_id: Some(structure.root_class.class_id),
+        };
+        structure.bookmarks.push(entity_list.clone());
+        structure.profiles.push(Profile {
+            name: "Retail".to_string(),
+            image: "Game.EXE".to_string(),
+            bookmarks: vec![entity_list.clone(), local_player.clone()],
+            root: Some(local_player.clone()),
+        });
+
+        assert!(structure.profile_for_image("other.exe").is_none());
+        let profile = structure.profile_for_image("game.exe").unwrap().clone();
+        assert_eq!(profile.name, "Retail");
+
+        assert_eq!(structure.merge_profile_bookmarks(&profile), 1);
+        assert_eq!(structure.bookmarks, [entity_list, local_player]);
+        assert_eq!(structure.merge_profile_bookmarks(&profile), 0);
+
+        let json = serde_json::to_string(&structure).unwrap();
+        let loaded: MemoryStructure = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.profiles, structure.profiles);
+
+        // Files saved before profiles existed still load
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value.as_object_mut().unwrap().remove("profiles");
+        let legacy: MemoryStructure = serde_json::from_value(value).unwrap();
+        assert!(legacy.profiles.is_empty());
+    }
+
+    #[test]
+    fn test_endianness_roundtrip_and_default() {
+        let class_def = ClassDefinition::new("TestClass".to_string());
+        let mut structure = MemoryStructure::new("RootInstance".to_string(), 0x1000, class_def);
+        assert_eq!(structure.endianness, Endianness::Little);
+        structure.endianness = Endianness::Big;
+
+        let json = serde_json::to_string(&structure).unwrap();
+        let loaded: MemoryStructure = serde_json::from_str(&json).unwrap();
+        assert_eq!(loaded.endianness, Endianness::Big);
+
+        // Undo snapshots carry the byte order as well
+        let snapshot = crate::memory::undo::snapshot_structure(&structure);
+        let restored = crate::memory::undo::restore_structure(&snapshot).unwrap();
+        assert_eq!(restored.endianness, Endianness::Big);
+
+        // Files saved before the setting existed are little-endian
+        let mut value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        value.as_object_mut().unwrap().remove("endianness");
+        let legacy: MemoryStructure = serde_json::from_value(value).unwrap();
+        assert_eq!(legacy.endianness, Endianness::Little);
+    }
+
+    #[test]
+    fn test_find_fields_by_name_and_comment() {
+        let mut player = ClassDefinition::new("Player".to_string());
+        player.add_named_field("Health".to_string(), FieldType::Int32);
+        player.add_named_field("armor".to_string(), FieldType::Int32);
+        player.set_comment_at(1, "absorbs health damage");
+        let mut entity = ClassDefinition::new("Entity".to_string());
+        entity.add_hex_field(FieldType::Hex64);
+        entity.add_named_field("max_health".to_string(), FieldType::Float);
+        let (player_id, entity_id) = (player.id, entity.id);
+        let mut structure = MemoryStructure::new("root".to_string(), 0x1000, player);
+        structure.register_class(entity);
+
+        assert_eq!(
+            structure.find_fields("HEALTH"),
+            vec![(entity_id, 1), (player_id, 0), (player_id, 1)]
+        );
+        assert_eq!(structure.find_fields("armor"), vec![(player_id, 1)]);
+        assert!(structure.find_fields("  ").is_empty());
+        assert!(structure.find_fields("speed").is_empty());
+    }
+
+    #[test]
+    fn test_field_offsets_and_classes_with_field_at() {
+        let mut vec3 = ClassDefinition::new("Vec3".to_string());
+        vec3.add_named_field("x".to_string(), FieldType::Float);
+        vec3.add_named_field("y".to_string(), FieldType::Float);
+        vec3.add_named_field("z".to_string(), FieldType::Float);
+        let mut player = ClassDefinition::new("Player".to_string());
+        player.add_hex_field(FieldType::Hex64);
+        player.add_class_instance("position".to_string(), &vec3);
+        player.add_named_field("health".to_string(), FieldType::Int32);
+        let mut entity = ClassDefinition::new("Entity".to_string());
+        entity.add_hex_field(FieldType::Hex64);
+        entity.add_hex_field(FieldType::Hex64);
+        entity.add_named_field("flags".to_string(), FieldType::UInt32);
+        let (vec3_id, player_id, entity_id) = (vec3.id, player.id, entity.id);
+        let mut structure = MemoryStructure::new("root".to_string(), 0x1000, player);
+        structure.register_class(vec3);
+        structure.register_class(entity);
+
+        // The embedded Vec3 takes 12 bytes
+        assert_eq!(structure.field_offsets(player_id), vec![0, 8, 0x14]);
+        assert!(structure.field_offsets(0xDEAD).is_empty());
+
+        assert_eq!(
+            structure.classes_with_field_at(8, None),
+            vec![entity_id, player_id, vec3_id]
+        );
+        assert_eq!(
+            structure.classes_with_field_at(8, Some(FieldType::Float)),
+            vec![vec3_id]
+        );
+        assert_eq!(
+            structure.classes_with_field_at(0x14, Some(FieldType::Int32)),
+            vec![player_id]
+        );
+        assert!(structure
+            .classes_with_field_at(0x10, None)
+            .contains(&entity_id));
+        assert!(structure.classes_with_field_at(0x40, None).is_empty());
+    }
+
+    #[test]
+    fn test_field_byte_ranges_merge_adjacent_fields() {
+        let mut class = ClassDefinition::new("Weapon".to_string());
+        class.add_named_field("ammo".to_string(), FieldType::Int32);
+        class.add_named_field("clip".to_string(), FieldType::Int32);
+        class.add_hex_field(FieldType::Hex64);
+        class.add_named_field("damage".to_string(), FieldType::Float);
+        let ids: Vec<u64> = class.fields.iter().map(|f| f.id).collect();
+        let class_id = class.id;
+        let structure = MemoryStructure::new("root".to_string(), 0x1000, class);
+
+        let selected: std::collections::HashSet<u64> = [ids[0], ids[1], ids[3]].into();
+        assert_eq!(
+            structure.field_byte_ranges(0x2000, class_id, &selected),
+            vec![(0x2000, 8), (0x2010, 4)]
+        );
+        let all: std::collections::HashSet<u64> = ids.iter().copied().collect();
+        assert_eq!(
+            structure.field_byte_ranges(0x2000, class_id, &all),
+            vec![(0x2000, 0x14)]
+        );
+        assert!(structure.field_byte_ranges(0x2000, 0xDEAD, &all).is_empty());
     }
 
     #[test]
@@ -566,6 +1437,92 @@ mod memory_structure_tests {
         assert_eq!(ms.root_class.fields[1].address, 0x2004);
     }
 
+    #[test]
+    fn test_find_field_address_follows_rebuild() {
+        let mut def = ClassDefinition::new("R".to_string());
+        def.add_named_field("a".to_string(), FieldType::Int32);
+        def.add_named_field("b".to_string(), FieldType::Int32);
+        let b_id = def.fields[1].id;
+        let mut ms = MemoryStructure::new("i".to_string(), 0x1000, def.clone());
+        assert_eq!(ms.find_field_address(0x1000, b_id), Some(0x1004));
+
+        // Inserting bytes before the field moves it; lookup must follow
+        if let Some(d) = ms.class_registry.get_mut(def.id) {
+            d.insert_hex_bytes_at(0, 8);
+        }
+        ms.rebuild_root_from_registry();
+        assert_eq!(ms.find_field_address(0x1000, b_id), Some(0x100C));
+        assert_eq!(ms.find_field_address(0x2000, b_id), None);
+    }
+
+    #[test]
+    fn test_pointer_chain_target_roundtrip() {
+        let mut def = ClassDefinition::new("R".to_string());
+        def.add_named_field("p".to_string(), FieldType::Int32);
+        def.set_field_type_at(0, FieldType::Pointer);
+        def.fields[0].pointer_target = Some(crate::memory::PointerTarget::Chain {
+            offsets: vec![0x10, -0x8],
+        });
+        let ms = MemoryStructure::new("i".to_string(), 0x1000, def.clone());
+        assert_eq!(ms.root_class.total_size, 8);
+
+        let json = serde_json::to_string(&ms).expect("serialize");
+        let ms2: MemoryStructure = serde_json::from_str(&json).expect("deserialize");
+        assert_eq!(
+            ms2.class_registry.get(def.id).unwrap().fields[0].pointer_target,
+            Some(crate::memory::PointerTarget::Chain {
+                offsets: vec![0x10, -0x8]
+            })
+        );
+    }
+
+    #[test]
+    fn test_signed_offset_parse_and_format() {
+        use crate::memory::{
+            format_signed_offset,
+            parse_signed_offset,
+        };
+
+        assert_eq!(parse_signed_offset("-0x10"), Some(-0x10));
+        assert_eq!(parse_signed_offset("+0x8"), Some(0x8));
+        assert_eq!(parse_signed_offset(" 0X20 "), Some(0x20));
+        assert_eq!(parse_signed_offset("-16"), Some(-16));
+        assert_eq!(parse_signed_offset("-0x8000000000000000"), Some(i64::MIN));
+        assert_eq!(parse_signed_offset(""), None);
+        assert_eq!(parse_signed_offset("-"), None);
+        assert_eq!(parse_signed_offset("0x"), None);
+        assert_eq!(parse_signed_offset("--1"), None);
+        assert_eq!(parse_signed_offset("0xZZ"), None);
+
+        for offset in [0, 0x8, -0x10, i64::MAX, i64::MIN] {
+            assert_eq!(
+                parse_signed_offset(&format_signed_offset(offset)),
+                Some(offset)
+            );
+        }
+    }
+
+    #[test]
+    fn test_walk_pointer_chain_negative_offset() {
+        use std::collections::HashMap;
+
+        use crate::memory::walk_pointer_chain;
+
+        // The pointer lands 0x10 past the start of an object; -0x10 steps back to it
+        let memory: HashMap<u64, u64> = [(0x2000, 0x5000), (0x5008, 0)].into();
+        let read = |address: u64| memory.get(&address).copied();
+        assert_eq!(walk_pointer_chain(0x2010, &[-0x10], read), Some(0x2000));
+        assert_eq!(
+            walk_pointer_chain(0x2010, &[-0x10, -0x8], read),
+            Some(0x4FF8)
+        );
+        // Unreadable address, null pointer and underflow below zero stop the walk
+        assert_eq!(walk_pointer_chain(0x2010, &[0x10, 0x0], read), None);
+        assert_eq!(walk_pointer_chain(0x2010, &[-0x10, 0x8, 0x0], read), None);
+        assert_eq!(walk_pointer_chain(0, &[0x8], read), None);
+        assert_eq!(walk_pointer_chain(0x8, &[-0x10], read), None);
+    }
+
     #[test]
     fn test_rename_updates_references_and_instances() {
         // Define classes: Root has field to Mid; Mid has a primitive
@@ -667,22 +1624,408 @@ mod memory_structure_tests {
             .unwrap();
         assert_eq!(class_def.name, "Child");
     }
-}
-
-#[cfg(test)]
-mod integration_tests {
-    use std::fs;
-
-    use super::*;
 
     #[test]
-    fn test_field_lookup_and_access() {
-        let mut class_def = ClassDefinition::new("TestClass".to_string());
-        class_def.add_named_field("health".to_string(), FieldType::Int32);
-        class_def.add_named_field("name".to_string(), FieldType::TextPointer);
-        class_def.add_hex_field(FieldType::Hex64);
+    fn test_base_class_comes_before_fields() {
+        let mut entity = ClassDefinition::new("Entity".to_string());
+        entity.add_named_field("vtable".to_string(), FieldType::Hex64);
+        entity.add_named_field("id".to_string(), FieldType::Int32);
+        let mut player = ClassDefinition::new("Player".to_string());
+        player.add_named_field("health".to_string(), FieldType::Float);
+        let (entity_id, player_id) = (entity.id, player.id);
+
+        let mut ms = MemoryStructure::new("root".to_string(), 0x1000, player);
+        ms.register_class(entity);
+        assert!(ms.set_base_class(player_id, Some(entity_id)));
+        ms.rebuild_root_from_registry();
 
-        let structure = MemoryStructure::new("RootInstance".to_string(), 0x1000, class_def);
+        let base = ms.root_class.base.as_ref().expect("base instance");
+        assert_eq!(base.class_id, entity_id);
+        assert_eq!(base.address, 0x1000);
+        assert_eq!(base.fields[1].address, 0x1008);
+        assert_eq!(ms.root_class.fields[0].address, 0x100C);
+        assert_eq!(ms.root_class.get_size(), 0x10);
+        assert_eq!(ms.field_offsets(player_id), vec![0xC]);
+        assert!(ms.find_instance(0x1000, entity_id).is_some());
+
+        // The base embeds the derived class through the base link
+        assert!(ms.would_create_cycle(entity_id, player_id));
+        assert!(!ms.set_base_class(entity_id, Some(player_id)));
+        assert!(!ms.set_base_class(player_id, Some(player_id)));
+
+        assert!(ms.set_base_class(player_id, None));
+        ms.rebuild_root_from_registry();
+        assert!(ms.root_class.base.is_none());
+        assert_eq!(ms.root_class.fields[0].address, 0x1000);
+    }
+
+    #[test]
+    fn test_orphaned_instance_after_class_removal() {
+        let mut inner = ClassDefinition::new("Inner".to_string());
+        inner.add_hex_field(FieldType::Hex32);
+        let inner_id = inner.id;
+        let mut root = ClassDefinition::new("Root".to_string());
+        root.add_class_instance("inner".to_string(), &inner);
+        let mut ms = MemoryStructure::new("root".to_string(), 0x1000, root);
+        ms.register_class(inner);
+        ms.create_nested_instances();
+        assert!(ms.root_class.fields[0].nested_instance.is_some());
+
+        // Removing the class leaves the nested instance behind, pointing at nothing
+        ms.class_registry.remove(inner_id);
+        let mut root_instance = ms.root_class.clone();
+        ms.bind_nested_for_instance(&mut root_instance);
+        let orphan = root_instance.fields[0].nested_instance.as_mut().unwrap();
+        assert!(ms.class_registry.get_by_id(orphan.class_id).is_none());
+        // Everything the tree does with it before drawing the "missing class" row
+        orphan.apply_block(Some(&[0u8; 4]));
+        assert_eq!(
+            orphan.get_display_name_with_registry(&ms.class_registry),
+            format!("inner: #{inner_id}")
+        );
+        ms.rebuild_root_from_registry();
+        assert_eq!(ms.root_class.fields.len(), 1);
+    }
+
+    #[test]
+    fn test_expanded_pointer_rebinds_after_target_edit() {
+        use crate::memory::types::PointerTarget;
+
+        let mut target = ClassDefinition::new("Target".to_string());
+        target.add_hex_field(FieldType::Hex32);
+        let target_id = target.id;
+        let mut root = ClassDefinition::new("Root".to_string());
+        root.add_named_field("next".to_string(), FieldType::Pointer);
+        root.fields[0].pointer_target = Some(PointerTarget::ClassId(target_id));
+        let mut ms = MemoryStructure::new("root".to_string(), 0x1000, root);
+        ms.register_class(target.clone());
+        ms.create_nested_instances();
+
+        // Expanding the pointer header in the tree binds an instance at the pointed-to address
+        let mut expanded = ClassInstance::new("next".to_string(), 0x5000, target);
+        ms.bind_nested_for_instance(&mut expanded);
+        ms.root_class.fields[0].nested_instance = Some(expanded);
+
+        ms.class_registry
+            .get_mut(target_id)
+            .unwrap()
+            .add_named_field("health".to_string(), FieldType::Float);
+        // What the GUI does after schedule_rebuild
+        ms.rebuild_root_from_registry();
+        ms.create_nested_instances();
+
+        let nested = ms.root_class.fields[0].nested_instance.as_ref().unwrap();
+        assert_eq!(nested.address, 0x5000);
+        assert_eq!(nested.fields.len(), 2);
+        assert_eq!(nested.fields[1].address, 0x5004);
+        assert_eq!(nested.total_size, 8);
+        let health_id = ms.class_registry.get(target_id).unwrap().fields[1].id;
+        assert_eq!(ms.find_field_address(0x5000, health_id), Some(0x5004));
+
+        // Retargeting the pointer drops the instance of the old class
+        let root_id = ms.root_class.class_id;
+        ms.class_registry.get_mut(root_id).unwrap().fields[0].pointer_target =
+            Some(PointerTarget::FieldType(FieldType::Int32));
+        ms.rebuild_root_from_registry();
+        assert!(ms.root_class.fields[0].nested_instance.is_none());
+    }
+
+    #[test]
+    fn test_validate_references_clears_missing_ids() {
+        use crate::memory::types::PointerTarget;
+
+        let mut inner = ClassDefinition::new("Inner".to_string());
+        inner.add_hex_field(FieldType::Hex32);
+        let mut root = ClassDefinition::new("Root".to_string());
+        root.add_class_instance("inner".to_string(), &inner);
+        root.add_named_field("state".to_string(), FieldType::Enum);
+        root.add_named_field("next".to_string(), FieldType::Pointer);
+        root.add_named_field("list".to_string(), FieldType::Pointer);
+        root.fields[1].enum_id = Some(999);
+        root.fields[2].pointer_target = Some(PointerTarget::ClassId(inner.id));
+        root.fields[3].pointer_target = Some(PointerTarget::Array {
+            element: Box::new(PointerTarget::ClassId(998)),
+            length: 4,
+        });
+        root.base_class_id = Some(997);
+        let root_id = root.id;
+
+        // `inner` is never registered, as if deleted from the file by hand
+        let mut ms = MemoryStructure::new("root".to_string(), 0, root);
+        let list_target = ms.class_registry.get(root_id).unwrap().fields[3]
+            .pointer_target
+            .clone()
+            .unwrap();
+        assert!(ms.is_target_missing(&list_target));
+        let repairs = ms.validate_references();
+        assert_eq!(repairs.len(), 5);
+
+        let def = ms.class_registry.get(root_id).unwrap();
+        assert_eq!(def.base_class_id, None);
+        assert_eq!(def.fields[0].field_type, FieldType::Hex64);
+        assert_eq!(def.fields[0].class_id, None);
+        assert_eq!(def.fields[1].enum_id, None);
+        assert_eq!(
+            def.fields[2].pointer_target,
+            Some(PointerTarget::FieldType(FieldType::Hex64))
+        );
+        assert_eq!(
+            def.fields[3].pointer_target,
+            Some(PointerTarget::Array {
+                element: Box::new(PointerTarget::FieldType(FieldType::Hex8)),
+                length: 4,
+            })
+        );
+        assert_eq!(def.fields[1].offset, 8);
+
+        // A repaired structure has nothing left to report
+        assert!(ms.validate_references().is_empty());
+    }
+
+    #[test]
+    fn test_validate_references_breaks_base_class_cycles() {
+        let mut a = ClassDefinition::new("A".to_string());
+        a.add_hex_field(FieldType::Hex32);
+        let mut b = ClassDefinition::new("B".to_string());
+        b.add_hex_field(FieldType::Hex32);
+        let (a_id, b_id) = (a.id, b.id);
+        // As loaded from a file edited by hand: A derives from B and B from A
+        a.base_class_id = Some(b_id);
+        b.base_class_id = Some(a_id);
+
+        let mut ms = MemoryStructure::new("root".to_string(), 0x1000, a);
+        ms.register_class(b);
+        let repairs = ms.validate_references();
+        assert_eq!(repairs.len(), 1);
+        let bases = [
+            ms.class_registry.get(a_id).unwrap().base_class_id,
+            ms.class_registry.get(b_id).unwrap().base_class_id,
+        ];
+        assert_eq!(bases.iter().filter(|base| base.is_none()).count(), 1);
+        assert!(ms.validate_references().is_empty());
+    }
+
+    #[test]
+    fn test_nested_instances_stop_on_base_class_cycles() {
+        let mut a = ClassDefinition::new("A".to_string());
+        a.add_hex_field(FieldType::Hex32);
+        let mut b = ClassDefinition::new("B".to_string());
+        b.add_hex_field(FieldType::Hex32);
+        a.base_class_id = Some(b.id);
+        b.base_class_id = Some(a.id);
+
+        let mut ms = MemoryStructure::new("root".to_string(), 0x1000, a);
+        ms.register_class(b);
+        ms.rebuild_root_from_registry();
+        let base = ms.root_class.base.as_deref().unwrap();
+        assert!(base.base.is_none());
+    }
+
+    #[test]
+    fn test_version_0_vector2_keeps_following_offsets() {
+        use crate::memory::types::PointerTarget;
+
+        let mut class_def = ClassDefinition::new("Old".to_string());
+        class_def.add_named_field("pos".to_string(), FieldType::Vector2);
+        class_def.add_named_field("hp".to_string(), FieldType::Int32);
+        class_def.add_named_field("path".to_string(), FieldType::Pointer);
+        class_def.fields[2].pointer_target = Some(PointerTarget::Array {
+            element: Box::new(PointerTarget::FieldType(FieldType::Vector2)),
+            length: 4,
+        });
+        let ms = MemoryStructure::new("root".to_string(), 0x1000, class_def);
+        let root_id = ms.root_class.class_id;
+
+        // Saved before the format version existed, when Vector2 took 4 bytes
+        let mut json = serde_json::to_value(&ms).unwrap();
+        json.as_object_mut().unwrap().remove("format_version");
+        let mut old: MemoryStructure = serde_json::from_value(json).unwrap();
+        assert_eq!(old.format_version, 0);
+        assert_eq!(old.migrate_format(), 2);
+        old.class_registry.set_pointer_size(8);
+        let def = old.class_registry.get(root_id).unwrap();
+        assert_eq!(def.fields[0].field_type, FieldType::Float);
+        assert_eq!(def.fields[1].offset, 4);
+        assert_eq!(
+            def.fields[2].pointer_target,
+            Some(PointerTarget::Array {
+                element: Box::new(PointerTarget::FieldType(FieldType::Float)),
+                length: 4,
+            })
+        );
+        assert_eq!(old.format_version, STRUCTURE_FORMAT_VERSION);
+
+        // Current files keep their 8-byte Vector2
+        let text = serde_json::to_string(&ms).unwrap();
+        let mut current: MemoryStructure = serde_json::from_str(&text).unwrap();
+        assert_eq!(current.migrate_format(), 0);
+        current.class_registry.set_pointer_size(8);
+        let def = current.class_registry.get(root_id).unwrap();
+        assert_eq!(def.fields[0].field_type, FieldType::Vector2);
+        assert_eq!(def.fields[1].offset, 8);
+    }
+
+    #[test]
+    fn test_replace_field_types_in_one_or_all_classes() {
+        use crate::memory::types::PointerTarget;
+
+        let mut other = ClassDefinition::new("Other".to_string());
+        other.add_hex_field(FieldType::Hex64);
+        let other_id = other.id;
+        let mut root = ClassDefinition::new("Root".to_string());
+        root.add_hex_field(FieldType::Hex64);
+        root.add_named_field("count".to_string(), FieldType::Int32);
+        root.add_hex_field(FieldType::Hex64);
+        let root_id = root.id;
+        let mut ms = MemoryStructure::new("root".to_string(), 0, root);
+        ms.register_class(other);
+
+        assert_eq!(ms.count_fields_of_type(Some(root_id), &FieldType::Hex64), 2);
+        assert_eq!(ms.count_fields_of_type(None, &FieldType::Hex64), 3);
+        assert_eq!(
+            ms.replace_field_types(Some(root_id), &FieldType::Hex64, &FieldType::Hex64),
+            0
+        );
+
+        let changed = ms.replace_field_types(Some(root_id), &FieldType::Hex64, &FieldType::Pointer);
+        assert_eq!(changed, 2);
+        let def = ms.class_registry.get(root_id).unwrap();
+        assert_eq!(def.fields[0].field_type, FieldType::Pointer);
+        assert_eq!(
+            def.fields[0].pointer_target,
+            Some(PointerTarget::FieldType(FieldType::Hex64))
+        );
+        assert_eq!(def.fields[1].field_type, FieldType::Int32);
+        assert_eq!(def.fields[2].field_type, FieldType::Pointer);
+        let other_def = ms.class_registry.get(other_id).unwrap();
+        assert_eq!(other_def.fields[0].field_type, FieldType::Hex64);
+
+        assert_eq!(
+            ms.replace_field_types(None, &FieldType::Hex64, &FieldType::Double),
+            1
+        );
+        assert_eq!(ms.count_fields_of_type(None, &FieldType::Hex64), 0);
+    }
+
+    #[test]
+    fn test_field_metadata_follows_field_through_edits() {
+        use crate::memory::TextEncoding;
+
+        let mut def = ClassDefinition::new("Player".to_string());
+        def.add_named_field("health".to_string(), FieldType::Int32);
+        def.add_named_field("name".to_string(), FieldType::TextPointer);
+        def.add_named_field("flags".to_string(), FieldType::UInt32);
+        def.set_comment_at(0, "current hp");
+        def.set_color_at(0, Some([200, 80, 80]));
+        def.set_display_base_at(2, Some(NumberBase::Binary));
+        def.set_text_length_at(1, Some(48));
+        def.set_text_encoding_at(1, Some(TextEncoding::Utf16Le));
+        let class_id = def.id;
+        let ids: Vec<u64> = def.fields.iter().map(|f| f.id).collect();
+        let mut ms = MemoryStructure::new("root".to_string(), 0x1000, def);
+
+        assert!(ms.rename_class(class_id, "LocalPlayer"));
+        let defm = ms.class_registry.get_mut(class_id).unwrap();
+        assert!(defm.move_field(0, 2));
+        defm.fields[0].name = Some("display_name".to_string());
+        // Retyping between integers keeps the comment and color
+        defm.set_field_type_at(2, FieldType::UInt32);
+        ms.rebuild_root_from_registry();
+
+        let def = ms.class_registry.get(class_id).unwrap();
+        assert_eq!(def.name, "LocalPlayer");
+        let field = |id: u64| def.fields.iter().find(|f| f.id == id).unwrap();
+        let health = field(ids[0]);
+        assert_eq!(def.fields[2].id, ids[0]);
+        assert_eq!(health.comment.as_deref(), Some("current hp"));
+        assert_eq!(health.color, Some([200, 80, 80]));
+        assert_eq!(health.field_type, FieldType::UInt32);
+        let name = field(ids[1]);
+        assert_eq!(name.name.as_deref(), Some("display_name"));
+        assert_eq!(name.text_length, Some(48));
+        assert_eq!(name.text_encoding, Some(TextEncoding::Utf16Le));
+        assert_eq!(name.comment, None);
+        let flags = field(ids[2]);
+        assert_eq!(flags.display_base, Some(NumberBase::Binary));
+        assert_eq!(flags.color, None);
+
+        // The rebuilt instance refers to the definitions by id, in the new order
+        let instance_ids: Vec<u64> = ms.root_class.fields.iter().map(|f| f.def_id).collect();
+        assert_eq!(instance_ids, [ids[1], ids[2], ids[0]]);
+        assert_eq!(ms.root_class.fields[2].address, 0x1000 + 12);
+    }
+
+    #[test]
+    fn test_class_referencing_fields_lists_nested_layouts() {
+        use std::collections::HashSet;
+
+        use crate::memory::types::PointerTarget;
+
+        let mut stats = ClassDefinition::new("Stats".to_string());
+        stats.add_named_field("hp".to_string(), FieldType::Int32);
+        let mut root = ClassDefinition::new("Root".to_string());
+        root.add_hex_field(FieldType::Hex64);
+        root.add_class_instance("stats".to_string(), &stats);
+        root.add_named_field("owner".to_string(), FieldType::Pointer);
+        root.fields[2].pointer_target = Some(PointerTarget::ClassId(stats.id));
+        root.add_named_field("count".to_string(), FieldType::Int32);
+        let root_id = root.id;
+        let all_ids: HashSet<u64> = root.fields.iter().map(|f| f.id).collect();
+        let plain_ids: HashSet<u64> = [root.fields[0].id, root.fields[3].id].into();
+        let mut ms = MemoryStructure::new("root".to_string(), 0, root);
+        ms.register_class(stats);
+
+        assert_eq!(
+            ms.class_referencing_fields(root_id, &all_ids),
+            [
+                "+0x8 stats: ClassInstance -> Stats",
+                "+0xC owner: Pointer -> Stats"
+            ]
+        );
+        assert!(ms.class_referencing_fields(root_id, &plain_ids).is_empty());
+        assert!(ms.class_referencing_fields(0, &all_ids).is_empty());
+    }
+
+    #[test]
+    fn test_class_summary_lists_leading_fields() {
+        let mut stats = ClassDefinition::new("Stats".to_string());
+        stats.add_named_field("hp".to_string(), FieldType::Int32);
+        stats.add_named_field("mana".to_string(), FieldType::Int32);
+        let mut root = ClassDefinition::new("Root".to_string());
+        root.add_hex_field(FieldType::Hex64);
+        root.add_class_instance("stats".to_string(), &stats);
+        root.add_named_field("count".to_string(), FieldType::Int32);
+        let root_id = root.id;
+        let stats_id = stats.id;
+        let mut ms = MemoryStructure::new("root".to_string(), 0, root);
+        ms.register_class(stats);
+
+        assert_eq!(
+            ms.class_summary(root_id, 2).unwrap(),
+            "0x14 bytes, 3 field(s)\n+0x0 <unnamed>: Hex64\n+0x8 stats: ClassInstance -> Stats\n…"
+        );
+        assert_eq!(
+            ms.class_summary(stats_id, 8).unwrap(),
+            "0x8 bytes, 2 field(s)\n+0x0 hp: Int32\n+0x4 mana: Int32"
+        );
+        assert!(ms.class_summary(0, 8).is_none());
+    }
+}
+
+#[cfg(test)]
+mod integration_tests {
+    use std::fs;
+
+    use super::*;
+
+    #[test]
+    fn test_field_lookup_and_access() {
+        let mut class_def = ClassDefinition::new("TestClass".to_string());
+        class_def.add_named_field("health".to_string(), FieldType::Int32);
+        class_def.add_named_field("name".to_string(), FieldType::TextPointer);
+        class_def.add_hex_field(FieldType::Hex64);
+
+        let structure = MemoryStructure::new("RootInstance".to_string(), 0x1000, class_def);
 
         // Test field lookup by index
         let first_field = structure.root_class.get_field_by_index(0);
@@ -800,3 +2143,627 @@ mod integration_tests {
         assert!(!nested.fields.is_empty());
     }
 }
+
+#[cfg(test)]
+mod undo_tests {
+    use std::time::{
+        Duration,
+        Instant,
+    };
+
+    use super::*;
+    use crate::memory::undo::{
+        describe_change,
+        restore_structure,
+        snapshot_structure,
+        UndoStack,
+    };
+
+    fn sample_structure() -> MemoryStructure {
+        let mut root_def = ClassDefinition::new("UndoRoot".to_string());
+        root_def.add_hex_field(FieldType::Hex64);
+        root_def.add_named_field("health".to_string(), FieldType::Int32);
+        MemoryStructure::new("root".to_string(), 0x1000, root_def)
+    }
+
+    #[test]
+    fn test_snapshot_restore_roundtrip() {
+        let ms = sample_structure();
+        let snapshot = snapshot_structure(&ms);
+        let restored = restore_structure(&snapshot).expect("restore");
+        assert_eq!(restored.root_class.address, 0x1000);
+        assert_eq!(restored.root_class.class_id, ms.root_class.class_id);
+        assert_eq!(restored.root_class.fields.len(), 2);
+        assert_eq!(snapshot_structure(&restored), snapshot);
+        assert!(restore_structure("not json").is_none());
+    }
+
+    #[test]
+    fn test_describe_change() {
+        let before = sample_structure();
+        let root_id = before.root_class.class_id;
+
+        let mut after = restore_structure(&snapshot_structure(&before)).unwrap();
+        after
+            .class_registry
+            .get_mut(root_id)
+            .unwrap()
+            .add_hex_field(FieldType::Hex32);
+        assert_eq!(describe_change(&before, &after), "Add field to 'UndoRoot'");
+
+        let mut after = restore_structure(&snapshot_structure(&before)).unwrap();
+        after
+            .class_registry
+            .get_mut(root_id)
+            .unwrap()
+            .set_field_type_at(1, FieldType::Float);
+        assert_eq!(
+            describe_change(&before, &after),
+            "Change type of 'health' to Float"
+        );
+
+        let mut after = restore_structure(&snapshot_structure(&before)).unwrap();
+        assert!(after.rename_class(root_id, "Player"));
+        assert_eq!(
+            describe_change(&before, &after),
+            "Rename class 'UndoRoot' to 'Player'"
+        );
+
+        let mut after = restore_structure(&snapshot_structure(&before)).unwrap();
+        after.set_root_address(0x2000);
+        assert_eq!(describe_change(&before, &after), "Change root address");
+    }
+
+    #[test]
+    fn test_comment_survives_rebuild_and_undo() {
+        let before = sample_structure();
+        let root_id = before.root_class.class_id;
+        let mut after = restore_structure(&snapshot_structure(&before)).unwrap();
+        after
+            .class_registry
+            .get_mut(root_id)
+            .unwrap()
+            .set_comment_at(1, "current hp");
+        after.rebuild_root_from_registry();
+        assert_eq!(describe_change(&before, &after), "Edit comment of 'health'");
+
+        let restored = restore_structure(&snapshot_structure(&after)).unwrap();
+        assert_eq!(
+            restored.class_registry.get(root_id).unwrap().fields[1]
+                .comment
+                .as_deref(),
+            Some("current hp")
+        );
+    }
+
+    #[test]
+    fn test_describe_color_change() {
+        let before = sample_structure();
+        let root_id = before.root_class.class_id;
+        let mut after = restore_structure(&snapshot_structure(&before)).unwrap();
+        after
+            .class_registry
+            .get_mut(root_id)
+            .unwrap()
+            .set_color_at(1, Some([110, 200, 110]));
+        assert_eq!(describe_change(&before, &after), "Change color of 'health'");
+    }
+
+    #[test]
+    fn test_snapshot_keeps_bookmarks() {
+        let before = sample_structure();
+        let mut after = restore_structure(&snapshot_structure(&before)).unwrap();
+        after.bookmarks.push(Bookmark {
+            name: "Local player".to_string(),
+            expr: "client.dll+0x10".to_string(),
+            class_id: Some(before.root_class.class_id),
+        });
+        assert_eq!(describe_change(&before, &after), "Edit bookmarks");
+
+        let restored = restore_structure(&snapshot_structure(&after)).unwrap();
+        assert_eq!(restored.bookmarks, after.bookmarks);
+    }
+
+    #[test]
+    fn test_snapshot_keeps_profiles() {
+        let before = sample_structure();
+        let mut after = restore_structure(&snapshot_structure(&before)).unwrap();
+        after.profiles.push(Profile {
+            name: "game.exe".to_string(),
+            image: "game.exe".to_string(),
+            bookmarks: Vec::new(),
+            root: None,
+        });
+        assert_eq!(describe_change(&before, &after), "Edit profiles");
+
+        let restored = restore_structure(&snapshot_structure(&after)).unwrap();
+        assert_eq!(restored.profiles, after.profiles);
+    }
+
+    #[test]
+    fn test_undo_stack_undo_redo() {
+        let mut stack = UndoStack::new(50);
+        let t0 = Instant::now();
+        stack.record("Add field".to_string(), "s0".to_string(), t0);
+        stack.record(
+            "Rename field".to_string(),
+            "s1".to_string(),
+            t0 + Duration::from_secs(5),
+        );
+
+        let (label, snapshot) = stack.undo("s2".to_string()).unwrap();
+        assert_eq!((label.as_str(), snapshot.as_str()), ("Rename field", "s1"));
+        let (label, snapshot) = stack.undo("s1".to_string()).unwrap();
+        assert_eq!((label.as_str(), snapshot.as_str()), ("Add field", "s0"));
+        assert!(stack.undo("s0".to_string()).is_none());
+
+        let (_, snapshot) = stack.redo("s0".to_string()).unwrap();
+        assert_eq!(snapshot, "s1");
+        // A new edit drops the remaining redo history
+        stack.record(
+            "Remove field".to_string(),
+            "s1".to_string(),
+            t0 + Duration::from_secs(10),
+        );
+        assert!(stack.redo("s3".to_string()).is_none());
+    }
+
+    #[test]
+    fn test_undo_stack_coalesces_and_caps() {
+        let mut stack = UndoStack::new(3);
+        let t0 = Instant::now();
+        stack.record("Edit".to_string(), "a".to_string(), t0);
+        stack.record(
+            "Edit".to_string(),
+            "b".to_string(),
+            t0 + Duration::from_millis(200),
+        );
+        assert_eq!(stack.undo_len(), 1);
+        let (_, snapshot) = stack.undo("c".to_string()).unwrap();
+        assert_eq!(snapshot, "a");
+
+        for i in 0..5u64 {
+            stack.record(
+                format!("Edit {i}"),
+                i.to_string(),
+                t0 + Duration::from_secs(i),
+            );
+        }
+        assert_eq!(stack.undo_len(), 3);
+        let (_, oldest) = (0..3)
+            .filter_map(|_| stack.undo(String::new()))
+            .last()
+            .unwrap();
+        assert_eq!(oldest, "2");
+    }
+}
+
+#[cfg(test)]
+mod clipboard_tests {
+    use std::collections::HashSet;
+
+    use super::*;
+    use crate::memory::{
+        clipboard::FieldClipboard,
+        EnumDefinition,
+        PointerTarget,
+    };
+
+    #[test]
+    fn test_paste_copies_fields_with_new_ids() {
+        let mut header = ClassDefinition::new("Header".to_string());
+        header.add_named_field("vtable".to_string(), FieldType::Pointer);
+        header.add_named_field("refcount".to_string(), FieldType::Int32);
+        header.add_hex_field(FieldType::Hex32);
+        let header_id = header.id;
+        let mut target = ClassDefinition::new("Target".to_string());
+        target.add_hex_field(FieldType::Hex64);
+        let target_id = target.id;
+
+        let mut ms = MemoryStructure::new("root".to_string(), 0, header);
+        ms.register_class(target);
+
+        let ids: HashSet<u64> = ms.class_registry.get(header_id).unwrap().fields[..2]
+            .iter()
+            .map(|f| f.id)
+            .collect();
+        let clipboard = FieldClipboard::copy(&ms, header_id, &ids).unwrap();
+        assert_eq!(clipboard.len(), 2);
+        assert_eq!(clipboard.paste_into(&mut ms, target_id, 0), 2);
+
+        let target = ms.class_registry.get(target_id).unwrap();
+        assert_eq!(target.fields.len(), 3);
+        assert_eq!(target.fields[0].name.as_deref(), Some("vtable"));
+        assert_eq!(target.fields[1].field_type, FieldType::Int32);
+        assert_eq!(target.fields[2].offset, 12);
+        assert!(!ids.contains(&target.fields[0].id));
+        assert!(!ids.contains(&target.fields[1].id));
+    }
+
+    #[test]
+    fn test_paste_remaps_and_drops_references() {
+        let mut inner = ClassDefinition::new("Inner".to_string());
+        inner.add_hex_field(FieldType::Hex64);
+        let inner_id = inner.id;
+        let mut state = EnumDefinition::new("State".to_string());
+        state.default_size = 4;
+        let state_id = state.id;
+
+        let mut source = ClassDefinition::new("Source".to_string());
+        source.add_class_instance("inner".to_string(), &inner);
+        source.add_named_field("state".to_string(), FieldType::Enum);
+        source.add_named_field("next".to_string(), FieldType::Pointer);
+        source.fields[1].enum_id = Some(state_id);
+        source.fields[2].pointer_target = Some(PointerTarget::ClassId(inner_id));
+        let source_id = source.id;
+
+        let mut ms = MemoryStructure::new("root".to_string(), 0, source);
+        ms.register_class(inner);
+        ms.enum_registry.register(state);
+        let ids: HashSet<u64> = ms
+            .class_registry
+            .get(source_id)
+            .unwrap()
+            .fields
+            .iter()
+            .map(|f| f.id)
+            .collect();
+        let clipboard = FieldClipboard::copy(&ms, source_id, &ids).unwrap();
+
+        // Same structure: references are kept
+        let mut other = ClassDefinition::new("Other".to_string());
+        other.add_hex_field(FieldType::Hex64);
+        let other_id = other.id;
+        ms.register_class(other);
+        clipboard.paste_into(&mut ms, other_id, 1);
+        let other = ms.class_registry.get(other_id).unwrap();
+        assert_eq!(other.fields[1].class_id, Some(inner_id));
+        assert_eq!(other.fields[2].enum_id, Some(state_id));
+        assert_eq!(
+            other.fields[3].pointer_target,
+            Some(PointerTarget::ClassId(inner_id))
+        );
+
+        // Pasting into Inner would embed Inner in itself; the instance degrades to hex
+        clipboard.paste_into(&mut ms, inner_id, 0);
+        let inner = ms.class_registry.get(inner_id).unwrap();
+        assert_eq!(inner.fields[0].field_type, FieldType::Hex64);
+        assert_eq!(inner.fields[0].class_id, None);
+
+        // Fresh structure without Inner or State: references are dropped
+        let mut root = ClassDefinition::new("Fresh".to_string());
+        root.add_hex_field(FieldType::Hex64);
+        let fresh_id = root.id;
+        let mut fresh = MemoryStructure::new("root".to_string(), 0, root);
+        clipboard.paste_into(&mut fresh, fresh_id, 0);
+        let fresh_def = fresh.class_registry.get(fresh_id).unwrap();
+        assert_eq!(fresh_def.fields[0].field_type, FieldType::Hex64);
+        assert_eq!(fresh_def.fields[1].enum_id, None);
+        assert_eq!(
+            fresh_def.fields[2].pointer_target,
+            Some(PointerTarget::FieldType(FieldType::Hex64))
+        );
+    }
+}
+
+#[cfg(test)]
+mod analyze_tests {
+    use super::*;
+    use crate::memory::{
+        analyze::{
+            classify_bytes,
+            detect_array_stride,
+            propose_layout,
+            stride_sample_len,
+        },
+        types::PointerTarget,
+    };
+
+    fn is_pointer(value: u64) -> bool {
+        (0x7FF0_0000_0000..0x7FF1_0000_0000).contains(&value)
+    }
+
+    #[test]
+    fn test_classify_pointer_float_and_hex() {
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&0x7FF0_1234_5678u64.to_le_bytes());
+        bytes.extend_from_slice(&1.5f32.to_le_bytes());
+        bytes.extend_from_slice(&0xFFFF_FFFFu32.to_le_bytes());
+        bytes.extend_from_slice(&0u64.to_le_bytes());
+        bytes.extend_from_slice(&[0xAA, 0xBB, 0xCC]);
+        assert_eq!(
+            classify_bytes(&bytes, 0x1000, &is_pointer),
+            vec![
+                FieldType::Pointer,
+                FieldType::Float,
+                FieldType::Hex32,
+                FieldType::Hex64,
+                FieldType::Hex16,
+                FieldType::Hex8,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_classify_text() {
+        let mut bytes = b"PlayerName".to_vec();
+        bytes.resize(32, 0);
+        assert_eq!(
+            classify_bytes(&bytes, 0x1000, &is_pointer),
+            vec![FieldType::Text]
+        );
+    }
+
+    #[test]
+    fn test_classify_aligns_leading_bytes() {
+        let bytes = vec![0u8; 14];
+        assert_eq!(
+            classify_bytes(&bytes, 0x1002, &is_pointer),
+            vec![FieldType::Hex16, FieldType::Hex32, FieldType::Hex64]
+        );
+    }
+
+    #[test]
+    fn test_propose_layout_keeps_typed_fields() {
+        let mut class = ClassDefinition::new("Analyzed".to_string());
+        class.add_hex_field(FieldType::Hex64);
+        class.add_named_field("health".to_string(), FieldType::Int32);
+        class.add_hex_field(FieldType::Hex32);
+        class.add_hex_field(FieldType::Hex64);
+        let instance = ClassInstance::new("inst".to_string(), 0x1000, class.clone());
+
+        let mut block = Vec::new();
+        block.extend_from_slice(&0x7FF0_0000_1000u64.to_le_bytes());
+        block.extend_from_slice(&100i32.to_le_bytes());
+        block.extend_from_slice(&[0u8; 4]);
+        block.extend_from_slice(&2.0f32.to_le_bytes());
+        block.extend_from_slice(&[0u8; 4]);
+
+        let proposal = propose_layout(&class, &instance, &block, &is_pointer);
+        let summary: Vec<(u64, FieldType, bool)> = proposal
+            .iter()
+            .map(|p| (p.offset, p.field.field_type.clone(), p.detected))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (0x0, FieldType::Pointer, true),
+                (0x8, FieldType::Int32, false),
+                (0xC, FieldType::Hex32, false),
+                (0x10, FieldType::Float, true),
+                (0x14, FieldType::Hex32, false),
+            ]
+        );
+        assert_eq!(proposal[0].field.name.as_deref(), Some("var_0"));
+        assert_eq!(
+            proposal[0].field.pointer_target,
+            Some(PointerTarget::FieldType(FieldType::Hex64))
+        );
+        assert_eq!(proposal[1].field.name.as_deref(), Some("health"));
+
+        class.replace_fields(proposal.into_iter().map(|p| p.field).collect());
+        assert_eq!(class.total_size, 0x18);
+    }
+
+    #[test]
+    fn test_detect_array_stride_finds_padding() {
+        // 0x10-byte elements (pointer, two floats) laid out 0x18 bytes apart
+        let mut bytes = Vec::new();
+        for i in 0..16u64 {
+            bytes.extend_from_slice(&(0x7FF0_0000_1000 + i * 0x100).to_le_bytes());
+            bytes.extend_from_slice(&1.5f32.to_le_bytes());
+            bytes.extend_from_slice(&(i as f32 + 2.0).to_le_bytes());
+            bytes.extend_from_slice(&[0u8; 8]);
+        }
+        bytes.truncate(stride_sample_len(0x10) as usize);
+        assert_eq!(
+            detect_array_stride(&bytes, 0x1000, 0x10, 8, &is_pointer),
+            Some(0x18)
+        );
+        // Nothing recognizable lines up
+        assert_eq!(
+            detect_array_stride(&[0u8; 0x140], 0x1000, 0x10, 8, &is_pointer),
+            None
+        );
+    }
+}
+
+#[cfg(test)]
+mod diagnostics_tests {
+    use super::*;
+    use crate::memory::{
+        diagnostics::{
+            alignment_warnings,
+            duplicate_id_warnings,
+            AlignmentWarning,
+            DuplicateIdWarning,
+        },
+        types::PointerTarget,
+    };
+
+    #[test]
+    fn test_aligned_class_has_no_warnings() {
+        let mut class = ClassDefinition::new("Aligned".to_string());
+        class.add_named_field("a".to_string(), FieldType::Int32);
+        class.add_named_field("b".to_string(), FieldType::Float);
+        class.add_named_field("c".to_string(), FieldType::Double);
+        let ms = MemoryStructure::new("root".to_string(), 0x1000, class);
+        assert!(alignment_warnings(&ms, &ms.root_class).is_empty());
+    }
+
+    #[test]
+    fn test_misaligned_field_and_size() {
+        let mut class = ClassDefinition::new("Packed".to_string());
+        class.add_hex_field(FieldType::Hex8);
+        class.add_named_field("health".to_string(), FieldType::Int32);
+        class.add_named_field("speed".to_string(), FieldType::Double);
+        let ms = MemoryStructure::new("root".to_string(), 0x1000, class);
+
+        let warnings = alignment_warnings(&ms, &ms.root_class);
+        assert_eq!(
+            warnings,
+            vec![
+                AlignmentWarning::MisalignedField {
+                    index: 1,
+                    offset: 1,
+                    alignment: 4,
+                },
+                AlignmentWarning::MisalignedField {
+                    index: 2,
+                    offset: 5,
+                    alignment: 8,
+                },
+                AlignmentWarning::UnalignedSize {
+                    size: 13,
+                    alignment: 8,
+                },
+            ]
+        );
+        assert_eq!(warnings[0].padding(), 3);
+        assert_eq!(warnings[1].padding(), 3);
+        assert_eq!(warnings[2].padding(), 3);
+    }
+
+    #[test]
+    fn test_insert_padding_fixes_warning() {
+        let mut class = ClassDefinition::new("Packed".to_string());
+        class.add_hex_field(FieldType::Hex8);
+        class.add_named_field("health".to_string(), FieldType::Int32);
+        let class_id = class.id;
+        let mut ms = MemoryStructure::new("root".to_string(), 0x1000, class);
+
+        let warning = alignment_warnings(&ms, &ms.root_class)[0].clone();
+        let AlignmentWarning::MisalignedField { index, .. } = warning else {
+            panic!("expected a misaligned field");
+        };
+        let def = ms.class_registry.get_mut(class_id).unwrap();
+        def.insert_hex_bytes_at(index, warning.padding());
+        assert_eq!(def.fields.len(), 4);
+        assert_eq!(def.fields[1].field_type, FieldType::Hex16);
+        assert_eq!(def.fields[2].field_type, FieldType::Hex8);
+        assert_eq!(def.fields[3].offset, 4);
+        assert_eq!(def.total_size, 8);
+
+        ms.rebuild_root_from_registry();
+        assert!(alignment_warnings(&ms, &ms.root_class).is_empty());
+    }
+
+    #[test]
+    fn test_embedded_class_uses_member_alignment() {
+        let mut inner = ClassDefinition::new("Inner".to_string());
+        inner.add_named_field("value".to_string(), FieldType::Double);
+        let mut outer = ClassDefinition::new("Outer".to_string());
+        outer.add_hex_field(FieldType::Hex32);
+        outer.add_class_instance("inner".to_string(), &inner);
+        let mut ms = MemoryStructure::new("root".to_string(), 0x1000, outer);
+        ms.register_class(inner);
+        ms.rebuild_root_from_registry();
+
+        let warnings = alignment_warnings(&ms, &ms.root_class);
+        assert_eq!(
+            warnings,
+            vec![
+                AlignmentWarning::MisalignedField {
+                    index: 1,
+                    offset: 4,
+                    alignment: 8,
+                },
+                AlignmentWarning::UnalignedSize {
+                    size: 12,
+                    alignment: 8,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_array_of_nested_class_layout() {
+        // Item embeds Inner, which the size stored in its definition does not count
+        let mut inner = ClassDefinition::new("Inner".to_string());
+        inner.add_named_field("value".to_string(), FieldType::Int64);
+        let mut item = ClassDefinition::new("Item".to_string());
+        item.add_class_instance("inner".to_string(), &inner);
+        let mut list = ClassDefinition::new("List".to_string());
+        list.add_named_field("items".to_string(), FieldType::Int32);
+        list.set_field_type_at(0, FieldType::Array);
+        list.fields[0].array_element = Some(PointerTarget::ClassId(item.id));
+        list.fields[0].array_length = Some(2);
+        list.add_named_field("count".to_string(), FieldType::Int32);
+        let mut ms = MemoryStructure::new("root".to_string(), 0x1000, list);
+        ms.register_class(inner);
+        ms.register_class(item);
+        ms.rebuild_root_from_registry();
+
+        assert_eq!(ms.root_class.fields[1].address, 0x1010);
+    }
+
+    #[test]
+    fn test_class_size_counts_embedded_classes() {
+        let mut inner = ClassDefinition::new("Inner".to_string());
+        inner.add_named_field("value".to_string(), FieldType::Int64);
+        let mut item = ClassDefinition::new("Item".to_string());
+        item.add_class_instance("inner".to_string(), &inner);
+        item.add_named_field("flags".to_string(), FieldType::UInt32);
+        let item_id = item.id;
+        let mut ms = MemoryStructure::new("root".to_string(), 0x1000, item);
+        ms.register_class(inner);
+        ms.rebuild_root_from_registry();
+
+        assert_eq!(ms.class_size(item_id), 12);
+        assert_eq!(ms.class_size(item_id), ms.root_class.get_size());
+    }
+
+    #[test]
+    fn test_duplicate_field_id() {
+        let mut class = ClassDefinition::new("Twins".to_string());
+        class.add_named_field("a".to_string(), FieldType::Int32);
+        class.add_named_field("b".to_string(), FieldType::Int32);
+        let id = class.fields[0].id;
+        class.fields[1].id = id;
+        let class_id = class.id;
+        let mut ms = MemoryStructure::new("root".to_string(), 0x1000, class);
+
+        let warnings = duplicate_id_warnings(&ms, &ms.root_class);
+        assert_eq!(
+            warnings,
+            vec![DuplicateIdWarning {
+                index: 1,
+                offset: 4,
+                id,
+            }]
+        );
+
+        ms.class_registry
+            .get_mut(class_id)
+            .unwrap()
+            .renew_field_id_at(1);
+        ms.rebuild_root_from_registry();
+        assert!(duplicate_id_warnings(&ms, &ms.root_class).is_empty());
+    }
+}
+
+#[cfg(test)]
+mod snapshot_tests {
+    use crate::memory::snapshot::Snapshot;
+
+    #[test]
+    fn test_slice_within_and_outside() {
+        let snapshot = Snapshot::new(0x1000, vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(snapshot.slice(0x1000, 2), Some(&[1u8, 2][..]));
+        assert_eq!(snapshot.slice(0x1004, 4), Some(&[5u8, 6, 7, 8][..]));
+        // Partly or fully outside the captured range
+        assert_eq!(snapshot.slice(0x1006, 4), None);
+        assert_eq!(snapshot.slice(0x0FFF, 1), None);
+        assert_eq!(snapshot.slice(0x2000, 1), None);
+    }
+
+    #[test]
+    fn test_differs() {
+        let snapshot = Snapshot::new(0x1000, vec![0xAA, 0xBB, 0xCC, 0xDD]);
+        assert!(!snapshot.differs(0x1000, &[0xAA, 0xBB]));
+        assert!(snapshot.differs(0x1002, &[0xCC, 0x00]));
+        // Not covered by the snapshot
+        assert!(!snapshot.differs(0x1003, &[0x00, 0x00]));
+        assert!(!snapshot.differs(0x0800, &[0x00]));
+    }
+}
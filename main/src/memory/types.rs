@@ -1,10 +1,18 @@
 use std::fmt;
 
+pub use handle::TextEncoding;
 use serde::{
     Deserialize,
     Serialize,
 };
 
+/// Pointer width structures are laid out with until a process tells otherwise
+pub const DEFAULT_POINTER_SIZE: u64 = 8;
+
+pub(crate) fn default_pointer_size() -> u64 {
+    DEFAULT_POINTER_SIZE
+}
+
 /// Represents all possible field types in the memory structure
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum FieldType {
@@ -38,14 +46,28 @@ pub enum FieldType {
     Vector3,
     Vector2,
 
+    // Rotation (x, y, z, w) and row-major 4x4 float matrix
+    Quaternion,
+    Matrix4x4,
+
+    // 16-byte GUID / UUID
+    Guid,
+
+    // Timestamps: Unix seconds since 1970 and FILETIME 100ns ticks since 1601
+    UnixTime32,
+    UnixTime64,
+    Win32FileTime,
+
     // Text types
     Text,
     TextPointer,
+    WideText,
+    WideTextPointer,
 
     // Class instance type (dynamic size)
     ClassInstance,
 
-    // Generic pointer (64-bit) that can point to any primitive type or class instance
+    // Generic pointer (process pointer width) that can point to any primitive type or class instance
     Pointer,
 
     // Enum type (32-bit underlying by default)
@@ -53,31 +75,66 @@ pub enum FieldType {
 
     // Array type (dynamic size; element type and length stored in FieldDefinition)
     Array,
+
+    // Bits of a backing integer (backing size, bit offset and width stored in FieldDefinition)
+    Bitfield,
 }
 
 impl FieldType {
-    /// Get the fixed size of the field type in bytes
+    /// Get the fixed size of the field type in bytes, with pointers as wide as in a 64-bit
+    /// process. Layouts use `get_size_for` with the pointer size of their structure.
     pub fn get_size(&self) -> u64 {
+        self.get_size_for(DEFAULT_POINTER_SIZE)
+    }
+
+    /// Fixed size of the field type for a process whose pointers are `pointer_size` bytes
+    pub fn get_size_for(&self, pointer_size: u64) -> u64 {
         match self {
-            FieldType::Hex64 | FieldType::Int64 | FieldType::UInt64 | FieldType::Double => 8,
+            FieldType::Hex64
+            | FieldType::Int64
+            | FieldType::UInt64
+            | FieldType::Double
+            | FieldType::Vector2
+            | FieldType::UnixTime64
+            | FieldType::Win32FileTime => 8,
             FieldType::Hex32
             | FieldType::Int32
             | FieldType::UInt32
             | FieldType::Float
-            | FieldType::Vector2 => 4,
+            | FieldType::UnixTime32 => 4,
             FieldType::Hex16 | FieldType::Int16 | FieldType::UInt16 => 2,
             FieldType::Hex8 | FieldType::Int8 | FieldType::UInt8 | FieldType::Bool => 1,
             FieldType::Vector3 => 12,
-            FieldType::Vector4 => 16,
+            FieldType::Vector4 | FieldType::Quaternion | FieldType::Guid => 16,
+            FieldType::Matrix4x4 => 64,
             FieldType::Text => 32,
-            FieldType::TextPointer => 8,
-            FieldType::Pointer => 8,
+            FieldType::WideText => 64,
+            FieldType::TextPointer | FieldType::WideTextPointer | FieldType::Pointer => {
+                pointer_size
+            }
             FieldType::Enum => 4,
-            FieldType::Array => 0, // Dynamic size; depends on element and length
+            FieldType::Bitfield => 4, // Default backing size; see FieldDefinition::get_size
+            FieldType::Array => 0,    // Dynamic size; depends on element and length
             FieldType::ClassInstance => 0, // Dynamic size
         }
     }
 
+    /// Alignment a C compiler gives the type. Arrays and class instances depend on their
+    /// definitions and are reported as 1 here
+    pub fn natural_alignment(&self, pointer_size: u64) -> u64 {
+        match self {
+            FieldType::Vector2
+            | FieldType::Vector3
+            | FieldType::Vector4
+            | FieldType::Quaternion
+            | FieldType::Matrix4x4
+            | FieldType::Guid => 4,
+            FieldType::WideText => 2,
+            FieldType::Text | FieldType::Array | FieldType::ClassInstance => 1,
+            t => t.get_size_for(pointer_size),
+        }
+    }
+
     /// Check if this is a hex type (which don't have names)
     pub fn is_hex_type(&self) -> bool {
         matches!(
@@ -86,6 +143,57 @@ impl FieldType {
         )
     }
 
+    /// Signed or unsigned integer types
+    pub fn is_integer(&self) -> bool {
+        matches!(
+            self,
+            FieldType::Int64
+                | FieldType::Int32
+                | FieldType::Int16
+                | FieldType::Int8
+                | FieldType::UInt64
+                | FieldType::UInt32
+                | FieldType::UInt16
+                | FieldType::UInt8
+        )
+    }
+
+    /// Show the raw integer of a timestamp field as a UTC date and time, e.g.
+    /// "2024-03-05 12:34:56 UTC". Values outside 1970..2200 are unlikely to be timestamps and
+    /// are shown as the plain number; other types give None.
+    pub fn format_timestamp(&self, raw: u64) -> Option<String> {
+        let seconds = match self {
+            FieldType::UnixTime32 => i64::from(raw as u32),
+            FieldType::UnixTime64 => raw as i64,
+            FieldType::Win32FileTime => {
+                (raw / FILETIME_TICKS_PER_SECOND) as i64 - FILETIME_UNIX_EPOCH
+            }
+            _ => return None,
+        };
+        if !(1..TIMESTAMP_MAX_SECONDS).contains(&seconds) {
+            return Some(raw.to_string());
+        }
+        let (year, month, day) = civil_from_days(seconds / 86_400);
+        let time = seconds % 86_400;
+        Some(format!(
+            "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+            time / 3600,
+            time / 60 % 60,
+            time % 60
+        ))
+    }
+
+    /// Base the value of an integer or hex field is shown in unless the field picks another
+    pub fn default_base(&self) -> Option<NumberBase> {
+        if self.is_hex_type() {
+            Some(NumberBase::Hexadecimal)
+        } else if self.is_integer() {
+            Some(NumberBase::Decimal)
+        } else {
+            None
+        }
+    }
+
     /// Check if this field type has a dynamic size
     pub fn is_dynamic_size(&self) -> bool {
         matches!(self, FieldType::ClassInstance | FieldType::Array)
@@ -112,22 +220,107 @@ impl FieldType {
             FieldType::Vector4 => "Vector4",
             FieldType::Vector3 => "Vector3",
             FieldType::Vector2 => "Vector2",
+            FieldType::Quaternion => "Quaternion",
+            FieldType::Matrix4x4 => "Matrix4x4",
+            FieldType::Guid => "Guid",
+            FieldType::UnixTime32 => "UnixTime32",
+            FieldType::UnixTime64 => "UnixTime64",
+            FieldType::Win32FileTime => "Win32FileTime",
             FieldType::Text => "Text",
             FieldType::TextPointer => "TextPointer",
+            FieldType::WideText => "WideText",
+            FieldType::WideTextPointer => "WideTextPointer",
             FieldType::ClassInstance => "ClassInstance",
             FieldType::Pointer => "Pointer",
             FieldType::Enum => "Enum",
             FieldType::Array => "Array",
+            FieldType::Bitfield => "Bitfield",
         }
     }
 }
 
+/// FILETIME counts 100ns ticks
+const FILETIME_TICKS_PER_SECOND: u64 = 10_000_000;
+
+/// Seconds from the FILETIME epoch (1601-01-01) to the Unix epoch
+const FILETIME_UNIX_EPOCH: i64 = 11_644_473_600;
+
+/// 2200-01-01 in Unix seconds; later timestamps are taken for plain numbers
+const TIMESTAMP_MAX_SECONDS: i64 = 7_258_118_400;
+
+/// Gregorian (year, month, day) of a day count since 1970-01-01, after Howard Hinnant's
+/// `civil_from_days`
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * mp + 2) / 5 + 1) as u32;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    (era * 400 + year_of_era + i64::from(month <= 2), month, day)
+}
+
 impl fmt::Display for FieldType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}", self.get_display_name())
     }
 }
 
+/// Base integer values are shown in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum NumberBase {
+    Decimal,
+    Hexadecimal,
+    Binary,
+}
+
+impl NumberBase {
+    pub const ALL: [NumberBase; 3] = [
+        NumberBase::Decimal,
+        NumberBase::Hexadecimal,
+        NumberBase::Binary,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            NumberBase::Decimal => "Dec",
+            NumberBase::Hexadecimal => "Hex",
+            NumberBase::Binary => "Bin",
+        }
+    }
+
+    /// Format a `size`-byte integer from its raw bits. Decimal sign-extends `signed` values;
+    /// hex and binary show the bits zero-padded to the full width.
+    pub fn format(self, raw: u64, size: u64, signed: bool) -> String {
+        let bits = (size.clamp(1, 8) * 8) as u32;
+        let raw = if bits == 64 {
+            raw
+        } else {
+            raw & ((1u64 << bits) - 1)
+        };
+        match self {
+            NumberBase::Decimal if signed => {
+                let shift = 64 - bits;
+                (((raw << shift) as i64) >> shift).to_string()
+            }
+            NumberBase::Decimal => raw.to_string(),
+            NumberBase::Hexadecimal => format!("0x{raw:0width$X}", width = (bits / 4) as usize),
+            NumberBase::Binary => format!("0b{raw:0width$b}", width = bits as usize),
+        }
+    }
+}
+
+/// Byte order of multi-byte values in the target
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Endianness {
+    #[default]
+    Little,
+    Big,
+}
+
 /// Target information for a `FieldType::Pointer`
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum PointerTarget {
@@ -142,4 +335,59 @@ pub enum PointerTarget {
         element: Box<PointerTarget>,
         length: u32,
     },
+    /// Multi-level pointer chain: each offset but the last is added and dereferenced,
+    /// the last one yields the final value address
+    Chain { offsets: Vec<i64> },
+}
+
+/// Format a chain offset as `+0x10` / `-0x8`
+pub fn format_signed_offset(offset: i64) -> String {
+    if offset < 0 {
+        format!("-0x{:X}", offset.unsigned_abs())
+    } else {
+        format!("+0x{:X}", offset)
+    }
+}
+
+/// Parse a chain offset such as `-0x10`, `+0x8`, `0x20` or `-16`: hex with a `0x` prefix,
+/// decimal otherwise, and a leading `-` to step back from the pointer
+pub fn parse_signed_offset(text: &str) -> Option<i64> {
+    let t = text.trim();
+    let (negative, magnitude) = match t.strip_prefix('-') {
+        Some(rest) => (true, rest),
+        None => (false, t.strip_prefix('+').unwrap_or(t)),
+    };
+    let magnitude = magnitude.trim();
+    let magnitude = match magnitude
+        .strip_prefix("0x")
+        .or_else(|| magnitude.strip_prefix("0X"))
+    {
+        Some(hex) => u64::from_str_radix(hex, 16).ok()?,
+        None => magnitude.parse::<u64>().ok()?,
+    };
+    let value = i128::from(magnitude);
+    i64::try_from(if negative { -value } else { value }).ok()
+}
+
+/// Follow a pointer chain from the pointer value `pointer`: every offset but the last is added
+/// and the pointer stored there is read, the last offset yields the final address. Negative
+/// offsets step back from the pointer; a null pointer, a failed read or an address that would
+/// leave the 64-bit range ends the walk.
+pub fn walk_pointer_chain(
+    pointer: u64,
+    offsets: &[i64],
+    mut read_pointer: impl FnMut(u64) -> Option<u64>,
+) -> Option<u64> {
+    let mut current = pointer;
+    for (i, offset) in offsets.iter().enumerate() {
+        if current == 0 {
+            return None;
+        }
+        let next = current.checked_add_signed(*offset)?;
+        if i + 1 == offsets.len() {
+            return Some(next);
+        }
+        current = read_pointer(next)?;
+    }
+    (current != 0).then_some(current)
 }
@@ -0,0 +1,328 @@
+use std::time::{
+    Duration,
+    Instant,
+};
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use crate::memory::{
+    definitions::{
+        ClassDefinition,
+        ClassDefinitionRegistry,
+        EnumDefinition,
+        EnumDefinitionRegistry,
+        FieldDefinition,
+    },
+    nodes::{
+        Bookmark,
+        ClassInstance,
+        MemoryStructure,
+        Profile,
+        STRUCTURE_FORMAT_VERSION,
+    },
+    types::Endianness,
+};
+
+/// Edits with the same label closer together than this collapse into one undo entry
+const COALESCE_WINDOW: Duration = Duration::from_millis(1000);
+
+/// Serializable view of everything the user edits: definitions and the root binding.
+/// Live instance data (nested pointer targets, read bytes) is intentionally left out.
+#[derive(Serialize, Deserialize)]
+struct StructureSnapshot {
+    root_name: String,
+    root_address: u64,
+    root_class_id: u64,
+    classes: Vec<ClassDefinition>,
+    enums: Vec<EnumDefinition>,
+    #[serde(default)]
+    bookmarks: Vec<Bookmark>,
+    #[serde(default)]
+    endianness: Endianness,
+    #[serde(default)]
+    profiles: Vec<Profile>,
+}
+
+/// Serialize the editable state of a memory structure. Definitions are sorted by id so that
+/// equal structures always produce equal snapshots.
+pub fn snapshot_structure(ms: &MemoryStructure) -> String {
+    let mut classes: Vec<ClassDefinition> = ms
+        .class_registry
+        .get_class_ids()
+        .into_iter()
+        .filter_map(|id| ms.class_registry.get(id).cloned())
+        .collect();
+    classes.sort_by_key(|d| d.id);
+    let mut enums: Vec<EnumDefinition> = ms
+        .enum_registry
+        .get_enum_ids()
+        .into_iter()
+        .filter_map(|id| ms.enum_registry.get(id).cloned())
+        .collect();
+    enums.sort_by_key(|d| d.id);
+    let snapshot = StructureSnapshot {
+        root_name: ms.root_class.name.clone(),
+        root_address: ms.root_class.address,
+        root_class_id: ms.root_class.class_id,
+        classes,
+        enums,
+        bookmarks: ms.bookmarks.clone(),
+        endianness: ms.endianness,
+        profiles: ms.profiles.clone(),
+    };
+    serde_json::to_string(&snapshot).unwrap_or_default()
+}
+
+/// Rebuild a memory structure (including nested instances) from a snapshot
+pub fn restore_structure(snapshot: &str) -> Option<MemoryStructure> {
+    let snapshot: StructureSnapshot = serde_json::from_str(snapshot).ok()?;
+    let root_def = snapshot
+        .classes
+        .iter()
+        .find(|d| d.id == snapshot.root_class_id)?
+        .clone();
+    let mut class_registry = ClassDefinitionRegistry::new();
+    for def in snapshot.classes {
+        class_registry.register(def);
+    }
+    let mut enum_registry = EnumDefinitionRegistry::new();
+    for def in snapshot.enums {
+        enum_registry.register(def);
+    }
+    let mut ms = MemoryStructure {
+        root_class: ClassInstance::new(snapshot.root_name, snapshot.root_address, root_def),
+        class_registry,
+        enum_registry,
+        bookmarks: snapshot.bookmarks,
+        endianness: snapshot.endianness,
+        profiles: snapshot.profiles,
+        format_version: STRUCTURE_FORMAT_VERSION,
+    };
+    ms.rebuild_root_from_registry();
+    ms.create_nested_instances();
+    Some(ms)
+}
+
+fn field_label(field: &FieldDefinition) -> String {
+    field
+        .name
+        .clone()
+        .unwrap_or_else(|| format!("{} @ 0x{:X}", field.field_type, field.offset))
+}
+
+fn same_field(a: &FieldDefinition, b: &FieldDefinition) -> bool {
+    a.id == b.id
+        && a.name == b.name
+        && a.field_type == b.field_type
+        && a.class_id == b.class_id
+        && a.pointer_target == b.pointer_target
+        && a.enum_id == b.enum_id
+        && a.enum_size == b.enum_size
+        && a.array_element == b.array_element
+        && a.array_length == b.array_length
+        && a.array_stride == b.array_stride
+        && a.display_base == b.display_base
+        && a.bitfield_layout() == b.bitfield_layout()
+        && a.comment == b.comment
+        && a.color == b.color
+        && a.text_encoding == b.text_encoding
+}
+
+fn describe_class_change(before: &ClassDefinition, after: &ClassDefinition) -> Option<String> {
+    if before.name != after.name {
+        return Some(format!(
+            "Rename class '{}' to '{}'",
+            before.name, after.name
+        ));
+    }
+    if after.fields.len() > before.fields.len() {
+        return Some(format!("Add field to '{}'", after.name));
+    }
+    if after.fields.len() < before.fields.len() {
+        return Some(format!("Remove field from '{}'", after.name));
+    }
+    let (old, new) = before
+        .fields
+        .iter()
+        .zip(&after.fields)
+        .find(|(a, b)| !same_field(a, b))?;
+    if old.id != new.id {
+        return Some(format!("Reorder fields of '{}'", after.name));
+    }
+    if old.field_type != new.field_type {
+        return Some(format!(
+            "Change type of '{}' to {}",
+            field_label(old),
+            new.field_type
+        ));
+    }
+    if old.name != new.name {
+        return Some(format!("Rename field '{}'", field_label(new)));
+    }
+    if old.comment != new.comment {
+        return Some(format!("Edit comment of '{}'", field_label(new)));
+    }
+    if old.color != new.color {
+        return Some(format!("Change color of '{}'", field_label(new)));
+    }
+    if old.text_encoding != new.text_encoding {
+        return Some(format!("Change encoding of '{}'", field_label(new)));
+    }
+    Some(format!("Edit field '{}'", field_label(new)))
+}
+
+/// Short human readable description of what changed between two structures
+pub fn describe_change(before: &MemoryStructure, after: &MemoryStructure) -> String {
+    if before.root_class.class_id != after.root_class.class_id {
+        let name = after
+            .class_registry
+            .get(after.root_class.class_id)
+            .map(|d| d.name.clone())
+            .unwrap_or_default();
+        return format!("Set root class '{name}'");
+    }
+    if before.root_class.address != after.root_class.address {
+        return String::from("Change root address");
+    }
+
+    let mut class_ids = after.class_registry.get_class_ids();
+    class_ids.sort_unstable();
+    for id in &class_ids {
+        let new = after.class_registry.get(*id);
+        match (before.class_registry.get(*id), new) {
+            (None, Some(new)) => return format!("Add class '{}'", new.name),
+            (Some(old), Some(new)) => {
+                if let Some(label) = describe_class_change(old, new) {
+                    return label;
+                }
+            }
+            _ => {}
+        }
+    }
+    let mut removed = before.class_registry.get_class_ids();
+    removed.sort_unstable();
+    if let Some(old) = removed
+        .into_iter()
+        .filter(|id| !after.class_registry.contains(*id))
+        .find_map(|id| before.class_registry.get(id))
+    {
+        return format!("Remove class '{}'", old.name);
+    }
+
+    let mut enum_ids = after.enum_registry.get_enum_ids();
+    enum_ids.sort_unstable();
+    for id in &enum_ids {
+        let Some(new) = after.enum_registry.get(*id) else {
+            continue;
+        };
+        match before.enum_registry.get(*id) {
+            None => return format!("Add enum '{}'", new.name),
+            Some(old) if old.name != new.name => {
+                return format!("Rename enum '{}' to '{}'", old.name, new.name);
+            }
+            Some(old) => {
+                let old_json = serde_json::to_string(old).unwrap_or_default();
+                let new_json = serde_json::to_string(new).unwrap_or_default();
+                if old_json != new_json {
+                    return format!("Edit enum '{}'", new.name);
+                }
+            }
+        }
+    }
+    if let Some(old) = before
+        .enum_registry
+        .get_enum_ids()
+        .into_iter()
+        .filter(|id| !after.enum_registry.contains(*id))
+        .find_map(|id| before.enum_registry.get(id))
+    {
+        return format!("Remove enum '{}'", old.name);
+    }
+    if before.bookmarks != after.bookmarks {
+        return String::from("Edit bookmarks");
+    }
+    if before.profiles != after.profiles {
+        return String::from("Edit profiles");
+    }
+    String::from("Edit")
+}
+
+struct UndoEntry {
+    label: String,
+    snapshot: String,
+    recorded_at: Instant,
+}
+
+/// Bounded undo/redo history of structure snapshots
+pub struct UndoStack {
+    undo: Vec<UndoEntry>,
+    redo: Vec<UndoEntry>,
+    capacity: usize,
+}
+
+impl UndoStack {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            undo: Vec::new(),
+            redo: Vec::new(),
+            capacity,
+        }
+    }
+
+    /// Record `before` as the state preceding an edit described by `label`.
+    /// Repeated edits with the same label in quick succession keep the first snapshot only.
+    pub fn record(&mut self, label: String, before: String, now: Instant) {
+        self.redo.clear();
+        if let Some(top) = self.undo.last_mut() {
+            if top.label == label && now.duration_since(top.recorded_at) < COALESCE_WINDOW {
+                top.recorded_at = now;
+                return;
+            }
+        }
+        self.undo.push(UndoEntry {
+            label,
+            snapshot: before,
+            recorded_at: now,
+        });
+        if self.undo.len() > self.capacity {
+            self.undo.remove(0);
+        }
+    }
+
+    /// Step back: returns the label of the undone edit and the snapshot to restore.
+    /// `current` becomes available for redo.
+    pub fn undo(&mut self, current: String) -> Option<(String, String)> {
+        let entry = self.undo.pop()?;
+        self.redo.push(UndoEntry {
+            label: entry.label.clone(),
+            snapshot: current,
+            recorded_at: entry.recorded_at,
+        });
+        Some((entry.label, entry.snapshot))
+    }
+
+    /// Re-apply the most recently undone edit
+    pub fn redo(&mut self, current: String) -> Option<(String, String)> {
+        let entry = self.redo.pop()?;
+        self.undo.push(UndoEntry {
+            label: entry.label.clone(),
+            snapshot: current,
+            // Never coalesce with an edit made after the redo
+            recorded_at: entry.recorded_at - COALESCE_WINDOW,
+        });
+        Some((entry.label, entry.snapshot))
+    }
+
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
+    }
+
+    #[cfg(test)]
+    pub fn undo_len(&self) -> usize {
+        self.undo.len()
+    }
+}
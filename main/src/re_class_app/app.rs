@@ -1,6 +1,22 @@
-use std::sync::Arc;
+use std::{
+    cell::RefCell,
+    collections::HashMap,
+    path::{
+        Path,
+        PathBuf,
+    },
+    sync::Arc,
+    time::{
+        Duration,
+        Instant,
+    },
+};
 
-use handle::AppHandle;
+use handle::{
+    AppHandle,
+    FileHandle,
+    ModuleExports,
+};
 use serde::{
     Deserialize,
     Serialize,
@@ -15,7 +31,13 @@ use vtd_libum::{
     DriverInterface,
 };
 
-use crate::memory::MemoryStructure;
+use crate::{
+    memory::MemoryStructure,
+    scan::ValueScan,
+};
+
+/// Most object addresses whose RTTI name is remembered; the cache starts over past this
+const MAX_RTTI_NAMES: usize = 4096;
 
 #[derive(Clone, Debug, Default, Serialize, Deserialize)]
 pub struct AppSignature {
@@ -39,6 +61,10 @@ pub struct ProcessState {
     pub processes: Vec<ProcessInfo>,
     pub modules: Vec<ProcessModuleInfo>,
     pub selected_process: Option<ProcessInfo>,
+    // Set when the attached process went away, to offer reattaching
+    pub exited_process: Option<ProcessInfo>,
+    // Memory dump the handle reads from instead of a process
+    pub dump_path: Option<PathBuf>,
 }
 
 impl ProcessState {
@@ -47,7 +73,39 @@ impl ProcessState {
             processes: Vec::new(),
             modules: Vec::new(),
             selected_process: None,
+            exited_process: None,
+            dump_path: None,
+        }
+    }
+}
+
+/// Export tables of the attached process by module base, read on the first lookup that lands
+/// in a module. Modules without a readable table are remembered as having no exports.
+#[derive(Default)]
+pub struct ExportCache {
+    modules: RefCell<HashMap<u64, ModuleExports>>,
+}
+
+impl ExportCache {
+    /// Name of the exported symbol at or shortly before `address` and the offset from it,
+    /// e.g. `("CreateFileW", 0x4)`
+    pub fn symbol(&self, handle: &AppHandle, address: u64) -> Option<(String, u64)> {
+        let module = handle.get_module_by_address(address)?;
+        let rva = address - module.base_address;
+        let to_owned = |(name, displacement): (&str, u64)| (name.to_string(), displacement);
+        if let Some(exports) = self.modules.borrow().get(&module.base_address) {
+            return exports.lookup(rva).map(to_owned);
         }
+        let exports = handle.read_module_exports(module.base_address, module.module_size);
+        let symbol = exports.lookup(rva).map(to_owned);
+        self.modules
+            .borrow_mut()
+            .insert(module.base_address, exports);
+        symbol
+    }
+
+    pub fn clear(&self) {
+        self.modules.borrow_mut().clear();
     }
 }
 
@@ -57,6 +115,19 @@ pub struct ReClassApp {
     pub process_state: ProcessState,
     pub memory_structure: Option<MemoryStructure>,
     pub signatures: Vec<AppSignature>,
+    // Minimum time between two reads of the same memory; cached bytes are shown in between
+    pub read_interval_ms: u64,
+    pub reads_paused: bool,
+    // Candidate addresses of the value scanner, narrowed down by next scans
+    pub value_scan: ValueScan,
+    // Pointer width of the attached process: 4 for 32-bit, 8 for 64-bit
+    pub pointer_size: u64,
+    // Last time a failed read made us check whether the process is still alive
+    liveness_checked_at: Option<Instant>,
+    // RTTI class names by object address and when they were resolved, dropped with the read
+    // cache and re-resolved once older than the read interval
+    rtti_names: HashMap<u64, (Instant, Option<String>)>,
+    pub exports: ExportCache,
 }
 
 impl ReClassApp {
@@ -73,6 +144,13 @@ impl ReClassApp {
             process_state: ProcessState::new(),
             memory_structure: None,
             signatures: Vec::new(),
+            read_interval_ms: 50,
+            reads_paused: false,
+            value_scan: ValueScan::default(),
+            pointer_size: 8,
+            liveness_checked_at: None,
+            rtti_names: HashMap::new(),
+            exports: ExportCache::default(),
         })
     }
 
@@ -82,10 +160,116 @@ impl ReClassApp {
     }
 
     pub fn create_handle(&mut self, process_id: ProcessId) -> anyhow::Result<()> {
-        self.handle = Some(AppHandle::create(self.ke_interface.clone(), process_id)?);
+        let handle = AppHandle::create(self.ke_interface.clone(), process_id)?;
+        self.install_handle(handle);
+        Ok(())
+    }
+
+    /// Read from a memory dump instead of a process. Minidumps carry their own address ranges
+    /// and pointer size; a raw dump is mapped at `base_address` with the given pointer size.
+    pub fn open_dump(
+        &mut self,
+        path: &Path,
+        base_address: u64,
+        pointer_size: u64,
+    ) -> anyhow::Result<()> {
+        let file = FileHandle::open(path, base_address, pointer_size)?;
+        self.install_handle(AppHandle::create_from_dump(file));
+        self.process_state.selected_process = None;
+        self.process_state.modules.clear();
+        self.process_state.dump_path = Some(path.to_path_buf());
         Ok(())
     }
 
+    fn install_handle(&mut self, handle: Arc<AppHandle>) {
+        let pointer_size = handle.pointer_size();
+        self.handle = Some(handle);
+        self.process_state.exited_process = None;
+        self.process_state.dump_path = None;
+        // Pointer fields change width, so every offset behind one moves
+        self.pointer_size = pointer_size;
+        if let Some(ms) = self.memory_structure.as_mut() {
+            ms.set_pointer_size(pointer_size);
+        }
+        // Candidates of another process are meaningless
+        self.value_scan.reset();
+        self.rtti_names.clear();
+        self.exports.clear();
+        self.apply_read_settings();
+    }
+
+    /// Drop the process handle; values render blank until attaching again
+    pub fn detach(&mut self) {
+        self.handle = None;
+        self.process_state.selected_process = None;
+        self.process_state.modules.clear();
+        self.process_state.dump_path = None;
+        self.value_scan.reset();
+        self.rtti_names.clear();
+        self.exports.clear();
+    }
+
+    /// After failed reads, check (at most once a second) whether the attached process exited.
+    /// If it did, detach and remember it in `exited_process`. Returns true on that transition.
+    pub fn check_process_exited(&mut self) -> bool {
+        const LIVENESS_CHECK_INTERVAL: Duration = Duration::from_secs(1);
+        let Some(handle) = &self.handle else {
+            return false;
+        };
+        if !handle.take_read_failure() {
+            return false;
+        }
+        let now = Instant::now();
+        if self
+            .liveness_checked_at
+            .is_some_and(|at| now.duration_since(at) < LIVENESS_CHECK_INTERVAL)
+        {
+            return false;
+        }
+        self.liveness_checked_at = Some(now);
+        if handle.is_process_alive() {
+            return false;
+        }
+        let exited = self.process_state.selected_process.take();
+        self.detach();
+        self.process_state.exited_process = exited;
+        true
+    }
+
+    /// Push the refresh interval and pause state to the attached handle's read cache
+    pub fn apply_read_settings(&self) {
+        if let Some(handle) = &self.handle {
+            handle.set_read_interval(Duration::from_millis(self.read_interval_ms));
+            handle.set_reads_paused(self.reads_paused);
+        }
+    }
+
+    pub fn invalidate_read_cache(&mut self) {
+        if let Some(handle) = &self.handle {
+            handle.clear_read_cache();
+        }
+        self.rtti_names.clear();
+    }
+
+    /// RTTI class name of the object at `object_address`, resolved again once the read
+    /// interval has passed unless reads are paused
+    pub fn rtti_name(&mut self, object_address: u64) -> Option<String> {
+        let handle = self.handle.as_ref()?;
+        let now = Instant::now();
+        let interval = Duration::from_millis(self.read_interval_ms);
+        if let Some((resolved_at, name)) = self.rtti_names.get(&object_address) {
+            if self.reads_paused || now.duration_since(*resolved_at) < interval {
+                return name.clone();
+            }
+        }
+        if self.rtti_names.len() >= MAX_RTTI_NAMES {
+            self.rtti_names.clear();
+        }
+        let name = handle.resolve_rtti_name(object_address);
+        self.rtti_names.insert(object_address, (now, name.clone()));
+        name
+    }
+
     pub fn fetch_modules(&mut self, process_id: ProcessId) -> anyhow::Result<()> {
         self.process_state.modules = self
             .ke_interface
@@ -112,7 +296,8 @@ impl ReClassApp {
             .find(|p| p.process_id == process_id)
     }
 
-    pub fn set_memory_structure(&mut self, memory_structure: MemoryStructure) {
+    pub fn set_memory_structure(&mut self, mut memory_structure: MemoryStructure) {
+        memory_structure.set_pointer_size(self.pointer_size);
         self.memory_structure = Some(memory_structure);
     }
 
@@ -1,4 +1,5 @@
 mod app;
+mod session;
 pub mod ui;
 
 pub use app::ReClassApp;
@@ -0,0 +1,105 @@
+use std::path::PathBuf;
+
+use serde::{
+    Deserialize,
+    Serialize,
+};
+
+use super::ui::TreeColors;
+
+const SESSION_DIR_NAME: &str = "reclass-rs";
+/// Key of the session in eframe's app storage
+const SESSION_KEY: &str = "session";
+/// Entries kept in the Recent menu
+pub const RECENT_FILES_MAX: usize = 10;
+
+/// UI state carried over between launches, kept in eframe's app storage
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SessionState {
+    /// Apply the rest of this state on the next launch
+    pub restore: bool,
+    /// Memory structure file that was last loaded or saved
+    pub last_file: Option<PathBuf>,
+    /// Image name of the process that was last attached
+    pub last_process: Option<String>,
+    pub ui_scale: f32,
+    pub hex_view_open: bool,
+    pub signatures_window_open: bool,
+    /// Structure files loaded or saved, newest first
+    pub recent_files: Vec<PathBuf>,
+    /// "Lock structure" toggle
+    pub structure_locked: bool,
+    /// Poll `refresh_hotkey` to pause reads while the window is in the background
+    pub refresh_hotkey_enabled: bool,
+    /// Windows virtual-key code of the pause hotkey
+    pub refresh_hotkey: u16,
+    /// Ids of the memory tree headers that were open, each with the id of the field or class
+    /// it shows
+    pub open_tree_header_owners: Vec<(u64, u64)>,
+    /// Hex bytes a newly created class starts with
+    pub new_class_bytes: u64,
+    /// Name new hex fields after their offset instead of leaving them unnamed
+    pub auto_name_fields: bool,
+    /// Type label and reference colors of the memory tree
+    pub tree_colors: TreeColors,
+    /// Nesting depth past which pointer targets and array elements are not rendered
+    pub max_render_depth: usize,
+    /// Decimals of float values; none shows them exactly
+    pub float_precision: Option<u8>,
+    /// Auto-saved copies of edits that were never saved, one per tab, offered for restore on
+    /// the next launch
+    pub recovery_files: Vec<PathBuf>,
+}
+
+impl Default for SessionState {
+    fn default() -> Self {
+        Self {
+            restore: true,
+            last_file: None,
+            last_process: None,
+            ui_scale: 1.0,
+            hex_view_open: false,
+            signatures_window_open: false,
+            recent_files: Vec::new(),
+            structure_locked: false,
+            refresh_hotkey_enabled: false,
+            // VK_F5
+            refresh_hotkey: 0x74,
+            open_tree_header_owners: Vec::new(),
+            // A single Hex64
+            new_class_bytes: 8,
+            auto_name_fields: false,
+            tree_colors: TreeColors::default(),
+            max_render_depth: 8,
+            float_precision: Some(3),
+            recovery_files: Vec::new(),
+        }
+    }
+}
+
+fn config_dir() -> Option<PathBuf> {
+    let config_dir = std::env::var_os("APPDATA")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("XDG_CONFIG_HOME").map(PathBuf::from))
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".config")))?;
+    Some(config_dir.join(SESSION_DIR_NAME))
+}
+
+/// Where auto-save writes the recovery files of tabs with unsaved edits
+pub fn recovery_dir() -> Option<PathBuf> {
+    config_dir()
+}
+
+impl SessionState {
+    /// Load the stored session, falling back to defaults if there is none or it is unreadable
+    pub fn load(storage: Option<&dyn eframe::Storage>) -> Self {
+        storage
+            .and_then(|storage| eframe::get_value(storage, SESSION_KEY))
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, storage: &mut dyn eframe::Storage) {
+        eframe::set_value(storage, SESSION_KEY, self);
+    }
+}
@@ -0,0 +1,228 @@
+use eframe::egui::{
+    self,
+    Context,
+    ScrollArea,
+};
+
+use super::ReClassGui;
+use crate::memory::analyze::{
+    detect_array_stride,
+    propose_layout,
+    stride_sample_len,
+    ProposedField,
+};
+
+/// Field layout proposed by "Analyze region", waiting for the user to accept or reject it
+pub(super) struct AnalyzeProposal {
+    pub class_id: u64,
+    pub class_name: String,
+    pub instance_address: u64,
+    pub fields: Vec<ProposedField>,
+}
+
+/// Element stride of a class array found by "Auto-detect", waiting for confirmation
+pub(super) struct StrideProposal {
+    pub class_id: u64,
+    pub field_index: usize,
+    pub field_name: String,
+    pub current: u64,
+    pub detected: u64,
+    /// `array_stride` to store: `None` when the detected stride packs the elements
+    pub stride: Option<u64>,
+}
+
+impl ReClassGui {
+    /// Sample the start of the array field `field_index` of `class_id` at `address` and
+    /// propose the stride its class elements appear to have
+    pub(super) fn detect_stride(&mut self, class_id: u64, field_index: usize, address: u64) {
+        let Some(handle) = self.app.handle.clone() else {
+            return;
+        };
+        let Some(ms) = self.app.get_memory_structure() else {
+            return;
+        };
+        let Some(fd) = ms
+            .class_registry
+            .get(class_id)
+            .and_then(|def| def.fields.get(field_index))
+        else {
+            return;
+        };
+        let element_size = ms.array_element_size(fd, &mut vec![class_id]);
+        let mut bytes = vec![0u8; stride_sample_len(element_size) as usize];
+        if handle.read_slice(address, bytes.as_mut_slice()).is_err() {
+            self.cycle_error_text = format!("Failed to read memory at 0x{address:X}");
+            self.cycle_error_open = true;
+            return;
+        }
+        let detected = detect_array_stride(
+            &bytes,
+            address,
+            element_size,
+            handle.pointer_size(),
+            &|value| handle.is_probably_pointer(value),
+        );
+        let Some(detected) = detected else {
+            self.cycle_error_text =
+                String::from("No stride lines up pointers or floats across the first elements");
+            self.cycle_error_open = true;
+            return;
+        };
+        self.stride_proposal = Some(StrideProposal {
+            class_id,
+            field_index,
+            field_name: fd.name.clone().unwrap_or_default(),
+            current: fd.array_step(element_size),
+            detected,
+            stride: (detected > element_size).then_some(detected),
+        });
+    }
+
+    pub(super) fn stride_proposal_window(&mut self, ctx: &Context) {
+        let Some(proposal) = &self.stride_proposal else {
+            return;
+        };
+        let mut open = true;
+        let mut decision: Option<bool> = None;
+        egui::Window::new("Detected stride")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "Elements of {} look 0x{:X} bytes apart (now 0x{:X}).",
+                    proposal.field_name, proposal.detected, proposal.current
+                ));
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            proposal.detected != proposal.current,
+                            egui::Button::new("Apply"),
+                        )
+                        .clicked()
+                    {
+                        decision = Some(true);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        decision = Some(false);
+                    }
+                });
+            });
+        if decision == Some(true) {
+            if let Some(proposal) = self.stride_proposal.take() {
+                if let Some(def) = self
+                    .app
+                    .get_memory_structure_mut()
+                    .and_then(|ms| ms.class_registry.get_mut(proposal.class_id))
+                {
+                    def.set_array_stride_at(proposal.field_index, proposal.stride);
+                    self.schedule_rebuild();
+                }
+            }
+        } else if decision.is_some() || !open {
+            self.stride_proposal = None;
+        }
+    }
+
+    /// Read the whole block of an instance and propose field types for its hex fields
+    pub(super) fn analyze_instance(&mut self, class_id: u64, instance_address: u64) {
+        let Some(handle) = self.app.handle.clone() else {
+            return;
+        };
+        let Some(ms) = self.app.get_memory_structure() else {
+            return;
+        };
+        let (Some(def), Some(instance)) = (
+            ms.class_registry.get(class_id),
+            ms.find_instance(instance_address, class_id),
+        ) else {
+            return;
+        };
+        let Some(block) = instance.read_block(&handle) else {
+            self.cycle_error_text = format!("Failed to read memory at 0x{instance_address:X}");
+            self.cycle_error_open = true;
+            return;
+        };
+        let fields = propose_layout(def, instance, &block, &|value| {
+            handle.is_probably_pointer(value)
+        });
+        self.analyze_proposal = Some(AnalyzeProposal {
+            class_id,
+            class_name: def.name.clone(),
+            instance_address,
+            fields,
+        });
+    }
+
+    pub(super) fn analyze_window(&mut self, ctx: &Context) {
+        let Some(proposal) = &self.analyze_proposal else {
+            return;
+        };
+        let mut open = true;
+        let mut accepted = false;
+        let mut rejected = false;
+        let detected = proposal.fields.iter().filter(|f| f.detected).count();
+        egui::Window::new(format!("Analyze region - {}", proposal.class_name))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "Instance at 0x{:X}: {detected} field(s) detected",
+                    proposal.instance_address
+                ));
+                ui.separator();
+                let highlight = ui.visuals().selection.bg_fill;
+                ScrollArea::vertical().max_height(400.0).show_rows(
+                    ui,
+                    ui.text_style_height(&egui::TextStyle::Monospace),
+                    proposal.fields.len(),
+                    |ui, rows| {
+                        for proposed in &proposal.fields[rows] {
+                            let fd = &proposed.field;
+                            let name = fd.name.clone().unwrap_or_default();
+                            let mut text = egui::RichText::new(format!(
+                                "+0x{:04X}  {:<10} {name}",
+                                proposed.offset,
+                                fd.field_type.to_string()
+                            ))
+                            .monospace();
+                            if proposed.detected {
+                                text = text.color(highlight).strong();
+                            } else {
+                                text = text.weak();
+                            }
+                            ui.label(text);
+                        }
+                    },
+                );
+                ui.separator();
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(detected > 0, egui::Button::new("Accept"))
+                        .on_hover_text("Replace the fields of the class with the proposal")
+                        .clicked()
+                    {
+                        accepted = true;
+                    }
+                    if ui.button("Reject").clicked() {
+                        rejected = true;
+                    }
+                });
+            });
+
+        if accepted {
+            if let Some(proposal) = self.analyze_proposal.take() {
+                if let Some(def) = self
+                    .app
+                    .get_memory_structure_mut()
+                    .and_then(|ms| ms.class_registry.get_mut(proposal.class_id))
+                {
+                    def.replace_fields(proposal.fields.into_iter().map(|f| f.field).collect());
+                    self.schedule_rebuild();
+                }
+            }
+        } else if rejected || !open {
+            self.analyze_proposal = None;
+        }
+    }
+}
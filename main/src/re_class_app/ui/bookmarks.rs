@@ -0,0 +1,217 @@
+use eframe::egui::{
+    self,
+    Context,
+    RichText,
+    ScrollArea,
+};
+
+use super::ReClassGui;
+use crate::memory::Bookmark;
+
+/// Inputs of the bookmarks panel; the bookmarks themselves are saved with the structure
+#[derive(Default)]
+pub(super) struct BookmarksPanelState {
+    pub open: bool,
+    pub name: String,
+    pub expr: String,
+    /// Bookmark being renamed inline and its edited name
+    pub rename: Option<(usize, String)>,
+}
+
+impl ReClassGui {
+    /// Save a labeled address with the current structure and show the bookmarks panel
+    pub(super) fn add_bookmark(&mut self, name: String, expr: String, class_id: Option<u64>) {
+        if let Some(ms) = self.app.get_memory_structure_mut() {
+            let name = if name.trim().is_empty() {
+                format!("Bookmark {}", ms.bookmarks.len() + 1)
+            } else {
+                name.trim().to_string()
+            };
+            ms.bookmarks.push(Bookmark {
+                name,
+                expr: expr.trim().to_string(),
+                class_id,
+            });
+            self.bookmarks.open = true;
+        }
+    }
+
+    /// Evaluate the bookmark's expression and make it the root, switching the root class if pinned
+    pub(super) fn open_bookmark(&mut self, bookmark: &Bookmark) {
+        let Some(address) = self.eval_root_address_input(&bookmark.expr) else {
+            self.cycle_error_text = match self.missing_module_in_expression(&bookmark.expr) {
+                Some(module) => format!("Module '{module}' is not loaded in the attached process."),
+                None => format!("Invalid address: {}", bookmark.expr),
+            };
+            self.cycle_error_open = true;
+            return;
+        };
+        let Some(ms) = self.app.get_memory_structure_mut() else {
+            return;
+        };
+        if let Some(class_id) = bookmark.class_id {
+            if class_id != ms.root_class.class_id && ms.set_root_class_by_id(class_id) {
+                self.root_class_type_buffer = None;
+                self.needs_rebuild = true;
+            }
+        }
+        self.navigate_to_address(address, bookmark.expr.clone());
+    }
+
+    pub(super) fn bookmarks_panel(&mut self, ctx: &Context) {
+        egui::SidePanel::right("bookmarks_panel")
+            .resizable(true)
+            .default_width(280.0)
+            .show(ctx, |ui| {
+                ui.heading("Bookmarks");
+                ui.separator();
+                let Some(ms) = self.app.get_memory_structure() else {
+                    ui.label("No structure loaded");
+                    return;
+                };
+                let root_class_id = ms.root_class.class_id;
+                let root_expr = self
+                    .root_address_expression
+                    .clone()
+                    .unwrap_or_else(|| format!("0x{:X}", ms.root_class.address));
+                let rows: Vec<(Bookmark, Option<String>)> = ms
+                    .bookmarks
+                    .iter()
+                    .map(|b| {
+                        let class_name = b.class_id.map(|id| {
+                            ms.class_registry
+                                .get(id)
+                                .map(|d| d.name.clone())
+                                .unwrap_or_else(|| format!("#{id} (missing)"))
+                        });
+                        (b.clone(), class_name)
+                    })
+                    .collect();
+
+                egui::Grid::new("bookmark_add_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Name:");
+                        ui.text_edit_singleline(&mut self.bookmarks.name);
+                        ui.end_row();
+                        ui.label("Address:");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.bookmarks.expr)
+                                .hint_text("client.dll+0x1234"),
+                        );
+                        ui.end_row();
+                    });
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(
+                            !self.bookmarks.expr.trim().is_empty(),
+                            egui::Button::new("Add"),
+                        )
+                        .clicked()
+                    {
+                        let name = std::mem::take(&mut self.bookmarks.name);
+                        let expr = std::mem::take(&mut self.bookmarks.expr);
+                        self.add_bookmark(name, expr, None);
+                    }
+                    if ui
+                        .button("Add current root")
+                        .on_hover_text("Bookmark the root address and pin the root class")
+                        .clicked()
+                    {
+                        let name = std::mem::take(&mut self.bookmarks.name);
+                        self.add_bookmark(name, root_expr.clone(), Some(root_class_id));
+                    }
+                });
+                ui.separator();
+
+                if rows.is_empty() {
+                    ui.label(RichText::new("No bookmarks yet").weak());
+                }
+                let mut open: Option<Bookmark> = None;
+                let mut remove: Option<usize> = None;
+                let mut rename: Option<(usize, String)> = None;
+                ScrollArea::vertical()
+                    .id_source("bookmarks_scroll")
+                    .show(ui, |ui| {
+                        for (index, (bookmark, class_name)) in rows.iter().enumerate() {
+                            ui.horizontal(|ui| {
+                                let editing =
+                                    matches!(&self.bookmarks.rename, Some((i, _)) if *i == index);
+                                if editing {
+                                    if let Some((_, buffer)) = self.bookmarks.rename.as_mut() {
+                                        let resp = ui.text_edit_singleline(buffer);
+                                        resp.request_focus();
+                                        if ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+                                            self.bookmarks.rename = None;
+                                        } else if resp.lost_focus() {
+                                            rename = Some((index, buffer.clone()));
+                                        }
+                                    }
+                                    return;
+                                }
+                                let resp =
+                                    ui.selectable_label(false, &bookmark.name).on_hover_text(
+                                        "Click to use as root address, double-click to rename",
+                                    );
+                                if resp.double_clicked() {
+                                    self.bookmarks.rename = Some((index, bookmark.name.clone()));
+                                } else if resp.clicked() {
+                                    open = Some(bookmark.clone());
+                                }
+                                resp.context_menu(|ui| {
+                                    if ui.button("Rename").clicked() {
+                                        self.bookmarks.rename =
+                                            Some((index, bookmark.name.clone()));
+                                        ui.close_menu();
+                                    }
+                                    if ui.button("Delete").clicked() {
+                                        remove = Some(index);
+                                        ui.close_menu();
+                                    }
+                                });
+                                let detail = match class_name {
+                                    Some(class_name) => {
+                                        format!("{}  [{class_name}]", bookmark.expr)
+                                    }
+                                    None => bookmark.expr.clone(),
+                                };
+                                ui.label(RichText::new(detail).monospace().weak());
+                                ui.with_layout(
+                                    egui::Layout::right_to_left(egui::Align::Center),
+                                    |ui| {
+                                        if ui.small_button("x").on_hover_text("Delete").clicked() {
+                                            remove = Some(index);
+                                        }
+                                    },
+                                );
+                            });
+                        }
+                    });
+
+                if let Some((index, name)) = rename {
+                    self.bookmarks.rename = None;
+                    let name = name.trim().to_string();
+                    if let Some(bookmark) = self
+                        .app
+                        .get_memory_structure_mut()
+                        .and_then(|ms| ms.bookmarks.get_mut(index))
+                    {
+                        if !name.is_empty() {
+                            bookmark.name = name;
+                        }
+                    }
+                }
+                if let Some(index) = remove {
+                    self.bookmarks.rename = None;
+                    if let Some(ms) = self.app.get_memory_structure_mut() {
+                        if index < ms.bookmarks.len() {
+                            ms.bookmarks.remove(index);
+                        }
+                    }
+                }
+                if let Some(bookmark) = open {
+                    self.open_bookmark(&bookmark);
+                }
+            });
+    }
+}
@@ -0,0 +1,93 @@
+use eframe::egui::{
+    self,
+    Context,
+};
+
+use super::ReClassGui;
+
+/// Field whose comment is being edited in the multiline editor window
+pub(super) struct CommentEditor {
+    pub class_id: u64,
+    pub field_id: u64,
+    pub field_label: String,
+    pub buffer: String,
+}
+
+impl ReClassGui {
+    pub(super) fn open_comment_editor(&mut self, class_id: u64, field_index: usize) {
+        let Some(fd) = self
+            .app
+            .get_memory_structure()
+            .and_then(|ms| ms.class_registry.get(class_id))
+            .and_then(|def| def.fields.get(field_index))
+        else {
+            return;
+        };
+        self.comment_editor = Some(CommentEditor {
+            class_id,
+            field_id: fd.id,
+            field_label: fd
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("{} @ 0x{:X}", fd.field_type, fd.offset)),
+            buffer: fd.comment.clone().unwrap_or_default(),
+        });
+    }
+
+    pub(super) fn comment_editor_window(&mut self, ctx: &Context) {
+        let Some(editor) = self.comment_editor.as_mut() else {
+            return;
+        };
+        let mut open = true;
+        let mut save = false;
+        let mut cancel = false;
+        egui::Window::new(format!("Comment - {}", editor.field_label))
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let resp = ui.add(
+                    egui::TextEdit::multiline(&mut editor.buffer)
+                        .desired_rows(6)
+                        .desired_width(f32::INFINITY)
+                        .hint_text("Notes about this field"),
+                );
+                if resp.has_focus()
+                    && ui.input(|i| i.modifiers.ctrl && i.key_pressed(egui::Key::Enter))
+                {
+                    save = true;
+                }
+                ui.horizontal(|ui| {
+                    if ui.button("Save").on_hover_text("Ctrl+Enter").clicked() {
+                        save = true;
+                    }
+                    if ui.button("Cancel").clicked() {
+                        cancel = true;
+                    }
+                    if ui
+                        .add_enabled(!editor.buffer.is_empty(), egui::Button::new("Clear"))
+                        .clicked()
+                    {
+                        editor.buffer.clear();
+                    }
+                });
+            });
+
+        if save {
+            if let Some(editor) = self.comment_editor.take() {
+                if let Some(def) = self
+                    .app
+                    .get_memory_structure_mut()
+                    .and_then(|ms| ms.class_registry.get_mut(editor.class_id))
+                {
+                    // Look the field up by id, it may have moved while the editor was open
+                    if let Some(index) = def.fields.iter().position(|f| f.id == editor.field_id) {
+                        def.set_comment_at(index, &editor.buffer);
+                    }
+                }
+            }
+        } else if cancel || !open {
+            self.comment_editor = None;
+        }
+    }
+}
@@ -0,0 +1,111 @@
+use std::path::PathBuf;
+
+use eframe::egui::{
+    self,
+    Context,
+};
+
+use super::{
+    memory_view::parse_hex_u64,
+    ReClassGui,
+};
+
+/// State of the "Load Memory Dump" window
+#[derive(Default)]
+pub(super) struct DumpLoadState {
+    pub open: bool,
+    pub path: Option<PathBuf>,
+    /// Address of the first byte of a raw dump; minidumps ignore it
+    pub base_address: String,
+    /// Raw dumps of 32-bit processes use 4-byte pointers
+    pub x86: bool,
+    pub error: Option<String>,
+}
+
+impl ReClassGui {
+    fn load_memory_dump(&mut self) {
+        let state = &mut self.dump_load;
+        let Some(path) = state.path.clone() else {
+            return;
+        };
+        let base_address = if state.base_address.trim().is_empty() {
+            0
+        } else {
+            match parse_hex_u64(&state.base_address) {
+                Some(address) => address,
+                None => {
+                    state.error = Some(String::from("Invalid base address"));
+                    return;
+                }
+            }
+        };
+        let pointer_size = if state.x86 { 4 } else { 8 };
+        match self.app.open_dump(&path, base_address, pointer_size) {
+            Ok(()) => {
+                self.dump_load.error = None;
+                self.dump_load.open = false;
+                self.modules_window_open = false;
+                self.frozen_fields.clear();
+                self.frozen_values.clear();
+                self.value_plots.clear();
+                self.app.invalidate_read_cache();
+            }
+            Err(err) => self.dump_load.error = Some(format!("{err:#}")),
+        }
+    }
+
+    /// Pick a minidump or a raw memory dump to browse instead of a live process
+    pub(super) fn dump_load_window(&mut self, ctx: &Context) {
+        let mut open = self.dump_load.open;
+        let mut load = false;
+        egui::Window::new("Load Memory Dump")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let state = &mut self.dump_load;
+                ui.horizontal(|ui| {
+                    if ui.button("Choose file...").clicked() {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("Memory dump", &["dmp", "bin", "raw"])
+                            .add_filter("All files", &["*"])
+                            .pick_file()
+                        {
+                            state.path = Some(path);
+                            state.error = None;
+                        }
+                    }
+                    ui.label(match &state.path {
+                        Some(path) => path.display().to_string(),
+                        None => String::from("No file chosen"),
+                    });
+                });
+                ui.separator();
+                ui.label("Raw dumps (minidumps describe their own memory):");
+                ui.horizontal(|ui| {
+                    ui.label("Base address:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut state.base_address)
+                            .hint_text("0x7FF600000000")
+                            .desired_width(160.0),
+                    )
+                    .on_hover_text("Address the first byte of the file was read from");
+                });
+                ui.checkbox(&mut state.x86, "32-bit process")
+                    .on_hover_text("Pointers in the dump are 4 bytes wide");
+                ui.separator();
+                if ui
+                    .add_enabled(state.path.is_some(), egui::Button::new("Load"))
+                    .clicked()
+                {
+                    load = true;
+                }
+                if let Some(error) = &state.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+            });
+        self.dump_load.open = open;
+        if load {
+            self.load_memory_dump();
+        }
+    }
+}
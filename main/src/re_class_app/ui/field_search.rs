@@ -0,0 +1,116 @@
+use eframe::egui::{
+    self,
+    RichText,
+    ScrollArea,
+    Ui,
+};
+
+use super::{
+    memory_view::FieldKey,
+    ReClassGui,
+};
+
+/// Results shown at once; refine the query to see the rest
+const FIELD_SEARCH_MAX_RESULTS: usize = 200;
+
+/// Search over field names and comments of all classes, in the definitions panel
+#[derive(Default)]
+pub(super) struct FieldSearchState {
+    pub query: String,
+    /// Root field (class id, field definition id) to scroll to once the tree has laid it out
+    pub reveal: Option<(u64, u64)>,
+}
+
+impl ReClassGui {
+    pub(super) fn field_search_section(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Fields:");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.field_search.query)
+                    .hint_text("Search names and comments"),
+            );
+            if ui.button("Clear").clicked() {
+                self.field_search.query.clear();
+            }
+        });
+        let Some(ms) = self.app.get_memory_structure() else {
+            return;
+        };
+        if self.field_search.query.trim().is_empty() {
+            return;
+        }
+        let found = ms.find_fields(&self.field_search.query);
+        let total = found.len();
+        let results: Vec<(u64, usize, String, Option<String>)> = found
+            .into_iter()
+            .take(FIELD_SEARCH_MAX_RESULTS)
+            .filter_map(|(class_id, index)| {
+                let def = ms.class_registry.get(class_id)?;
+                let fd = def.fields.get(index)?;
+                let label = format!(
+                    "{}::{} @ +0x{:X}",
+                    def.name,
+                    fd.name.clone().unwrap_or_default(),
+                    fd.offset
+                );
+                Some((class_id, index, label, fd.comment.clone()))
+            })
+            .collect();
+
+        if results.is_empty() {
+            ui.label(RichText::new("No matching fields").weak());
+            return;
+        }
+        let mut reveal: Option<(u64, usize)> = None;
+        ScrollArea::vertical()
+            .id_source("field_search_scroll")
+            .max_height(180.0)
+            .show(ui, |ui| {
+                for (class_id, index, label, comment) in &results {
+                    let mut resp = ui.selectable_label(false, RichText::new(label).monospace());
+                    if let Some(comment) = comment {
+                        resp = resp.on_hover_text(comment);
+                    }
+                    if resp.clicked() {
+                        reveal = Some((*class_id, *index));
+                    }
+                }
+                if total > results.len() {
+                    ui.label(RichText::new(format!("{} more...", total - results.len())).weak());
+                }
+            });
+        if let Some((class_id, index)) = reveal {
+            self.reveal_field(class_id, index);
+        }
+    }
+
+    /// Make `class_id` the root class, select its field and scroll the tree to it
+    fn reveal_field(&mut self, class_id: u64, field_index: usize) {
+        let Some(ms) = self.app.get_memory_structure_mut() else {
+            return;
+        };
+        if ms.root_class.class_id != class_id {
+            if !ms.set_root_class_by_id(class_id) {
+                return;
+            }
+            self.root_class_type_buffer = None;
+        }
+        let Some(field_def_id) = ms
+            .class_registry
+            .get(class_id)
+            .and_then(|def| def.fields.get(field_index))
+            .map(|fd| fd.id)
+        else {
+            return;
+        };
+        let instance_address = ms.root_class.address;
+        self.selected_fields.clear();
+        self.selected_fields.insert(FieldKey {
+            instance_address,
+            field_def_id,
+        });
+        self.selected_instance_address = Some(instance_address);
+        self.selection_anchor = Some((instance_address, field_index));
+        self.field_search.reveal = Some((class_id, field_def_id));
+    }
+}
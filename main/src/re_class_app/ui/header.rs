@@ -6,10 +6,16 @@ use eframe::egui::{
     Ui,
 };
 
-use super::ReClassGui;
+use super::{
+    memory_view,
+    ReClassGui,
+};
+use crate::memory::Endianness;
 
 impl ReClassGui {
     pub(super) fn header_bar(&mut self, ui: &mut Ui) {
+        let mut detach = false;
+        let mut reattach = false;
         ui.with_layout(Layout::left_to_right(egui::Align::Center), |ui| {
             if ui
                 .add(egui::Button::new("Attach to Process").min_size(egui::vec2(140.0, 0.0)))
@@ -19,16 +25,35 @@ impl ReClassGui {
                 self.attach_window_open = true;
                 let _ = self.app.fetch_processes();
             }
+            if ui
+                .add(egui::Button::new("Load Dump").min_size(egui::vec2(90.0, 0.0)))
+                .on_hover_text("Browse memory saved in a minidump or raw dump file")
+                .clicked()
+            {
+                self.dump_load.open = true;
+            }
 
             if let Some(selected) = &self.app.process_state.selected_process {
                 let txt = RichText::new(format!(
-                    "Attached: {}  (PID {})",
+                    "Attached: {}  (PID {}, {})",
                     selected.get_image_base_name().unwrap_or("Unknown"),
-                    selected.process_id
+                    selected.process_id,
+                    if self.app.pointer_size == 4 {
+                        "x86"
+                    } else {
+                        "x64"
+                    }
                 ))
                 .strong()
                 .text_style(TextStyle::Button);
                 ui.label(txt);
+                if ui
+                    .add(egui::Button::new("Detach").min_size(egui::vec2(70.0, 0.0)))
+                    .on_hover_text("Release the process; values stay blank until attaching again")
+                    .clicked()
+                {
+                    detach = true;
+                }
                 if ui
                     .add(egui::Button::new("Modules").min_size(egui::vec2(84.0, 0.0)))
                     .on_hover_text("View loaded modules for the attached process")
@@ -44,6 +69,67 @@ impl ReClassGui {
                 {
                     self.signatures_window_open = true;
                 }
+                if ui
+                    .add(egui::Button::new("Pattern Scan").min_size(egui::vec2(100.0, 0.0)))
+                    .on_hover_text("Search a module for a byte pattern")
+                    .clicked()
+                {
+                    self.pattern_scan.open = true;
+                }
+                if ui
+                    .add(egui::Button::new("Scan Value").min_size(egui::vec2(100.0, 0.0)))
+                    .on_hover_text("Find addresses holding a known number")
+                    .clicked()
+                {
+                    self.value_scan_window.open = true;
+                }
+                if ui
+                    .add(egui::Button::new("Find References").min_size(egui::vec2(110.0, 0.0)))
+                    .on_hover_text("Find addresses holding a pointer to the current root")
+                    .clicked()
+                {
+                    self.reference_scan.open = true;
+                }
+                self.quick_view_box(ui);
+            } else if let Some(path) = &self.app.process_state.dump_path {
+                let txt = RichText::new(format!(
+                    "Dump: {}  ({}, read-only)",
+                    path.file_name().map_or_else(
+                        || path.display().to_string(),
+                        |n| n.to_string_lossy().into()
+                    ),
+                    if self.app.pointer_size == 4 {
+                        "x86"
+                    } else {
+                        "x64"
+                    }
+                ))
+                .strong()
+                .text_style(TextStyle::Button);
+                ui.label(txt).on_hover_text(path.display().to_string());
+                if ui
+                    .add(egui::Button::new("Close Dump").min_size(egui::vec2(90.0, 0.0)))
+                    .on_hover_text("Stop reading from the dump file")
+                    .clicked()
+                {
+                    detach = true;
+                }
+            } else if let Some(exited) = &self.app.process_state.exited_process {
+                let txt = RichText::new(format!(
+                    "Process exited: {}  (PID {})",
+                    exited.get_image_base_name().unwrap_or("Unknown"),
+                    exited.process_id
+                ))
+                .color(ui.visuals().warn_fg_color)
+                .text_style(TextStyle::Button);
+                ui.label(txt);
+                if ui
+                    .add(egui::Button::new("Reattach").min_size(egui::vec2(84.0, 0.0)))
+                    .on_hover_text("Attach to a running process with the same name")
+                    .clicked()
+                {
+                    reattach = true;
+                }
             } else {
                 ui.label(
                     RichText::new("Not attached")
@@ -51,8 +137,29 @@ impl ReClassGui {
                         .text_style(TextStyle::Button),
                 );
             }
+            self.navigation_buttons(ui);
+            ui.separator();
+            ui.toggle_value(&mut self.bookmarks.open, "Bookmarks")
+                .on_hover_text("Show the labeled addresses saved with this structure");
+            ui.toggle_value(&mut self.profiles.open, "Profiles")
+                .on_hover_text("Bookmarks and a root applied when attaching to a process image");
+            ui.toggle_value(&mut self.hex_view_open, "Hex View")
+                .on_hover_text("Show a raw hex dump panel next to the structure view");
+            ui.separator();
+            ui.toggle_value(&mut self.structure_locked, "Lock structure")
+                .on_hover_text(
+                    "Protect the layout from edits; values can still be read and written",
+                );
 
             ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                ui.toggle_value(&mut self.settings_window_open, "Settings")
+                    .on_hover_text("Preferences kept between launches");
+                ui.toggle_value(&mut self.restore_last_session, "Restore last session")
+                    .on_hover_text(
+                        "Reopen the last file, reattach to the last process and restore the \
+                         layout on the next launch",
+                    );
+                ui.separator();
                 ui.label(
                     RichText::new(format!("{}%", (self.ui_scale * 100.0).round()))
                         .weak()
@@ -74,7 +181,76 @@ impl ReClassGui {
                     self.ui_scale = (self.ui_scale - 0.05).clamp(0.8, 1.8);
                     ui.ctx().set_pixels_per_point(self.ui_scale);
                 }
+
+                ui.separator();
+                let throttled = self
+                    .app
+                    .handle
+                    .as_ref()
+                    .map_or(0, |handle| handle.throttled_read_count());
+                if throttled > 0 {
+                    ui.label(
+                        RichText::new(format!("{throttled} throttled"))
+                            .small()
+                            .color(ui.visuals().warn_fg_color),
+                    )
+                    .on_hover_text(
+                        "Reads at these addresses failed several times in a row and are skipped \
+                         for a moment to keep the UI responsive; their values may be missing or \
+                         stale until they are retried",
+                    );
+                }
+                self.refresh_hotkey_menu(ui);
+                let pause = ui
+                    .toggle_value(&mut self.app.reads_paused, "Pause")
+                    .on_hover_text("Stop reading memory and keep showing the last values");
+                let interval = ui
+                    .add(
+                        egui::Slider::new(&mut self.app.read_interval_ms, 1..=1000)
+                            .logarithmic(true)
+                            .suffix(" ms"),
+                    )
+                    .on_hover_text("Minimum time between two reads of the same memory");
+                if pause.changed() || interval.changed() {
+                    self.app.apply_read_settings();
+                }
+                ui.label(RichText::new("Refresh").text_style(TextStyle::Button));
+
+                ui.separator();
+                ui.selectable_value(&mut self.offsets_from_root, false, "Parent")
+                    .on_hover_text("Offsets count from the start of the class that owns the field");
+                ui.selectable_value(&mut self.offsets_from_root, true, "Root")
+                    .on_hover_text(
+                        "Offsets count from the start of the whole struct (the root, or the \
+                         instance a pointer leads to)",
+                    );
+                ui.label(RichText::new("Offsets relative to").text_style(TextStyle::Button));
+
+                ui.separator();
+                ui.add(
+                    egui::DragValue::new(&mut self.pointer_preview_depth)
+                        .clamp_range(1..=memory_view::POINTER_PREVIEW_MAX_DEPTH),
+                )
+                .on_hover_text("How many pointers a pointer-to-pointer preview follows");
+                ui.label(RichText::new("Ptr depth").text_style(TextStyle::Button));
+
+                if let Some(ms) = self.app.get_memory_structure_mut() {
+                    ui.separator();
+                    ui.selectable_value(&mut ms.endianness, Endianness::Little, "Little")
+                        .on_hover_text("Values are little-endian, as on x86/x64");
+                    ui.selectable_value(&mut ms.endianness, Endianness::Big, "Big")
+                        .on_hover_text(
+                            "Values are big-endian, e.g. console memory or network buffers",
+                        );
+                    ui.label(RichText::new("Byte order").text_style(TextStyle::Button));
+                }
             });
         });
+        if detach {
+            self.detach_from_process();
+        }
+        if reattach {
+            self.reattach_exited_process();
+        }
     }
 }
@@ -0,0 +1,232 @@
+use eframe::egui::{
+    self,
+    Color32,
+    Context,
+    RichText,
+    ScrollArea,
+    Sense,
+};
+use handle::AppHandle;
+
+use super::ReClassGui;
+
+const HEX_VIEW_BYTES_PER_ROW: u64 = 16;
+const HEX_VIEW_PAGE_SIZE: u64 = 0x1000;
+
+/// Read `len` bytes at `address` one page at a time so that an unmapped page only blanks out
+/// its own bytes
+fn read_paged(handle: &AppHandle, address: u64, len: u64) -> Vec<Option<u8>> {
+    let mut out = Vec::with_capacity(len as usize);
+    let end = address.saturating_add(len);
+    let mut cursor = address;
+    while cursor < end {
+        let page_end = (cursor / HEX_VIEW_PAGE_SIZE + 1)
+            .saturating_mul(HEX_VIEW_PAGE_SIZE)
+            .min(end);
+        let mut chunk = vec![0u8; (page_end - cursor) as usize];
+        if handle.read_slice(cursor, &mut chunk).is_ok() {
+            out.extend(chunk.into_iter().map(Some));
+        } else {
+            out.extend(std::iter::repeat_n(None, chunk.len()));
+        }
+        cursor = page_end;
+    }
+    out
+}
+
+impl ReClassGui {
+    fn hex_view_selected_range(&self) -> Option<(u64, u64)> {
+        let (anchor, cursor) = self.hex_view_selection?;
+        Some((anchor.min(cursor), anchor.max(cursor)))
+    }
+
+    /// Append hex fields covering the selected bytes to the root class
+    fn create_field_from_hex_selection(&mut self) {
+        let Some((start, end)) = self.hex_view_selected_range() else {
+            return;
+        };
+        if let Some(ms) = self.app.get_memory_structure_mut() {
+            let root_id = ms.root_class.class_id;
+            if let Some(def) = ms.class_registry.get_mut(root_id) {
+                let created = def.add_hex_bytes(end - start + 1);
+                if self.auto_name_fields {
+                    def.name_fields_by_offset(created);
+                }
+                self.schedule_rebuild();
+            }
+        }
+    }
+
+    pub(super) fn hex_view_panel(&mut self, ctx: &Context) {
+        egui::SidePanel::right("hex_view_panel")
+            .resizable(true)
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.heading("Hex View");
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label("Address:");
+                    let resp = ui.text_edit_singleline(&mut self.hex_view_address_buffer);
+                    let commit = ui.button("Go").clicked()
+                        || (resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)));
+                    if commit {
+                        let input = self.hex_view_address_buffer.trim().to_string();
+                        match self.eval_root_address_input(&input) {
+                            Some(addr) => {
+                                self.hex_view_address = addr;
+                                self.hex_view_selection = None;
+                            }
+                            None => {
+                                self.cycle_error_text = format!("Invalid address: {input}");
+                                self.cycle_error_open = true;
+                            }
+                        }
+                    }
+                    if ui
+                        .button("Root")
+                        .on_hover_text("Jump to the root class address")
+                        .clicked()
+                    {
+                        if let Some(ms) = self.app.get_memory_structure() {
+                            self.hex_view_address = ms.root_class.address;
+                            self.hex_view_address_buffer = format!("0x{:X}", self.hex_view_address);
+                            self.hex_view_selection = None;
+                        }
+                    }
+                    ui.label("Bytes:");
+                    ui.add(
+                        egui::DragValue::new(&mut self.hex_view_length)
+                            .clamp_range(HEX_VIEW_BYTES_PER_ROW..=0x10_0000)
+                            .hexadecimal(1, false, true)
+                            .prefix("0x"),
+                    );
+                });
+                if let Some((start, end)) = self.hex_view_selected_range() {
+                    ui.label(
+                        RichText::new(format!(
+                            "Selected 0x{start:X} - 0x{end:X} ({} bytes)",
+                            end - start + 1
+                        ))
+                        .weak(),
+                    );
+                }
+                ui.separator();
+
+                let Some(handle) = self.app.handle.clone() else {
+                    ui.label("Attach to a process to view memory");
+                    return;
+                };
+                let base = self.hex_view_address;
+                let total_rows = self.hex_view_length.div_ceil(HEX_VIEW_BYTES_PER_ROW) as usize;
+                let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+                ScrollArea::vertical()
+                    .id_source(("hex_view_rows", base))
+                    .auto_shrink([false, false])
+                    .show_rows(ui, row_height, total_rows, |ui, rows| {
+                        let first = base.wrapping_add(rows.start as u64 * HEX_VIEW_BYTES_PER_ROW);
+                        let shown = rows.start as u64 * HEX_VIEW_BYTES_PER_ROW;
+                        let len = (rows.len() as u64 * HEX_VIEW_BYTES_PER_ROW)
+                            .min(self.hex_view_length.saturating_sub(shown));
+                        let bytes = read_paged(&handle, first, len);
+                        for (row_index, row_bytes) in
+                            bytes.chunks(HEX_VIEW_BYTES_PER_ROW as usize).enumerate()
+                        {
+                            let row_address =
+                                first.wrapping_add(row_index as u64 * HEX_VIEW_BYTES_PER_ROW);
+                            self.hex_view_row(ui, row_address, row_bytes);
+                        }
+                    });
+            });
+    }
+
+    fn hex_view_row(&mut self, ui: &mut egui::Ui, row_address: u64, row_bytes: &[Option<u8>]) {
+        let selected = self.hex_view_selected_range();
+        ui.horizontal(|ui| {
+            ui.spacing_mut().item_spacing.x = 4.0;
+            ui.label(
+                RichText::new(format!("{row_address:016X}"))
+                    .monospace()
+                    .color(Color32::from_rgb(130, 130, 130)),
+            );
+            ui.add_space(6.0);
+            for (i, byte) in row_bytes.iter().enumerate() {
+                let address = row_address.wrapping_add(i as u64);
+                let text = byte
+                    .map(|b| format!("{b:02X}"))
+                    .unwrap_or_else(|| "??".into());
+                let mut rich = RichText::new(text).monospace();
+                if byte.is_none() {
+                    rich = rich.weak();
+                }
+                let in_selection = selected
+                    .map(|(start, end)| (start..=end).contains(&address))
+                    .unwrap_or(false);
+                if in_selection {
+                    rich = rich.background_color(Color32::from_rgb(50, 80, 130));
+                }
+                if i == 8 {
+                    ui.add_space(6.0);
+                }
+                let resp = ui
+                    .add(egui::Label::new(rich).sense(Sense::click()))
+                    .on_hover_text(format!("0x{address:X}"));
+                if resp.clicked() {
+                    let extend = ui.input(|i| i.modifiers.shift);
+                    match self.hex_view_selection {
+                        Some((anchor, _)) if extend => {
+                            self.hex_view_selection = Some((anchor, address));
+                        }
+                        _ => {
+                            self.hex_view_selection = Some((address, address));
+                            let _ = arboard::Clipboard::new()
+                                .and_then(|mut cb| cb.set_text(format!("0x{address:X}")));
+                        }
+                    }
+                }
+                if resp.secondary_clicked() && !in_selection {
+                    self.hex_view_selection = Some((address, address));
+                }
+                resp.context_menu(|ui| {
+                    let len = self
+                        .hex_view_selected_range()
+                        .map(|(start, end)| end - start + 1)
+                        .unwrap_or(1);
+                    if ui.button("Copy address").clicked() {
+                        let _ = arboard::Clipboard::new()
+                            .and_then(|mut cb| cb.set_text(format!("0x{address:X}")));
+                        ui.close_menu();
+                    }
+                    if ui
+                        .add_enabled(
+                            self.app.get_memory_structure().is_some(),
+                            egui::Button::new(format!("Create field of this size ({len} bytes)")),
+                        )
+                        .on_hover_text("Append hex fields of this size to the root class")
+                        .clicked()
+                    {
+                        self.create_field_from_hex_selection();
+                        ui.close_menu();
+                    }
+                });
+            }
+            // Pad a short last row so the ASCII column lines up
+            for i in row_bytes.len()..HEX_VIEW_BYTES_PER_ROW as usize {
+                if i == 8 {
+                    ui.add_space(6.0);
+                }
+                ui.label(RichText::new("  ").monospace());
+            }
+            ui.add_space(10.0);
+            let ascii: String = row_bytes
+                .iter()
+                .map(|b| match b {
+                    Some(b) if b.is_ascii_graphic() || *b == b' ' => *b as char,
+                    _ => '.',
+                })
+                .collect();
+            ui.label(RichText::new(ascii).monospace());
+        });
+    }
+}
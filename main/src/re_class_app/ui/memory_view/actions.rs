@@ -1,12 +1,23 @@
 use std::collections::HashSet;
 
-use super::context_menu::FieldCtx;
+use handle::AppHandle;
+
+use super::{
+    context_menu::FieldCtx,
+    util::{
+        FieldKey,
+        ValueFormat,
+        ARRAY_MAX_LENGTH,
+    },
+};
 use crate::{
     memory::{
+        clipboard::FieldClipboard,
         ClassDefinition,
+        Endianness,
         FieldType,
         MemoryStructure,
-        PointerTarget,
+        NumberBase,
     },
     re_class_app::ReClassGui,
 };
@@ -18,55 +29,58 @@ impl ReClassGui {
         }
         if let Some(ms) = self.app.get_memory_structure_mut() {
             if let Some(def) = ms.class_registry.get_mut(ctx.owner_class_id) {
-                let mut remaining = num_bytes;
-                while remaining >= 8 {
-                    def.add_hex_field(FieldType::Hex64);
-                    remaining -= 8;
-                }
-                while remaining >= 4 {
-                    def.add_hex_field(FieldType::Hex32);
-                    remaining -= 4;
-                }
-                while remaining >= 2 {
-                    def.add_hex_field(FieldType::Hex16);
-                    remaining -= 2;
-                }
-                while remaining > 0 {
-                    def.add_hex_field(FieldType::Hex8);
-                    remaining -= 1;
+                let created = def.add_hex_bytes(num_bytes as u64);
+                if self.auto_name_fields {
+                    def.name_fields_by_offset(created);
                 }
                 self.schedule_rebuild();
             }
         }
     }
 
+    pub(super) fn set_field_color(&mut self, ctx: &FieldCtx, color: Option<[u8; 3]>) {
+        if let Some(def) = self
+            .app
+            .get_memory_structure_mut()
+            .and_then(|ms| ms.class_registry.get_mut(ctx.owner_class_id))
+        {
+            def.set_color_at(ctx.field_index, color);
+        }
+    }
+
+    /// Show an integer field in `base`, or in the default of its type for `None`
+    pub(super) fn set_field_display_base(&mut self, ctx: &FieldCtx, base: Option<NumberBase>) {
+        if let Some(def) = self
+            .app
+            .get_memory_structure_mut()
+            .and_then(|ms| ms.class_registry.get_mut(ctx.owner_class_id))
+        {
+            def.set_display_base_at(ctx.field_index, base);
+        }
+    }
+
+    /// Resize an array field; the element rows follow once the structure is rebuilt
+    pub(super) fn set_array_length(&mut self, ctx: &FieldCtx, length: u32) {
+        if let Some(fd) = self
+            .app
+            .get_memory_structure_mut()
+            .and_then(|ms| ms.class_registry.get_mut(ctx.owner_class_id))
+            .and_then(|def| def.fields.get_mut(ctx.field_index))
+        {
+            fd.array_length = Some(length.min(ARRAY_MAX_LENGTH));
+            self.schedule_rebuild();
+        }
+    }
+
     pub(super) fn insert_n_bytes_here(&mut self, ctx: &FieldCtx, num_bytes: usize) {
         if num_bytes == 0 {
             return;
         }
         if let Some(ms) = self.app.get_memory_structure_mut() {
             if let Some(def) = ms.class_registry.get_mut(ctx.owner_class_id) {
-                let mut remaining = num_bytes;
-                let mut insert_index = ctx.field_index;
-                while remaining >= 8 {
-                    def.insert_hex_field_at(insert_index, FieldType::Hex64);
-                    insert_index += 1;
-                    remaining -= 8;
-                }
-                while remaining >= 4 {
-                    def.insert_hex_field_at(insert_index, FieldType::Hex32);
-                    insert_index += 1;
-                    remaining -= 4;
-                }
-                while remaining >= 2 {
-                    def.insert_hex_field_at(insert_index, FieldType::Hex16);
-                    insert_index += 1;
-                    remaining -= 2;
-                }
-                while remaining > 0 {
-                    def.insert_hex_field_at(insert_index, FieldType::Hex8);
-                    insert_index += 1;
-                    remaining -= 1;
+                let created = def.insert_hex_bytes_at(ctx.field_index, num_bytes as u64);
+                if self.auto_name_fields {
+                    def.name_fields_by_offset(created);
                 }
                 self.schedule_rebuild();
             }
@@ -124,7 +138,7 @@ impl ReClassGui {
         new_type: FieldType,
     ) {
         let ms = unsafe { &mut *mem_ptr };
-        if let Some(def) = ms.class_registry.get_mut(owner_class_id) {
+        if let Some(def) = ms.class_registry.get(owner_class_id) {
             // Map ids to indices each pass since set_field_type_at may update structure but keeps order
             let indices: Vec<usize> = def
                 .fields
@@ -139,30 +153,7 @@ impl ReClassGui {
                 })
                 .collect();
             for idx in indices {
-                def.set_field_type_at(idx, new_type.clone());
-                if new_type == FieldType::Pointer {
-                    if let Some(fd) = def.fields.get_mut(idx) {
-                        fd.pointer_target = Some(PointerTarget::FieldType(FieldType::Hex64));
-                    }
-                } else if new_type == FieldType::Enum {
-                    if let Some(fd) = def.fields.get_mut(idx) {
-                        let ids = ms.enum_registry.get_enum_ids();
-                        if let Some(first) = ids.into_iter().next() {
-                            fd.enum_id = Some(first);
-                        } else {
-                            fd.enum_id = None;
-                        }
-                    }
-                } else if new_type == FieldType::Array {
-                    if let Some(fd) = def.fields.get_mut(idx) {
-                        if fd.array_element.is_none() {
-                            fd.array_element = Some(PointerTarget::FieldType(FieldType::Hex8));
-                        }
-                        if fd.array_length.is_none() {
-                            fd.array_length = Some(1);
-                        }
-                    }
-                }
+                ms.set_field_type_with_defaults(owner_class_id, idx, new_type.clone());
             }
             self.schedule_rebuild();
         }
@@ -206,7 +197,10 @@ impl ReClassGui {
                 counter += 1;
             }
             let mut new_def = ClassDefinition::new(name.clone());
-            new_def.add_hex_field(FieldType::Hex64);
+            let created = new_def.add_hex_bytes(self.new_class_bytes);
+            if self.auto_name_fields {
+                new_def.name_fields_by_offset(created);
+            }
             let cid = new_def.id;
             planned.push((idx, cid, name, new_def));
         }
@@ -227,4 +221,144 @@ impl ReClassGui {
         }
         self.schedule_rebuild();
     }
+
+    /// Move a field within its class; offsets and nested instances follow on the next rebuild
+    pub(super) fn move_field(&mut self, owner_class_id: u64, from: usize, to: usize) {
+        if let Some(ms) = self.app.get_memory_structure_mut() {
+            if let Some(def) = ms.class_registry.get_mut(owner_class_id) {
+                if from != to && def.move_field(from, to) {
+                    // Index-based shift-click anchor no longer points at the same field
+                    self.selection_anchor = None;
+                    self.schedule_rebuild();
+                }
+            }
+        }
+    }
+
+    pub(super) fn copy_selected_fields(
+        &mut self,
+        mem_ptr: *mut MemoryStructure,
+        owner_class_id: u64,
+        selected_field_ids: &HashSet<u64>,
+    ) {
+        let ms = unsafe { &*mem_ptr };
+        if let Some(clipboard) = FieldClipboard::copy(ms, owner_class_id, selected_field_ids) {
+            self.field_clipboard = Some(clipboard);
+        }
+    }
+
+    /// Paste the copied fields before the field under `ctx`
+    pub(super) fn paste_fields_here(&mut self, ctx: &FieldCtx) {
+        let Some(clipboard) = self.field_clipboard.as_ref() else {
+            return;
+        };
+        let ms = unsafe { &mut *ctx.mem_ptr };
+        if clipboard.paste_into(ms, ctx.owner_class_id, ctx.field_index) > 0 {
+            self.schedule_rebuild();
+        }
+    }
+
+    /// Freeze the field under `ctx` by snapshotting its current bytes, or unfreeze it
+    pub(super) fn toggle_freeze_field(&mut self, ctx: &FieldCtx) {
+        let ms = unsafe { &*ctx.mem_ptr };
+        let Some(fd) = ms
+            .class_registry
+            .get(ctx.owner_class_id)
+            .and_then(|d| d.fields.get(ctx.field_index))
+        else {
+            return;
+        };
+        let key = FieldKey {
+            instance_address: ctx.instance_address,
+            field_def_id: fd.id,
+        };
+        if self.frozen_fields.remove(&key) {
+            self.frozen_values.remove(&key);
+            return;
+        }
+        let size = match fd.field_type {
+            FieldType::Enum => fd
+                .enum_id
+                .and_then(|eid| ms.enum_registry.get(eid))
+                .map(|ed| ed.default_size as u64)
+                .unwrap_or(4),
+            _ => fd.get_size_for(ms.pointer_size()),
+        };
+        let Some(handle) = self.app.handle.as_ref() else {
+            return;
+        };
+        if size == 0 {
+            return;
+        }
+        let mut bytes = vec![0u8; size as usize];
+        if handle.read_slice(ctx.address, bytes.as_mut_slice()).is_ok() {
+            self.frozen_fields.insert(key);
+            self.frozen_values.insert(key, bytes);
+        }
+    }
+
+    /// Write the snapshotted bytes of all frozen fields back to the process. Fields whose
+    /// write fails are unfrozen, so the failure is reported once instead of every frame.
+    pub(crate) fn apply_frozen_values(&mut self) {
+        if self.frozen_fields.is_empty() {
+            return;
+        }
+        let (Some(handle), Some(ms)) = (self.app.handle.as_ref(), self.app.get_memory_structure())
+        else {
+            return;
+        };
+        let mut failed = Vec::new();
+        for key in &self.frozen_fields {
+            // Re-resolve from the instance tree so rebuilt layouts keep writing to the right place
+            let Some(address) = ms.find_field_address(key.instance_address, key.field_def_id)
+            else {
+                continue;
+            };
+            if let Some(bytes) = self.frozen_values.get(key) {
+                if let Err(err) = handle.write_slice(address, bytes) {
+                    failed.push((*key, address, err.to_string()));
+                }
+            }
+        }
+        let Some((_, address, err)) = failed.first() else {
+            return;
+        };
+        let mut text = format!("Unfroze the field at 0x{address:X}, writing it failed: {err}");
+        if failed.len() > 1 {
+            text.push_str(&format!(
+                "\n{} other frozen field(s) failed and were unfrozen too",
+                failed.len() - 1
+            ));
+        }
+        for (key, ..) in &failed {
+            self.frozen_fields.remove(key);
+            self.frozen_values.remove(key);
+        }
+        self.cycle_error_text = text;
+        self.cycle_error_open = true;
+    }
+
+    /// Byte order of the shown structure, which values are read and written in
+    pub(crate) fn byte_order(&self) -> Endianness {
+        self.app
+            .get_memory_structure()
+            .map(|ms| ms.endianness)
+            .unwrap_or_default()
+    }
+
+    /// How values are shown, following the display settings
+    pub(super) fn value_format(&self) -> ValueFormat {
+        ValueFormat {
+            float_precision: self.float_precision,
+            endianness: self.byte_order(),
+        }
+    }
+
+    /// Write an edited value to the process, telling the user when the write fails
+    pub(super) fn write_field_value(&mut self, handle: &AppHandle, address: u64, bytes: &[u8]) {
+        if let Err(err) = handle.write_slice(address, bytes) {
+            self.cycle_error_text = format!("Failed to write the value at 0x{address:X}: {err}");
+            self.cycle_error_open = true;
+        }
+    }
 }
@@ -0,0 +1,112 @@
+use eframe::egui::{
+    self,
+    Context,
+    RichText,
+    Ui,
+};
+
+use crate::{
+    memory::{
+        snapshot::Snapshot,
+        MemoryStructure,
+    },
+    re_class_app::ReClassGui,
+};
+
+/// Instance shown in the compare panel next to the main tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ComparePin {
+    pub address: u64,
+    pub class_id: u64,
+    pub label: String,
+}
+
+impl ReClassGui {
+    /// Show the instance of `class_id` at `address` in the compare panel
+    pub(super) fn pin_to_compare(&mut self, address: u64, class_id: u64, label: String) {
+        self.compare_pin = Some(ComparePin {
+            address,
+            class_id,
+            label,
+        });
+    }
+
+    /// "Pin to compare" entry of instance and element header menus
+    pub(super) fn pin_to_compare_button(
+        &mut self,
+        ui: &mut Ui,
+        address: u64,
+        class_id: u64,
+        label: String,
+    ) {
+        if ui
+            .button("Pin to compare")
+            .on_hover_text("Show this instance in a side panel to compare it with the main tree")
+            .clicked()
+        {
+            self.pin_to_compare(address, class_id, label);
+            ui.close_menu();
+        }
+    }
+
+    /// Right-hand panel rendering the pinned instance with the regular tree. When it has the
+    /// class of the root, fields whose bytes differ from the root's at the same offset are
+    /// tinted like snapshot changes.
+    pub(crate) fn compare_panel(&mut self, ctx: &Context) {
+        let Some(pin) = self.compare_pin.clone() else {
+            return;
+        };
+        let mut unpin = false;
+        egui::SidePanel::right("compare_panel")
+            .resizable(true)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.heading("Compare");
+                    if ui.button("Unpin").clicked() {
+                        unpin = true;
+                    }
+                });
+                let handle = self.app.handle.clone();
+                let Some(ms) = self.app.get_memory_structure_mut() else {
+                    ui.label("No structure loaded");
+                    return;
+                };
+                let mem_ptr: *mut MemoryStructure = ms as *mut _;
+                let ms: &mut MemoryStructure = unsafe { &mut *mem_ptr };
+                let Some(def) = ms.class_registry.get(pin.class_id).cloned() else {
+                    ui.label("The pinned class no longer exists");
+                    return;
+                };
+                ui.label(
+                    RichText::new(format!("{} @ 0x{:X}: {}", pin.label, pin.address, def.name))
+                        .strong(),
+                );
+                let reference = (ms.root_class.class_id == pin.class_id)
+                    .then(|| handle.as_ref().and_then(|h| ms.root_class.read_block(h)))
+                    .flatten()
+                    .map(|bytes| Snapshot::new(pin.address, bytes));
+                if reference.is_some() {
+                    ui.label(RichText::new("Tinted fields differ from the root instance").weak());
+                }
+                ui.separator();
+                // Borrow the snapshot tint for the differences while this panel renders
+                let saved_snapshot = std::mem::replace(&mut self.snapshot, reference);
+                let saved_compare =
+                    std::mem::replace(&mut self.snapshot_compare, self.snapshot.is_some());
+                self.render_detached_instance(
+                    ui,
+                    pin.label.clone(),
+                    pin.address,
+                    def,
+                    mem_ptr,
+                    "compare_tree_scroll",
+                );
+                self.snapshot = saved_snapshot;
+                self.snapshot_compare = saved_compare;
+            });
+        if unpin {
+            self.compare_pin = None;
+        }
+    }
+}
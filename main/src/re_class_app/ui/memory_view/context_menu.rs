@@ -1,15 +1,75 @@
 use eframe::egui;
 
+use super::util::{
+    field_type_color,
+    format_offset_path,
+    read_pointer_value,
+    FieldKey,
+    ARRAY_MAX_LENGTH,
+    ARRAY_MAX_STRIDE,
+};
 use crate::{
+    export::cpp::generate_cpp_snippet,
     memory::{
+        format_signed_offset,
+        parse_signed_offset,
         ClassDefinition,
         FieldType,
         MemoryStructure,
+        NumberBase,
         PointerTarget,
+        TextEncoding,
+        TEXT_MAX_LENGTH,
     },
     re_class_app::ReClassGui,
 };
 
+/// Types offered by "Change type" and "Replace field types"
+pub const CHANGE_TYPE_CHOICES: [FieldType; 32] = [
+    FieldType::Hex8,
+    FieldType::Hex16,
+    FieldType::Hex32,
+    FieldType::Hex64,
+    FieldType::Int8,
+    FieldType::Int16,
+    FieldType::Int32,
+    FieldType::Int64,
+    FieldType::UInt8,
+    FieldType::UInt16,
+    FieldType::UInt32,
+    FieldType::UInt64,
+    FieldType::Bool,
+    FieldType::Float,
+    FieldType::Double,
+    FieldType::Vector2,
+    FieldType::Vector3,
+    FieldType::Vector4,
+    FieldType::Quaternion,
+    FieldType::Matrix4x4,
+    FieldType::Guid,
+    FieldType::UnixTime32,
+    FieldType::UnixTime64,
+    FieldType::Win32FileTime,
+    FieldType::Text,
+    FieldType::TextPointer,
+    FieldType::WideText,
+    FieldType::WideTextPointer,
+    FieldType::Pointer,
+    FieldType::Enum,
+    FieldType::Array,
+    FieldType::Bitfield,
+];
+
+/// Quick picks offered above the color picker of "Set color"
+const FIELD_COLOR_PRESETS: [[u8; 3]; 6] = [
+    [230, 90, 90],
+    [240, 160, 70],
+    [230, 210, 90],
+    [110, 200, 110],
+    [90, 190, 220],
+    [190, 130, 230],
+];
+
 pub(super) struct FieldCtx {
     pub mem_ptr: *mut MemoryStructure,
     pub owner_class_id: u64,
@@ -20,6 +80,62 @@ pub(super) struct FieldCtx {
 }
 
 impl ReClassGui {
+    /// Instance an embedded class or pointer-to-class field leads to, for "Pin to compare"
+    fn compare_target(&self, ctx: &FieldCtx) -> Option<(u64, u64, String)> {
+        let ms = unsafe { &*ctx.mem_ptr };
+        let fd = ms
+            .class_registry
+            .get(ctx.owner_class_id)?
+            .fields
+            .get(ctx.field_index)?;
+        let label = fd
+            .name
+            .clone()
+            .unwrap_or_else(|| format!("0x{:X}", ctx.address));
+        match (&fd.field_type, &fd.pointer_target) {
+            (FieldType::ClassInstance, _) => Some((ctx.address, fd.class_id?, label)),
+            (FieldType::Pointer, Some(PointerTarget::ClassId(cid))) => {
+                let handle = self.app.handle.as_ref()?;
+                let target =
+                    read_pointer_value(handle, ctx.address, ms.endianness).filter(|t| *t != 0)?;
+                Some((target, *cid, label))
+            }
+            _ => None,
+        }
+    }
+
+    /// Class a ClassInstance, Pointer->Class or class-element Array field refers to, with the
+    /// address of its instance when there is one to show
+    fn definition_target(&self, ctx: &FieldCtx) -> Option<(u64, Option<u64>)> {
+        let ms = unsafe { &*ctx.mem_ptr };
+        let fd = ms
+            .class_registry
+            .get(ctx.owner_class_id)?
+            .fields
+            .get(ctx.field_index)?;
+        let pointee = |pointer_address: u64| {
+            self.app
+                .handle
+                .as_ref()
+                .and_then(|h| read_pointer_value(h, pointer_address, ms.endianness))
+                .filter(|target| *target != 0)
+        };
+        match (&fd.field_type, &fd.pointer_target, &fd.array_element) {
+            (FieldType::ClassInstance, _, _) => Some((fd.class_id?, Some(ctx.address))),
+            (FieldType::Pointer, Some(PointerTarget::ClassId(cid)), _) => {
+                Some((*cid, pointee(ctx.address)))
+            }
+            (FieldType::Array, _, Some(PointerTarget::ClassId(cid))) => {
+                Some((*cid, Some(ctx.address)))
+            }
+            (FieldType::Array, ..) => {
+                let cid = fd.array_pointee_class()?;
+                Some((cid, pointee(ctx.address)))
+            }
+            _ => None,
+        }
+    }
+
     pub(super) fn context_menu_for_field(&mut self, response: &egui::Response, ctx: FieldCtx) {
         response.context_menu(|ui| {
             // If multiple fields are selected in the same instance/class, show only bulk operations
@@ -37,8 +153,40 @@ impl ReClassGui {
                     .collect();
                 if selected_ids.len() > 1 {
                     ui.label("Selection actions");
+                    if ui.button("Copy fields").clicked() {
+                        self.copy_selected_fields(ctx.mem_ptr, owner, &selected_ids);
+                        ui.close_menu();
+                        return;
+                    }
+                    if ui
+                        .button("Copy as C++")
+                        .on_hover_text(
+                            "Copy the selected fields as struct members with their offsets",
+                        )
+                        .clicked()
+                    {
+                        let ms = unsafe { &*ctx.mem_ptr };
+                        let snippet = generate_cpp_snippet(ms, owner, &selected_ids);
+                        let _ = arboard::Clipboard::new().and_then(|mut cb| cb.set_text(snippet));
+                        ui.close_menu();
+                        return;
+                    }
+                    // Writes values, not the layout, so it stays available while locked
+                    let ranges = unsafe { &*ctx.mem_ptr }.field_byte_ranges(
+                        ctx.instance_address,
+                        owner,
+                        &selected_ids,
+                    );
+                    self.zero_fields_button(ui, ranges);
+                    if self.structure_locked {
+                        locked_note(ui);
+                        return;
+                    }
+                    if self.paste_fields_button(ui, &ctx) {
+                        return;
+                    }
                     if ui.button("Remove fields").clicked() {
-                        self.remove_selected_fields(ctx.mem_ptr, owner, &selected_ids);
+                        self.request_field_removal(ctx.mem_ptr, owner, selected_ids);
                         ui.close_menu();
                         return;
                     }
@@ -62,11 +210,20 @@ impl ReClassGui {
                             FieldType::Vector2,
                             FieldType::Vector3,
                             FieldType::Vector4,
+                            FieldType::Quaternion,
+                            FieldType::Matrix4x4,
+                            FieldType::Guid,
+                            FieldType::UnixTime32,
+                            FieldType::UnixTime64,
+                            FieldType::Win32FileTime,
                             FieldType::Text,
                             FieldType::TextPointer,
+                            FieldType::WideText,
+                            FieldType::WideTextPointer,
                             FieldType::Pointer,
                             FieldType::Enum,
                             FieldType::Array,
+                            FieldType::Bitfield,
                         ] {
                             let label = format!("{t:?}");
                             if ui.button(label).clicked() {
@@ -94,13 +251,219 @@ impl ReClassGui {
                     .and_then(|mut cb| cb.set_text(format!("0x{:X}", ctx.address)));
                 ui.close_menu();
             }
+            if ui
+                .button("Copy offset path")
+                .on_hover_text(
+                    "Offsets from the root to this field, e.g. *0x10 -> 0x8 -> 0x40; \
+                     * marks a pointer that is followed",
+                )
+                .clicked()
+            {
+                let path =
+                    format_offset_path(&self.offset_hops, ctx.address - ctx.instance_address);
+                let _ = arboard::Clipboard::new().and_then(|mut cb| cb.set_text(path));
+                ui.close_menu();
+            }
+            if let Some((address, class_id, label)) = self.compare_target(&ctx) {
+                self.pin_to_compare_button(ui, address, class_id, label);
+                self.copy_instance_table_button(ui, ctx.mem_ptr, class_id, address);
+            }
+            if let Some((class_id, address)) = self.definition_target(&ctx) {
+                let is_root = unsafe { (*ctx.mem_ptr).root_class.class_id == class_id };
+                if ui
+                    .add_enabled(!is_root, egui::Button::new("Go to class definition"))
+                    .on_hover_text("Make the referenced class the root to edit its layout")
+                    .on_disabled_hover_text("The referenced class is already the root")
+                    .clicked()
+                {
+                    self.go_to_class_definition(class_id, address);
+                    ui.close_menu();
+                }
+            }
+            if ui
+                .add_enabled(!self.structure_locked, egui::Button::new("Edit comment"))
+                .clicked()
+            {
+                self.open_comment_editor(ctx.owner_class_id, ctx.field_index);
+                ui.close_menu();
+            }
+            if !self.structure_locked {
+                let colors = unsafe {
+                    (*ctx.mem_ptr)
+                        .class_registry
+                        .get(ctx.owner_class_id)
+                        .and_then(|d| d.fields.get(ctx.field_index))
+                        .map(|fd| {
+                            (
+                                fd.color,
+                                field_type_color(Some(fd), self.tree_colors.field_color()),
+                            )
+                        })
+                };
+                if let Some((custom, current)) = colors {
+                    ui.menu_button("Set color", |ui| {
+                        let mut picked = current;
+                        ui.horizontal(|ui| {
+                            for [r, g, b] in FIELD_COLOR_PRESETS {
+                                let preset = egui::Color32::from_rgb(r, g, b);
+                                if ui.add(egui::Button::new("    ").fill(preset)).clicked() {
+                                    picked = preset;
+                                }
+                            }
+                        });
+                        egui::color_picker::color_picker_color32(
+                            ui,
+                            &mut picked,
+                            egui::color_picker::Alpha::Opaque,
+                        );
+                        if picked != current {
+                            self.set_field_color(&ctx, Some([picked.r(), picked.g(), picked.b()]));
+                        }
+                    });
+                    if custom.is_some() && ui.button("Clear color").clicked() {
+                        self.set_field_color(&ctx, None);
+                        ui.close_menu();
+                    }
+                }
+                let bases = unsafe {
+                    (*ctx.mem_ptr)
+                        .class_registry
+                        .get(ctx.owner_class_id)
+                        .and_then(|d| d.fields.get(ctx.field_index))
+                        .and_then(|fd| Some((fd.display_base, fd.field_type.default_base()?)))
+                };
+                if let Some((current, default)) = bases {
+                    ui.menu_button("Display as", |ui| {
+                        let mut picked = current;
+                        ui.radio_value(&mut picked, None, format!("Default ({})", default.label()));
+                        for base in NumberBase::ALL {
+                            ui.radio_value(&mut picked, Some(base), base.label());
+                        }
+                        if picked != current {
+                            self.set_field_display_base(&ctx, picked);
+                            ui.close_menu();
+                        }
+                    });
+                }
+            }
+            if ui.button("Bookmark this address").clicked() {
+                // Embedded class instances pin their class so the bookmark opens them as root
+                let (name, class_id) = unsafe {
+                    (*ctx.mem_ptr)
+                        .class_registry
+                        .get(ctx.owner_class_id)
+                        .and_then(|d| d.fields.get(ctx.field_index))
+                        .map(|fd| {
+                            let class_id = (fd.field_type == FieldType::ClassInstance)
+                                .then_some(fd.class_id)
+                                .flatten();
+                            (fd.name.clone().unwrap_or_default(), class_id)
+                        })
+                        .unwrap_or_default()
+                };
+                self.add_bookmark(name, format!("0x{:X}", ctx.address), class_id);
+                ui.close_menu();
+            }
+            {
+                // Pointer fields: Some(pointee class) if the pointer targets a class
+                let pointee = unsafe {
+                    (*ctx.mem_ptr)
+                        .class_registry
+                        .get(ctx.owner_class_id)
+                        .and_then(|d| d.fields.get(ctx.field_index))
+                        .filter(|fd| fd.field_type == FieldType::Pointer)
+                        .map(|fd| match fd.pointer_target {
+                            Some(PointerTarget::ClassId(cid)) => Some(cid),
+                            _ => None,
+                        })
+                };
+                if let Some(class_id) = pointee {
+                    if ui
+                        .add_enabled(
+                            self.app.handle.is_some(),
+                            egui::Button::new("Follow pointer as root"),
+                        )
+                        .on_hover_text("Make the address this pointer holds the root")
+                        .clicked()
+                    {
+                        self.follow_pointer_as_root(ctx.address, class_id);
+                        ui.close_menu();
+                    }
+                }
+            }
             if let Some(val) = ctx.value_preview.clone() {
                 if ui.button("Copy value").clicked() {
                     let _ = arboard::Clipboard::new().and_then(|mut cb| cb.set_text(val));
                     ui.close_menu();
                 }
             }
+            {
+                let freeze_state = unsafe {
+                    (*ctx.mem_ptr)
+                        .class_registry
+                        .get(ctx.owner_class_id)
+                        .and_then(|d| d.fields.get(ctx.field_index))
+                        .filter(|fd| !fd.field_type.is_dynamic_size())
+                        .map(|fd| {
+                            self.frozen_fields.contains(&FieldKey {
+                                instance_address: ctx.instance_address,
+                                field_def_id: fd.id,
+                            })
+                        })
+                };
+                if let Some(is_frozen) = freeze_state {
+                    let label = if is_frozen {
+                        "Unfreeze value"
+                    } else {
+                        "Freeze value"
+                    };
+                    if ui
+                        .add_enabled(self.app.handle.is_some(), egui::Button::new(label))
+                        .clicked()
+                    {
+                        self.toggle_freeze_field(&ctx);
+                        ui.close_menu();
+                    }
+                }
+            }
+            self.plot_value_button(ui, &ctx);
+            {
+                let field_id = unsafe {
+                    (*ctx.mem_ptr)
+                        .class_registry
+                        .get(ctx.owner_class_id)
+                        .and_then(|d| d.fields.get(ctx.field_index))
+                        .map(|fd| fd.id)
+                };
+                if let Some(field_id) = field_id {
+                    if ui.button("Copy field").clicked() {
+                        self.copy_selected_fields(
+                            ctx.mem_ptr,
+                            ctx.owner_class_id,
+                            &std::collections::HashSet::from([field_id]),
+                        );
+                        ui.close_menu();
+                    }
+                }
+                self.paste_fields_button(ui, &ctx);
+            }
+            // Everything below changes the layout
+            if self.structure_locked {
+                locked_note(ui);
+                return;
+            }
             ui.separator();
+            if ui
+                .add_enabled(
+                    self.app.handle.is_some(),
+                    egui::Button::new("Analyze region"),
+                )
+                .on_hover_text("Read the instance and propose types for its hex fields")
+                .clicked()
+            {
+                self.analyze_instance(ctx.owner_class_id, ctx.instance_address);
+                ui.close_menu();
+            }
             ui.menu_button("Add bytes at end", |ui| {
                 for &(label, n) in &[
                     ("4 bytes", 4usize),
@@ -162,80 +525,63 @@ impl ReClassGui {
             });
 
             {
-                let can_remove = unsafe {
+                let field_id = unsafe {
                     (*ctx.mem_ptr)
                         .class_registry
                         .get(ctx.owner_class_id)
-                        .map(|d| d.fields.len() > 1)
-                        .unwrap_or(false)
+                        .filter(|d| d.fields.len() > 1)
+                        .and_then(|d| d.fields.get(ctx.field_index))
+                        .map(|f| f.id)
                 };
-                let resp = ui.add_enabled(can_remove, egui::Button::new("Remove field"));
+                let resp = ui.add_enabled(field_id.is_some(), egui::Button::new("Remove field"));
                 if resp.clicked() {
-                    let ms = unsafe { &mut *ctx.mem_ptr };
-                    if let Some(def) = ms.class_registry.get_mut(ctx.owner_class_id) {
-                        def.remove_field_at(ctx.field_index);
-                        self.schedule_rebuild();
+                    if let Some(field_id) = field_id {
+                        self.request_field_removal(
+                            ctx.mem_ptr,
+                            ctx.owner_class_id,
+                            [field_id].into(),
+                        );
                     }
                     ui.close_menu();
                 }
             }
+            {
+                let field_count = unsafe {
+                    (*ctx.mem_ptr)
+                        .class_registry
+                        .get(ctx.owner_class_id)
+                        .map(|d| d.fields.len())
+                        .unwrap_or(0)
+                };
+                if ui
+                    .add_enabled(ctx.field_index > 0, egui::Button::new("Move up"))
+                    .clicked()
+                {
+                    self.move_field(ctx.owner_class_id, ctx.field_index, ctx.field_index - 1);
+                    ui.close_menu();
+                }
+                if ui
+                    .add_enabled(
+                        ctx.field_index + 1 < field_count,
+                        egui::Button::new("Move down"),
+                    )
+                    .clicked()
+                {
+                    self.move_field(ctx.owner_class_id, ctx.field_index, ctx.field_index + 1);
+                    ui.close_menu();
+                }
+            }
             ui.menu_button("Change type", |ui| {
-                for t in [
-                    FieldType::Hex8,
-                    FieldType::Hex16,
-                    FieldType::Hex32,
-                    FieldType::Hex64,
-                    FieldType::Int8,
-                    FieldType::Int16,
-                    FieldType::Int32,
-                    FieldType::Int64,
-                    FieldType::UInt8,
-                    FieldType::UInt16,
-                    FieldType::UInt32,
-                    FieldType::UInt64,
-                    FieldType::Bool,
-                    FieldType::Float,
-                    FieldType::Double,
-                    FieldType::Vector2,
-                    FieldType::Vector3,
-                    FieldType::Vector4,
-                    FieldType::Text,
-                    FieldType::TextPointer,
-                    FieldType::Pointer,
-                    FieldType::Enum,
-                    FieldType::Array,
-                ] {
+                for t in CHANGE_TYPE_CHOICES {
                     let label = format!("{t:?}");
                     if ui.button(label).clicked() {
                         let ms = unsafe { &mut *ctx.mem_ptr };
-                        if let Some(def) = ms.class_registry.get_mut(ctx.owner_class_id) {
-                            def.set_field_type_at(ctx.field_index, t.clone());
-                            if t == FieldType::Pointer {
-                                if let Some(fd) = def.fields.get_mut(ctx.field_index) {
-                                    fd.pointer_target =
-                                        Some(PointerTarget::FieldType(FieldType::Hex64));
-                                }
-                            } else if t == FieldType::Enum {
-                                if let Some(fd) = def.fields.get_mut(ctx.field_index) {
-                                    let ids =
-                                        unsafe { (*ctx.mem_ptr).enum_registry.get_enum_ids() };
-                                    if let Some(first) = ids.into_iter().next() {
-                                        fd.enum_id = Some(first);
-                                    } else {
-                                        fd.enum_id = None;
-                                    }
-                                }
-                            } else if t == FieldType::Array {
-                                if let Some(fd) = def.fields.get_mut(ctx.field_index) {
-                                    if fd.array_element.is_none() {
-                                        fd.array_element =
-                                            Some(PointerTarget::FieldType(FieldType::Hex8));
-                                    }
-                                    if fd.array_length.is_none() {
-                                        fd.array_length = Some(1);
-                                    }
-                                }
-                            }
+                        if ms.class_registry.contains(ctx.owner_class_id) {
+                            ms.set_field_type_with_defaults(
+                                ctx.owner_class_id,
+                                ctx.field_index,
+                                t.clone(),
+                            );
                             self.schedule_rebuild();
                         }
                         ui.close_menu();
@@ -297,6 +643,96 @@ impl ReClassGui {
                             self.schedule_rebuild();
                         }
                     }
+                } else if matches!(field_type_opt, Some(FieldType::Bitfield)) {
+                    ui.separator();
+                    let layout = ms
+                        .class_registry
+                        .get(ctx.owner_class_id)
+                        .and_then(|d| d.fields.get(ctx.field_index))
+                        .map(|fd| fd.bitfield_layout());
+                    if let Some((size, offset, width)) = layout {
+                        ui.menu_button("Bitfield", |ui| {
+                            let (mut new_size, mut new_offset, mut new_width) =
+                                (size, offset, width);
+                            ui.horizontal(|ui| {
+                                ui.label("Backing size:");
+                                for bytes in [1u8, 2, 4, 8] {
+                                    ui.selectable_value(&mut new_size, bytes, format!("{bytes}"));
+                                }
+                            });
+                            let bits = new_size * 8;
+                            ui.horizontal(|ui| {
+                                ui.label("Bit offset:");
+                                ui.add(
+                                    egui::DragValue::new(&mut new_offset).clamp_range(0..=bits - 1),
+                                );
+                                ui.label("Width:");
+                                ui.add(egui::DragValue::new(&mut new_width).clamp_range(1..=bits));
+                            });
+                            if (new_size, new_offset, new_width) != (size, offset, width) {
+                                if let Some(defm) = ms.class_registry.get_mut(ctx.owner_class_id) {
+                                    defm.set_bitfield_at(
+                                        ctx.field_index,
+                                        new_size,
+                                        new_offset,
+                                        new_width,
+                                    );
+                                }
+                                self.schedule_rebuild();
+                            }
+                        });
+                    }
+                } else if matches!(
+                    field_type_opt,
+                    Some(FieldType::Text | FieldType::TextPointer)
+                ) {
+                    ui.separator();
+                    let encoding = ms
+                        .class_registry
+                        .get(ctx.owner_class_id)
+                        .and_then(|d| d.fields.get(ctx.field_index))
+                        .map(|fd| fd.text_encoding.unwrap_or_default());
+                    if let Some(encoding) = encoding {
+                        ui.menu_button(format!("Encoding: {}", encoding.label()), |ui| {
+                            for choice in TextEncoding::ALL {
+                                if ui.radio(choice == encoding, choice.label()).clicked() {
+                                    if let Some(defm) =
+                                        ms.class_registry.get_mut(ctx.owner_class_id)
+                                    {
+                                        defm.set_text_encoding_at(ctx.field_index, Some(choice));
+                                    }
+                                    ui.close_menu();
+                                }
+                            }
+                        });
+                    }
+                    let lengths = ms
+                        .class_registry
+                        .get(ctx.owner_class_id)
+                        .and_then(|d| d.fields.get(ctx.field_index))
+                        .map(|fd| (fd.text_read_length(), fd.text_length.is_some()));
+                    if let Some((length, custom)) = lengths {
+                        ui.menu_button("String length…", |ui| {
+                            let mut new_length = length;
+                            ui.horizontal(|ui| {
+                                ui.label("Bytes:");
+                                ui.add(
+                                    egui::DragValue::new(&mut new_length)
+                                        .clamp_range(1..=TEXT_MAX_LENGTH),
+                                );
+                            });
+                            let mut update = (new_length != length).then_some(Some(new_length));
+                            if custom && ui.button("Default").clicked() {
+                                update = Some(None);
+                            }
+                            if let Some(new_length) = update {
+                                if let Some(defm) = ms.class_registry.get_mut(ctx.owner_class_id) {
+                                    defm.set_text_length_at(ctx.field_index, new_length);
+                                }
+                                self.schedule_rebuild();
+                            }
+                        });
+                    }
                 } else if matches!(field_type_opt, Some(FieldType::Array)) {
                     ui.separator();
                     ui.label("Array element type:");
@@ -321,8 +757,16 @@ impl ReClassGui {
                                 FieldType::Vector2,
                                 FieldType::Vector3,
                                 FieldType::Vector4,
+                                FieldType::Quaternion,
+                                FieldType::Matrix4x4,
+                                FieldType::Guid,
+                                FieldType::UnixTime32,
+                                FieldType::UnixTime64,
+                                FieldType::Win32FileTime,
                                 FieldType::Text,
                                 FieldType::TextPointer,
+                                FieldType::WideText,
+                                FieldType::WideTextPointer,
                                 FieldType::Enum,
                             ] {
                                 let label = format!("{t:?}");
@@ -397,7 +841,10 @@ impl ReClassGui {
                                     name
                                 };
                                 let mut new_def = ClassDefinition::new(unique_name.clone());
-                                new_def.add_hex_field(FieldType::Hex64);
+                                let created = new_def.add_hex_bytes(self.new_class_bytes);
+                                if self.auto_name_fields {
+                                    new_def.name_fields_by_offset(created);
+                                }
                                 let cid = new_def.id;
                                 ms.class_registry.register(new_def);
                                 if let Some(defm) = ms.class_registry.get_mut(ctx.owner_class_id) {
@@ -430,12 +877,54 @@ impl ReClassGui {
                                 }
                             }
                         });
+                        ui.menu_button("Pointer to class", |ui| {
+                            if ui
+                                .button("void*")
+                                .on_hover_text("Plain 8-byte pointers without a target class")
+                                .clicked()
+                            {
+                                if let Some(fdm) = ms
+                                    .class_registry
+                                    .get_mut(ctx.owner_class_id)
+                                    .and_then(|defm| defm.fields.get_mut(ctx.field_index))
+                                {
+                                    fdm.array_element =
+                                        Some(PointerTarget::FieldType(FieldType::Pointer));
+                                    fdm.pointer_target = None;
+                                }
+                                self.schedule_rebuild();
+                                ui.close_menu();
+                            }
+                            ui.separator();
+                            for id in ms.class_registry.get_class_ids() {
+                                let name = ms
+                                    .class_registry
+                                    .get(id)
+                                    .map(|d| d.name.clone())
+                                    .unwrap_or_default();
+                                if ui.button(name).clicked() {
+                                    // Pointers never make the layout cyclic, any class will do
+                                    if let Some(fdm) = ms
+                                        .class_registry
+                                        .get_mut(ctx.owner_class_id)
+                                        .and_then(|defm| defm.fields.get_mut(ctx.field_index))
+                                    {
+                                        fdm.array_element =
+                                            Some(PointerTarget::FieldType(FieldType::Pointer));
+                                        fdm.pointer_target = Some(PointerTarget::ClassId(id));
+                                    }
+                                    self.schedule_rebuild();
+                                    ui.close_menu();
+                                }
+                            }
+                        });
                     });
                     ui.horizontal(|ui| {
                         ui.label("Length:");
                         let mut len_val: u32 = current_len;
-                        let resp =
-                            ui.add(egui::DragValue::new(&mut len_val).clamp_range(0..=1_048_576));
+                        let resp = ui.add(
+                            egui::DragValue::new(&mut len_val).clamp_range(0..=ARRAY_MAX_LENGTH),
+                        );
                         if resp.changed() {
                             if let Some(defm) = ms.class_registry.get_mut(ctx.owner_class_id) {
                                 if let Some(fdm) = defm.fields.get_mut(ctx.field_index) {
@@ -445,6 +934,66 @@ impl ReClassGui {
                             self.schedule_rebuild();
                         }
                     });
+                    let strides = ms
+                        .class_registry
+                        .get(ctx.owner_class_id)
+                        .and_then(|d| d.fields.get(ctx.field_index))
+                        .map(|fd| {
+                            let element_size =
+                                ms.array_element_size(fd, &mut vec![ctx.owner_class_id]);
+                            (
+                                element_size,
+                                fd.array_step(element_size),
+                                fd.array_stride.is_some(),
+                                matches!(fd.array_element, Some(PointerTarget::ClassId(_))),
+                            )
+                        });
+                    if let Some((element_size, stride, custom, class_elements)) = strides {
+                        ui.menu_button("Stride…", |ui| {
+                            let mut new_stride = stride;
+                            let max_stride = ARRAY_MAX_STRIDE.max(element_size);
+                            ui.horizontal(|ui| {
+                                ui.label("Bytes:");
+                                ui.add(
+                                    egui::DragValue::new(&mut new_stride)
+                                        .clamp_range(element_size..=max_stride),
+                                )
+                                .on_hover_text(
+                                    "Distance from the start of one element to the next",
+                                );
+                            });
+                            let mut update = (new_stride != stride)
+                                .then(|| (new_stride > element_size).then_some(new_stride));
+                            if custom && ui.button("Packed").clicked() {
+                                update = Some(None);
+                            }
+                            if class_elements
+                                && ui
+                                    .add_enabled(
+                                        self.app.handle.is_some(),
+                                        egui::Button::new("Auto-detect"),
+                                    )
+                                    .on_hover_text(
+                                        "Try strides past the class size and pick the one that \
+                                         lines up pointers and floats in the first elements",
+                                    )
+                                    .clicked()
+                            {
+                                self.detect_stride(
+                                    ctx.owner_class_id,
+                                    ctx.field_index,
+                                    ctx.address,
+                                );
+                                ui.close_menu();
+                            }
+                            if let Some(new_stride) = update {
+                                if let Some(defm) = ms.class_registry.get_mut(ctx.owner_class_id) {
+                                    defm.set_array_stride_at(ctx.field_index, new_stride);
+                                }
+                                self.schedule_rebuild();
+                            }
+                        });
+                    }
                 }
             }
 
@@ -452,6 +1001,11 @@ impl ReClassGui {
                 if let Some(def) = ms.class_registry.get_mut(ctx.owner_class_id) {
                     if let Some(fd) = def.fields.get(ctx.field_index) {
                         if fd.field_type == FieldType::Pointer {
+                            let current_offsets = match &fd.pointer_target {
+                                Some(PointerTarget::Chain { offsets }) => Some(offsets.clone()),
+                                _ => None,
+                            };
+                            let chain_def_id = fd.id;
                             ui.menu_button("Pointer target", |ui| {
                                 ui.menu_button("Primitive", |ui| {
                                     for t in [
@@ -473,8 +1027,16 @@ impl ReClassGui {
                                         FieldType::Vector2,
                                         FieldType::Vector3,
                                         FieldType::Vector4,
+                                        FieldType::Quaternion,
+                                        FieldType::Matrix4x4,
+                                        FieldType::Guid,
+                                        FieldType::UnixTime32,
+                                        FieldType::UnixTime64,
+                                        FieldType::Win32FileTime,
                                         FieldType::Text,
                                         FieldType::TextPointer,
+                                        FieldType::WideText,
+                                        FieldType::WideTextPointer,
                                         FieldType::Enum,
                                     ] {
                                         let label = format!("{t:?}");
@@ -580,7 +1142,11 @@ impl ReClassGui {
                                             };
                                             let mut new_def =
                                                 ClassDefinition::new(unique_name.clone());
-                                            new_def.add_hex_field(FieldType::Hex64);
+                                            let created =
+                                                new_def.add_hex_bytes(self.new_class_bytes);
+                                            if self.auto_name_fields {
+                                                new_def.name_fields_by_offset(created);
+                                            }
                                             let cid = new_def.id;
                                             ms.class_registry.register(new_def);
                                             if let Some(defm) =
@@ -676,7 +1242,10 @@ impl ReClassGui {
                                             name
                                         };
                                         let mut new_def = ClassDefinition::new(unique_name.clone());
-                                        new_def.add_hex_field(FieldType::Hex64);
+                                        let created = new_def.add_hex_bytes(self.new_class_bytes);
+                                        if self.auto_name_fields {
+                                            new_def.name_fields_by_offset(created);
+                                        }
                                         let cid = new_def.id;
                                         ms.class_registry.register(new_def);
                                         if let Some(defm) =
@@ -719,6 +1288,78 @@ impl ReClassGui {
                                     }
                                 });
                             });
+                            ui.menu_button("Pointer chain…", |ui| {
+                                let mut offsets = current_offsets.clone().unwrap_or_default();
+                                let mut changed = false;
+                                let mut remove_index: Option<usize> = None;
+                                if current_offsets.is_none() {
+                                    ui.label("Converts this pointer into a chain");
+                                }
+                                for (i, offset) in offsets.iter_mut().enumerate() {
+                                    ui.horizontal(|ui| {
+                                        ui.label(format!("[{i}]"));
+                                        // Typed text is kept until it parses, so "-" can be
+                                        // entered before the digits
+                                        let key = (chain_def_id, i);
+                                        let mut text = self
+                                            .chain_offset_buffers
+                                            .get(&key)
+                                            .cloned()
+                                            .unwrap_or_else(|| format_signed_offset(*offset));
+                                        let parsed = parse_signed_offset(&text);
+                                        let resp = ui
+                                            .add(
+                                                egui::TextEdit::singleline(&mut text)
+                                                    .desired_width(90.0)
+                                                    .text_color_opt(
+                                                        parsed
+                                                            .is_none()
+                                                            .then(|| ui.visuals().error_fg_color),
+                                                    ),
+                                            )
+                                            .on_hover_text(
+                                                "Hex with 0x, decimal otherwise; a leading - \
+                                                 steps back from the pointer, e.g. -0x10",
+                                            );
+                                        if resp.changed() {
+                                            if let Some(value) = parse_signed_offset(&text) {
+                                                changed |= value != *offset;
+                                                *offset = value;
+                                            }
+                                            self.chain_offset_buffers.insert(key, text);
+                                        }
+                                        if resp.lost_focus() {
+                                            self.chain_offset_buffers.remove(&key);
+                                        }
+                                        if ui.button("Remove").clicked() {
+                                            remove_index = Some(i);
+                                        }
+                                    });
+                                }
+                                if let Some(i) = remove_index {
+                                    offsets.remove(i);
+                                    // Buffers are keyed by position
+                                    self.chain_offset_buffers
+                                        .retain(|(def_id, _), _| *def_id != chain_def_id);
+                                    changed = true;
+                                }
+                                if ui.button("Add offset").clicked() {
+                                    offsets.push(0);
+                                    changed = true;
+                                }
+                                if changed {
+                                    let ms = unsafe { &mut *ctx.mem_ptr };
+                                    if let Some(defm) =
+                                        ms.class_registry.get_mut(ctx.owner_class_id)
+                                    {
+                                        if let Some(fdm) = defm.fields.get_mut(ctx.field_index) {
+                                            fdm.pointer_target =
+                                                Some(PointerTarget::Chain { offsets });
+                                        }
+                                    }
+                                    self.schedule_rebuild();
+                                }
+                            });
                         }
                     }
                 }
@@ -738,7 +1379,10 @@ impl ReClassGui {
                     name
                 };
                 let mut new_def = ClassDefinition::new(unique_name.clone());
-                new_def.add_hex_field(FieldType::Hex64);
+                let created = new_def.add_hex_bytes(self.new_class_bytes);
+                if self.auto_name_fields {
+                    new_def.name_fields_by_offset(created);
+                }
                 let cid = new_def.id;
                 ms.class_registry.register(new_def.clone());
                 if let Some(def) = ms.class_registry.get_mut(ctx.owner_class_id) {
@@ -752,4 +1396,57 @@ impl ReClassGui {
             }
         });
     }
+
+    /// "Paste fields" entry; returns true when the menu was closed by a paste
+    fn paste_fields_button(&mut self, ui: &mut egui::Ui, ctx: &FieldCtx) -> bool {
+        let count = self.field_clipboard.as_ref().map(|c| c.len()).unwrap_or(0);
+        let label = match count {
+            0 => String::from("Paste fields"),
+            1 => String::from("Paste 1 field here"),
+            n => format!("Paste {n} fields here"),
+        };
+        let resp = ui
+            .add_enabled(
+                count > 0 && !self.structure_locked,
+                egui::Button::new(label),
+            )
+            .on_hover_text("Insert the copied fields before this one");
+        if resp.clicked() {
+            self.paste_fields_here(ctx);
+            ui.close_menu();
+            return true;
+        }
+        false
+    }
+
+    /// Menu of an array element header: the instance of `class_id` at `address`, labeled like
+    /// `Player[3]`
+    pub(super) fn array_element_menu(
+        &mut self,
+        ui: &mut egui::Ui,
+        address: u64,
+        class_id: u64,
+        label: String,
+    ) {
+        if ui
+            .button("Open element as root")
+            .on_hover_text("Make this element the root; the current root is kept as a breadcrumb")
+            .clicked()
+        {
+            self.move_root_with_breadcrumb(address, Some(class_id), format!("0x{address:X}"));
+            ui.close_menu();
+        }
+        if ui.button("Bookmark element").clicked() {
+            self.add_bookmark(label.clone(), format!("0x{address:X}"), Some(class_id));
+            ui.close_menu();
+        }
+        self.pin_to_compare_button(ui, address, class_id, label);
+    }
+}
+
+/// Shown in place of the editing entries while the structure is locked
+fn locked_note(ui: &mut egui::Ui) {
+    ui.separator();
+    ui.label(egui::RichText::new("Structure is locked").weak())
+        .on_hover_text("Turn off \"Lock structure\" in the header to edit the layout");
 }
@@ -4,6 +4,7 @@ use eframe::egui::{
     self,
     Color32,
     RichText,
+    ScrollArea,
     Ui,
 };
 use handle::AppHandle;
@@ -11,20 +12,77 @@ use handle::AppHandle;
 use super::{
     context_menu::FieldCtx,
     util::{
+        append_field_comment,
+        array_element_string,
+        bitfield_value_string,
+        class_pointer_header_suffix,
+        field_comment_label,
+        field_header_text,
+        field_type_color,
         field_value_string,
+        format_chain_offsets,
+        is_live_address,
+        is_value_editable,
+        liveness_icon,
+        matrix_tooltip,
+        parse_hex_u64,
+        parse_value_bytes,
+        pointer_chain_value_string,
+        pointer_header_suffix,
+        pointer_header_text,
+        pointer_preview_string,
+        pointer_validity_dot,
+        read_field_pointer,
+        read_field_value,
+        read_pointer_value,
+        read_value,
+        row_column,
+        rtti_matches,
         text_edit_autowidth,
+        text_value_string,
+        to_target_order,
+        unreadable_label,
+        value_bytes_tooltip,
+        FieldDrag,
         FieldKey,
+        FieldValue,
+        OffsetHop,
+        PointerValidity,
+        ARRAY_MAX_LENGTH,
+        NAME_COLUMN_WIDTH,
+        SIZE_COLUMN_WIDTH,
+        TYPE_COLUMN_WIDTH,
     },
 };
 use crate::memory::{
     ClassDefinition,
     ClassInstance,
+    Endianness,
+    EnumDefinition,
     FieldType,
     MemoryStructure,
     MemoryStructure as MSForSig,
     PointerTarget,
+    TextEncoding,
 };
 
+/// Definition of the enum an Enum field points at, if it has one
+fn field_enum_definition(
+    class_def: &ClassDefinition,
+    idx: usize,
+    field_type: &FieldType,
+    mem_ptr: *mut MemoryStructure,
+) -> Option<EnumDefinition> {
+    if *field_type != FieldType::Enum {
+        return None;
+    }
+    let eid = class_def.fields.get(idx)?.enum_id?;
+    unsafe { (mem_ptr).as_ref() }?
+        .enum_registry
+        .get_by_id(eid)
+        .cloned()
+}
+
 fn enum_suffix_for_field(
     class_def: &ClassDefinition,
     field: &crate::memory::MemoryField,
@@ -61,40 +119,108 @@ fn enum_value_string(
     memory: &MSForSig,
 ) -> Option<String> {
     let def = class_def.fields.iter().find(|fd| fd.id == field.def_id)?;
-    let eid = def.enum_id?;
-    let edef = memory.enum_registry.get_by_id(eid)?;
-    let size = edef.default_size;
-    let (val_u64, val_str) = match size {
-        1 => {
-            let v = handle.read_sized::<u8>(field.address).ok()? as u64;
-            (v, v.to_string())
-        }
-        2 => {
-            let v = handle.read_sized::<u16>(field.address).ok()? as u64;
-            (v, v.to_string())
-        }
-        8 => {
-            let v = handle.read_sized::<u64>(field.address).ok()?;
-            (v, v.to_string())
-        }
-        _ => {
-            let v = handle.read_sized::<u32>(field.address).ok()? as u64;
-            (v, v.to_string())
-        }
+    let Some(edef) = def
+        .enum_id
+        .and_then(|eid| memory.enum_registry.get_by_id(eid))
+    else {
+        // No enum picked yet: show the raw 4-byte value
+        return read_field_value::<u32>(handle, field, memory.endianness).map(|v| v.to_string());
     };
-    if let Some(variant) = edef
-        .variants
-        .iter()
-        .find(|variant| (variant.value as u64) == val_u64)
-    {
-        Some(variant.name.clone())
-    } else {
-        Some(val_str)
-    }
+    Some(edef.format_value(enum_raw_value(handle, field, edef, memory.endianness)?))
+}
+
+/// Backing integer of an enum field, read as `backing_size` bytes
+fn enum_raw_value(
+    handle: &AppHandle,
+    field: &crate::memory::MemoryField,
+    edef: &EnumDefinition,
+    order: Endianness,
+) -> Option<u64> {
+    Some(match edef.backing_size() {
+        1 => read_field_value::<u8>(handle, field, order)? as u64,
+        2 => read_field_value::<u16>(handle, field, order)? as u64,
+        8 => read_field_value::<u64>(handle, field, order)?,
+        _ => read_field_value::<u32>(handle, field, order)? as u64,
+    })
+}
+
+/// Name of the `default_size` enum element of an array at `address`; unreadable reads as 0
+fn enum_element_string(
+    handle: &AppHandle,
+    edef: &EnumDefinition,
+    address: u64,
+    order: Endianness,
+) -> String {
+    let raw = match edef.default_size {
+        1 => read_value::<u8>(handle, address, order).unwrap_or(0) as u64,
+        2 => read_value::<u16>(handle, address, order).unwrap_or(0) as u64,
+        8 => read_value::<u64>(handle, address, order).unwrap_or(0),
+        _ => read_value::<u32>(handle, address, order).unwrap_or(0) as u64,
+    };
+    edef.format_value(raw)
+}
+
+/// Raw number typed for an enum field ("5", "-1", "0x10") as backing integer bytes
+fn parse_enum_value_bytes(edef: &EnumDefinition, input: &str) -> Option<Vec<u8>> {
+    let t = input.trim();
+    let value = match t.strip_prefix('-') {
+        Some(rest) => i64::try_from(parse_hex_u64(rest)?).ok()?.checked_neg()?,
+        None => parse_hex_u64(t)? as i64,
+    };
+    edef.encode_value(value)
 }
 use crate::re_class_app::ReClassGui;
 
+/// Maximum height of the scrollable element list of an expanded array
+const ARRAY_VIEW_MAX_HEIGHT: f32 = 320.0;
+
 impl ReClassGui {
+    /// Scrollable list of `count` rows of varying height where only the rows inside the
+    /// viewport are laid out. Heights of rows seen before are remembered per list, unseen
+    /// rows are assumed to be one widget high.
+    fn show_virtual_rows(
+        &mut self,
+        ui: &mut Ui,
+        id_source: impl std::hash::Hash,
+        count: usize,
+        mut add_row: impl FnMut(&mut Self, &mut Ui, usize),
+    ) {
+        let id = ui.make_persistent_id(id_source);
+        let default_height = ui.spacing().interact_size.y + ui.spacing().item_spacing.y;
+        let mut heights = self.virtual_row_heights.remove(&id).unwrap_or_default();
+        heights.resize(count, default_height);
+        ScrollArea::vertical()
+            .id_source(id)
+            .max_height(ARRAY_VIEW_MAX_HEIGHT)
+            .show_viewport(ui, |ui, viewport| {
+                let total: f32 = heights.iter().sum();
+                ui.set_height(total);
+
+                let mut first = 0;
+                let mut top = 0.0;
+                while first < count && top + heights[first] < viewport.min.y {
+                    top += heights[first];
+                    first += 1;
+                }
+                let rect = egui::Rect::from_x_y_ranges(
+                    ui.max_rect().x_range(),
+                    (ui.max_rect().top() + top)..=(ui.max_rect().top() + total),
+                );
+                ui.allocate_ui_at_rect(rect, |ui| {
+                    ui.skip_ahead_auto_ids(first);
+                    let mut row = first;
+                    while row < count && top < viewport.max.y {
+                        let start = ui.cursor().top();
+                        add_row(self, ui, row);
+                        heights[row] = (ui.cursor().top() - start).max(1.0);
+                        top += heights[row];
+                        row += 1;
+                    }
+                });
+            });
+        self.virtual_row_heights.insert(id, heights);
+    }
+
     fn compute_display_size_for(
         &self,
         field_type: &FieldType,
@@ -113,7 +239,61 @@ impl ReClassGui {
                 }
             }
         }
-        field_type.get_size()
+        if matches!(field_type, FieldType::Bitfield | FieldType::Text) {
+            if let Some(fd) = class_def.fields.iter().find(|fdef| fdef.id == field.def_id) {
+                return fd.get_size();
+            }
+        }
+        field_type.get_size_for(self.app.pointer_size)
+    }
+
+    /// Value text of a simple field row; enums and bitfields need their definition to decode
+    pub(super) fn simple_field_value(
+        &self,
+        handle: Option<&Arc<AppHandle>>,
+        class_def: &ClassDefinition,
+        field: &crate::memory::MemoryField,
+        field_type: &FieldType,
+        mem_ptr: *mut MemoryStructure,
+    ) -> FieldValue {
+        let Some(handle) = handle else {
+            return FieldValue::NotAttached;
+        };
+        let value = match field_type {
+            FieldType::Enum => unsafe { (mem_ptr).as_ref() }
+                .and_then(|ms| enum_value_string(handle, class_def, field, ms)),
+            FieldType::Bitfield => class_def
+                .fields
+                .iter()
+                .find(|fd| fd.id == field.def_id)
+                .and_then(|fd| bitfield_value_string(handle, fd, field, self.byte_order())),
+            FieldType::Text | FieldType::TextPointer => class_def
+                .fields
+                .iter()
+                .find(|fd| fd.id == field.def_id)
+                .and_then(|fd| {
+                    text_value_string(
+                        handle,
+                        field,
+                        field_type,
+                        fd.text_read_length(),
+                        fd.text_encoding.unwrap_or_default(),
+                        self.byte_order(),
+                    )
+                }),
+            _ => {
+                let fd = class_def.fields.iter().find(|fd| fd.id == field.def_id);
+                field_value_string(
+                    Some(handle.clone()),
+                    field,
+                    field_type,
+                    fd.and_then(|fd| fd.display_base),
+                    fd.and_then(|fd| fd.text_encoding).unwrap_or_default(),
+                    self.value_format(),
+                )
+            }
+        };
+        FieldValue::from_read(value)
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -137,7 +317,11 @@ impl ReClassGui {
             .get(&key)
             .cloned()
             .unwrap_or_else(|| current_name.unwrap_or_default());
-        let resp = text_edit_autowidth(ui, &mut fname);
+        let resp = ui
+            .add_enabled_ui(!self.structure_locked, |ui| {
+                text_edit_autowidth(ui, &mut fname)
+            })
+            .inner;
         if resp.changed() {
             self.field_name_buffers.insert(key, fname.clone());
         }
@@ -170,10 +354,11 @@ impl ReClassGui {
         path: &[usize],
         instance_address: u64,
         def_ids: &[u64],
+        changed: bool,
         ctx: FieldCtx,
-    ) {
-        let row_bg = if idx % 2 == 0 {
-            Color32::from_black_alpha(12)
+    ) -> egui::Response {
+        let row_bg = if idx.is_multiple_of(2) {
+            self.tree_colors.row_stripe_color()
         } else {
             Color32::TRANSPARENT
         };
@@ -185,16 +370,30 @@ impl ReClassGui {
             instance_address,
             field_def_id: def_id,
         };
+        if self.frozen_fields.contains(&key) {
+            ui.painter().rect_filled(
+                rect.expand2(egui::vec2(4.0, 2.0)),
+                4.0,
+                Color32::from_rgba_unmultiplied(80, 170, 255, 28),
+            );
+        }
+        if changed {
+            ui.painter().rect_filled(
+                rect.expand2(egui::vec2(4.0, 2.0)),
+                4.0,
+                Color32::from_rgba_unmultiplied(230, 200, 60, 40),
+            );
+        }
         if self.selected_fields.contains(&key) {
             ui.painter().rect_filled(
                 rect.expand2(egui::vec2(4.0, 2.0)),
                 4.0,
-                Color32::from_white_alpha(18),
+                self.tree_colors.selection_fill_color(),
             );
             ui.painter().rect_stroke(
                 rect.expand2(egui::vec2(4.0, 2.0)),
                 4.0,
-                egui::Stroke::new(1.5, Color32::from_rgb(100, 160, 255)),
+                egui::Stroke::new(1.5, self.tree_colors.selection_stroke_color()),
             );
         }
         if resp.hovered() {
@@ -203,11 +402,96 @@ impl ReClassGui {
                 4.0,
                 egui::Stroke::new(1.0, Color32::from_white_alpha(12)),
             );
+            self.hovered_def_id_next = Some(def_id);
+        } else if self.hovered_def_id == Some(def_id) {
+            // The same field of another instance of the class is under the mouse
+            ui.painter().rect_stroke(
+                rect.expand2(egui::vec2(4.0, 2.0)),
+                4.0,
+                egui::Stroke::new(
+                    1.0,
+                    self.tree_colors
+                        .selection_stroke_color()
+                        .gamma_multiply(0.35),
+                ),
+            );
         }
         if resp.clicked() {
             self.update_selection_for_click(ui, instance_address, idx, def_ids, def_id);
         }
+        self.handle_field_drag(ui, &resp, rect, idx, instance_address, ctx.owner_class_id);
         self.context_menu_for_field(&resp, ctx);
+        resp
+    }
+
+    /// Drag-and-drop reordering: pressing on a row and dragging picks it up; rows of the same
+    /// instance show where it would land and perform the move on release.
+    fn handle_field_drag(
+        &mut self,
+        ui: &mut Ui,
+        resp: &egui::Response,
+        rect: egui::Rect,
+        idx: usize,
+        instance_address: u64,
+        owner_class_id: u64,
+    ) {
+        if self.field_drag.is_none()
+            && !self.structure_locked
+            && resp.is_pointer_button_down_on()
+            && ui.input(|i| i.pointer.is_decidedly_dragging())
+        {
+            self.field_drag = Some(FieldDrag {
+                instance_address,
+                owner_class_id,
+                from_index: idx,
+            });
+        }
+        let Some(drag) = self.field_drag else {
+            return;
+        };
+        if drag.instance_address != instance_address || drag.owner_class_id != owner_class_id {
+            return;
+        }
+        let row = rect.expand2(egui::vec2(4.0, ui.spacing().item_spacing.y / 2.0));
+        let Some(pos) = ui.input(|i| i.pointer.interact_pos()) else {
+            return;
+        };
+        if !row.contains(pos) {
+            return;
+        }
+        // Insertion point: before this row when over its top half, after it otherwise
+        let below = pos.y > row.center().y;
+        let insert_at = if below { idx + 1 } else { idx };
+        let line_y = if below { row.bottom() } else { row.top() };
+        ui.painter().hline(
+            row.x_range(),
+            line_y,
+            egui::Stroke::new(2.0, Color32::from_rgb(100, 160, 255)),
+        );
+        if ui.input(|i| i.pointer.any_released()) {
+            self.field_drag = None;
+            let to = if insert_at > drag.from_index {
+                insert_at - 1
+            } else {
+                insert_at
+            };
+            self.move_field(owner_class_id, drag.from_index, to);
+        }
+    }
+
+    /// Pointer color of the tree, or the broken-reference color when the pointer leads to a
+    /// class or enum that no longer exists
+    fn pointer_target_color(
+        &self,
+        target: Option<&PointerTarget>,
+        mem_ptr: *mut MemoryStructure,
+    ) -> Color32 {
+        let ms = unsafe { &*mem_ptr };
+        if target.is_some_and(|t| ms.is_target_missing(t)) {
+            self.tree_colors.broken_color()
+        } else {
+            self.tree_colors.pointer_color()
+        }
     }
 
     #[allow(clippy::too_many_arguments)]
@@ -228,15 +512,16 @@ impl ReClassGui {
         let def_id = *def_ids.get(idx).unwrap_or(&0);
         let ptr_target = fd_opt.and_then(|fd| fd.pointer_target.clone());
         if matches!(ptr_target, Some(PointerTarget::ClassId(_))) {
-            let offset_from_class = field.address.saturating_sub(instance_address);
+            let offset_from_class = self.display_offset(field.address, instance_address);
             let mut header = format!(
                 "+0x{:04X}  0x{:08X}    {}: Pointer",
                 offset_from_class,
                 field.address,
                 fd_opt.and_then(|fd| fd.name.clone()).unwrap_or_default()
             );
+            let mut label = String::new();
             if let Some(PointerTarget::ClassId(cid)) = &ptr_target {
-                let label = if let Some(ms) = unsafe { (mem_ptr).as_ref() } {
+                label = if let Some(ms) = unsafe { (mem_ptr).as_ref() } {
                     if let Some(cd) = ms.class_registry.get_by_id(*cid) {
                         cd.name.clone()
                     } else {
@@ -247,9 +532,18 @@ impl ReClassGui {
                 };
                 header.push_str(&format!(" -> {}", label));
             }
+            let mut validity = None;
             if let Some(h) = &handle {
-                if let Ok(ptr) = h.read_sized::<u64>(field.address) {
-                    header.push_str(&format!(" (-> 0x{ptr:016X})"));
+                if let Some(ptr) = read_field_pointer(h, field, self.byte_order()) {
+                    validity = Some(PointerValidity::of(h, ptr));
+                    let rtti = self.app.rtti_name(ptr);
+                    header.push_str(&class_pointer_header_suffix(
+                        h,
+                        &self.app.exports,
+                        ptr,
+                        &label,
+                        rtti.as_deref(),
+                    ));
                     if ptr != 0 {
                         match &ptr_target {
                             Some(PointerTarget::ClassId(cid)) => {
@@ -276,9 +570,18 @@ impl ReClassGui {
                     }
                 }
             }
-            let collapsing = egui::CollapsingHeader::new(header)
-                .default_open(false)
+            let live = field
+                .nested_instance
+                .as_ref()
+                .is_some_and(|nested| is_live_address(handle.as_ref(), nested.address));
+            let comment_tip = append_field_comment(&mut header, fd_opt);
+            let color = self.pointer_target_color(ptr_target.as_ref(), mem_ptr);
+            let header_text = pointer_header_text(ui, header, fd_opt, validity, color);
+            let collapsing = egui::CollapsingHeader::new(header_text)
+                .default_open(self.tree_header_default_open(ui, ("ptr_field", def_id, &path)))
+                .icon(liveness_icon(live))
                 .id_source(("ptr_field", def_id, path.clone()))
+                .open(self.tree_open_state(path.len() + 1))
                 .show(ui, |ui| {
                     ui.horizontal(|ui| {
                         ui.label("Name:");
@@ -293,10 +596,33 @@ impl ReClassGui {
                             true,
                         );
                     });
+                    // Runtime type from RTTI, highlighted when it differs from the declared class
+                    if let Some(nested) = field.nested_instance.as_ref() {
+                        if let Some(rtti_name) = self.app.rtti_name(nested.address) {
+                            let declared = unsafe { (mem_ptr).as_ref() }
+                                .and_then(|ms| ms.class_registry.get_by_id(nested.class_id))
+                                .map(|cd| cd.name.clone())
+                                .unwrap_or_default();
+                            let color = if rtti_matches(&rtti_name, &declared) {
+                                Color32::from_rgb(140, 200, 140)
+                            } else {
+                                Color32::from_rgb(230, 170, 90)
+                            };
+                            ui.horizontal(|ui| {
+                                ui.label("RTTI:");
+                                ui.colored_label(color, &rtti_name).on_hover_text(format!(
+                                    "Runtime class from the vtable; declared as {declared}"
+                                ));
+                            });
+                        }
+                    }
                     if let Some(nested) = field.nested_instance.as_mut() {
                         ui.separator();
-                        path.push(idx);
+                        path.push(def_id as usize);
+                        self.offset_hops
+                            .push(OffsetHop::Pointer(field.address - instance_address));
                         self.render_instance(ui, nested, handle.clone(), mem_ptr, path);
+                        self.offset_hops.pop();
                         path.pop();
                     }
                 });
@@ -308,13 +634,19 @@ impl ReClassGui {
                 address: field.address,
                 value_preview: None,
             };
+            if let Some(tip) = comment_tip {
+                collapsing.header_response.clone().on_hover_text(tip);
+            }
+            self.remember_tree_header(ui, &collapsing.header_response, def_id);
             if collapsing.header_response.clicked() {
                 self.update_selection_for_click(ui, instance_address, idx, def_ids, def_id);
             }
             self.context_menu_for_field(&collapsing.header_response, ctx);
         } else if matches!(ptr_target, Some(PointerTarget::Array { .. })) {
+            let mut live = false;
+            let mut validity = None;
             let mut header = {
-                let offset_from_class = field.address.saturating_sub(instance_address);
+                let offset_from_class = self.display_offset(field.address, instance_address);
                 let mut h = format!(
                     "+0x{:04X}  0x{:08X}    {}: Pointer -> Array",
                     offset_from_class,
@@ -322,8 +654,10 @@ impl ReClassGui {
                     fd_opt.and_then(|fd| fd.name.clone()).unwrap_or_default()
                 );
                 if let Some(hd) = &handle {
-                    if let Ok(ptr) = hd.read_sized::<u64>(field.address) {
-                        h.push_str(&format!(" (-> 0x{ptr:016X})"));
+                    if let Some(ptr) = read_field_pointer(hd, field, self.byte_order()) {
+                        h.push_str(&pointer_header_suffix(hd, &self.app.exports, ptr));
+                        live = is_live_address(Some(hd), ptr);
+                        validity = Some(PointerValidity::of(hd, ptr));
                     }
                 }
                 h
@@ -354,172 +688,74 @@ impl ReClassGui {
                         }
                     }
                     PointerTarget::Array { .. } => String::from("Array"),
+                    PointerTarget::Chain { .. } => String::from("Chain"),
                 };
                 header.push_str(&format!(" [{}] {}", length, desc));
             }
-            let collapsing = egui::CollapsingHeader::new(header)
-                .default_open(false)
+            let comment_tip = append_field_comment(&mut header, fd_opt);
+            let color = self.pointer_target_color(ptr_target.as_ref(), mem_ptr);
+            let header_text = pointer_header_text(ui, header, fd_opt, validity, color);
+            let collapsing = egui::CollapsingHeader::new(header_text)
+                .default_open(self.tree_header_default_open(ui, ("ptr_arr_field", def_id, &path)))
+                .icon(liveness_icon(live))
                 .id_source(("ptr_arr_field", def_id, path.clone()))
+                .open(self.tree_open_state(path.len() + 1))
                 .show(ui, |ui| {
                     if let (Some(hd), Some(PointerTarget::Array { element, length })) =
                         (handle.as_ref(), &ptr_target)
                     {
-                        if let Ok(ptr) = hd.read_sized::<u64>(field.address) {
+                        if let Some(ptr) = read_field_pointer(hd, field, self.byte_order()) {
                             if ptr != 0 {
                                 let len = *length as usize;
+                                let fmt = self.value_format();
+                                let list_id = ("ptr_arr_rows", def_id, path.clone());
                                 match element.as_ref() {
                                     PointerTarget::FieldType(t) => {
-                                        let elem_size = t.get_size();
-                                        for i in 0..len {
-                                            let elem_addr = ptr + (i as u64) * elem_size;
-                                            let val = match t {
-                                                FieldType::Hex64 => hd
-                                                    .read_sized::<u64>(elem_addr)
-                                                    .ok()
-                                                    .map(|v| format!("0x{v:016X}")),
-                                                FieldType::Hex32 => hd
-                                                    .read_sized::<u32>(elem_addr)
-                                                    .ok()
-                                                    .map(|v| format!("0x{v:08X}")),
-                                                FieldType::Hex16 => hd
-                                                    .read_sized::<u16>(elem_addr)
-                                                    .ok()
-                                                    .map(|v| format!("0x{v:04X}")),
-                                                FieldType::Hex8 => hd
-                                                    .read_sized::<u8>(elem_addr)
-                                                    .ok()
-                                                    .map(|v| format!("0x{v:02X}")),
-                                                FieldType::UInt64 => hd
-                                                    .read_sized::<u64>(elem_addr)
-                                                    .ok()
-                                                    .map(|v| v.to_string()),
-                                                FieldType::UInt32 => hd
-                                                    .read_sized::<u32>(elem_addr)
-                                                    .ok()
-                                                    .map(|v| v.to_string()),
-                                                FieldType::UInt16 => hd
-                                                    .read_sized::<u16>(elem_addr)
-                                                    .ok()
-                                                    .map(|v| v.to_string()),
-                                                FieldType::UInt8 => hd
-                                                    .read_sized::<u8>(elem_addr)
-                                                    .ok()
-                                                    .map(|v| v.to_string()),
-                                                FieldType::Int64 => hd
-                                                    .read_sized::<i64>(elem_addr)
-                                                    .ok()
-                                                    .map(|v| v.to_string()),
-                                                FieldType::Int32 => hd
-                                                    .read_sized::<i32>(elem_addr)
-                                                    .ok()
-                                                    .map(|v| v.to_string()),
-                                                FieldType::Int16 => hd
-                                                    .read_sized::<i16>(elem_addr)
-                                                    .ok()
-                                                    .map(|v| v.to_string()),
-                                                FieldType::Int8 => hd
-                                                    .read_sized::<i8>(elem_addr)
-                                                    .ok()
-                                                    .map(|v| v.to_string()),
-                                                FieldType::Bool => {
-                                                    hd.read_sized::<u8>(elem_addr).ok().map(|v| {
-                                                        if v != 0 {
-                                                            "true".to_string()
-                                                        } else {
-                                                            "false".to_string()
-                                                        }
-                                                    })
-                                                }
-                                                FieldType::Float => hd
-                                                    .read_sized::<f32>(elem_addr)
-                                                    .ok()
-                                                    .map(|v| format!("{v}")),
-                                                FieldType::Double => hd
-                                                    .read_sized::<f64>(elem_addr)
-                                                    .ok()
-                                                    .map(|v| format!("{v}")),
-                                                FieldType::Vector2
-                                                | FieldType::Vector3
-                                                | FieldType::Vector4 => {
-                                                    let lenb = t.get_size() as usize;
-                                                    let mut buf = vec![0u8; lenb];
-                                                    hd.read_slice(elem_addr, buf.as_mut_slice())
-                                                        .ok()
-                                                        .map(|_| {
-                                                            buf.iter()
-                                                                .map(|b| format!("{b:02X}"))
-                                                                .collect::<Vec<_>>()
-                                                                .join(" ")
-                                                        })
-                                                }
-                                                FieldType::Text => {
-                                                    hd.read_string(elem_addr, Some(32)).ok()
+                                        let elem_size = t.get_size_for(self.app.pointer_size);
+                                        let row_height =
+                                            ui.text_style_height(&egui::TextStyle::Monospace);
+                                        ScrollArea::vertical()
+                                            .id_source(list_id)
+                                            .max_height(ARRAY_VIEW_MAX_HEIGHT)
+                                            .show_rows(ui, row_height, len, |ui, rows| {
+                                                for i in rows {
+                                                    let elem_addr = ptr + (i as u64) * elem_size;
+                                                    let val =
+                                                        array_element_string(hd, t, elem_addr, fmt);
+                                                    ui.monospace(format!(
+                                                        "[{}] 0x{:08X}{}",
+                                                        i,
+                                                        elem_addr,
+                                                        val.map(|vv| format!(" = {vv}"))
+                                                            .unwrap_or_default()
+                                                    ));
                                                 }
-                                                FieldType::TextPointer | FieldType::Pointer => hd
-                                                    .read_sized::<u64>(elem_addr)
-                                                    .ok()
-                                                    .map(|v| format!("0x{v:016X}")),
-                                                _ => None,
-                                            };
-                                            ui.monospace(format!(
-                                                "[{}] 0x{:08X}{}",
-                                                i,
-                                                elem_addr,
-                                                val.map(|vv| format!(" = {vv}"))
-                                                    .unwrap_or_default()
-                                            ));
-                                        }
+                                            });
                                     }
                                     PointerTarget::EnumId(eid) => {
                                         if let Some(ms) = unsafe { (mem_ptr).as_ref() } {
                                             if let Some(ed) = ms.enum_registry.get_by_id(*eid) {
-                                                let sz = ed.default_size;
-                                                for i in 0..len {
-                                                    let elem_addr = ptr + (i as u64) * (sz as u64);
-                                                    let (raw_u64, raw_str) = match sz {
-                                                        1 => {
-                                                            let v = hd
-                                                                .read_sized::<u8>(elem_addr)
-                                                                .ok()
-                                                                .unwrap_or(0)
-                                                                as u64;
-                                                            (v, v.to_string())
+                                                let sz = ed.default_size as u64;
+                                                let row_height = ui
+                                                    .text_style_height(&egui::TextStyle::Monospace);
+                                                ScrollArea::vertical()
+                                                    .id_source(list_id)
+                                                    .max_height(ARRAY_VIEW_MAX_HEIGHT)
+                                                    .show_rows(ui, row_height, len, |ui, rows| {
+                                                        for i in rows {
+                                                            let elem_addr = ptr + (i as u64) * sz;
+                                                            let name = enum_element_string(
+                                                                hd,
+                                                                ed,
+                                                                elem_addr,
+                                                                fmt.endianness,
+                                                            );
+                                                            ui.monospace(format!(
+                                                                "[{}] 0x{:08X} = {}",
+                                                                i, elem_addr, name
+                                                            ));
                                                         }
-                                                        2 => {
-                                                            let v = hd
-                                                                .read_sized::<u16>(elem_addr)
-                                                                .ok()
-                                                                .unwrap_or(0)
-                                                                as u64;
-                                                            (v, v.to_string())
-                                                        }
-                                                        8 => {
-                                                            let v = hd
-                                                                .read_sized::<u64>(elem_addr)
-                                                                .ok()
-                                                                .unwrap_or(0);
-                                                            (v, v.to_string())
-                                                        }
-                                                        _ => {
-                                                            let v = hd
-                                                                .read_sized::<u32>(elem_addr)
-                                                                .ok()
-                                                                .unwrap_or(0)
-                                                                as u64;
-                                                            (v, v.to_string())
-                                                        }
-                                                    };
-                                                    let name = ed
-                                                        .variants
-                                                        .iter()
-                                                        .find(|v| (v.value as u64) == raw_u64)
-                                                        .map(|v| v.name.clone())
-                                                        .unwrap_or(raw_str);
-                                                    ui.monospace(format!(
-                                                        "[{}] 0x{:08X} = {}",
-                                                        i, elem_addr, name
-                                                    ));
-                                                }
+                                                    });
                                             }
                                         }
                                     }
@@ -528,43 +764,56 @@ impl ReClassGui {
                                             if let Some(class_def) =
                                                 ms.class_registry.get_by_id(*cid).cloned()
                                             {
-                                                let elem_size = class_def.total_size.max(1);
-                                                for i in 0..len {
-                                                    let elem_addr = ptr + (i as u64) * elem_size;
-                                                    let mut nested = ClassInstance::new(
-                                                        format!(
-                                                            "{}[{}]",
-                                                            fd_opt
-                                                                .and_then(|fd| fd.name.clone())
-                                                                .unwrap_or_default(),
-                                                            i
-                                                        ),
-                                                        elem_addr,
-                                                        class_def.clone(),
-                                                    );
-                                                    ms.bind_nested_for_instance(&mut nested);
-                                                    ui.separator();
-                                                    ui.label(
-                                                        RichText::new(format!(
-                                                            "Element [{}] @ 0x{:08X}",
-                                                            i, elem_addr
-                                                        ))
-                                                        .strong(),
-                                                    );
-                                                    path.push(idx);
-                                                    self.render_instance(
-                                                        ui,
-                                                        &mut nested,
-                                                        handle.clone(),
-                                                        mem_ptr,
-                                                        path,
-                                                    );
-                                                    path.pop();
-                                                }
+                                                let elem_size = ms.class_size(*cid).max(1);
+                                                let field_name = fd_opt
+                                                    .and_then(|fd| fd.name.clone())
+                                                    .unwrap_or_default();
+                                                let pointer_offset =
+                                                    field.address - instance_address;
+                                                self.show_virtual_rows(
+                                                    ui,
+                                                    list_id,
+                                                    len,
+                                                    |this, ui, i| {
+                                                        let elem_addr =
+                                                            ptr + (i as u64) * elem_size;
+                                                        let mut nested = ClassInstance::new(
+                                                            format!("{}[{}]", field_name, i),
+                                                            elem_addr,
+                                                            class_def.clone(),
+                                                        );
+                                                        ms.bind_nested_for_instance(&mut nested);
+                                                        ui.separator();
+                                                        ui.label(
+                                                            RichText::new(format!(
+                                                                "Element [{}] @ 0x{:08X}",
+                                                                i, elem_addr
+                                                            ))
+                                                            .strong(),
+                                                        );
+                                                        path.push(def_id as usize);
+                                                        path.push(i);
+                                                        this.offset_hops.extend([
+                                                            OffsetHop::Pointer(pointer_offset),
+                                                            OffsetHop::Embedded(elem_addr - ptr),
+                                                        ]);
+                                                        this.render_instance(
+                                                            ui,
+                                                            &mut nested,
+                                                            handle.clone(),
+                                                            mem_ptr,
+                                                            path,
+                                                        );
+                                                        this.offset_hops
+                                                            .truncate(this.offset_hops.len() - 2);
+                                                        path.pop();
+                                                        path.pop();
+                                                    },
+                                                );
                                             }
                                         }
                                     }
-                                    PointerTarget::Array { .. } => {}
+                                    PointerTarget::Array { .. } | PointerTarget::Chain { .. } => {}
                                 }
                             }
                         }
@@ -578,163 +827,84 @@ impl ReClassGui {
                 address: field.address,
                 value_preview: None,
             };
+            if let Some(tip) = comment_tip {
+                collapsing.header_response.clone().on_hover_text(tip);
+            }
+            self.remember_tree_header(ui, &collapsing.header_response, def_id);
             if collapsing.header_response.clicked() {
                 self.update_selection_for_click(ui, instance_address, idx, def_ids, def_id);
             }
             self.context_menu_for_field(&collapsing.header_response, ctx);
         } else {
             let inner = ui.horizontal(|ui| {
-                let offset_from_class = field.address.saturating_sub(instance_address);
+                let offset_from_class = self.display_offset(field.address, instance_address);
                 ui.monospace(format!(
                     "+0x{:04X}  0x{:08X}",
                     offset_from_class, field.address
                 ));
-                if let Some(name) = fd_opt.and_then(|fd| fd.name.clone()) {
-                    self.render_field_name_inline_editor(
-                        ui,
-                        mem_ptr,
-                        instance_class_id,
-                        instance_address,
-                        def_id,
-                        idx,
-                        Some(name),
-                        false,
-                    );
-                    let ptr_target = fd_opt.and_then(|fd| fd.pointer_target.clone());
-                    let type_label = match &ptr_target {
-                        Some(PointerTarget::FieldType(t)) => {
-                            format!(": {} -> {}", FieldType::Pointer, t)
-                        }
-                        Some(PointerTarget::ClassId(cid)) => {
-                            let label = if let Some(ms) = unsafe { (mem_ptr).as_ref() } {
-                                if let Some(cd) = ms.class_registry.get_by_id(*cid) {
-                                    cd.name.clone()
-                                } else {
-                                    format!("#{}", cid)
-                                }
-                            } else {
-                                format!("#{}", cid)
-                            };
-                            format!(": {} -> {}", FieldType::Pointer, label)
-                        }
-                        Some(PointerTarget::EnumId(eid)) => {
-                            let label = if let Some(ms) = unsafe { (mem_ptr).as_ref() } {
-                                if let Some(ed) = ms.enum_registry.get_by_id(*eid) {
-                                    ed.name.clone()
-                                } else {
-                                    format!("#{}", eid)
-                                }
-                            } else {
-                                format!("#{}", eid)
-                            };
-                            format!(": {} -> {}", FieldType::Pointer, label)
-                        }
-                        Some(PointerTarget::Array { element, length }) => match element.as_ref() {
-                            PointerTarget::FieldType(t) => {
-                                format!(": {} -> Array [{}] {}", FieldType::Pointer, length, t)
-                            }
-                            PointerTarget::EnumId(eid) => {
-                                let label = if let Some(ms) = unsafe { (mem_ptr).as_ref() } {
-                                    if let Some(ed) = ms.enum_registry.get_by_id(*eid) {
-                                        ed.name.clone()
-                                    } else {
-                                        format!("#{}", eid)
-                                    }
-                                } else {
-                                    format!("#{}", eid)
-                                };
-                                format!(": {} -> Array [{}] {}", FieldType::Pointer, length, label)
-                            }
-                            PointerTarget::ClassId(cid) => {
-                                let label = if let Some(ms) = unsafe { (mem_ptr).as_ref() } {
-                                    if let Some(cd) = ms.class_registry.get_by_id(*cid) {
-                                        cd.name.clone()
-                                    } else {
-                                        format!("#{}", cid)
-                                    }
-                                } else {
-                                    format!("#{}", cid)
-                                };
-                                format!(": {} -> Array [{}] {}", FieldType::Pointer, length, label)
-                            }
-                            PointerTarget::Array { .. } => {
-                                String::from(": Pointer -> Array [..] Array")
-                            }
-                        },
-                        None => format!(": {}", FieldType::Pointer),
-                    };
-                    ui.colored_label(Color32::from_rgb(170, 190, 255), type_label);
-                } else {
-                    let ptr_target = fd_opt.and_then(|fd| fd.pointer_target.clone());
-                    let type_label = match &ptr_target {
-                        Some(PointerTarget::FieldType(t)) => {
-                            format!("{} -> {}", FieldType::Pointer, t)
-                        }
-                        Some(PointerTarget::ClassId(cid)) => {
-                            let label = if let Some(ms) = unsafe { (mem_ptr).as_ref() } {
-                                if let Some(cd) = ms.class_registry.get_by_id(*cid) {
-                                    cd.name.clone()
-                                } else {
-                                    format!("#{}", cid)
-                                }
-                            } else {
-                                format!("#{}", cid)
-                            };
-                            format!("{} -> {}", FieldType::Pointer, label)
-                        }
-                        Some(PointerTarget::EnumId(eid)) => {
-                            let label = if let Some(ms) = unsafe { (mem_ptr).as_ref() } {
-                                if let Some(ed) = ms.enum_registry.get_by_id(*eid) {
-                                    ed.name.clone()
-                                } else {
-                                    format!("#{}", eid)
-                                }
-                            } else {
-                                format!("#{}", eid)
-                            };
-                            format!("{} -> {}", FieldType::Pointer, label)
-                        }
-                        Some(PointerTarget::Array { element, length }) => match element.as_ref() {
-                            PointerTarget::FieldType(t) => {
-                                format!("{} -> Array [{}] {}", FieldType::Pointer, length, t)
-                            }
-                            PointerTarget::EnumId(eid) => {
-                                let label = if let Some(ms) = unsafe { (mem_ptr).as_ref() } {
-                                    if let Some(ed) = ms.enum_registry.get_by_id(*eid) {
-                                        ed.name.clone()
-                                    } else {
-                                        format!("#{}", eid)
-                                    }
-                                } else {
-                                    format!("#{}", eid)
-                                };
-                                format!("{} -> Array [{}] {}", FieldType::Pointer, length, label)
-                            }
-                            PointerTarget::ClassId(cid) => {
-                                let label = if let Some(ms) = unsafe { (mem_ptr).as_ref() } {
-                                    if let Some(cd) = ms.class_registry.get_by_id(*cid) {
-                                        cd.name.clone()
-                                    } else {
-                                        format!("#{}", cid)
-                                    }
-                                } else {
-                                    format!("#{}", cid)
-                                };
-                                format!("{} -> Array [{}] {}", FieldType::Pointer, length, label)
-                            }
-                            PointerTarget::Array { .. } => {
-                                String::from("Pointer -> Array [..] Array")
-                            }
-                        },
-                        None => format!("{}", FieldType::Pointer),
-                    };
-                    ui.colored_label(Color32::from_rgb(170, 190, 255), type_label);
+                let name = fd_opt.and_then(|fd| fd.name.clone());
+                row_column(ui, NAME_COLUMN_WIDTH, |ui| {
+                    if let Some(name) = name {
+                        self.render_field_name_inline_editor(
+                            ui,
+                            mem_ptr,
+                            instance_class_id,
+                            instance_address,
+                            def_id,
+                            idx,
+                            Some(name),
+                            false,
+                        );
+                    }
+                });
+                let type_label =
+                    pointer_type_label(fd_opt.and_then(|fd| fd.pointer_target.as_ref()), unsafe {
+                        (mem_ptr).as_ref()
+                    });
+                let color = self.pointer_target_color(
+                    fd_opt.and_then(|fd| fd.pointer_target.as_ref()),
+                    mem_ptr,
+                );
+                row_column(ui, TYPE_COLUMN_WIDTH, |ui| {
+                    ui.colored_label(field_type_color(fd_opt, color), type_label);
+                });
+                row_column(ui, SIZE_COLUMN_WIDTH, |ui| {
+                    ui.label(RichText::new(format!("({} bytes)", self.app.pointer_size)).weak());
+                });
+                if let Some(validity) = handle.as_ref().and_then(|h| {
+                    read_field_pointer(h, field, self.byte_order())
+                        .map(|ptr| PointerValidity::of(h, ptr))
+                }) {
+                    pointer_validity_dot(ui, validity);
                 }
-                let display_size = FieldType::Pointer.get_size();
-                ui.label(RichText::new(format!(" ({} bytes)", display_size)).weak());
-                if let Some(val) = field_value_string(handle.clone(), field, &FieldType::Pointer) {
-                    ui.monospace(format!("= {val}"));
+                if let Some(PointerTarget::Chain { offsets }) =
+                    fd_opt.and_then(|fd| fd.pointer_target.as_ref())
+                {
+                    if let Some(h) = handle.as_ref() {
+                        ui.monospace(pointer_chain_value_string(
+                            h,
+                            &self.app.exports,
+                            field.address,
+                            offsets,
+                            self.byte_order(),
+                        ));
+                    }
+                } else if let Some(preview) = handle.as_ref().and_then(|h| {
+                    pointer_preview_string(
+                        h,
+                        &self.app.exports,
+                        field,
+                        fd_opt.and_then(|fd| fd.pointer_target.as_ref()),
+                        self.pointer_preview_depth,
+                        fd_opt.and_then(|fd| fd.text_encoding).unwrap_or_default(),
+                        self.value_format(),
+                    )
+                }) {
+                    ui.monospace(preview);
+                } else if handle.is_some() {
+                    unreadable_label(ui, "-> ??", field.address);
                 }
+                field_comment_label(ui, fd_opt);
             });
             let ctx = FieldCtx {
                 mem_ptr,
@@ -742,9 +912,16 @@ impl ReClassGui {
                 field_index: idx,
                 instance_address,
                 address: field.address,
-                value_preview: field_value_string(handle.clone(), field, &FieldType::Pointer),
+                value_preview: field_value_string(
+                    handle.clone(),
+                    field,
+                    &FieldType::Pointer,
+                    None,
+                    TextEncoding::default(),
+                    self.value_format(),
+                ),
             };
-            self.paint_row_and_handle_selection(
+            let _ = self.paint_row_and_handle_selection(
                 ui,
                 inner.response.rect,
                 idx,
@@ -753,6 +930,7 @@ impl ReClassGui {
                 &path.clone(),
                 instance_address,
                 def_ids,
+                self.changed_since_snapshot(field),
                 ctx,
             );
         }
@@ -772,9 +950,19 @@ impl ReClassGui {
         class_def: &ClassDefinition,
         def_ids: &[u64],
     ) {
-        let (header_text, len_u32) = if let Some(fd) = class_def.fields.get(idx) {
+        let (mut header_text, len_u32) = if let Some(fd) = class_def.fields.get(idx) {
             let len = fd.array_length.unwrap_or(0);
+            let pointee = fd.array_pointee_class().map(|cid| {
+                unsafe { &*mem_ptr }
+                    .class_registry
+                    .get(cid)
+                    .map(|cd| cd.name.clone())
+                    .unwrap_or_else(|| format!("#{cid}"))
+            });
             let desc = match &fd.array_element {
+                Some(PointerTarget::FieldType(_)) if pointee.is_some() => {
+                    format!("{}*", pointee.unwrap_or_default())
+                }
                 Some(PointerTarget::FieldType(t)) => format!("{}", t),
                 Some(PointerTarget::EnumId(eid)) => {
                     if let Some(ms) = unsafe { (mem_ptr).as_ref() } {
@@ -799,15 +987,21 @@ impl ReClassGui {
                     }
                 }
                 Some(PointerTarget::Array { .. }) => String::from("Array"),
+                Some(PointerTarget::Chain { .. }) => String::from("Chain"),
                 None => String::from("<elem?>"),
             };
+            let stride = fd
+                .array_stride
+                .map(|stride| format!(" stride 0x{stride:X}"))
+                .unwrap_or_default();
             (
                 format!(
-                    "0x{:08X}    {}: Array -> [{}] {}",
+                    "0x{:08X}    {}: Array -> [{}] {}{}",
                     field.address,
                     fd.name.clone().unwrap_or_default(),
                     len,
-                    desc
+                    desc,
+                    stride
                 ),
                 len,
             )
@@ -826,218 +1020,162 @@ impl ReClassGui {
             )
         };
 
+        let comment_tip = append_field_comment(&mut header_text, class_def.fields.get(idx));
         let def_id = *def_ids.get(idx).unwrap_or(&0);
-        let collapsing = egui::CollapsingHeader::new(header_text)
-            .default_open(false)
-            .id_source(("arr_field", def_id, path.clone()))
-            .show(ui, |ui| {
-                if let Some(fd) = class_def.fields.get(idx) {
-                    let len = len_u32 as usize;
-                    match &fd.array_element {
-                        Some(PointerTarget::FieldType(t)) => {
-                            if let Some(h) = &handle {
-                                let elem_size = t.get_size();
-                                for i in 0..len {
-                                    let elem_addr = field.address + (i as u64) * elem_size;
-                                    let offset_from_class =
-                                        elem_addr.saturating_sub(instance_address);
-                                    let val = match t {
-                                        FieldType::Hex64 => h
-                                            .read_sized::<u64>(elem_addr)
-                                            .ok()
-                                            .map(|v| format!("0x{v:016X}")),
-                                        FieldType::Hex32 => h
-                                            .read_sized::<u32>(elem_addr)
-                                            .ok()
-                                            .map(|v| format!("0x{v:08X}")),
-                                        FieldType::Hex16 => h
-                                            .read_sized::<u16>(elem_addr)
-                                            .ok()
-                                            .map(|v| format!("0x{v:04X}")),
-                                        FieldType::Hex8 => h
-                                            .read_sized::<u8>(elem_addr)
-                                            .ok()
-                                            .map(|v| format!("0x{v:02X}")),
-                                        FieldType::UInt64 => h
-                                            .read_sized::<u64>(elem_addr)
-                                            .ok()
-                                            .map(|v| v.to_string()),
-                                        FieldType::UInt32 => h
-                                            .read_sized::<u32>(elem_addr)
-                                            .ok()
-                                            .map(|v| v.to_string()),
-                                        FieldType::UInt16 => h
-                                            .read_sized::<u16>(elem_addr)
-                                            .ok()
-                                            .map(|v| v.to_string()),
-                                        FieldType::UInt8 => h
-                                            .read_sized::<u8>(elem_addr)
-                                            .ok()
-                                            .map(|v| v.to_string()),
-                                        FieldType::Int64 => h
-                                            .read_sized::<i64>(elem_addr)
-                                            .ok()
-                                            .map(|v| v.to_string()),
-                                        FieldType::Int32 => h
-                                            .read_sized::<i32>(elem_addr)
-                                            .ok()
-                                            .map(|v| v.to_string()),
-                                        FieldType::Int16 => h
-                                            .read_sized::<i16>(elem_addr)
-                                            .ok()
-                                            .map(|v| v.to_string()),
-                                        FieldType::Int8 => h
-                                            .read_sized::<i8>(elem_addr)
-                                            .ok()
-                                            .map(|v| v.to_string()),
-                                        FieldType::Bool => {
-                                            h.read_sized::<u8>(elem_addr).ok().map(|v| {
-                                                if v != 0 {
-                                                    "true".to_string()
-                                                } else {
-                                                    "false".to_string()
-                                                }
-                                            })
-                                        }
-                                        FieldType::Float => h
-                                            .read_sized::<f32>(elem_addr)
-                                            .ok()
-                                            .map(|v| format!("{v}")),
-                                        FieldType::Double => h
-                                            .read_sized::<f64>(elem_addr)
-                                            .ok()
-                                            .map(|v| format!("{v}")),
-                                        FieldType::Vector2
-                                        | FieldType::Vector3
-                                        | FieldType::Vector4 => {
-                                            let lenb = t.get_size() as usize;
-                                            let mut buf = vec![0u8; lenb];
-                                            h.read_slice(elem_addr, buf.as_mut_slice()).ok().map(
-                                                |_| {
-                                                    buf.iter()
-                                                        .map(|b| format!("{b:02X}"))
-                                                        .collect::<Vec<_>>()
-                                                        .join(" ")
-                                                },
-                                            )
-                                        }
-                                        FieldType::Text => h.read_string(elem_addr, Some(32)).ok(),
-                                        FieldType::TextPointer | FieldType::Pointer => h
-                                            .read_sized::<u64>(elem_addr)
-                                            .ok()
-                                            .map(|v| format!("0x{v:016X}")),
-                                        _ => None,
-                                    };
-                                    ui.monospace(format!(
-                                        "+0x{:04X}  0x{:08X}  [{}]{}",
-                                        offset_from_class,
-                                        elem_addr,
-                                        i,
-                                        val.map(|vv| format!(" = {vv}")).unwrap_or_default()
-                                    ));
-                                }
-                            }
-                        }
-                        Some(PointerTarget::EnumId(eid)) => {
-                            if let (Some(h), Some(ms)) =
-                                (handle.as_ref(), unsafe { (mem_ptr).as_ref() })
-                            {
-                                if let Some(ed) = ms.enum_registry.get_by_id(*eid) {
-                                    let sz = ed.default_size;
-                                    for i in 0..len {
-                                        let elem_addr = field.address + (i as u64) * (sz as u64);
+        let broken = class_def.fields.get(idx).is_some_and(|fd| {
+            let ms = unsafe { &*mem_ptr };
+            fd.array_element
+                .as_ref()
+                .is_some_and(|t| ms.is_target_missing(t))
+                || fd
+                    .array_pointee_class()
+                    .is_some_and(|cid| !ms.class_registry.contains(cid))
+        });
+        let collapsing = egui::CollapsingHeader::new(field_header_text(
+            header_text,
+            class_def.fields.get(idx),
+            broken.then(|| self.tree_colors.broken_color()),
+        ))
+        .default_open(self.tree_header_default_open(ui, ("arr_field", def_id, &path)))
+        .id_source(("arr_field", def_id, path.clone()))
+        .open(self.tree_open_state(path.len() + 1))
+        .show(ui, |ui| {
+            if let Some(fd) = class_def.fields.get(idx) {
+                let len = len_u32 as usize;
+                let fmt = self.value_format();
+                let order = fmt.endianness;
+                if let Some(cid) = fd.array_pointee_class() {
+                    self.render_class_pointer_elements(
+                        ui,
+                        instance_address,
+                        handle.clone(),
+                        mem_ptr,
+                        path,
+                        field.address,
+                        cid,
+                        len,
+                        fd.array_step(self.app.pointer_size),
+                        def_id,
+                    );
+                    return;
+                }
+                match &fd.array_element {
+                    Some(PointerTarget::FieldType(t)) => {
+                        if let Some(h) = &handle {
+                            let elem_size = fd.array_step(t.get_size_for(self.app.pointer_size));
+                            let base = field.address;
+                            let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+                            ScrollArea::vertical()
+                                .id_source(("arr_rows", def_id, path.clone()))
+                                .max_height(ARRAY_VIEW_MAX_HEIGHT)
+                                .show_rows(ui, row_height, len, |ui, rows| {
+                                    for i in rows {
+                                        let elem_addr = base + (i as u64) * elem_size;
                                         let offset_from_class =
-                                            elem_addr.saturating_sub(instance_address);
-                                        let (raw_u64, raw_str) = match sz {
-                                            1 => {
-                                                let v =
-                                                    h.read_sized::<u8>(elem_addr).ok().unwrap_or(0)
-                                                        as u64;
-                                                (v, v.to_string())
-                                            }
-                                            2 => {
-                                                let v = h
-                                                    .read_sized::<u16>(elem_addr)
-                                                    .ok()
-                                                    .unwrap_or(0)
-                                                    as u64;
-                                                (v, v.to_string())
-                                            }
-                                            8 => {
-                                                let v = h
-                                                    .read_sized::<u64>(elem_addr)
-                                                    .ok()
-                                                    .unwrap_or(0);
-                                                (v, v.to_string())
-                                            }
-                                            _ => {
-                                                let v = h
-                                                    .read_sized::<u32>(elem_addr)
-                                                    .ok()
-                                                    .unwrap_or(0)
-                                                    as u64;
-                                                (v, v.to_string())
-                                            }
-                                        };
-                                        let name = ed
-                                            .variants
-                                            .iter()
-                                            .find(|v| (v.value as u64) == raw_u64)
-                                            .map(|v| v.name.clone())
-                                            .unwrap_or(raw_str);
+                                            self.display_offset(elem_addr, instance_address);
+                                        let val = array_element_string(h, t, elem_addr, fmt);
                                         ui.monospace(format!(
-                                            "+0x{:04X}  0x{:08X}  [{}] = {}",
-                                            offset_from_class, elem_addr, i, name
+                                            "+0x{:04X}  0x{:08X}  [{}]{}",
+                                            offset_from_class,
+                                            elem_addr,
+                                            i,
+                                            val.map(|vv| format!(" = {vv}")).unwrap_or_default()
                                         ));
                                     }
-                                }
-                            }
+                                });
                         }
-                        Some(PointerTarget::Array { .. }) => {
-                            ui.monospace("<nested array rendering not supported>");
+                    }
+                    Some(PointerTarget::EnumId(eid)) => {
+                        if let (Some(h), Some(ms)) =
+                            (handle.as_ref(), unsafe { (mem_ptr).as_ref() })
+                        {
+                            if let Some(ed) = ms.enum_registry.get_by_id(*eid) {
+                                let sz = ed.default_size;
+                                let step = fd.array_step(sz as u64);
+                                let base = field.address;
+                                let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+                                ScrollArea::vertical()
+                                    .id_source(("arr_rows", def_id, path.clone()))
+                                    .max_height(ARRAY_VIEW_MAX_HEIGHT)
+                                    .show_rows(ui, row_height, len, |ui, rows| {
+                                        for i in rows {
+                                            let elem_addr = base + (i as u64) * step;
+                                            let offset_from_class =
+                                                self.display_offset(elem_addr, instance_address);
+                                            let name = enum_element_string(h, ed, elem_addr, order);
+                                            ui.monospace(format!(
+                                                "+0x{:04X}  0x{:08X}  [{}] = {}",
+                                                offset_from_class, elem_addr, i, name
+                                            ));
+                                        }
+                                    });
+                            }
                         }
-                        Some(PointerTarget::ClassId(cid)) => {
-                            if let Some(ms) = unsafe { (mem_ptr).as_mut() } {
-                                if let Some(class_def) = ms.class_registry.get_by_id(*cid).cloned()
-                                {
-                                    let elem_size = class_def.total_size.max(1);
-                                    for i in 0..len {
-                                        let elem_addr = field.address + (i as u64) * elem_size;
-                                        let mut nested = ClassInstance::new(
-                                            format!("{}[{}]", class_def.name, i),
-                                            elem_addr,
-                                            class_def.clone(),
-                                        );
-                                        ms.bind_nested_for_instance(&mut nested);
-                                        ui.separator();
-                                        ui.label(
+                    }
+                    Some(PointerTarget::Array { .. }) | Some(PointerTarget::Chain { .. }) => {
+                        ui.monospace("<nested array rendering not supported>");
+                    }
+                    Some(PointerTarget::ClassId(cid)) => {
+                        if let Some(ms) = unsafe { (mem_ptr).as_mut() } {
+                            if let Some(class_def) = ms.class_registry.get_by_id(*cid).cloned() {
+                                let elem_size = fd.array_step(
+                                    ms.array_element_size(fd, &mut vec![instance_class_id])
+                                        .max(1),
+                                );
+                                let base = field.address;
+                                let list_id = ("arr_rows", def_id, path.clone());
+                                self.show_virtual_rows(ui, list_id, len, |this, ui, i| {
+                                    let elem_addr = base + (i as u64) * elem_size;
+                                    let mut nested = ClassInstance::new(
+                                        format!("{}[{}]", class_def.name, i),
+                                        elem_addr,
+                                        class_def.clone(),
+                                    );
+                                    ms.bind_nested_for_instance(&mut nested);
+                                    ui.separator();
+                                    let element_label = format!("{}[{}]", class_def.name, i);
+                                    ui.add(
+                                        egui::Label::new(
                                             RichText::new(format!(
                                                 "Element [{}] @ 0x{:08X}",
                                                 i, elem_addr
                                             ))
                                             .strong(),
-                                        );
-                                        path.push(idx);
-                                        path.push(i);
-                                        self.render_instance(
+                                        )
+                                        .sense(egui::Sense::click()),
+                                    )
+                                    .context_menu(|ui| {
+                                        this.array_element_menu(
                                             ui,
-                                            &mut nested,
-                                            handle.clone(),
-                                            mem_ptr,
-                                            path,
+                                            elem_addr,
+                                            class_def.id,
+                                            element_label,
                                         );
-                                        path.pop();
-                                        path.pop();
-                                    }
-                                }
+                                    });
+                                    path.push(def_id as usize);
+                                    path.push(i);
+                                    this.offset_hops
+                                        .push(OffsetHop::Embedded(elem_addr - instance_address));
+                                    this.render_instance_block(
+                                        ui,
+                                        &mut nested,
+                                        handle.clone(),
+                                        mem_ptr,
+                                        path,
+                                    );
+                                    this.offset_hops.pop();
+                                    path.pop();
+                                    path.pop();
+                                });
                             }
                         }
-                        None => {
-                            ui.monospace("<no element type set>");
-                        }
+                    }
+                    None => {
+                        ui.monospace("<no element type set>");
                     }
                 }
-            });
+            }
+        });
 
         let ctx = FieldCtx {
             mem_ptr,
@@ -1047,12 +1185,118 @@ impl ReClassGui {
             address: field.address,
             value_preview: None,
         };
+        if let Some(tip) = comment_tip {
+            collapsing.header_response.clone().on_hover_text(tip);
+        }
+        // Length editor on top of the right end of the header row
+        let header_rect = collapsing.header_response.rect;
+        let editor_rect = egui::Rect::from_min_max(
+            egui::pos2(header_rect.right() - 120.0, header_rect.top()),
+            header_rect.right_bottom(),
+        );
+        let mut editor_ui = ui.child_ui(
+            editor_rect,
+            egui::Layout::right_to_left(egui::Align::Center),
+        );
+        let mut length = len_u32;
+        if editor_ui
+            .add_enabled(
+                !self.structure_locked,
+                egui::DragValue::new(&mut length)
+                    .clamp_range(0..=ARRAY_MAX_LENGTH)
+                    .prefix("len "),
+            )
+            .on_hover_text("Number of elements")
+            .changed()
+        {
+            self.set_array_length(&ctx, length);
+        }
+        self.remember_tree_header(ui, &collapsing.header_response, def_id);
         if collapsing.header_response.clicked() {
             self.update_selection_for_click(ui, instance_address, idx, def_ids, def_id);
         }
         self.context_menu_for_field(&collapsing.header_response, ctx);
     }
 
+    /// Elements of an array of pointers to a class: the pointer value of every slot, `step`
+    /// bytes apart, expandable into the instance it points to
+    #[allow(clippy::too_many_arguments)]
+    fn render_class_pointer_elements(
+        &mut self,
+        ui: &mut Ui,
+        instance_address: u64,
+        handle: Option<Arc<AppHandle>>,
+        mem_ptr: *mut MemoryStructure,
+        path: &mut Vec<usize>,
+        base: u64,
+        class_id: u64,
+        len: usize,
+        step: u64,
+        def_id: u64,
+    ) {
+        let Some(ms) = (unsafe { mem_ptr.as_ref() }) else {
+            return;
+        };
+        let Some(class_def) = ms.class_registry.get_by_id(class_id).cloned() else {
+            ui.monospace("<element class missing>");
+            return;
+        };
+        let list_id = ("arr_rows", def_id, path.clone());
+        self.show_virtual_rows(ui, list_id, len, |this, ui, i| {
+            let elem_addr = base + (i as u64) * step;
+            let offset_from_class = this.display_offset(elem_addr, instance_address);
+            let target = handle
+                .as_ref()
+                .and_then(|h| read_pointer_value(h, elem_addr, this.byte_order()));
+            let label = format!(
+                "+0x{:04X}  0x{:08X}  [{}] = {}",
+                offset_from_class,
+                elem_addr,
+                i,
+                target
+                    .map(|v| format!("0x{v:016X}"))
+                    .unwrap_or_else(|| "??".to_string())
+            );
+            let Some(target) = target.filter(|v| *v != 0) else {
+                ui.monospace(label);
+                return;
+            };
+            let header = egui::CollapsingHeader::new(
+                RichText::new(format!("{label} -> {}", class_def.name)).monospace(),
+            )
+            .default_open(this.tree_header_default_open(ui, ("arr_ptr_elem", def_id, &path, i)))
+            .icon(liveness_icon(is_live_address(handle.as_ref(), target)))
+            .id_source(("arr_ptr_elem", def_id, path.clone(), i))
+            .open(this.tree_open_state(path.len() + 2))
+            .show(ui, |ui| {
+                let mut nested = ClassInstance::new(
+                    format!("{}[{}]", class_def.name, i),
+                    target,
+                    class_def.clone(),
+                );
+                ms.bind_nested_for_instance(&mut nested);
+                path.push(def_id as usize);
+                path.push(i);
+                this.offset_hops
+                    .push(OffsetHop::Pointer(elem_addr - instance_address));
+                this.render_instance(ui, &mut nested, handle.clone(), mem_ptr, path);
+                this.offset_hops.pop();
+                path.pop();
+                path.pop();
+            })
+            .header_response;
+            this.remember_tree_header(ui, &header, def_id);
+            header.context_menu(|ui| {
+                this.array_element_menu(
+                    ui,
+                    target,
+                    class_def.id,
+                    format!("{}[{}]", class_def.name, i),
+                );
+            });
+        });
+    }
+
     #[allow(clippy::too_many_arguments)]
     fn render_class_instance_field(
         &mut self,
@@ -1068,6 +1312,11 @@ impl ReClassGui {
         def_ids: &[u64],
     ) {
         let fd_opt = class_def.fields.get(idx);
+        let broken = field.nested_instance.as_ref().is_some_and(|nested| {
+            !unsafe { &*mem_ptr }
+                .class_registry
+                .contains(nested.class_id)
+        });
         let (fname_display, cname_display) = if let Some(nested) = &field.nested_instance {
             (
                 fd_opt.and_then(|fd| fd.name.clone()).unwrap_or_default(),
@@ -1083,14 +1332,21 @@ impl ReClassGui {
                 "ClassInstance".to_string(),
             )
         };
-        let header = format!(
+        let mut header = format!(
             "0x{:08X}    {}: {}    [ClassInstance]",
             field.address, fname_display, cname_display
         );
+        let comment_tip = append_field_comment(&mut header, fd_opt);
         let def_id = *def_ids.get(idx).unwrap_or(&0);
-        let collapsing = egui::CollapsingHeader::new(header)
-            .default_open(false)
+        let collapsing = egui::CollapsingHeader::new(field_header_text(
+            header,
+            fd_opt,
+            broken.then(|| self.tree_colors.broken_color()),
+        ))
+        .default_open(self.tree_header_default_open(ui, ("ci_field", def_id, &path)))
+            .icon(liveness_icon(is_live_address(handle.as_ref(), field.address)))
             .id_source(("ci_field", def_id, path.clone()))
+            .open(self.tree_open_state(path.len() + 1))
             .show(ui, |ui| {
                 ui.horizontal(|ui| {
                     ui.label("Name:");
@@ -1110,6 +1366,14 @@ impl ReClassGui {
                         let current_type = nested.class_id;
                         let available = unsafe { (*mem_ptr).class_registry.get_class_ids() };
                         let mut selected = self.class_type_buffers.get(&tkey).cloned().unwrap_or(current_type);
+                        if self.structure_locked {
+                            ui.label(unsafe { &*mem_ptr }
+                                .class_registry
+                                .get(current_type)
+                                .map(|d| d.name.clone())
+                                .unwrap_or_else(|| current_type.to_string()));
+                            return;
+                        }
                         egui::ComboBox::from_id_source(("ci_type_combo", tkey))
                             .selected_text(selected.to_string())
                             .show_ui(ui, |ui| {
@@ -1140,8 +1404,11 @@ impl ReClassGui {
                 });
                 if let Some(nested) = field.nested_instance.as_mut() {
                     ui.separator();
-                    path.push(idx);
-                    self.render_instance(ui, nested, handle.clone(), mem_ptr, path);
+                    path.push(def_id as usize);
+                    self.offset_hops
+                        .push(OffsetHop::Embedded(field.address - instance_address));
+                    self.render_instance_fields(ui, nested, handle.clone(), mem_ptr, path);
+                    self.offset_hops.pop();
                     path.pop();
                 }
             });
@@ -1153,6 +1420,10 @@ impl ReClassGui {
             address: field.address,
             value_preview: None,
         };
+        if let Some(tip) = comment_tip {
+            collapsing.header_response.clone().on_hover_text(tip);
+        }
+        self.remember_tree_header(ui, &collapsing.header_response, def_id);
         if collapsing.header_response.clicked() {
             self.update_selection_for_click(ui, instance_address, idx, def_ids, def_id);
         }
@@ -1174,59 +1445,128 @@ impl ReClassGui {
         def_ids: &[u64],
         field_type: &FieldType,
     ) {
+        let enum_def = field_enum_definition(class_def, idx, field_type, mem_ptr);
         let inner = ui.horizontal(|ui| {
-            let offset_from_class = field.address.saturating_sub(instance_address);
+            let offset_from_class = self.display_offset(field.address, instance_address);
             ui.monospace(format!(
                 "+0x{:04X}  0x{:08X}",
                 offset_from_class, field.address
             ));
             let def_id = class_def.fields.get(idx).map(|fd| fd.id).unwrap_or(0);
-            if let Some(name) = class_def.fields.get(idx).and_then(|fd| fd.name.clone()) {
-                self.render_field_name_inline_editor(
-                    ui,
-                    mem_ptr,
-                    instance_class_id,
-                    instance_address,
-                    def_id,
-                    idx,
-                    Some(name),
-                    false,
-                );
-                let enum_suffix = if let Some(ms) = unsafe { (mem_ptr).as_ref() } {
-                    enum_suffix_for_field(class_def, field, ms)
-                } else {
-                    String::new()
-                };
-                ui.colored_label(
-                    Color32::from_rgb(170, 190, 255),
-                    format!(": {}{}", field_type, enum_suffix),
-                );
+            let name = class_def.fields.get(idx).and_then(|fd| fd.name.clone());
+            row_column(ui, NAME_COLUMN_WIDTH, |ui| {
+                if let Some(name) = name {
+                    self.render_field_name_inline_editor(
+                        ui,
+                        mem_ptr,
+                        instance_class_id,
+                        instance_address,
+                        def_id,
+                        idx,
+                        Some(name),
+                        false,
+                    );
+                }
+            });
+            let enum_suffix = if let Some(ms) = unsafe { (mem_ptr).as_ref() } {
+                enum_suffix_for_field(class_def, field, ms)
             } else {
-                let enum_suffix = if let Some(ms) = unsafe { (mem_ptr).as_ref() } {
-                    enum_suffix_for_field(class_def, field, ms)
-                } else {
-                    String::new()
-                };
+                String::new()
+            };
+            let color = match class_def.fields.get(idx) {
+                Some(fd) if fd.field_type == FieldType::Enum => {
+                    let ms = unsafe { &*mem_ptr };
+                    match fd.enum_id.filter(|eid| ms.enum_registry.contains(*eid)) {
+                        Some(_) => self.tree_colors.enum_color(),
+                        None => self.tree_colors.broken_color(),
+                    }
+                }
+                _ => self.tree_colors.field_color(),
+            };
+            row_column(ui, TYPE_COLUMN_WIDTH, |ui| {
                 ui.colored_label(
-                    Color32::from_rgb(170, 190, 255),
+                    field_type_color(class_def.fields.get(idx), color),
                     format!("{}{}", field_type, enum_suffix),
                 );
-            }
+            });
             let display_size = self.compute_display_size_for(field_type, class_def, field, mem_ptr);
-            ui.label(RichText::new(format!(" ({} bytes)", display_size)).weak());
-            let value_str = if matches!(field_type, FieldType::Enum) {
-                if let (Some(h), Some(ms)) = (handle.as_ref(), unsafe { (mem_ptr).as_ref() }) {
-                    enum_value_string(h, class_def, field, ms)
+            row_column(ui, SIZE_COLUMN_WIDTH, |ui| {
+                ui.label(RichText::new(format!("({} bytes)", display_size)).weak());
+            });
+            let value_str =
+                self.simple_field_value(handle.as_ref(), class_def, field, field_type, mem_ptr);
+            let key = FieldKey {
+                instance_address,
+                field_def_id: def_id,
+            };
+            let value = if let Some(mut buf) = self.value_edit_buffers.get(&key).cloned() {
+                ui.monospace("=");
+                let resp = text_edit_autowidth(ui, &mut buf);
+                if self.value_edit_focus_pending {
+                    resp.request_focus();
+                    self.value_edit_focus_pending = false;
+                }
+                if resp.lost_focus() {
+                    if ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        let bytes = match &enum_def {
+                            Some(edef) => parse_enum_value_bytes(edef, &buf),
+                            None => parse_value_bytes(
+                                field_type,
+                                &buf,
+                                class_def.fields.get(idx).and_then(|fd| fd.display_base),
+                            ),
+                        };
+                        match (handle.as_ref(), bytes) {
+                            (Some(h), Some(bytes)) => {
+                                self.write_field_value(
+                                    h,
+                                    field.address,
+                                    &to_target_order(bytes, self.byte_order()),
+                                );
+                                self.value_edit_buffers.remove(&key);
+                            }
+                            _ => {
+                                // Keep editing on invalid or out-of-range input
+                                self.value_edit_buffers.insert(key, buf);
+                                self.value_edit_focus_pending = true;
+                            }
+                        }
+                    } else {
+                        self.value_edit_buffers.remove(&key);
+                    }
                 } else {
-                    None
+                    self.value_edit_buffers.insert(key, buf);
                 }
+                None
             } else {
-                field_value_string(handle.clone(), field, field_type)
+                match value_str {
+                    FieldValue::Value(val) => {
+                        // Enum values open a variant picker on click
+                        let resp = if enum_def.is_some() {
+                            ui.monospace(format!("= {val} ▾"))
+                        } else {
+                            ui.monospace(format!("= {val}"))
+                        };
+                        if let Some(h) = handle.as_ref() {
+                            if *field_type == FieldType::Matrix4x4 {
+                                matrix_tooltip(ui, &resp, h, field, self.value_format());
+                            } else {
+                                value_bytes_tooltip(ui, &resp, h, field, display_size);
+                            }
+                        }
+                        Some((resp, val))
+                    }
+                    FieldValue::ReadFailed => {
+                        unreadable_label(ui, "= ??", field.address);
+                        None
+                    }
+                    FieldValue::NotAttached => None,
+                }
             };
-            if let Some(val) = value_str {
-                ui.monospace(format!("= {val}"));
-            }
+            field_comment_label(ui, class_def.fields.get(idx));
+            value
         });
+        self.field_row_height = inner.response.rect.height() + ui.spacing().item_spacing.y;
         let def_id = *def_ids.get(idx).unwrap_or(&0);
         let ctx = FieldCtx {
             mem_ptr,
@@ -1234,19 +1574,123 @@ impl ReClassGui {
             field_index: idx,
             instance_address,
             address: field.address,
-            value_preview: field_value_string(handle.clone(), field, field_type),
+            value_preview: self
+                .simple_field_value(handle.as_ref(), class_def, field, field_type, mem_ptr)
+                .into_value(),
         };
-        self.paint_row_and_handle_selection(
+        let row_resp = self.paint_row_and_handle_selection(
             ui,
             inner.response.rect,
             idx,
             "row_field",
             def_id,
-            &path.to_owned(),
+            path,
             instance_address,
             def_ids,
+            self.changed_since_snapshot(field),
             ctx,
         );
+        // Double-click on the "= value" part starts inline editing
+        if let Some((value_resp, val)) = inner.inner {
+            let on_value = row_resp
+                .interact_pointer_pos()
+                .map(|pos| value_resp.rect.contains(pos))
+                .unwrap_or(false);
+            if let (Some(h), Some(edef)) = (handle.as_ref(), enum_def.as_ref()) {
+                let key = FieldKey {
+                    instance_address,
+                    field_def_id: def_id,
+                };
+                self.enum_value_popup(
+                    ui,
+                    &value_resp,
+                    row_resp.clicked() && on_value,
+                    h,
+                    field,
+                    edef,
+                    key,
+                );
+            }
+            if row_resp.double_clicked()
+                && on_value
+                && handle.is_some()
+                && is_value_editable(field_type)
+            {
+                let key = FieldKey {
+                    instance_address,
+                    field_def_id: def_id,
+                };
+                // Floats are edited in full, not rounded to the display precision
+                let text = match field_type {
+                    FieldType::Float | FieldType::Double => field_value_string(
+                        handle.clone(),
+                        field,
+                        field_type,
+                        None,
+                        TextEncoding::default(),
+                        self.value_format().exact(),
+                    )
+                    .unwrap_or(val),
+                    _ => val,
+                };
+                self.value_edit_buffers.insert(key, text);
+                self.value_edit_focus_pending = true;
+            }
+        }
+    }
+
+    /// Variant picker below an enum value. Picking a variant writes its value (as wide as the
+    /// enum's backing integer) to the field; "Raw value…" starts editing the number instead.
+    #[allow(clippy::too_many_arguments)]
+    fn enum_value_popup(
+        &mut self,
+        ui: &mut Ui,
+        value_resp: &egui::Response,
+        clicked: bool,
+        handle: &AppHandle,
+        field: &crate::memory::MemoryField,
+        edef: &EnumDefinition,
+        key: FieldKey,
+    ) {
+        let popup_id = ui.make_persistent_id(("enum_value_popup", key));
+        let was_open = ui.memory(|m| m.is_popup_open(popup_id));
+        let current =
+            enum_raw_value(handle, field, edef, self.byte_order()).map(|raw| edef.value_of(raw));
+        let mut write: Option<i64> = None;
+        let mut edit_raw = false;
+        egui::popup_below_widget(ui, popup_id, value_resp, |ui| {
+            ui.set_min_width(160.0);
+            for variant in &edef.variants {
+                if ui
+                    .selectable_label(current == Some(variant.value), &variant.name)
+                    .clicked()
+                {
+                    write = Some(variant.value);
+                }
+            }
+            if !edef.variants.is_empty() {
+                ui.separator();
+            }
+            if ui.button("Raw value…").clicked() {
+                edit_raw = true;
+            }
+        });
+        if let Some(bytes) = write.and_then(|value| edef.encode_value(value)) {
+            self.write_field_value(
+                handle,
+                field.address,
+                &to_target_order(bytes, self.byte_order()),
+            );
+        }
+        if edit_raw {
+            let text = current.map(|v| v.to_string()).unwrap_or_default();
+            self.value_edit_buffers.insert(key, text);
+            self.value_edit_focus_pending = true;
+        }
+        // The popup closes on any click, so only a click while it was closed opens it
+        if clicked && !was_open {
+            ui.memory_mut(|m| m.open_popup(popup_id));
+        }
     }
 
     fn update_selection_for_click(
@@ -1341,6 +1785,51 @@ impl ReClassGui {
         }
     }
 
+    /// Rows of at least the field row height that fit between the cursor and the bottom of the
+    /// view; every row when no row was measured yet
+    fn rows_left_in_view(&self, ui: &Ui) -> usize {
+        if self.field_row_height <= 0.0 {
+            return usize::MAX;
+        }
+        let left = (ui.clip_rect().bottom() - ui.cursor().top()).max(0.0);
+        (left / self.field_row_height).ceil() as usize + 1
+    }
+
+    fn is_next_row_visible(&self, ui: &Ui) -> bool {
+        if self.field_row_height <= 0.0 {
+            return true;
+        }
+        let rect = egui::Rect::from_min_size(
+            ui.cursor().min,
+            egui::vec2(ui.available_width(), self.field_row_height),
+        );
+        ui.is_rect_visible(rect)
+    }
+
+    /// Scrollable tree of a throwaway instance of `def` at `address` that is not part of the
+    /// root tree, as shown by the compare panel and the Quick View window
+    pub(super) fn render_detached_instance(
+        &mut self,
+        ui: &mut Ui,
+        label: String,
+        address: u64,
+        def: ClassDefinition,
+        mem_ptr: *mut MemoryStructure,
+        scroll_id: &str,
+    ) {
+        let handle = self.app.handle.clone();
+        let mut instance = ClassInstance::new(label, address, def);
+        unsafe { &*mem_ptr }.bind_nested_for_instance(&mut instance);
+        ScrollArea::vertical().id_source(scroll_id).show(ui, |ui| {
+            let path: &mut Vec<usize> = &mut Vec::new();
+            self.render_instance(ui, &mut instance, handle, mem_ptr, path);
+        });
+    }
+
+    /// Render an instance that starts a struct of its own (the root or a pointer target);
+    /// offsets relative to the root count from its start. `path` holds the definition ids of
+    /// the fields leading here (and array element indices), so expanded headers stay open
+    /// when fields are reordered.
     pub(super) fn render_instance(
         &mut self,
         ui: &mut Ui,
@@ -1349,16 +1838,135 @@ impl ReClassGui {
         mem_ptr: *mut MemoryStructure,
         path: &mut Vec<usize>,
     ) {
-        let class_def = unsafe { &*mem_ptr }
+        let outer_base = std::mem::replace(&mut self.offset_base, instance.address);
+        self.render_instance_block(ui, instance, handle, mem_ptr, path);
+        self.offset_base = outer_base;
+    }
+
+    /// Read and render an instance, keeping the offset base of the enclosing struct (used for
+    /// elements of embedded arrays)
+    fn render_instance_block(
+        &mut self,
+        ui: &mut Ui,
+        instance: &mut ClassInstance,
+        handle: Option<Arc<AppHandle>>,
+        mem_ptr: *mut MemoryStructure,
+        path: &mut Vec<usize>,
+    ) {
+        // Self-referencing classes (lists, trees) could otherwise be followed without end
+        if self.render_depth >= self.max_render_depth {
+            ui.label(
+                RichText::new("… (max depth reached, increase in settings)")
+                    .weak()
+                    .italics(),
+            );
+            return;
+        }
+        // Rows are read as they come into view; field formatters use those bytes where possible
+        instance.apply_block(None);
+        self.render_depth += 1;
+        self.render_instance_fields(ui, instance, handle, mem_ptr, path);
+        self.render_depth -= 1;
+    }
+
+    /// Collapsible "[base: Name]" section with the fields of the base class, above the fields
+    /// of the derived class
+    fn render_base_section(
+        &mut self,
+        ui: &mut Ui,
+        base: &mut ClassInstance,
+        owner_class_id: u64,
+        handle: Option<Arc<AppHandle>>,
+        mem_ptr: *mut MemoryStructure,
+        path: &mut Vec<usize>,
+    ) {
+        let name = unsafe { &*mem_ptr }
+            .class_registry
+            .get(base.class_id)
+            .map(|d| d.name.clone())
+            .unwrap_or_else(|| format!("#{}", base.class_id));
+        let header = format!(
+            "+0x{:04X}  0x{:08X}    [base: {name}]    (size 0x{:X})",
+            self.display_offset(base.address, base.address),
+            base.address,
+            base.total_size
+        );
+        let collapsing = egui::CollapsingHeader::new(RichText::new(header).monospace())
+            .default_open(self.tree_header_default_open(ui, ("base", owner_class_id, &path)))
+            .id_source(("base", owner_class_id, path.clone()))
+            .open(self.tree_open_state(path.len() + 1))
+            .show(ui, |ui| {
+                // The base has no field index in the derived class
+                path.push(usize::MAX);
+                self.render_instance_fields(ui, base, handle.clone(), mem_ptr, path);
+                path.pop();
+            });
+        self.remember_tree_header(ui, &collapsing.header_response, owner_class_id);
+        let (class_id, address) = (base.class_id, base.address);
+        collapsing.header_response.context_menu(|ui| {
+            if ui.button("Go to class definition").clicked() {
+                self.go_to_class_definition(class_id, Some(address));
+                ui.close_menu();
+            }
+        });
+    }
+
+    /// Offset shown in a row's `+0x` column: from the owning instance, or with "Offsets
+    /// relative to Root" from the start of the struct it is embedded in
+    fn display_offset(&self, address: u64, instance_address: u64) -> u64 {
+        let base = if self.offsets_from_root {
+            self.offset_base
+        } else {
+            instance_address
+        };
+        address.saturating_sub(base)
+    }
+
+    /// Render the fields of an instance. The first visible row without bytes reads the rows up to
+    /// the bottom of the view in one go; embedded instances receive their bytes from the
+    /// enclosing instance, or read their own rows the same way.
+    fn render_instance_fields(
+        &mut self,
+        ui: &mut Ui,
+        instance: &mut ClassInstance,
+        handle: Option<Arc<AppHandle>>,
+        mem_ptr: *mut MemoryStructure,
+        path: &mut Vec<usize>,
+    ) {
+        // The class may have been removed while a stale nested instance still refers to it
+        let Some(class_def) = unsafe { &*mem_ptr }
             .class_registry
             .get_by_id(instance.class_id)
-            .unwrap();
+        else {
+            ui.colored_label(
+                self.tree_colors.broken_color(),
+                format!("⚠ missing class #{}", instance.class_id),
+            );
+            return;
+        };
         let def_ids: Vec<u64> = class_def.fields.iter().map(|fd| fd.id).collect();
-        for (idx, field) in instance.fields.iter_mut().enumerate() {
+        // A field picked in the field search is revealed once its row has been laid out
+        let reveal = self
+            .field_search
+            .reveal
+            .filter(|(class_id, _)| path.is_empty() && *class_id == instance.class_id)
+            .and_then(|(_, def_id)| def_ids.iter().position(|id| *id == def_id));
+        if let Some(base) = instance.base.as_deref_mut() {
+            self.render_base_section(ui, base, instance.class_id, handle.clone(), mem_ptr, path);
+        }
+        for idx in 0..instance.fields.len() {
+            let row_top = ui.cursor().top();
             let fd_opt = class_def.fields.get(idx);
             let field_type = fd_opt
                 .map(|fd| fd.field_type.clone())
                 .unwrap_or(FieldType::Hex8);
+            if let Some(h) = &handle {
+                if instance.fields[idx].data.is_none() && self.is_next_row_visible(ui) {
+                    let rows = self.rows_left_in_view(ui);
+                    instance.read_fields(h, idx..idx.saturating_add(rows));
+                }
+            }
+            let field = &mut instance.fields[idx];
             match field_type {
                 FieldType::Pointer => self.render_pointer_field(
                     ui,
@@ -1396,6 +2004,13 @@ impl ReClassGui {
                     class_def,
                     &def_ids,
                 ),
+                // Rows scrolled out of view only reserve their space; nothing is read for them
+                _ if !self.is_next_row_visible(ui) => {
+                    ui.allocate_space(egui::vec2(
+                        1.0,
+                        self.field_row_height - ui.spacing().item_spacing.y,
+                    ));
+                }
                 _ => self.render_simple_field(
                     ui,
                     instance.address,
@@ -1410,6 +2025,52 @@ impl ReClassGui {
                     &field_type,
                 ),
             }
+            if reveal == Some(idx) {
+                let rect = egui::Rect::from_x_y_ranges(
+                    ui.max_rect().x_range(),
+                    row_top..=ui.cursor().top(),
+                );
+                ui.scroll_to_rect(rect, Some(egui::Align::Center));
+            }
+        }
+        if path.is_empty() {
+            // Done, or the field no longer exists in the root class
+            self.field_search.reveal = None;
+        }
+    }
+}
+
+/// Type column text of a pointer row, e.g. `Pointer -> Entity` or `Pointer -> Array [4] float`
+fn pointer_type_label(target: Option<&PointerTarget>, ms: Option<&MemoryStructure>) -> String {
+    let class_name = |cid: u64| {
+        ms.and_then(|ms| ms.class_registry.get_by_id(cid))
+            .map(|cd| cd.name.clone())
+            .unwrap_or_else(|| format!("#{cid}"))
+    };
+    let enum_name = |eid: u64| {
+        ms.and_then(|ms| ms.enum_registry.get_by_id(eid))
+            .map(|ed| ed.name.clone())
+            .unwrap_or_else(|| format!("#{eid}"))
+    };
+    let pointer = FieldType::Pointer;
+    match target {
+        Some(PointerTarget::FieldType(t)) => format!("{pointer} -> {t}"),
+        Some(PointerTarget::ClassId(cid)) => format!("{pointer} -> {}", class_name(*cid)),
+        Some(PointerTarget::EnumId(eid)) => format!("{pointer} -> {}", enum_name(*eid)),
+        Some(PointerTarget::Array { element, length }) => match element.as_ref() {
+            PointerTarget::FieldType(t) => format!("{pointer} -> Array [{length}] {t}"),
+            PointerTarget::EnumId(eid) => {
+                format!("{pointer} -> Array [{length}] {}", enum_name(*eid))
+            }
+            PointerTarget::ClassId(cid) => {
+                format!("{pointer} -> Array [{length}] {}", class_name(*cid))
+            }
+            PointerTarget::Array { .. } => String::from("Pointer -> Array [..] Array"),
+            PointerTarget::Chain { .. } => String::from("Pointer -> Array [..] Chain"),
+        },
+        Some(PointerTarget::Chain { offsets }) => {
+            format!("{pointer} -> Chain [{}]", format_chain_offsets(offsets))
         }
+        None => pointer.to_string(),
     }
 }
@@ -1,7 +1,30 @@
 mod actions;
+mod compare;
 mod context_menu;
 mod instance;
 mod panel;
+mod plot;
+mod quick_view;
+mod remove_fields;
+mod shortcuts;
+mod snapshot;
+mod table;
 mod util;
+mod warnings;
+mod zero_fields;
 
-pub use util::FieldKey;
+#[cfg(test)]
+mod tests;
+
+pub use compare::ComparePin;
+pub use context_menu::CHANGE_TYPE_CHOICES;
+pub use plot::ValuePlot;
+pub use quick_view::QuickView;
+pub use util::{
+    parse_hex_u64,
+    read_pointer_value,
+    FieldDrag,
+    FieldKey,
+    OffsetHop,
+    POINTER_PREVIEW_MAX_DEPTH,
+};
@@ -1,8 +1,14 @@
-use std::sync::Arc;
+use std::{
+    collections::HashSet,
+    hash::Hash,
+    sync::Arc,
+};
 
 use eframe::egui::{
     self,
+    collapsing_header::CollapsingState,
     Layout,
+    Response,
     ScrollArea,
     Ui,
 };
@@ -10,18 +16,67 @@ use handle::AppHandle;
 
 use super::util::{
     parse_hex_u64,
+    read_pointer_value,
     text_edit_autowidth,
 };
 use crate::{
     memory::{
         ClassDefinition,
-        FieldType,
         MemoryStructure,
     },
     re_class_app::ReClassGui,
 };
 
+/// Levels below the root opened by "Expand all"; deeper pointers stay closed so a large
+/// pointer graph is not dereferenced all at once
+const EXPAND_ALL_MAX_DEPTH: usize = 4;
+
+/// Repaired references listed by name after loading; the rest are only counted
+const MAX_LISTED_REPAIRS: usize = 20;
+
 impl ReClassGui {
+    /// Forced open state of a tree header `depth` levels below the root header, if any
+    pub(super) fn tree_open_state(&self, depth: usize) -> Option<bool> {
+        match self.tree_open_override {
+            Some((false, _)) => Some(false),
+            Some((true, _)) if depth <= EXPAND_ALL_MAX_DEPTH => Some(true),
+            _ => None,
+        }
+    }
+
+    /// Whether the tree header with `id_source` starts open when egui has no state stored for
+    /// it, e.g. after a restart
+    pub(super) fn tree_header_default_open(&self, ui: &Ui, id_source: impl Hash) -> bool {
+        self.open_tree_headers
+            .contains_key(&ui.make_persistent_id(id_source).value())
+    }
+
+    /// Record whether the tree header behind `header` is open after it was shown. `owner` is
+    /// the field or class the header shows, so the entry can be dropped once it is deleted.
+    pub(super) fn remember_tree_header(&mut self, ui: &Ui, header: &Response, owner: u64) {
+        let open = CollapsingState::load(ui.ctx(), header.id).is_some_and(|s| s.is_open());
+        if open {
+            self.open_tree_headers.insert(header.id.value(), owner);
+        } else {
+            self.open_tree_headers.remove(&header.id.value());
+        }
+    }
+
+    /// Forget open tree headers of fields and classes that no tab has anymore
+    pub(crate) fn prune_open_tree_headers(&mut self) {
+        let mut ids = HashSet::new();
+        for ms in self.tab_structures() {
+            for class_id in ms.class_registry.get_class_ids() {
+                ids.insert(class_id);
+                if let Some(def) = ms.class_registry.get(class_id) {
+                    ids.extend(def.fields.iter().map(|fd| fd.id));
+                }
+            }
+        }
+        self.open_tree_headers
+            .retain(|_, owner| ids.contains(owner));
+    }
+
     fn eval_address_expr(&self, input: &str) -> Option<u64> {
         // Simple recursive-descent parser supporting:
         // numbers (hex 0x.. or decimal), <module.dll>, $SignatureName, +, -, parentheses (), deref [expr]
@@ -183,7 +238,8 @@ impl ReClassGui {
                     }
                     // read pointer-sized value at addr
                     let handle = self.gui.app.handle.as_ref()?;
-                    let v = handle.read_sized::<u64>(addr).ok()?;
+                    let order = self.gui.byte_order();
+                    let v = read_pointer_value(handle, addr, order)?;
                     return Some(v);
                 }
                 // Module ref
@@ -228,9 +284,136 @@ impl ReClassGui {
             None
         }
     }
+    fn module_base_by_name(&self, name: &str) -> Option<u64> {
+        self.app
+            .get_modules()
+            .iter()
+            .find(|m| {
+                m.get_base_dll_name()
+                    .map(|n| n.eq_ignore_ascii_case(name))
+                    .unwrap_or(false)
+            })
+            .map(|m| m.base_address)
+    }
+
+    /// Resolve `module.dll+0x10`-style expressions against the attached process's modules.
+    /// Each `+`-separated term is either a number (hex `0x..` or decimal) or a module name.
+    fn parse_address_expression(&self, s: &str) -> Option<u64> {
+        let mut acc: u64 = 0;
+        for term in s.split('+').map(str::trim) {
+            if term.is_empty() {
+                return None;
+            }
+            let value = parse_hex_u64(term).or_else(|| self.module_base_by_name(term))?;
+            acc = acc.wrapping_add(value);
+        }
+        Some(acc)
+    }
+
+    /// Name of the first module referenced by an address expression that is not loaded
+    pub(crate) fn missing_module_in_expression(&self, s: &str) -> Option<String> {
+        s.split('+')
+            .map(str::trim)
+            .filter(|term| !term.is_empty() && parse_hex_u64(term).is_none())
+            .find(|term| self.module_base_by_name(term).is_none())
+            .map(|term| term.to_string())
+    }
+
+    pub(crate) fn eval_root_address_input(&self, input: &str) -> Option<u64> {
+        self.eval_address_expr(input)
+            .or_else(|| self.parse_address_expression(input))
+            .or_else(|| parse_hex_u64(input))
+    }
+
+    /// Re-evaluate the last committed root address expression, e.g. after attaching to a new process
+    pub(crate) fn resolve_root_address_expression(&self) -> Option<u64> {
+        let expr = self.root_address_expression.as_ref()?;
+        self.eval_root_address_input(expr)
+    }
+
+    /// Load a saved `{ memory, signatures }` JSON file and make it the current structure
+    pub(crate) fn load_structure_file(&mut self, path: &std::path::Path) -> anyhow::Result<()> {
+        let text = std::fs::read_to_string(path)?;
+        let repairs = self.install_structure_json(&text)?;
+        self.last_structure_path = Some(path.to_path_buf());
+        self.remember_recent_file(path);
+        self.report_reference_repairs(&repairs);
+        Ok(())
+    }
+
+    /// Make the structure and signatures of a saved `{ memory, signatures }` JSON text the
+    /// current ones, with a fresh undo history. Returns the dangling references that were
+    /// cleared.
+    pub(crate) fn install_structure_json(&mut self, text: &str) -> anyhow::Result<Vec<String>> {
+        // Expect a wrapper with memory and signatures
+        #[derive(serde::Deserialize)]
+        struct AppSave {
+            memory: MemoryStructure,
+            #[serde(default)]
+            signatures: Vec<crate::re_class_app::app::AppSignature>,
+        }
+        let mut wrapper = serde_json::from_str::<AppSave>(text)?;
+        wrapper.memory.class_registry.reseed_id_counters();
+        wrapper.memory.enum_registry.reseed_id_counters();
+        wrapper.memory.migrate_format();
+        let repairs = wrapper.memory.validate_references();
+        // Laid out for the pointer width of the attached process
+        wrapper
+            .memory
+            .class_registry
+            .set_pointer_size(self.app.pointer_size);
+        wrapper.memory.create_nested_instances();
+        self.app.set_memory_structure(wrapper.memory);
+        self.app.signatures = wrapper.signatures;
+        self.root_address_buffer = None;
+        self.root_address_expression = None;
+        self.reset_undo_history();
+        self.root_breadcrumbs.clear();
+        self.mark_structure_saved();
+        self.prune_open_tree_headers();
+        Ok(repairs)
+    }
+
+    /// Tell the user which dangling class or enum references were cleared on load
+    pub(crate) fn report_reference_repairs(&mut self, repairs: &[String]) {
+        if repairs.is_empty() {
+            return;
+        }
+        let mut text = format!(
+            "Cleared {} reference(s) to missing classes or enums:",
+            repairs.len()
+        );
+        for line in repairs.iter().take(MAX_LISTED_REPAIRS) {
+            text.push_str("\n- ");
+            text.push_str(line);
+        }
+        if repairs.len() > MAX_LISTED_REPAIRS {
+            text.push_str(&format!(
+                "\n... and {} more",
+                repairs.len() - MAX_LISTED_REPAIRS
+            ));
+        }
+        self.cycle_error_text = text;
+        self.cycle_error_open = true;
+    }
+
     pub(crate) fn memory_structure_panel(&mut self, ui: &mut Ui) {
         ui.horizontal(|ui| {
             ui.heading("Memory Structure");
+            if ui
+                .button("Expand all")
+                .on_hover_text(format!(
+                    "Open pointers, arrays and embedded classes up to {EXPAND_ALL_MAX_DEPTH} levels deep"
+                ))
+                .clicked()
+            {
+                self.tree_open_override = Some((true, EXPAND_ALL_MAX_DEPTH as u8 + 2));
+            }
+            if ui.button("Collapse all").clicked() {
+                self.tree_open_override = Some((false, 1));
+            }
+            self.shortcuts_help_button(ui);
+            self.snapshot_buttons(ui);
             ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
                 if ui
                     .button("Load")
@@ -240,21 +423,71 @@ impl ReClassGui {
                     if let Some(path) = rfd::FileDialog::new()
                         .add_filter("JSON", &["json"])
                         .pick_file()
+                    {
+                        if let Err(err) = self.open_structure_file(&path) {
+                            self.cycle_error_text =
+                                format!("Failed to load {}: {err}", path.display());
+                            self.cycle_error_open = true;
+                        }
+                    }
+                }
+                ui.menu_button("Recent", |ui| {
+                    // Files that were moved or deleted since are dropped from the list
+                    self.recent_files.retain(|p| p.exists());
+                    if self.recent_files.is_empty() {
+                        ui.label("No recent files");
+                    }
+                    let mut open: Option<std::path::PathBuf> = None;
+                    for path in &self.recent_files {
+                        let name = path
+                            .file_name()
+                            .map(|n| n.to_string_lossy().into_owned())
+                            .unwrap_or_else(|| path.display().to_string());
+                        if ui
+                            .button(name)
+                            .on_hover_text(path.display().to_string())
+                            .clicked()
+                        {
+                            open = Some(path.clone());
+                        }
+                    }
+                    if let Some(path) = open {
+                        if let Err(err) = self.open_structure_file(&path) {
+                            self.cycle_error_text =
+                                format!("Failed to load {}: {err}", path.display());
+                            self.cycle_error_open = true;
+                        }
+                        ui.close_menu();
+                    }
+                });
+                if ui
+                    .button("Import ReClass.NET")
+                    .on_hover_text("Import classes and enums from a ReClass.NET project")
+                    .clicked()
+                {
+                    if let Some(path) = rfd::FileDialog::new()
+                        .add_filter("ReClass.NET XML", &["xml"])
+                        .pick_file()
                     {
                         if let Ok(text) = std::fs::read_to_string(&path) {
-                            // Expect a wrapper with memory and signatures
-                            #[derive(serde::Deserialize)]
-                            struct AppSave {
-                                memory: MemoryStructure,
-                                #[serde(default)]
-                                signatures: Vec<crate::re_class_app::app::AppSignature>,
-                            }
-                            if let Ok(mut wrapper) = serde_json::from_str::<AppSave>(&text) {
-                                wrapper.memory.class_registry.reseed_id_counters();
-                                wrapper.memory.enum_registry.reseed_id_counters();
-                                wrapper.memory.create_nested_instances();
-                                self.app.set_memory_structure(wrapper.memory);
-                                self.app.signatures = wrapper.signatures;
+                            match crate::import::reclass_net::import_reclass_net(&text) {
+                                Ok(mut ms) => {
+                                    let repairs = ms.validate_references();
+                                    if !repairs.is_empty() {
+                                        ms.rebuild_root_from_registry();
+                                    }
+                                    self.open_tab_for_project();
+                                    self.app.set_memory_structure(ms);
+                                    self.root_address_buffer = None;
+                                    self.root_address_expression = None;
+                                    self.reset_undo_history();
+                                    self.last_structure_path = None;
+                                    self.report_reference_repairs(&repairs);
+                                }
+                                Err(err) => {
+                                    self.cycle_error_text = format!("Import failed: {err}");
+                                    self.cycle_error_open = true;
+                                }
                             }
                         }
                     }
@@ -279,24 +512,117 @@ impl ReClassGui {
                                 signatures: &self.app.signatures,
                             };
                             if let Ok(text) = serde_json::to_string_pretty(&wrapper) {
-                                let _ = std::fs::write(path, text);
+                                match std::fs::write(&path, text) {
+                                    Ok(()) => {
+                                        self.mark_structure_saved();
+                                        self.discard_recovery_file();
+                                        self.remember_recent_file(&path);
+                                        self.last_structure_path = Some(path);
+                                    }
+                                    Err(err) => {
+                                        self.cycle_error_text =
+                                            format!("Failed to save {}: {err}", path.display());
+                                        self.cycle_error_open = true;
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+                if ui
+                    .button("Export C++")
+                    .on_hover_text("Export all classes and enums as a C++ header")
+                    .clicked()
+                {
+                    if let Some(ms) = self.app.get_memory_structure() {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("C++ Header", &["h", "hpp"])
+                            .set_file_name("memory_structure.h")
+                            .save_file()
+                        {
+                            let text = crate::export::cpp::generate_cpp_header(ms);
+                            if let Err(err) = std::fs::write(&path, text) {
+                                self.cycle_error_text =
+                                    format!("Failed to write {}: {err}", path.display());
+                                self.cycle_error_open = true;
+                            }
+                        }
+                    }
+                }
+                if ui
+                    .button("Export Rust")
+                    .on_hover_text("Export all classes and enums as #[repr(C)] Rust structs")
+                    .clicked()
+                {
+                    if let Some(ms) = self.app.get_memory_structure() {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("Rust", &["rs"])
+                            .set_file_name("memory_structure.rs")
+                            .save_file()
+                        {
+                            let text = crate::export::rust::generate_rust_struct(ms);
+                            if let Err(err) = std::fs::write(&path, text) {
+                                self.cycle_error_text =
+                                    format!("Failed to write {}: {err}", path.display());
+                                self.cycle_error_open = true;
+                            }
+                        }
+                    }
+                }
+                if ui
+                    .button("Export offsets")
+                    .on_hover_text(
+                        "Export the offset of every field from the root, following embedded \
+                         instances, as C++ constexprs or JSON",
+                    )
+                    .clicked()
+                {
+                    if let Some(ms) = self.app.get_memory_structure() {
+                        if let Some(path) = rfd::FileDialog::new()
+                            .add_filter("C++ Header", &["h", "hpp"])
+                            .add_filter("JSON", &["json"])
+                            .set_file_name("offsets.h")
+                            .save_file()
+                        {
+                            let json = path
+                                .extension()
+                                .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+                            let text = if json {
+                                crate::export::offsets::generate_offsets_json(ms)
+                            } else {
+                                crate::export::offsets::generate_offsets_cpp(ms)
+                            };
+                            if let Err(err) = std::fs::write(&path, text) {
+                                self.cycle_error_text =
+                                    format!("Failed to write {}: {err}", path.display());
+                                self.cycle_error_open = true;
                             }
                         }
                     }
                 }
                 if ui
                     .button("New")
-                    .on_hover_text("Create a fresh root class with a Hex64 field")
+                    .on_hover_text("Create a fresh root class of hex bytes, sized in Settings")
                     .clicked()
                 {
                     let mut root_def = ClassDefinition::new("Root".to_string());
-                    root_def.add_hex_field(FieldType::Hex64);
+                    let created = root_def.add_hex_bytes(self.new_class_bytes);
+                    if self.auto_name_fields {
+                        root_def.name_fields_by_offset(created);
+                    }
                     let ms = crate::memory::MemoryStructure::new("root".to_string(), 0, root_def);
+                    self.open_tab_for_project();
                     self.app.set_memory_structure(ms);
+                    self.root_address_buffer = None;
+                    self.root_address_expression = None;
+                    self.reset_undo_history();
+                    self.mark_structure_saved();
+                    self.last_structure_path = None;
                 }
             });
         });
         ui.separator();
+        self.root_breadcrumb_bar(ui);
 
         let handle_arc = self.app.handle.clone();
         if let Some(ms) = self.app.get_memory_structure_mut() {
@@ -306,6 +632,58 @@ impl ReClassGui {
         }
     }
 
+    /// "Base:" combo box of the root class; the base class layout is placed before its fields
+    fn base_class_picker(&mut self, ui: &mut Ui, memory: &mut MemoryStructure) {
+        let class_id = memory.root_class.class_id;
+        let current = memory
+            .class_registry
+            .get(class_id)
+            .and_then(|def| def.base_class_id);
+        let class_label = |id: u64| {
+            memory
+                .class_registry
+                .get(id)
+                .map(|def| def.name.clone())
+                .unwrap_or_else(|| format!("#{id}"))
+        };
+        let mut candidates: Vec<(u64, String)> = memory
+            .class_registry
+            .get_class_ids()
+            .into_iter()
+            .filter(|id| *id != class_id)
+            .map(|id| (id, class_label(id)))
+            .collect();
+        candidates.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(&b.0)));
+        let mut selected = current;
+        ui.label("Base:");
+        ui.add_enabled_ui(!self.structure_locked, |ui| {
+            egui::ComboBox::from_id_source("root_base_class")
+                .selected_text(current.map_or_else(|| String::from("(none)"), class_label))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut selected, None, "(none)");
+                    for (id, name) in &candidates {
+                        ui.selectable_value(&mut selected, Some(*id), name);
+                    }
+                })
+                .response
+                .on_hover_text("Class whose fields come first, like a C++ base class");
+        });
+        if selected == current {
+            return;
+        }
+        let error = format!(
+            "Making '{}' the base of '{}' would create a class cycle.",
+            selected.map(class_label).unwrap_or_default(),
+            class_label(class_id)
+        );
+        if memory.set_base_class(class_id, selected) {
+            self.schedule_rebuild();
+        } else {
+            self.cycle_error_text = error;
+            self.cycle_error_open = true;
+        }
+    }
+
     pub(super) fn render_memory_structure_impl(
         &mut self,
         ui: &mut Ui,
@@ -327,8 +705,14 @@ impl ReClassGui {
         };
 
         let mem_ptr: *mut MemoryStructure = memory as *mut _;
-        egui::CollapsingHeader::new(header)
-            .default_open(false)
+        let root_header = egui::CollapsingHeader::new(header)
+            .default_open(self.tree_header_default_open(ui, "root"))
+            .open(
+                self.field_search
+                    .reveal
+                    .map(|_| true)
+                    .or(self.tree_open_state(0)),
+            )
             .id_source("root")
             .show(ui, |ui| {
                 ui.horizontal(|ui| {
@@ -341,7 +725,11 @@ impl ReClassGui {
                                 .map(|d| d.name.clone())
                                 .unwrap_or_default()
                         });
-                    let resp_name = text_edit_autowidth(ui, &mut root_class_name);
+                    let resp_name = ui
+                        .add_enabled_ui(!self.structure_locked, |ui| {
+                            text_edit_autowidth(ui, &mut root_class_name)
+                        })
+                        .inner;
                     if resp_name.changed() {
                         self.root_class_type_buffer = Some(root_class_name.clone());
                     }
@@ -374,15 +762,22 @@ impl ReClassGui {
                     let enter_on_this = ui.input(|i| i.key_pressed(egui::Key::Enter))
                         && ui.memory(|m| m.has_focus(resp.id));
                     if resp.lost_focus() || enter_on_this {
-                        // Support expressions: arithmetic, <module>, deref []
-                        let parsed = self
-                            .eval_address_expr(&base_hex)
-                            .or_else(|| parse_hex_u64(&base_hex));
-                        if let Some(addr) = parsed {
+                        // Support expressions: arithmetic, <module>, module.dll+offset, deref []
+                        if let Some(addr) = self.eval_root_address_input(&base_hex) {
+                            let previous = memory.root_class.address;
                             memory.set_root_address(addr);
+                            self.app.invalidate_read_cache();
+                            self.root_address_expression = Some(base_hex.trim().to_string());
+                            self.record_address_visit(previous, addr);
+                        } else if let Some(module) = self.missing_module_in_expression(&base_hex) {
+                            self.cycle_error_text =
+                                format!("Module '{module}' is not loaded in the attached process.");
+                            self.cycle_error_open = true;
                         }
                     }
+                    self.base_class_picker(ui, memory);
                 });
+                self.layout_warnings_section(ui, memory);
 
                 ui.separator();
                 ScrollArea::vertical()
@@ -398,5 +793,10 @@ impl ReClassGui {
                         );
                     });
             });
+        let (class_id, address) = (memory.root_class.class_id, memory.root_class.address);
+        self.remember_tree_header(ui, &root_header.header_response, class_id);
+        root_header.header_response.context_menu(|ui| {
+            self.copy_instance_table_button(ui, mem_ptr, class_id, address);
+        });
     }
 }
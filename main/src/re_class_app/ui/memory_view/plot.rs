@@ -0,0 +1,193 @@
+use std::{
+    collections::VecDeque,
+    time::{
+        Duration,
+        Instant,
+    },
+};
+
+use eframe::egui::{
+    self,
+    Context,
+    RichText,
+    Ui,
+};
+use egui_plot::{
+    Line,
+    Plot,
+    PlotPoints,
+};
+use handle::AppHandle;
+
+use super::{
+    context_menu::FieldCtx,
+    util::{
+        read_value,
+        FieldKey,
+    },
+};
+use crate::{
+    memory::{
+        Endianness,
+        FieldType,
+    },
+    re_class_app::ReClassGui,
+};
+
+/// Samples kept per plotted field; the oldest are dropped first
+const PLOT_MAX_SAMPLES: usize = 600;
+
+/// Recent values of a field shown in its "Plot" window, as (seconds since opened, value)
+pub struct ValuePlot {
+    label: String,
+    field_type: FieldType,
+    opened: Instant,
+    last_sample: Option<Instant>,
+    samples: VecDeque<[f64; 2]>,
+}
+
+impl ValuePlot {
+    fn push(&mut self, now: Instant, value: f64) {
+        if self.samples.len() == PLOT_MAX_SAMPLES {
+            self.samples.pop_front();
+        }
+        let t = now.duration_since(self.opened).as_secs_f64();
+        self.samples.push_back([t, value]);
+        self.last_sample = Some(now);
+    }
+}
+
+/// Integer, hex and floating point fields have a value that can be plotted
+pub(super) fn is_plottable(field_type: &FieldType) -> bool {
+    field_type.is_integer()
+        || field_type.is_hex_type()
+        || matches!(field_type, FieldType::Float | FieldType::Double)
+}
+
+/// Current value of a plottable field at `address` as a float
+fn read_plot_value(
+    handle: &AppHandle,
+    field_type: &FieldType,
+    address: u64,
+    order: Endianness,
+) -> Option<f64> {
+    Some(match field_type {
+        FieldType::Int8 => f64::from(read_value::<i8>(handle, address, order)?),
+        FieldType::Int16 => f64::from(read_value::<i16>(handle, address, order)?),
+        FieldType::Int32 => f64::from(read_value::<i32>(handle, address, order)?),
+        FieldType::Int64 => read_value::<i64>(handle, address, order)? as f64,
+        FieldType::UInt8 | FieldType::Hex8 => f64::from(read_value::<u8>(handle, address, order)?),
+        FieldType::UInt16 | FieldType::Hex16 => {
+            f64::from(read_value::<u16>(handle, address, order)?)
+        }
+        FieldType::UInt32 | FieldType::Hex32 => {
+            f64::from(read_value::<u32>(handle, address, order)?)
+        }
+        FieldType::UInt64 | FieldType::Hex64 => read_value::<u64>(handle, address, order)? as f64,
+        FieldType::Float => f64::from(read_value::<f32>(handle, address, order)?),
+        FieldType::Double => read_value::<f64>(handle, address, order)?,
+        _ => return None,
+    })
+}
+
+impl ReClassGui {
+    /// "Plot value" entry of the field menu for numeric fields
+    pub(super) fn plot_value_button(&mut self, ui: &mut Ui, ctx: &FieldCtx) {
+        let ms = unsafe { &*ctx.mem_ptr };
+        let Some(fd) = ms
+            .class_registry
+            .get(ctx.owner_class_id)
+            .and_then(|d| d.fields.get(ctx.field_index))
+            .filter(|fd| is_plottable(&fd.field_type))
+        else {
+            return;
+        };
+        if ui
+            .add_enabled(self.app.handle.is_some(), egui::Button::new("Plot value"))
+            .on_hover_text("Graph the value of this field over time in a window")
+            .clicked()
+        {
+            let key = FieldKey {
+                instance_address: ctx.instance_address,
+                field_def_id: fd.id,
+            };
+            let label = fd
+                .name
+                .clone()
+                .unwrap_or_else(|| format!("{} @ 0x{:X}", fd.field_type, ctx.address));
+            self.value_plots.entry(key).or_insert_with(|| ValuePlot {
+                label,
+                field_type: fd.field_type.clone(),
+                opened: Instant::now(),
+                last_sample: None,
+                samples: VecDeque::new(),
+            });
+            ui.close_menu();
+        }
+    }
+
+    /// Add a sample to every plot once per refresh interval while reads are running
+    pub(crate) fn sample_value_plots(&mut self) {
+        if self.value_plots.is_empty() || self.app.reads_paused {
+            return;
+        }
+        let (Some(handle), Some(ms)) = (self.app.handle.as_ref(), self.app.get_memory_structure())
+        else {
+            return;
+        };
+        let interval = Duration::from_millis(self.app.read_interval_ms);
+        let now = Instant::now();
+        for (key, plot) in self.value_plots.iter_mut() {
+            if plot
+                .last_sample
+                .is_some_and(|last| now.duration_since(last) < interval)
+            {
+                continue;
+            }
+            // Re-resolve like frozen fields so a rebuilt layout keeps sampling the same field
+            let Some(address) = ms.find_field_address(key.instance_address, key.field_def_id)
+            else {
+                continue;
+            };
+            if let Some(value) = read_plot_value(handle, &plot.field_type, address, ms.endianness) {
+                plot.push(now, value);
+            }
+        }
+    }
+
+    /// One window per plotted field with its value history and min/max/current
+    pub(crate) fn value_plot_windows(&mut self, ctx: &Context) {
+        let mut closed: Vec<FieldKey> = Vec::new();
+        for (key, plot) in &self.value_plots {
+            let mut open = true;
+            egui::Window::new(format!("Plot: {}", plot.label))
+                .id(egui::Id::new(("value_plot", key)))
+                .open(&mut open)
+                .default_size([360.0, 220.0])
+                .resizable(true)
+                .show(ctx, |ui| {
+                    let values = plot.samples.iter().map(|[_, v]| *v);
+                    let min = values.clone().fold(f64::INFINITY, f64::min);
+                    let max = values.fold(f64::NEG_INFINITY, f64::max);
+                    match plot.samples.back() {
+                        Some([_, current]) => ui.label(
+                            RichText::new(format!("current {current}   min {min}   max {max}"))
+                                .monospace(),
+                        ),
+                        None => ui.label("No samples yet"),
+                    };
+                    let points: PlotPoints = plot.samples.iter().copied().collect();
+                    Plot::new(("value_plot_graph", key))
+                        .allow_scroll(false)
+                        .x_axis_label("s")
+                        .show(ui, |plot_ui| plot_ui.line(Line::new(points)));
+                });
+            if !open {
+                closed.push(*key);
+            }
+        }
+        for key in closed {
+            self.value_plots.remove(&key);
+        }
+    }
+}
@@ -0,0 +1,169 @@
+use eframe::egui::{
+    self,
+    Context,
+    RichText,
+    Ui,
+};
+
+use crate::{
+    memory::MemoryStructure,
+    re_class_app::ReClassGui,
+};
+
+/// Throwaway instance shown in the "Quick View" window without moving the root
+#[derive(Debug, Default)]
+pub struct QuickView {
+    pub open: bool,
+    /// Address expression as typed, e.g. `0x1A2B3C` or `client.dll+0x10`
+    pub expr: String,
+    pub class_id: Option<u64>,
+    /// Address the expression evaluated to when it was viewed
+    pub address: Option<u64>,
+    pub error: Option<String>,
+}
+
+impl ReClassGui {
+    /// Address box of the header; Enter opens the typed address in the Quick View window
+    pub(crate) fn quick_view_box(&mut self, ui: &mut Ui) {
+        let resp = ui
+            .add(
+                egui::TextEdit::singleline(&mut self.quick_view.expr)
+                    .hint_text("Quick view address")
+                    .desired_width(150.0),
+            )
+            .on_hover_text(
+                "Press Enter to peek at an address with a class of your choice; the root stays \
+                 where it is",
+            );
+        if resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+            self.view_quick_address();
+        }
+    }
+
+    /// Evaluate the typed expression and show it, with the root class unless one was chosen
+    fn view_quick_address(&mut self) {
+        let expr = self.quick_view.expr.trim().to_string();
+        self.quick_view.open = true;
+        match self.eval_root_address_input(&expr) {
+            Some(address) => {
+                self.quick_view.address = Some(address);
+                self.quick_view.error = None;
+            }
+            None => {
+                self.quick_view.address = None;
+                self.quick_view.error = Some(match self.missing_module_in_expression(&expr) {
+                    Some(module) => format!("Module '{module}' is not loaded"),
+                    None => format!("Invalid address: {expr}"),
+                });
+            }
+        }
+        let Some(ms) = self.app.get_memory_structure() else {
+            return;
+        };
+        if !self
+            .quick_view
+            .class_id
+            .is_some_and(|id| ms.class_registry.contains(id))
+        {
+            self.quick_view.class_id = Some(ms.root_class.class_id);
+        }
+    }
+
+    pub(crate) fn quick_view_window(&mut self, ctx: &Context) {
+        if !self.quick_view.open {
+            return;
+        }
+        let mut open = true;
+        let mut view = false;
+        let mut promote: Option<(u64, u64)> = None;
+        egui::Window::new("Quick View")
+            .open(&mut open)
+            .resizable(true)
+            .default_width(460.0)
+            .default_height(420.0)
+            .show(ctx, |ui| {
+                let Some(ms) = self.app.get_memory_structure_mut() else {
+                    ui.label("No structure loaded");
+                    return;
+                };
+                let mem_ptr: *mut MemoryStructure = ms as *mut _;
+                let ms: &mut MemoryStructure = unsafe { &mut *mem_ptr };
+                let mut classes: Vec<(u64, String)> = ms
+                    .class_registry
+                    .get_class_ids()
+                    .into_iter()
+                    .filter_map(|id| ms.class_registry.get(id).map(|d| (id, d.name.clone())))
+                    .collect();
+                classes.sort_by_key(|(_, name)| name.to_lowercase());
+                let class_name = |id: Option<u64>| {
+                    classes
+                        .iter()
+                        .find(|(cid, _)| Some(*cid) == id)
+                        .map_or_else(|| String::from("<class>"), |(_, name)| name.clone())
+                };
+                ui.horizontal(|ui| {
+                    let resp = ui.add(
+                        egui::TextEdit::singleline(&mut self.quick_view.expr)
+                            .hint_text("0x7FF600001000")
+                            .desired_width(180.0),
+                    );
+                    if resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter)) {
+                        view = true;
+                    }
+                    egui::ComboBox::from_id_source("quick_view_class")
+                        .selected_text(class_name(self.quick_view.class_id))
+                        .show_ui(ui, |ui| {
+                            for (id, name) in &classes {
+                                ui.selectable_value(&mut self.quick_view.class_id, Some(*id), name);
+                            }
+                        });
+                    if ui.button("View").clicked() {
+                        view = true;
+                    }
+                });
+                if let Some(error) = &self.quick_view.error {
+                    ui.colored_label(ui.visuals().error_fg_color, error);
+                }
+                let (Some(address), Some(def)) = (
+                    self.quick_view.address,
+                    self.quick_view
+                        .class_id
+                        .and_then(|id| ms.class_registry.get(id))
+                        .cloned(),
+                ) else {
+                    return;
+                };
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(format!("0x{address:X}: {}", def.name)).strong());
+                    if ui
+                        .button("Promote to root")
+                        .on_hover_text(
+                            "Make this address and class the root; the current root is kept as a \
+                             breadcrumb",
+                        )
+                        .clicked()
+                    {
+                        promote = Some((address, def.id));
+                    }
+                });
+                ui.separator();
+                self.render_detached_instance(
+                    ui,
+                    String::from("quick view"),
+                    address,
+                    def,
+                    mem_ptr,
+                    "quick_view_tree_scroll",
+                );
+            });
+        if view {
+            self.view_quick_address();
+        }
+        if let Some((address, class_id)) = promote {
+            let expr = self.quick_view.expr.trim().to_string();
+            self.move_root_with_breadcrumb(address, Some(class_id), expr);
+            open = false;
+        }
+        self.quick_view.open &= open;
+    }
+}
@@ -0,0 +1,72 @@
+use std::collections::HashSet;
+
+use eframe::egui::{
+    self,
+    Context,
+};
+
+use crate::{
+    memory::MemoryStructure,
+    re_class_app::ReClassGui,
+};
+
+impl ReClassGui {
+    /// Remove fields of `owner_class_id`, first asking when one of them embeds, points to or
+    /// holds an array of a class
+    pub(super) fn request_field_removal(
+        &mut self,
+        mem_ptr: *mut MemoryStructure,
+        owner_class_id: u64,
+        field_ids: HashSet<u64>,
+    ) {
+        let referencing = unsafe { &*mem_ptr }.class_referencing_fields(owner_class_id, &field_ids);
+        if referencing.is_empty() {
+            self.remove_selected_fields(mem_ptr, owner_class_id, &field_ids);
+        } else {
+            self.remove_fields_prompt = Some((owner_class_id, field_ids));
+        }
+    }
+
+    /// Confirmation before removing fields that hold nested class layouts
+    pub(crate) fn remove_fields_prompt_window(&mut self, ctx: &Context) {
+        let Some((owner_class_id, field_ids)) = self.remove_fields_prompt.clone() else {
+            return;
+        };
+        let Some(ms) = self.app.memory_structure.as_mut() else {
+            self.remove_fields_prompt = None;
+            return;
+        };
+        let mem_ptr: *mut MemoryStructure = ms;
+        let referencing = ms.class_referencing_fields(owner_class_id, &field_ids);
+        let mut open = true;
+        let mut decision: Option<bool> = None;
+        egui::Window::new("Remove fields")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "Remove {} field(s)? These hold the layout of another class; the classes \
+                     stay, but this class no longer shows them:",
+                    field_ids.len()
+                ));
+                for line in &referencing {
+                    ui.monospace(line);
+                }
+                ui.horizontal(|ui| {
+                    if ui.button("Remove").clicked() {
+                        decision = Some(true);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        decision = Some(false);
+                    }
+                });
+            });
+        if decision == Some(true) {
+            self.remove_selected_fields(mem_ptr, owner_class_id, &field_ids);
+        }
+        if decision.is_some() || !open {
+            self.remove_fields_prompt = None;
+        }
+    }
+}
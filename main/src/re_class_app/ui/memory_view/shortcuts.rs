@@ -0,0 +1,167 @@
+use std::collections::HashSet;
+
+use eframe::egui::{
+    self,
+    Context,
+    Key,
+    KeyboardShortcut,
+    Modifiers,
+    RichText,
+    Ui,
+};
+
+use super::util::FieldKey;
+use crate::{
+    memory::{
+        FieldType,
+        MemoryStructure,
+    },
+    re_class_app::ReClassGui,
+};
+
+/// Field types set by the number keys 1-8
+const TYPE_KEYS: [(Key, FieldType); 8] = [
+    (Key::Num1, FieldType::Hex8),
+    (Key::Num2, FieldType::Hex16),
+    (Key::Num3, FieldType::Hex32),
+    (Key::Num4, FieldType::Hex64),
+    (Key::Num5, FieldType::Int8),
+    (Key::Num6, FieldType::Int16),
+    (Key::Num7, FieldType::Int32),
+    (Key::Num8, FieldType::Int64),
+];
+
+/// Key and description pairs shown in the "?" popup
+const SHORTCUT_HELP: &[(&str, &str)] = &[
+    ("Delete", "Remove the selected fields"),
+    ("Insert", "Add a Hex64 after the selection"),
+    ("1 2 3 4", "Change type to Hex8 / Hex16 / Hex32 / Hex64"),
+    ("5 6 7 8", "Change type to Int8 / Int16 / Int32 / Int64"),
+    ("Ctrl+A", "Select all fields of the instance"),
+    ("Escape", "Clear the selection"),
+    ("Ctrl+Z / Ctrl+Y", "Undo / redo"),
+    ("Ctrl+G", "Go to address"),
+    ("Alt+Left / Alt+Right", "Previous / next root address"),
+    ("F2", "Rename the class or enum last clicked in Definitions"),
+];
+
+impl ReClassGui {
+    /// Keyboard editing of the selected fields. Ignored while a text field has focus.
+    pub(crate) fn handle_field_shortcuts(&mut self, ctx: &Context) {
+        if ctx.memory(|m| m.focused().is_some()) || self.selected_fields.is_empty() {
+            return;
+        }
+        if ctx.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Escape)) {
+            self.clear_field_selection();
+            return;
+        }
+        let Some(ms) = self.app.get_memory_structure_mut() else {
+            return;
+        };
+        let mem_ptr: *mut MemoryStructure = ms as *mut _;
+        // All selected fields belong to one instance, so any of them names the owning class
+        let Some((owner_class_id, _)) = self
+            .selected_fields
+            .iter()
+            .find_map(|key| ms.class_registry.find_field(key.field_def_id))
+        else {
+            return;
+        };
+        let selected_ids: HashSet<u64> = self
+            .selected_fields
+            .iter()
+            .map(|key| key.field_def_id)
+            .collect();
+
+        let select_all = KeyboardShortcut::new(Modifiers::COMMAND, Key::A);
+        if ctx.input_mut(|i| i.consume_shortcut(&select_all)) {
+            self.select_all_fields(owner_class_id);
+            return;
+        }
+        // The remaining keys change the layout
+        if self.structure_locked {
+            return;
+        }
+        if ctx.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Delete)) {
+            self.request_field_removal(mem_ptr, owner_class_id, selected_ids);
+        } else if ctx.input_mut(|i| i.consume_key(Modifiers::NONE, Key::Insert)) {
+            self.insert_hex64_after_selection(owner_class_id, &selected_ids);
+        } else if let Some(new_type) = TYPE_KEYS.iter().find_map(|(key, field_type)| {
+            ctx.input_mut(|i| i.consume_key(Modifiers::NONE, *key))
+                .then(|| field_type.clone())
+        }) {
+            self.change_selected_fields_type(mem_ptr, owner_class_id, &selected_ids, new_type);
+        }
+    }
+
+    fn clear_field_selection(&mut self) {
+        self.selected_fields.clear();
+        self.selected_instance_address = None;
+        self.selection_anchor = None;
+    }
+
+    fn select_all_fields(&mut self, owner_class_id: u64) {
+        let Some(instance_address) = self.selected_instance_address else {
+            return;
+        };
+        let Some(def) = self
+            .app
+            .get_memory_structure()
+            .and_then(|ms| ms.class_registry.get(owner_class_id))
+        else {
+            return;
+        };
+        self.selected_fields = def
+            .fields
+            .iter()
+            .map(|fd| FieldKey {
+                instance_address,
+                field_def_id: fd.id,
+            })
+            .collect();
+        self.selection_anchor = Some((instance_address, 0));
+    }
+
+    fn insert_hex64_after_selection(&mut self, owner_class_id: u64, selected_ids: &HashSet<u64>) {
+        let Some(def) = self
+            .app
+            .get_memory_structure_mut()
+            .and_then(|ms| ms.class_registry.get_mut(owner_class_id))
+        else {
+            return;
+        };
+        let Some(last) = def
+            .fields
+            .iter()
+            .rposition(|fd| selected_ids.contains(&fd.id))
+        else {
+            return;
+        };
+        let created = def.insert_hex_bytes_at(last + 1, 8);
+        if self.auto_name_fields {
+            def.name_fields_by_offset(created);
+        }
+        self.schedule_rebuild();
+    }
+
+    /// "?" button listing the keyboard shortcuts
+    pub(super) fn shortcuts_help_button(&mut self, ui: &mut Ui) {
+        ui.menu_button("?", |ui| {
+            ui.label(RichText::new("Keyboard shortcuts").strong());
+            ui.label(RichText::new("Field keys act on the selected rows").weak());
+            ui.separator();
+            egui::Grid::new("shortcut_help_grid")
+                .num_columns(2)
+                .spacing(egui::vec2(16.0, 4.0))
+                .show(ui, |ui| {
+                    for (keys, action) in SHORTCUT_HELP {
+                        ui.monospace(*keys);
+                        ui.label(*action);
+                        ui.end_row();
+                    }
+                });
+        })
+        .response
+        .on_hover_text("Keyboard shortcuts");
+    }
+}
@@ -0,0 +1,71 @@
+use eframe::egui::{
+    self,
+    Ui,
+};
+
+use crate::{
+    memory::{
+        snapshot::Snapshot,
+        MemoryField,
+    },
+    re_class_app::ReClassGui,
+};
+
+impl ReClassGui {
+    /// Whether Compare mode is on and the live bytes of `field` differ from the snapshot
+    pub(super) fn changed_since_snapshot(&self, field: &MemoryField) -> bool {
+        if !self.snapshot_compare {
+            return false;
+        }
+        match (&self.snapshot, &field.data) {
+            (Some(snapshot), Some(data)) => snapshot.differs(field.address, data),
+            _ => false,
+        }
+    }
+
+    /// Capture the bytes of the root instance block
+    fn capture_snapshot(&mut self) {
+        let Some(handle) = self.app.handle.clone() else {
+            return;
+        };
+        let Some(ms) = self.app.get_memory_structure() else {
+            return;
+        };
+        let address = ms.root_class.address;
+        match ms.root_class.read_block(&handle) {
+            Some(bytes) => {
+                self.snapshot = Some(Snapshot::new(address, bytes));
+                self.snapshot_compare = true;
+            }
+            None => {
+                self.cycle_error_text = format!("Could not read 0x{address:X} for a snapshot");
+                self.cycle_error_open = true;
+            }
+        }
+    }
+
+    /// Snapshot / Compare / Clear snapshot buttons of the memory structure header
+    pub(super) fn snapshot_buttons(&mut self, ui: &mut Ui) {
+        if ui
+            .add_enabled(self.app.handle.is_some(), egui::Button::new("Snapshot"))
+            .on_hover_text("Capture the current bytes of the root instance")
+            .clicked()
+        {
+            self.capture_snapshot();
+        }
+        let Some(snapshot) = &self.snapshot else {
+            return;
+        };
+        let hover = format!(
+            "Highlight fields whose bytes differ from the snapshot of 0x{:X} (0x{:X} bytes)",
+            snapshot.address,
+            snapshot.bytes.len()
+        );
+        ui.toggle_value(&mut self.snapshot_compare, "Compare")
+            .on_hover_text(hover);
+        if ui.button("Clear snapshot").clicked() {
+            self.snapshot = None;
+            self.snapshot_compare = false;
+        }
+    }
+}
@@ -0,0 +1,183 @@
+use std::sync::Arc;
+
+use eframe::egui::Ui;
+use handle::AppHandle;
+
+use super::util::{
+    read_field_pointer,
+    FieldValue,
+};
+use crate::{
+    memory::{
+        FieldType,
+        MemoryField,
+        MemoryStructure,
+        PointerTarget,
+    },
+    re_class_app::ReClassGui,
+};
+
+const TABLE_HEADER: [&str; 5] = ["Offset", "Address", "Name", "Type", "Value"];
+
+/// Columns of one field in "Copy instance as table"
+type TableRow = [String; 5];
+
+/// Rows padded with spaces so the columns line up in a monospace font
+fn format_table(rows: &[TableRow]) -> String {
+    let header = TABLE_HEADER.map(String::from);
+    let mut widths = header.each_ref().map(|cell| cell.chars().count());
+    for row in rows {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+    let mut out = String::new();
+    for row in std::iter::once(&header).chain(rows) {
+        let line = row
+            .iter()
+            .zip(widths)
+            .map(|(cell, width)| format!("{cell:<width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        out.push_str(line.trim_end());
+        out.push('\n');
+    }
+    out
+}
+
+impl ReClassGui {
+    /// Append a row per field of the `class_id` instance at `address`, base class fields
+    /// first. Embedded instances and class pointers follow their row, indented, until the
+    /// render depth limit.
+    fn instance_table_rows(
+        &self,
+        handle: Option<&Arc<AppHandle>>,
+        mem_ptr: *mut MemoryStructure,
+        class_id: u64,
+        address: u64,
+        depth: usize,
+        rows: &mut Vec<TableRow>,
+    ) {
+        let ms = unsafe { &*mem_ptr };
+        let Some(class_def) = ms.class_registry.get(class_id) else {
+            return;
+        };
+        if let Some(base_id) = class_def
+            .base_class_id
+            .filter(|id| ms.class_registry.contains(*id))
+        {
+            self.instance_table_rows(handle, mem_ptr, base_id, address, depth, rows);
+        }
+        let indent = "  ".repeat(depth);
+        for (fd, offset) in class_def.fields.iter().zip(ms.field_offsets(class_id)) {
+            let field_address = address.saturating_add(offset);
+            let field = MemoryField {
+                def_id: fd.id,
+                ..MemoryField::new_hex(field_address)
+            };
+            let class_name = |id: u64| {
+                ms.class_registry
+                    .get(id)
+                    .map(|d| d.name.clone())
+                    .unwrap_or_else(|| format!("#{id}"))
+            };
+            let (type_name, value, nested) = match (&fd.field_type, &fd.pointer_target) {
+                (FieldType::ClassInstance, _) => {
+                    let cid = fd.class_id.unwrap_or_default();
+                    (class_name(cid), String::new(), Some((cid, field_address)))
+                }
+                (FieldType::Pointer, target) => {
+                    let pointer = handle.and_then(|h| read_field_pointer(h, &field, ms.endianness));
+                    let value = match (handle, pointer) {
+                        (None, _) => String::new(),
+                        (Some(_), None) => String::from("??"),
+                        (Some(_), Some(ptr)) => format!("-> 0x{ptr:X}"),
+                    };
+                    match target {
+                        Some(PointerTarget::ClassId(cid)) => (
+                            format!("Pointer -> {}", class_name(*cid)),
+                            value,
+                            pointer.filter(|ptr| *ptr != 0).map(|ptr| (*cid, ptr)),
+                        ),
+                        _ => (String::from("Pointer"), value, None),
+                    }
+                }
+                (FieldType::Array, _) => (
+                    String::from("Array"),
+                    format!("[{}]", fd.array_length.unwrap_or(0)),
+                    None,
+                ),
+                (FieldType::Enum, _) => {
+                    let enum_name = fd
+                        .enum_id
+                        .and_then(|eid| ms.enum_registry.get_by_id(eid))
+                        .map_or_else(|| String::from("<enum?>"), |ed| ed.name.clone());
+                    let value =
+                        self.simple_field_value(handle, class_def, &field, &fd.field_type, mem_ptr);
+                    (format!("Enum -> {enum_name}"), table_value(value), None)
+                }
+                (field_type, _) => {
+                    let value =
+                        self.simple_field_value(handle, class_def, &field, field_type, mem_ptr);
+                    (field_type.to_string(), table_value(value), None)
+                }
+            };
+            rows.push([
+                format!("+0x{offset:04X}"),
+                format!("0x{field_address:08X}"),
+                format!("{indent}{}", fd.name.as_deref().unwrap_or("")),
+                type_name,
+                value,
+            ]);
+            if let Some((cid, nested_address)) = nested {
+                if depth + 1 < self.max_render_depth {
+                    self.instance_table_rows(handle, mem_ptr, cid, nested_address, depth + 1, rows);
+                }
+            }
+        }
+    }
+
+    /// Text table (offset, address, name, type, value) of the instance with current values
+    pub(super) fn instance_table(
+        &self,
+        mem_ptr: *mut MemoryStructure,
+        class_id: u64,
+        address: u64,
+    ) -> String {
+        let mut rows = Vec::new();
+        let handle = self.app.handle.clone();
+        self.instance_table_rows(handle.as_ref(), mem_ptr, class_id, address, 0, &mut rows);
+        format_table(&rows)
+    }
+
+    /// "Copy instance as table" entry of instance header menus
+    pub(super) fn copy_instance_table_button(
+        &self,
+        ui: &mut Ui,
+        mem_ptr: *mut MemoryStructure,
+        class_id: u64,
+        address: u64,
+    ) {
+        if ui
+            .button("Copy instance as table")
+            .on_hover_text(
+                "Copy every field of this instance with its offset, address, type and current \
+                 value as an aligned text table",
+            )
+            .clicked()
+        {
+            let table = self.instance_table(mem_ptr, class_id, address);
+            let _ = arboard::Clipboard::new().and_then(|mut cb| cb.set_text(table));
+            ui.close_menu();
+        }
+    }
+}
+
+/// Value column text; control characters of strings would break the row
+fn table_value(value: FieldValue) -> String {
+    match value {
+        FieldValue::Value(text) => text.replace(['\n', '\r', '\t'], " "),
+        FieldValue::ReadFailed => String::from("??"),
+        FieldValue::NotAttached => String::new(),
+    }
+}
@@ -0,0 +1,155 @@
+use super::util::{
+    array_element_string,
+    format_float,
+    format_vector,
+    parse_value_bytes,
+    rtti_matches,
+    ValueFormat,
+};
+use crate::memory::{
+    Endianness,
+    FieldType,
+    NumberBase,
+};
+
+#[cfg(test)]
+mod parse_value_tests {
+    use super::*;
+
+    #[test]
+    fn test_hex_field_reads_0b_as_hex_digits() {
+        assert_eq!(
+            parse_value_bytes(&FieldType::Hex16, "0b1f", None),
+            Some(0x0B1Fu16.to_le_bytes().to_vec())
+        );
+        assert_eq!(
+            parse_value_bytes(&FieldType::Hex16, "0b1f", Some(NumberBase::Hexadecimal)),
+            Some(0x0B1Fu16.to_le_bytes().to_vec())
+        );
+    }
+
+    #[test]
+    fn test_hex_field_in_binary_takes_0b_prefix() {
+        assert_eq!(
+            parse_value_bytes(&FieldType::Hex8, "0b101", Some(NumberBase::Binary)),
+            Some(vec![5])
+        );
+        // Not binary digits
+        assert_eq!(
+            parse_value_bytes(&FieldType::Hex16, "0b1f", Some(NumberBase::Binary)),
+            None
+        );
+    }
+
+    #[test]
+    fn test_multibyte_input_is_rejected_without_panicking() {
+        assert_eq!(parse_value_bytes(&FieldType::Int32, "€5", None), None);
+        assert_eq!(parse_value_bytes(&FieldType::Hex32, "€5", None), None);
+        assert_eq!(parse_value_bytes(&FieldType::Int8, "0x€", None), None);
+    }
+
+    #[test]
+    fn test_signed_field_takes_raw_bits_after_prefix() {
+        assert_eq!(
+            parse_value_bytes(&FieldType::Int8, "0xFF", None),
+            Some(vec![0xFF])
+        );
+        assert_eq!(
+            parse_value_bytes(&FieldType::Int8, "0b11111111", Some(NumberBase::Binary)),
+            Some(vec![0xFF])
+        );
+        assert_eq!(parse_value_bytes(&FieldType::Int8, "255", None), None);
+    }
+}
+
+#[cfg(test)]
+mod format_float_tests {
+    use super::*;
+
+    fn decimals(n: u8) -> ValueFormat {
+        ValueFormat {
+            float_precision: Some(n),
+            endianness: Endianness::Little,
+        }
+    }
+
+    fn exact() -> ValueFormat {
+        decimals(3).exact()
+    }
+
+    #[test]
+    fn test_fixed_decimals() {
+        assert_eq!(format_float(1.25f32, decimals(3)), "1.250");
+        assert_eq!(format_float(-2.5f64, decimals(0)), "-2");
+        assert_eq!(format_float(0.0f32, decimals(2)), "0.00");
+    }
+
+    #[test]
+    fn test_exact_form_reads_back() {
+        assert_eq!(format_float(1.25f32, exact()), "1.25");
+        let v = 0.1f32 + 0.2f32;
+        assert_eq!(format_float(v, exact()).parse::<f32>(), Ok(v));
+    }
+
+    #[test]
+    fn test_tiny_and_huge_values_use_exponent() {
+        assert_eq!(format_float(0.0001f32, decimals(3)), "1.000e-4");
+        assert_eq!(format_float(1.5e8f64, decimals(1)), "1.5e8");
+    }
+
+    #[test]
+    fn test_non_finite_values() {
+        assert_eq!(format_float(f32::NAN, decimals(3)), "NaN");
+        assert_eq!(format_float(f64::INFINITY, exact()), "inf");
+        assert_eq!(format_float(f32::NEG_INFINITY, decimals(1)), "-inf");
+    }
+
+    #[test]
+    fn test_vector_in_structure_byte_order() {
+        let mut bytes = 1.5f32.to_le_bytes().to_vec();
+        bytes.extend((-2.0f32).to_le_bytes());
+        assert_eq!(format_vector(&bytes, decimals(1)), "(1.5, -2.0)");
+        let big = ValueFormat {
+            endianness: Endianness::Big,
+            ..decimals(1)
+        };
+        let mut bytes = 1.5f32.to_be_bytes().to_vec();
+        bytes.extend((-2.0f32).to_be_bytes());
+        assert_eq!(format_vector(&bytes, big), "(1.5, -2.0)");
+    }
+
+    #[test]
+    fn test_array_elements_of_a_dump() {
+        let mut bytes = 0x1234u16.to_le_bytes().to_vec();
+        bytes.extend((-7i16).to_le_bytes());
+        bytes.extend(0.5f32.to_le_bytes());
+        let path =
+            std::env::temp_dir().join(format!("reclass-elements-{}.bin", std::process::id()));
+        std::fs::write(&path, bytes).unwrap();
+        let file = handle::FileHandle::open(&path, 0x2000, 8).unwrap();
+        let _ = std::fs::remove_file(&path);
+        let handle = handle::AppHandle::create_from_dump(file);
+
+        let fmt = decimals(2);
+        let at = |t: FieldType, address| array_element_string(&handle, &t, address, fmt);
+        assert_eq!(at(FieldType::Hex16, 0x2000).as_deref(), Some("0x1234"));
+        assert_eq!(at(FieldType::Int16, 0x2002).as_deref(), Some("-7"));
+        assert_eq!(at(FieldType::Float, 0x2004).as_deref(), Some("0.50"));
+        assert_eq!(at(FieldType::Bool, 0x2000).as_deref(), Some("true"));
+        // Past the end of the dump
+        assert_eq!(at(FieldType::UInt64, 0x2004), None);
+    }
+}
+
+#[cfg(test)]
+mod rtti_name_tests {
+    use super::*;
+
+    #[test]
+    fn test_rtti_matches_with_or_without_namespace() {
+        assert!(rtti_matches("game::Player", "Player"));
+        assert!(rtti_matches("game::Player", "game::Player"));
+        assert!(!rtti_matches("game::Enemy", "Player"));
+        assert!(!rtti_matches("game::Player", "ui::Player"));
+    }
+}
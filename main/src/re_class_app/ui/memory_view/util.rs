@@ -1,4 +1,10 @@
-use std::sync::Arc;
+use std::{
+    fmt::{
+        Display,
+        LowerExp,
+    },
+    sync::Arc,
+};
 
 use eframe::egui::{
     self,
@@ -7,11 +13,25 @@ use eframe::egui::{
     TextStyle,
     Ui,
 };
-use handle::AppHandle;
+use handle::{
+    AppHandle,
+    Pod,
+};
 
-use crate::memory::{
-    FieldType,
-    MemoryField,
+use crate::{
+    memory::{
+        format_signed_offset,
+        walk_pointer_chain,
+        Endianness,
+        FieldDefinition,
+        FieldType,
+        MemoryField,
+        NumberBase,
+        PointerTarget,
+        TextEncoding,
+        TEXT_MAX_LENGTH,
+    },
+    re_class_app::app::ExportCache,
 };
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
@@ -20,15 +40,203 @@ pub struct FieldKey {
     pub field_def_id: u64,
 }
 
+/// A field row being dragged to a new position within its class
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FieldDrag {
+    pub instance_address: u64,
+    pub owner_class_id: u64,
+    pub from_index: usize,
+}
+
 pub fn parse_hex_u64(s: &str) -> Option<u64> {
     let t = s.trim();
     if let Some(stripped) = t.strip_prefix("0x").or_else(|| t.strip_prefix("0X")) {
         u64::from_str_radix(stripped, 16).ok()
+    } else if let Some(stripped) = t.strip_prefix("0b").or_else(|| t.strip_prefix("0B")) {
+        u64::from_str_radix(stripped, 2).ok()
     } else {
         t.parse::<u64>().ok()
     }
 }
 
+/// Color of a field's type label: its custom color if one is set, else `default` from the
+/// tree colors
+pub fn field_type_color(fd: Option<&FieldDefinition>, default: Color32) -> Color32 {
+    fd.and_then(|fd| fd.color)
+        .map(|[r, g, b]| Color32::from_rgb(r, g, b))
+        .unwrap_or(default)
+}
+
+/// Collapsing header text, tinted with the field's custom color if one is set, else with
+/// `default` when given
+pub fn field_header_text(
+    header: String,
+    fd: Option<&FieldDefinition>,
+    default: Option<Color32>,
+) -> egui::WidgetText {
+    match fd.and_then(|fd| fd.color) {
+        Some([r, g, b]) => egui::RichText::new(header)
+            .color(Color32::from_rgb(r, g, b))
+            .into(),
+        None => match default {
+            Some(color) => egui::RichText::new(header).color(color).into(),
+            None => header.into(),
+        },
+    }
+}
+
+/// Where the address held by a pointer field leads
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointerValidity {
+    Null,
+    /// Inside a module or readable memory
+    Mapped,
+    /// Neither null nor readable, most likely not a pointer at all
+    Unmapped,
+}
+
+impl PointerValidity {
+    pub fn of(handle: &AppHandle, ptr: u64) -> Self {
+        if ptr == 0 {
+            PointerValidity::Null
+        } else if handle.is_address_mapped(ptr) {
+            PointerValidity::Mapped
+        } else {
+            PointerValidity::Unmapped
+        }
+    }
+
+    fn color(&self, ui: &Ui) -> Color32 {
+        match self {
+            PointerValidity::Null => ui.visuals().error_fg_color,
+            PointerValidity::Mapped => Color32::from_rgb(0x4C, 0xC0, 0x5A),
+            PointerValidity::Unmapped => ui.visuals().warn_fg_color,
+        }
+    }
+
+    fn description(&self) -> &'static str {
+        match self {
+            PointerValidity::Null => "Null pointer",
+            PointerValidity::Mapped => "Points to mapped memory",
+            PointerValidity::Unmapped => "Points to unmapped memory; probably not a pointer",
+        }
+    }
+}
+
+const POINTER_DOT: &str = "\u{25CF}";
+
+/// Colored dot in a pointer row telling real pointers from null and garbage values
+pub fn pointer_validity_dot(ui: &mut Ui, validity: PointerValidity) {
+    ui.colored_label(validity.color(ui), POINTER_DOT)
+        .on_hover_text(validity.description());
+}
+
+/// `field_header_text` of a pointer header, led by its validity dot when the pointer was read
+pub fn pointer_header_text(
+    ui: &Ui,
+    header: String,
+    fd: Option<&FieldDefinition>,
+    validity: Option<PointerValidity>,
+    default: Color32,
+) -> egui::WidgetText {
+    let Some(validity) = validity else {
+        return field_header_text(header, fd, Some(default));
+    };
+    let text_color = field_type_color(fd, default);
+    let mut job = egui::text::LayoutJob::default();
+    for text in [
+        egui::RichText::new(format!("{POINTER_DOT} ")).color(validity.color(ui)),
+        egui::RichText::new(header).color(text_color),
+    ] {
+        text.append_to(
+            &mut job,
+            ui.style(),
+            egui::FontSelection::Default,
+            egui::Align::Center,
+        );
+    }
+    job.into()
+}
+
+/// Field comments are cut to this many characters in a row; the full text is shown on hover
+const FIELD_COMMENT_MAX_CHARS: usize = 60;
+
+/// First line of a field comment shortened to fit in a row, and whether anything was cut off
+fn comment_summary(comment: &str) -> (String, bool) {
+    let comment = comment.trim();
+    let first_line = comment.lines().next().unwrap_or_default();
+    let mut summary: String = first_line.chars().take(FIELD_COMMENT_MAX_CHARS).collect();
+    let truncated = summary.len() < comment.len();
+    if truncated {
+        summary.push_str("...");
+    }
+    (summary, truncated)
+}
+
+/// Dimmed `// comment` at the end of a field row. The row's click area covers the label, so the
+/// tooltip for a cut off comment is shown by hand.
+pub fn field_comment_label(ui: &mut Ui, fd: Option<&FieldDefinition>) {
+    let Some(comment) = fd.and_then(|fd| fd.comment.as_deref()) else {
+        return;
+    };
+    let (summary, truncated) = comment_summary(comment);
+    let resp = ui.label(
+        egui::RichText::new(format!("// {summary}"))
+            .weak()
+            .italics(),
+    );
+    if truncated && ui.rect_contains_pointer(resp.rect) {
+        egui::show_tooltip_text(ui.ctx(), resp.id.with("comment"), comment);
+    }
+}
+
+/// Red marker in place of a value whose memory could not be read, telling the address on hover
+pub fn unreadable_label(ui: &mut Ui, text: &str, address: u64) {
+    let resp = ui.label(
+        egui::RichText::new(text)
+            .monospace()
+            .color(ui.visuals().error_fg_color),
+    );
+    if ui.rect_contains_pointer(resp.rect) {
+        egui::show_tooltip_text(
+            ui.ctx(),
+            resp.id.with("unreadable"),
+            format!("Unreadable: reading 0x{address:X} failed"),
+        );
+    }
+}
+
+/// Append a field comment to a collapsing header, returning the full text if it was cut off
+pub fn append_field_comment<'a>(
+    header: &mut String,
+    fd: Option<&'a FieldDefinition>,
+) -> Option<&'a str> {
+    let comment = fd.and_then(|fd| fd.comment.as_deref())?;
+    let (summary, truncated) = comment_summary(comment);
+    header.push_str(&format!("  // {summary}"));
+    truncated.then_some(comment)
+}
+
+/// Minimum widths of the name, type and size columns of a field row
+pub(super) const NAME_COLUMN_WIDTH: f32 = 150.0;
+pub(super) const TYPE_COLUMN_WIDTH: f32 = 150.0;
+pub(super) const SIZE_COLUMN_WIDTH: f32 = 70.0;
+
+/// Lay out one column of a field row at least `width` wide, so the columns after it line up
+/// across rows. Wider content only pushes the rest of its own row.
+pub fn row_column<R>(ui: &mut Ui, width: f32, add_contents: impl FnOnce(&mut Ui) -> R) -> R {
+    let size = egui::vec2(width, ui.spacing().interact_size.y);
+    ui.allocate_ui_with_layout(
+        size,
+        egui::Layout::left_to_right(egui::Align::Center),
+        |ui| {
+            ui.set_min_width(width);
+            add_contents(ui)
+        },
+    )
+    .inner
+}
+
 pub fn text_edit_autowidth(ui: &mut Ui, text: &mut String) -> egui::Response {
     let display = if text.is_empty() {
         " ".to_string()
@@ -45,67 +253,481 @@ pub fn text_edit_autowidth(ui: &mut Ui, text: &mut String) -> egui::Response {
     )
 }
 
+/// Read a plain numeric value (integers/floats) at the field's address, preferring the bytes
+/// already present in `field.data` from the owning instance's block read
+pub fn read_field_value<T: TargetValue>(
+    handle: &AppHandle,
+    field: &MemoryField,
+    order: Endianness,
+) -> Option<T> {
+    let value = match field.data.as_deref() {
+        Some(data) if data.len() >= std::mem::size_of::<T>() => {
+            // SAFETY: `TargetValue` types are `Pod`, valid for any bit pattern
+            unsafe { std::ptr::read_unaligned(data.as_ptr() as *const T) }
+        }
+        _ => handle.read_sized::<T>(field.address).ok()?,
+    };
+    Some(from_target_order(value, order))
+}
+
+/// Read a primitive value at `address` in the byte order `order`
+pub fn read_value<T: TargetValue>(
+    handle: &AppHandle,
+    address: u64,
+    order: Endianness,
+) -> Option<T> {
+    handle
+        .read_sized::<T>(address)
+        .ok()
+        .map(|value| from_target_order(value, order))
+}
+
+/// Primitive values read from the target, byte-swapped for big-endian structures
+pub trait TargetValue: Pod {
+    fn swap_bytes(self) -> Self;
+}
+
+macro_rules! impl_target_value {
+    ($($t:ty),*) => {
+        $(impl TargetValue for $t {
+            fn swap_bytes(self) -> Self {
+                <$t>::swap_bytes(self)
+            }
+        })*
+    };
+}
+impl_target_value!(u8, u16, u32, u64, i8, i16, i32, i64);
+
+impl TargetValue for f32 {
+    fn swap_bytes(self) -> Self {
+        f32::from_bits(self.to_bits().swap_bytes())
+    }
+}
+
+impl TargetValue for f64 {
+    fn swap_bytes(self) -> Self {
+        f64::from_bits(self.to_bits().swap_bytes())
+    }
+}
+
+fn from_target_order<T: TargetValue>(value: T, order: Endianness) -> T {
+    if order == Endianness::Big {
+        value.swap_bytes()
+    } else {
+        value
+    }
+}
+
+/// Little-endian bytes of a scalar about to be written, reordered for big-endian structures
+pub fn to_target_order(mut bytes: Vec<u8>, order: Endianness) -> Vec<u8> {
+    if order == Endianness::Big {
+        bytes.reverse();
+    }
+    bytes
+}
+
+/// Read the pointer stored in the field, 4 or 8 bytes wide depending on the process
+pub fn read_field_pointer(
+    handle: &AppHandle,
+    field: &MemoryField,
+    order: Endianness,
+) -> Option<u64> {
+    if handle.pointer_size() == 4 {
+        read_field_value::<u32>(handle, field, order).map(u64::from)
+    } else {
+        read_field_value::<u64>(handle, field, order)
+    }
+}
+
+/// Read a pointer of the process's pointer size at `address`
+pub fn read_pointer_value(handle: &AppHandle, address: u64, order: Endianness) -> Option<u64> {
+    if handle.pointer_size() == 4 {
+        read_value::<u32>(handle, address, order).map(u64::from)
+    } else {
+        read_value::<u64>(handle, address, order)
+    }
+}
+
+/// Read `len` bytes at the field's address, preferring `field.data`
+pub fn read_field_bytes(handle: &AppHandle, field: &MemoryField, len: usize) -> Option<Vec<u8>> {
+    match field.data.as_deref() {
+        Some(data) if data.len() >= len => Some(data[..len].to_vec()),
+        _ => {
+            let mut buf = vec![0u8; len];
+            handle.read_slice(field.address, buf.as_mut_slice()).ok()?;
+            Some(buf)
+        }
+    }
+}
+
+/// Bytes listed by the value tooltip; longer fields are cut off
+const VALUE_BYTES_TOOLTIP_MAX: usize = 32;
+
+/// Hover tooltip of a value listing the raw bytes it was decoded from, e.g. "bytes: 00 00 80 3F"
+pub fn value_bytes_tooltip(
+    ui: &Ui,
+    resp: &egui::Response,
+    handle: &AppHandle,
+    field: &MemoryField,
+    size: u64,
+) {
+    if !ui.rect_contains_pointer(resp.rect) {
+        return;
+    }
+    let len = (size as usize).min(VALUE_BYTES_TOOLTIP_MAX);
+    let Some(bytes) = read_field_bytes(handle, field, len) else {
+        return;
+    };
+    let hex: Vec<String> = bytes.iter().map(|b| format!("{b:02X}")).collect();
+    let more = if size as usize > len { " …" } else { "" };
+    egui::show_tooltip_text(
+        ui.ctx(),
+        resp.id.with("value_bytes"),
+        format!("bytes: {}{more}", hex.join(" ")),
+    );
+}
+
+/// How values are turned into text, built by the GUI from its settings
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ValueFormat {
+    /// Decimals of float values; none shows the shortest form that reads back exactly
+    pub float_precision: Option<u8>,
+    /// Byte order values are read in, from the shown structure
+    pub endianness: Endianness,
+}
+
+impl ValueFormat {
+    /// The same format with floats in full, e.g. to pre-fill a value editor without rounding
+    /// the value
+    pub fn exact(self) -> Self {
+        Self {
+            float_precision: None,
+            ..self
+        }
+    }
+}
+
+/// Format a float with the precision of `format`. With fixed decimals, values that would print
+/// as a very long number or as zero (garbage reads) switch to scientific notation.
+pub fn format_float<T: Display + LowerExp + Into<f64> + Copy>(v: T, format: ValueFormat) -> String {
+    let x: f64 = v.into();
+    if x.is_nan() {
+        String::from("NaN")
+    } else if x.is_infinite() {
+        String::from(if x > 0.0 { "inf" } else { "-inf" })
+    } else if let Some(decimals) = format.float_precision {
+        let decimals = usize::from(decimals);
+        let tiny = decimals > 0 && x.abs() < 10f64.powi(-(decimals as i32));
+        if x != 0.0 && (tiny || x.abs() >= 1e7) {
+            format!("{v:.decimals$e}")
+        } else {
+            format!("{v:.decimals$}")
+        }
+    } else {
+        format!("{v}")
+    }
+}
+
+/// Decode consecutive f32 values, e.g. "(1.250, -3.000, 42.100)" with 3 decimals
+pub fn format_vector(bytes: &[u8], format: ValueFormat) -> String {
+    let components: Vec<String> = bytes
+        .chunks_exact(4)
+        .map(|c| {
+            let component = [c[0], c[1], c[2], c[3]];
+            let value = if format.endianness == Endianness::Big {
+                f32::from_be_bytes(component)
+            } else {
+                f32::from_le_bytes(component)
+            };
+            format_float(value, format)
+        })
+        .collect();
+    format!("({})", components.join(", "))
+}
+
+/// Compact form of a 4x4 float matrix for its row: the first row, e.g.
+/// "[(1.000, 0.000, 0.000, 0.000), …]". `matrix_tooltip` shows all rows.
+pub fn format_matrix_row(bytes: &[u8], format: ValueFormat) -> String {
+    format!(
+        "[{}, …]",
+        format_vector(&bytes[..bytes.len().min(16)], format)
+    )
+}
+
+/// Hover text of a Matrix4x4 value listing its four rows, one per line
+pub fn matrix_tooltip(
+    ui: &Ui,
+    resp: &egui::Response,
+    handle: &AppHandle,
+    field: &MemoryField,
+    format: ValueFormat,
+) {
+    if !ui.rect_contains_pointer(resp.rect) {
+        return;
+    }
+    let Some(bytes) = read_field_bytes(handle, field, 64) else {
+        return;
+    };
+    let rows: Vec<String> = bytes
+        .chunks_exact(16)
+        .map(|row| format_vector(row, format))
+        .collect();
+    egui::show_tooltip_text(ui.ctx(), resp.id.with("matrix_rows"), rows.join("\n"));
+}
+
+/// Format 16 bytes as `{XXXXXXXX-XXXX-XXXX-XXXX-XXXXXXXXXXXX}`; the first three groups are
+/// little-endian and the last two are kept in byte order, like a Windows `GUID`
+pub fn format_guid(bytes: &[u8]) -> Option<String> {
+    let b: &[u8; 16] = bytes.get(..16)?.try_into().ok()?;
+    Some(format!(
+        "{{{:08X}-{:04X}-{:04X}-{:02X}{:02X}-{:02X}{:02X}{:02X}{:02X}{:02X}{:02X}}}",
+        u32::from_le_bytes([b[0], b[1], b[2], b[3]]),
+        u16::from_le_bytes([b[4], b[5]]),
+        u16::from_le_bytes([b[6], b[7]]),
+        b[8],
+        b[9],
+        b[10],
+        b[11],
+        b[12],
+        b[13],
+        b[14],
+        b[15]
+    ))
+}
+
+/// Upper bound of UTF-16 code units read for a wide string behind a pointer
+const WIDE_TEXT_MAX_UNITS: usize = 64;
+
+/// Decode little-endian UTF-16 code units up to the first null terminator
+pub fn decode_utf16_until_nul(bytes: &[u8]) -> String {
+    let units: Vec<u16> = bytes
+        .chunks_exact(2)
+        .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+        .take_while(|unit| *unit != 0)
+        .collect();
+    String::from_utf16_lossy(&units)
+}
+
+/// Read a null terminated UTF-16 string of at most `WIDE_TEXT_MAX_UNITS` code units
+pub fn read_wide_string(handle: &AppHandle, address: u64) -> Option<String> {
+    let mut units = [0u16; WIDE_TEXT_MAX_UNITS];
+    handle.read_slice(address, &mut units).ok()?;
+    let len = units
+        .iter()
+        .position(|unit| *unit == 0)
+        .unwrap_or(units.len());
+    Some(String::from_utf16_lossy(&units[..len]))
+}
+
+/// Read `len` bytes at `address` as a narrow string, cut at the first null terminator
+pub fn read_text(handle: &AppHandle, address: u64, len: usize) -> Option<String> {
+    handle
+        .read_string_enc(address, len, TextEncoding::Utf8)
+        .ok()
+}
+
+/// Value of one `field_type` element of an array at `address`, as shown in its element row
+pub fn array_element_string(
+    handle: &AppHandle,
+    field_type: &FieldType,
+    address: u64,
+    format: ValueFormat,
+) -> Option<String> {
+    let order = format.endianness;
+    match field_type {
+        FieldType::Hex64 => {
+            read_value::<u64>(handle, address, order).map(|v| format!("0x{v:016X}"))
+        }
+        FieldType::Hex32 => read_value::<u32>(handle, address, order).map(|v| format!("0x{v:08X}")),
+        FieldType::Hex16 => read_value::<u16>(handle, address, order).map(|v| format!("0x{v:04X}")),
+        FieldType::Hex8 => read_value::<u8>(handle, address, order).map(|v| format!("0x{v:02X}")),
+        FieldType::UInt64 => read_value::<u64>(handle, address, order).map(|v| v.to_string()),
+        FieldType::UInt32 => read_value::<u32>(handle, address, order).map(|v| v.to_string()),
+        FieldType::UInt16 => read_value::<u16>(handle, address, order).map(|v| v.to_string()),
+        FieldType::UInt8 => read_value::<u8>(handle, address, order).map(|v| v.to_string()),
+        FieldType::Int64 => read_value::<i64>(handle, address, order).map(|v| v.to_string()),
+        FieldType::Int32 => read_value::<i32>(handle, address, order).map(|v| v.to_string()),
+        FieldType::Int16 => read_value::<i16>(handle, address, order).map(|v| v.to_string()),
+        FieldType::Int8 => read_value::<i8>(handle, address, order).map(|v| v.to_string()),
+        FieldType::Bool => read_value::<u8>(handle, address, order).map(|v| (v != 0).to_string()),
+        FieldType::Float => {
+            read_value::<f32>(handle, address, order).map(|v| format_float(v, format))
+        }
+        FieldType::Double => {
+            read_value::<f64>(handle, address, order).map(|v| format_float(v, format))
+        }
+        FieldType::Vector2 | FieldType::Vector3 | FieldType::Vector4 | FieldType::Quaternion => {
+            let mut buf = vec![0u8; field_type.get_size() as usize];
+            handle
+                .read_slice(address, buf.as_mut_slice())
+                .ok()
+                .map(|_| format_vector(&buf, format))
+        }
+        FieldType::Matrix4x4 => {
+            let mut buf = [0u8; 64];
+            handle
+                .read_slice(address, buf.as_mut_slice())
+                .ok()
+                .map(|_| format_matrix_row(&buf, format))
+        }
+        FieldType::UnixTime32 => read_value::<u32>(handle, address, order)
+            .and_then(|v| field_type.format_timestamp(v.into())),
+        FieldType::UnixTime64 | FieldType::Win32FileTime => {
+            read_value::<u64>(handle, address, order).and_then(|v| field_type.format_timestamp(v))
+        }
+        FieldType::Guid => {
+            let mut buf = [0u8; 16];
+            handle
+                .read_slice(address, buf.as_mut_slice())
+                .ok()
+                .and_then(|_| format_guid(&buf))
+        }
+        FieldType::Text => read_text(handle, address, field_type.get_size() as usize),
+        FieldType::WideText => read_wide_string(handle, address),
+        FieldType::TextPointer | FieldType::WideTextPointer | FieldType::Pointer => {
+            read_pointer_value(handle, address, order).map(|v| format!("0x{v:016X}"))
+        }
+        _ => None,
+    }
+}
+
+/// Value of a Text or TextPointer field reading `len` bytes of the string in `encoding`
+pub fn text_value_string(
+    handle: &AppHandle,
+    field: &MemoryField,
+    field_type: &FieldType,
+    len: usize,
+    encoding: TextEncoding,
+    order: Endianness,
+) -> Option<String> {
+    if *field_type == FieldType::TextPointer {
+        // The string itself lives outside of the block and is read from the process
+        return match read_field_pointer(handle, field, order)? {
+            0 => Some(String::from("(null)")),
+            ptr => handle.read_string_enc(ptr, len, encoding).ok(),
+        };
+    }
+    let local = field
+        .data
+        .as_deref()
+        .filter(|data| data.len() >= len)
+        .map(|data| encoding.decode(&data[..len]));
+    local.or_else(|| handle.read_string_enc(field.address, len, encoding).ok())
+}
+
+/// What the value column of a field row shows
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FieldValue {
+    /// No process attached, so there is nothing to read
+    NotAttached,
+    /// Attached, but the memory at the field could not be read
+    ReadFailed,
+    Value(String),
+}
+
+impl FieldValue {
+    /// Classify the result of a read made with an attached handle
+    pub fn from_read(value: Option<String>) -> Self {
+        value.map_or(FieldValue::ReadFailed, FieldValue::Value)
+    }
+
+    pub fn into_value(self) -> Option<String> {
+        match self {
+            FieldValue::Value(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+/// Value text of a field of `field_type`. Integer and hex fields are shown in `base`, or in
+/// the type's default base (hex for Hex types, decimal for integers) when it is `None`.
+/// Text fields are decoded with `encoding`.
 pub fn field_value_string(
     handle: Option<Arc<AppHandle>>,
     field: &MemoryField,
     field_type: &FieldType,
+    base: Option<NumberBase>,
+    encoding: TextEncoding,
+    format: ValueFormat,
 ) -> Option<String> {
     let handle = handle.as_ref()?;
-    let addr = field.address;
+    let integer_text = |raw: u64| {
+        let signed = matches!(
+            field_type,
+            FieldType::Int64 | FieldType::Int32 | FieldType::Int16 | FieldType::Int8
+        );
+        base.or(field_type.default_base())
+            .unwrap_or(NumberBase::Decimal)
+            .format(raw, field_type.get_size(), signed)
+    };
     match field_type {
-        FieldType::Hex64 => handle
-            .read_sized::<u64>(addr)
-            .ok()
-            .map(|v| format!("0x{v:016X}")),
-        FieldType::Hex32 => handle
-            .read_sized::<u32>(addr)
-            .ok()
-            .map(|v| format!("0x{v:08X}")),
-        FieldType::Hex16 => handle
-            .read_sized::<u16>(addr)
-            .ok()
-            .map(|v| format!("0x{v:04X}")),
-        FieldType::Hex8 => handle
-            .read_sized::<u8>(addr)
-            .ok()
-            .map(|v| format!("0x{v:02X}")),
-
-        FieldType::UInt64 => handle.read_sized::<u64>(addr).ok().map(|v| v.to_string()),
-        FieldType::UInt32 => handle.read_sized::<u32>(addr).ok().map(|v| v.to_string()),
-        FieldType::UInt16 => handle.read_sized::<u16>(addr).ok().map(|v| v.to_string()),
-        FieldType::UInt8 => handle.read_sized::<u8>(addr).ok().map(|v| v.to_string()),
-
-        FieldType::Int64 => handle.read_sized::<i64>(addr).ok().map(|v| v.to_string()),
-        FieldType::Int32 => handle.read_sized::<i32>(addr).ok().map(|v| v.to_string()),
-        FieldType::Int16 => handle.read_sized::<i16>(addr).ok().map(|v| v.to_string()),
-        FieldType::Int8 => handle.read_sized::<i8>(addr).ok().map(|v| v.to_string()),
-
-        FieldType::Bool => handle.read_sized::<u8>(addr).ok().map(|v| {
+        FieldType::Hex64 | FieldType::UInt64 | FieldType::Int64 => {
+            read_field_value::<u64>(handle, field, format.endianness).map(integer_text)
+        }
+        FieldType::Hex32 | FieldType::UInt32 | FieldType::Int32 => {
+            read_field_value::<u32>(handle, field, format.endianness)
+                .map(|v| integer_text(v.into()))
+        }
+        FieldType::Hex16 | FieldType::UInt16 | FieldType::Int16 => {
+            read_field_value::<u16>(handle, field, format.endianness)
+                .map(|v| integer_text(v.into()))
+        }
+        FieldType::Hex8 | FieldType::UInt8 | FieldType::Int8 => {
+            read_field_value::<u8>(handle, field, format.endianness).map(|v| integer_text(v.into()))
+        }
+
+        FieldType::Bool => read_field_value::<u8>(handle, field, format.endianness).map(|v| {
             if v != 0 {
                 "true".to_string()
             } else {
                 "false".to_string()
             }
         }),
-        FieldType::Float => handle.read_sized::<f32>(addr).ok().map(|v| format!("{v}")),
-        FieldType::Double => handle.read_sized::<f64>(addr).ok().map(|v| format!("{v}")),
+        FieldType::Float => read_field_value::<f32>(handle, field, format.endianness)
+            .map(|v| format_float(v, format)),
+        FieldType::Double => read_field_value::<f64>(handle, field, format.endianness)
+            .map(|v| format_float(v, format)),
 
-        FieldType::Vector3 | FieldType::Vector4 | FieldType::Vector2 => {
+        FieldType::Vector3 | FieldType::Vector4 | FieldType::Vector2 | FieldType::Quaternion => {
             let len = field_type.get_size() as usize;
-            let mut buf = vec![0u8; len];
-            (handle.read_slice(addr, buf.as_mut_slice()).ok()).map(|_| {
-                buf.iter()
-                    .map(|b| format!("{b:02X}"))
-                    .collect::<Vec<_>>()
-                    .join(" ")
-            })
+            read_field_bytes(handle, field, len).map(|buf| format_vector(&buf, format))
+        }
+        FieldType::Matrix4x4 => {
+            read_field_bytes(handle, field, 64).map(|buf| format_matrix_row(&buf, format))
         }
+        FieldType::Guid => read_field_bytes(handle, field, 16).and_then(|buf| format_guid(&buf)),
+        FieldType::UnixTime32 => read_field_value::<u32>(handle, field, format.endianness)
+            .and_then(|v| field_type.format_timestamp(v.into())),
+        FieldType::UnixTime64 | FieldType::Win32FileTime => {
+            read_field_value::<u64>(handle, field, format.endianness)
+                .and_then(|v| field_type.format_timestamp(v))
+        }
+
+        FieldType::Text => text_value_string(
+            handle,
+            field,
+            field_type,
+            field_type.get_size() as usize,
+            encoding,
+            format.endianness,
+        ),
+        FieldType::TextPointer => text_value_string(
+            handle,
+            field,
+            field_type,
+            TEXT_MAX_LENGTH,
+            encoding,
+            format.endianness,
+        ),
 
-        FieldType::Text => handle.read_string(addr, Some(32)).ok(),
-        FieldType::TextPointer => {
-            if let Ok(ptr) = handle.read_sized::<u64>(addr) {
+        FieldType::WideText => {
+            let len = field_type.get_size() as usize;
+            read_field_bytes(handle, field, len).map(|buf| decode_utf16_until_nul(&buf))
+        }
+        FieldType::WideTextPointer => {
+            if let Some(ptr) = read_field_pointer(handle, field, format.endianness) {
                 if ptr != 0 {
-                    handle.read_string(ptr, None).ok()
+                    read_wide_string(handle, ptr)
                 } else {
                     Some(String::from("(null)"))
                 }
@@ -118,5 +740,377 @@ pub fn field_value_string(
         FieldType::Array => None,
         FieldType::ClassInstance => None,
         FieldType::Enum => None,
+        // Needs the bit range from the definition, see `bitfield_value_string`
+        FieldType::Bitfield => None,
+    }
+}
+
+/// Read the backing integer of a Bitfield field and show the extracted bits with their range,
+/// e.g. "5 (bit3..bit5)"
+pub fn bitfield_value_string(
+    handle: &AppHandle,
+    fd: &FieldDefinition,
+    field: &MemoryField,
+    order: Endianness,
+) -> Option<String> {
+    let (size, offset, width) = fd.bitfield_layout();
+    let mut bytes = read_field_bytes(handle, field, size as usize)?;
+    if order == Endianness::Big {
+        bytes.reverse();
+    }
+    let mut raw = [0u8; 8];
+    raw[..bytes.len()].copy_from_slice(&bytes);
+    let value = fd.extract_bits(u64::from_le_bytes(raw));
+    let range = if width == 1 {
+        format!("bit{offset}")
+    } else {
+        format!("bit{offset}..bit{}", offset + width - 1)
+    };
+    Some(format!("{value} ({range})"))
+}
+
+/// Whether a simple field's value can be edited inline and written back
+pub fn is_value_editable(field_type: &FieldType) -> bool {
+    matches!(
+        field_type,
+        FieldType::Hex64
+            | FieldType::Hex32
+            | FieldType::Hex16
+            | FieldType::Hex8
+            | FieldType::Int64
+            | FieldType::Int32
+            | FieldType::Int16
+            | FieldType::Int8
+            | FieldType::UInt64
+            | FieldType::UInt32
+            | FieldType::UInt16
+            | FieldType::UInt8
+            | FieldType::Bool
+            | FieldType::Float
+            | FieldType::Double
+    )
+}
+
+/// Parse user input into the little-endian bytes of `field_type`.
+/// Returns None if the text does not parse or does not fit the type's width.
+/// Hex fields take unprefixed hex digits, so `0b` only means binary when the field is shown
+/// in binary (`base`); otherwise `0b1f` is the hex value 0xB1F.
+pub fn parse_value_bytes(
+    field_type: &FieldType,
+    input: &str,
+    base: Option<NumberBase>,
+) -> Option<Vec<u8>> {
+    let t = input.trim();
+    let parse_hex = |s: &str| -> Option<u64> {
+        if base == Some(NumberBase::Binary) {
+            if let Some(bits) = s.strip_prefix("0b").or_else(|| s.strip_prefix("0B")) {
+                return u64::from_str_radix(bits, 2).ok();
+            }
+        }
+        let digits = s
+            .strip_prefix("0x")
+            .or_else(|| s.strip_prefix("0X"))
+            .unwrap_or(s);
+        u64::from_str_radix(digits, 16).ok()
+    };
+    let parse_signed = |s: &str| -> Option<i128> {
+        match s.strip_prefix('-') {
+            Some(rest) => parse_hex_u64(rest).map(|v| -(v as i128)),
+            None => parse_hex_u64(s).map(|v| v as i128),
+        }
+    };
+    // Hex and binary input of a signed field may spell the raw bits, as shown in those bases
+    let radix_prefixed = t.len() > 2 && matches!(t.get(..2), Some("0x" | "0X" | "0b" | "0B"));
+    match field_type {
+        FieldType::Hex64 => parse_hex(t).map(|v| v.to_le_bytes().to_vec()),
+        FieldType::Hex32 => parse_hex(t)
+            .and_then(|v| u32::try_from(v).ok())
+            .map(|v| v.to_le_bytes().to_vec()),
+        FieldType::Hex16 => parse_hex(t)
+            .and_then(|v| u16::try_from(v).ok())
+            .map(|v| v.to_le_bytes().to_vec()),
+        FieldType::Hex8 => parse_hex(t)
+            .and_then(|v| u8::try_from(v).ok())
+            .map(|v| v.to_le_bytes().to_vec()),
+
+        FieldType::UInt64 => parse_hex_u64(t).map(|v| v.to_le_bytes().to_vec()),
+        FieldType::UInt32 => parse_hex_u64(t)
+            .and_then(|v| u32::try_from(v).ok())
+            .map(|v| v.to_le_bytes().to_vec()),
+        FieldType::UInt16 => parse_hex_u64(t)
+            .and_then(|v| u16::try_from(v).ok())
+            .map(|v| v.to_le_bytes().to_vec()),
+        FieldType::UInt8 => parse_hex_u64(t)
+            .and_then(|v| u8::try_from(v).ok())
+            .map(|v| v.to_le_bytes().to_vec()),
+
+        FieldType::Int64 => parse_signed(t)
+            .and_then(|v| {
+                i64::try_from(v).ok().or_else(|| {
+                    radix_prefixed
+                        .then(|| u64::try_from(v).ok().map(|u| u as i64))
+                        .flatten()
+                })
+            })
+            .map(|v| v.to_le_bytes().to_vec()),
+        FieldType::Int32 => parse_signed(t)
+            .and_then(|v| {
+                i32::try_from(v).ok().or_else(|| {
+                    radix_prefixed
+                        .then(|| u32::try_from(v).ok().map(|u| u as i32))
+                        .flatten()
+                })
+            })
+            .map(|v| v.to_le_bytes().to_vec()),
+        FieldType::Int16 => parse_signed(t)
+            .and_then(|v| {
+                i16::try_from(v).ok().or_else(|| {
+                    radix_prefixed
+                        .then(|| u16::try_from(v).ok().map(|u| u as i16))
+                        .flatten()
+                })
+            })
+            .map(|v| v.to_le_bytes().to_vec()),
+        FieldType::Int8 => parse_signed(t)
+            .and_then(|v| {
+                i8::try_from(v).ok().or_else(|| {
+                    radix_prefixed
+                        .then(|| u8::try_from(v).ok().map(|u| u as i8))
+                        .flatten()
+                })
+            })
+            .map(|v| v.to_le_bytes().to_vec()),
+
+        FieldType::Bool => match t.to_ascii_lowercase().as_str() {
+            "true" | "1" => Some(vec![1]),
+            "false" | "0" => Some(vec![0]),
+            _ => None,
+        },
+        FieldType::Float => t.parse::<f32>().ok().map(|v| v.to_le_bytes().to_vec()),
+        FieldType::Double => t.parse::<f64>().ok().map(|v| v.to_le_bytes().to_vec()),
+        _ => None,
+    }
+}
+
+/// One step from the start of the tree to a nested instance
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OffsetHop {
+    /// Embedded instance or array element at this offset of the enclosing instance
+    Embedded(u64),
+    /// Pointer at this offset of the enclosing instance, followed to its target
+    Pointer(u64),
+}
+
+/// Offset path of a field like "*0x10 -> 0x8 -> 0x40": the hops leading to its instance,
+/// pointers marked with `*`, then the field's own offset
+pub fn format_offset_path(hops: &[OffsetHop], offset: u64) -> String {
+    hops.iter()
+        .map(|hop| match hop {
+            OffsetHop::Embedded(o) => format!("0x{o:X}"),
+            OffsetHop::Pointer(o) => format!("*0x{o:X}"),
+        })
+        .chain(std::iter::once(format!("0x{offset:X}")))
+        .collect::<Vec<_>>()
+        .join(" -> ")
+}
+
+pub fn format_chain_offsets(offsets: &[i64]) -> String {
+    offsets
+        .iter()
+        .map(|o| format_signed_offset(*o))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Walk a pointer chain starting at the pointer stored at `address`.
+/// Returns the final value address, or None if any intermediate pointer is null or unreadable.
+pub fn resolve_pointer_chain(
+    handle: &AppHandle,
+    address: u64,
+    offsets: &[i64],
+    order: Endianness,
+) -> Option<u64> {
+    let pointer = read_pointer_value(handle, address, order)?;
+    walk_pointer_chain(pointer, offsets, |next| {
+        read_pointer_value(handle, next, order)
+    })
+}
+
+/// Longest array the length editors accept
+pub(super) const ARRAY_MAX_LENGTH: u32 = 1_048_576;
+
+/// Widest distance between array elements the stride editor accepts
+pub(super) const ARRAY_MAX_STRIDE: u64 = 0x10000;
+
+/// Upper bound for the pointer preview depth setting, keeps long chains from flooding the row
+pub const POINTER_PREVIEW_MAX_DEPTH: u8 = 8;
+
+/// `client.dll+0x1234` if `address` lies inside a loaded module, or
+/// `client.dll!SomeExport+0x4` if it lands on or shortly after an exported symbol
+pub fn module_relative_address(
+    handle: &AppHandle,
+    exports: &ExportCache,
+    address: u64,
+) -> Option<String> {
+    let module = handle.get_module_by_address(address)?;
+    let module_name = module.get_base_dll_name().unwrap_or("?");
+    Some(match exports.symbol(handle, address) {
+        Some((symbol, 0)) => format!("{module_name}!{symbol}"),
+        Some((symbol, displacement)) => format!("{module_name}!{symbol}+0x{displacement:X}"),
+        None => format!("{module_name}+0x{:X}", address - module.base_address),
+    })
+}
+
+/// Whether a pointer or instance at `address` leads somewhere readable
+pub fn is_live_address(handle: Option<&Arc<AppHandle>>, address: u64) -> bool {
+    address != 0 && handle.is_some_and(|h| h.read_sized::<u8>(address).is_ok())
+}
+
+/// Header arrow drawn like egui's default one, but hollow when the pointer or instance behind
+/// the header is null or unreadable, so live targets stand out without expanding them
+pub fn liveness_icon(live: bool) -> impl FnOnce(&mut Ui, f32, &egui::Response) + 'static {
+    move |ui, openness, response| {
+        let visuals = ui.style().interact(response);
+        let rect =
+            egui::Rect::from_center_size(response.rect.center(), response.rect.size() * 0.75)
+                .expand(visuals.expansion);
+        let rotation = egui::emath::Rot2::from_angle(egui::remap(
+            openness,
+            0.0..=1.0,
+            -std::f32::consts::TAU / 4.0..=0.0,
+        ));
+        let points = [rect.left_top(), rect.right_top(), rect.center_bottom()]
+            .into_iter()
+            .map(|p| rect.center() + rotation * (p - rect.center()))
+            .collect();
+        let shape = if live {
+            egui::Shape::convex_polygon(points, visuals.fg_stroke.color, egui::Stroke::NONE)
+        } else {
+            egui::Shape::closed_line(points, egui::Stroke::new(1.0, visuals.fg_stroke.color))
+        };
+        ui.painter().add(shape);
+    }
+}
+
+/// " (-> 0x00007FF6ABCD1234 client.dll+0x1234)" appended to pointer headers
+pub fn pointer_header_suffix(handle: &AppHandle, exports: &ExportCache, ptr: u64) -> String {
+    format!(" ({})", pointer_address_text(handle, exports, ptr))
+}
+
+/// Header suffix of a pointer to `class_name`: the address it holds, plus the runtime class
+/// from RTTI when it differs from the declared one, e.g. " (-> 0x1A2B, RTTI game::Enemy)"
+pub fn class_pointer_header_suffix(
+    handle: &AppHandle,
+    exports: &ExportCache,
+    ptr: u64,
+    class_name: &str,
+    rtti: Option<&str>,
+) -> String {
+    let address = pointer_address_text(handle, exports, ptr);
+    match rtti {
+        Some(rtti) if ptr != 0 && !rtti_matches(rtti, class_name) => {
+            format!(" ({address}, RTTI {rtti})")
+        }
+        _ => format!(" ({address})"),
+    }
+}
+
+/// Whether an RTTI name like `game::Player` names the class `declared`, which may or may not
+/// carry the namespace
+pub fn rtti_matches(rtti: &str, declared: &str) -> bool {
+    rtti == declared || rtti.rsplit("::").next() == Some(declared)
+}
+
+fn pointer_address_text(handle: &AppHandle, exports: &ExportCache, ptr: u64) -> String {
+    match module_relative_address(handle, exports, ptr) {
+        Some(module) => format!("-> 0x{ptr:016X} {module}"),
+        None => format!("-> 0x{ptr:016X}"),
+    }
+}
+
+/// Preview of a pointer field: the address it holds and the value it points to, e.g.
+/// "-> 0x1A2B = 42". Pointers to pointers are followed up to `max_depth` times
+/// ("-> 0xA -> 0xB = 0x0"), stopping at null, unreadable or already visited addresses.
+/// Addresses inside a module are followed by their module-relative form.
+pub fn pointer_preview_string(
+    handle: &Arc<AppHandle>,
+    exports: &ExportCache,
+    field: &MemoryField,
+    target: Option<&PointerTarget>,
+    max_depth: u8,
+    encoding: TextEncoding,
+    format: ValueFormat,
+) -> Option<String> {
+    let mut address = read_field_pointer(handle, field, format.endianness)?;
+    let target_type = match target {
+        Some(PointerTarget::FieldType(t)) => Some(t),
+        _ => None,
+    };
+    let mut out = String::new();
+    let mut visited = std::collections::HashSet::new();
+    let mut remaining = max_depth.clamp(1, POINTER_PREVIEW_MAX_DEPTH);
+    loop {
+        if !out.is_empty() {
+            out.push(' ');
+        }
+        if address == 0 {
+            out.push_str("-> null");
+            return Some(out);
+        }
+        out.push_str(&format!("-> 0x{address:X}"));
+        if let Some(module) = module_relative_address(handle, exports, address) {
+            out.push_str(&format!(" ({module})"));
+        }
+        if !visited.insert(address) {
+            out.push_str(" (cycle)");
+            return Some(out);
+        }
+        remaining -= 1;
+        let pointee = MemoryField::new_hex(address);
+        match target_type {
+            Some(FieldType::Pointer) if remaining > 0 => {
+                match read_pointer_value(handle, address, format.endianness) {
+                    Some(next) => address = next,
+                    None => {
+                        out.push_str(" = ??");
+                        return Some(out);
+                    }
+                }
+            }
+            Some(t) if *t != FieldType::Pointer => {
+                let value =
+                    field_value_string(Some(handle.clone()), &pointee, t, None, encoding, format)
+                        .unwrap_or_else(|| String::from("??"));
+                out.push_str(&format!(" = {value}"));
+                return Some(out);
+            }
+            // Untyped targets and the last hop of a pointer chain show the raw qword
+            _ => {
+                let value = read_field_value::<u64>(handle, &pointee, format.endianness)
+                    .map(|v| format!("0x{v:X}"))
+                    .unwrap_or_else(|| String::from("??"));
+                out.push_str(&format!(" = {value}"));
+                return Some(out);
+            }
+        }
+    }
+}
+
+pub fn pointer_chain_value_string(
+    handle: &AppHandle,
+    exports: &ExportCache,
+    address: u64,
+    offsets: &[i64],
+    order: Endianness,
+) -> String {
+    let Some(final_addr) = resolve_pointer_chain(handle, address, offsets, order) else {
+        return String::from("(invalid chain)");
+    };
+    match read_value::<u64>(handle, final_addr, order) {
+        Some(v) => match module_relative_address(handle, exports, final_addr) {
+            Some(module) => format!("-> 0x{final_addr:X} ({module}) = 0x{v:016X}"),
+            None => format!("-> 0x{final_addr:X} = 0x{v:016X}"),
+        },
+        None => String::from("(invalid chain)"),
     }
 }
@@ -0,0 +1,130 @@
+use eframe::egui::{
+    self,
+    RichText,
+    Ui,
+};
+
+use crate::{
+    memory::{
+        diagnostics::{
+            alignment_warnings,
+            duplicate_id_warnings,
+            AlignmentWarning,
+        },
+        MemoryStructure,
+    },
+    re_class_app::ReClassGui,
+};
+
+/// Fix picked from the warnings list
+enum LayoutFix {
+    Alignment(AlignmentWarning),
+    /// Give the field at this index a fresh id
+    DuplicateId(usize),
+}
+
+impl ReClassGui {
+    /// Collapsible list of alignment problems and duplicate field ids of the root class, each
+    /// with a fix
+    pub(super) fn layout_warnings_section(&mut self, ui: &mut Ui, memory: &mut MemoryStructure) {
+        let warnings = alignment_warnings(memory, &memory.root_class);
+        let duplicates = duplicate_id_warnings(memory, &memory.root_class);
+        if warnings.is_empty() && duplicates.is_empty() {
+            return;
+        }
+        let class_id = memory.root_class.class_id;
+        let Some(def) = memory.class_registry.get(class_id) else {
+            return;
+        };
+        let warn_color = ui.visuals().warn_fg_color;
+        let locked = self.structure_locked;
+        let mut fix: Option<LayoutFix> = None;
+        egui::CollapsingHeader::new(
+            RichText::new(format!("Warnings ({})", warnings.len() + duplicates.len()))
+                .color(warn_color),
+        )
+        .id_source("alignment_warnings")
+        .default_open(!duplicates.is_empty())
+        .show(ui, |ui| {
+            for warning in &duplicates {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(format!("+0x{:04X}", warning.offset)).monospace());
+                    let fd = &def.fields[warning.index];
+                    let name = fd.name.clone().unwrap_or_default();
+                    ui.label(format!(
+                        "{} {name} shares id {} with an earlier field",
+                        fd.field_type, warning.id
+                    ));
+                    if ui
+                        .add_enabled(!locked, egui::Button::new("New id").small())
+                        .on_hover_text("Give the field an id of its own")
+                        .clicked()
+                    {
+                        fix = Some(LayoutFix::DuplicateId(warning.index));
+                    }
+                });
+            }
+            for warning in &warnings {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(format!("+0x{:04X}", warning.offset())).monospace());
+                    let text = match warning {
+                        AlignmentWarning::MisalignedField {
+                            index, alignment, ..
+                        } => {
+                            let fd = &def.fields[*index];
+                            let name = fd.name.clone().unwrap_or_default();
+                            format!(
+                                "{} {name} is not {alignment}-byte aligned",
+                                fd.field_type
+                            )
+                        }
+                        AlignmentWarning::UnalignedSize { size, alignment } => format!(
+                            "Size 0x{size:X} is not a multiple of the largest member alignment ({alignment})"
+                        ),
+                    };
+                    ui.label(text);
+                    let hover = match warning {
+                        AlignmentWarning::MisalignedField { .. } => {
+                            format!("Insert {} hex byte(s) before the field", warning.padding())
+                        }
+                        AlignmentWarning::UnalignedSize { .. } => {
+                            format!("Add {} hex byte(s) at the end", warning.padding())
+                        }
+                    };
+                    if ui
+                        .add_enabled(!locked, egui::Button::new("Insert padding to align").small())
+                        .on_hover_text(hover)
+                        .clicked()
+                    {
+                        fix = Some(LayoutFix::Alignment(warning.clone()));
+                    }
+                });
+            }
+        });
+
+        let Some(fix) = fix else {
+            return;
+        };
+        let Some(def) = memory.class_registry.get_mut(class_id) else {
+            return;
+        };
+        match fix {
+            LayoutFix::Alignment(warning) => match warning {
+                AlignmentWarning::MisalignedField { index, .. } => {
+                    let created = def.insert_hex_bytes_at(index, warning.padding());
+                    if self.auto_name_fields {
+                        def.name_fields_by_offset(created);
+                    }
+                }
+                AlignmentWarning::UnalignedSize { .. } => {
+                    let created = def.add_hex_bytes(warning.padding());
+                    if self.auto_name_fields {
+                        def.name_fields_by_offset(created);
+                    }
+                }
+            },
+            LayoutFix::DuplicateId(index) => def.renew_field_id_at(index),
+        }
+        self.schedule_rebuild();
+    }
+}
@@ -0,0 +1,80 @@
+use eframe::egui::{
+    self,
+    Context,
+};
+
+use crate::re_class_app::ReClassGui;
+
+impl ReClassGui {
+    /// "Zero selected fields" entry of the selection menu; asks before writing anything
+    pub(super) fn zero_fields_button(&mut self, ui: &mut egui::Ui, ranges: Vec<(u64, u64)>) {
+        if ui
+            .add_enabled(
+                self.app.handle.is_some() && !ranges.is_empty(),
+                egui::Button::new("Zero selected fields"),
+            )
+            .on_hover_text("Overwrite the bytes of the selected fields in the process with zeros")
+            .clicked()
+        {
+            self.zero_fields_prompt = Some(ranges);
+            ui.close_menu();
+        }
+    }
+
+    /// Confirmation before zeroing field bytes in the live process
+    pub(crate) fn zero_fields_prompt_window(&mut self, ctx: &Context) {
+        let Some(ranges) = self.zero_fields_prompt.clone() else {
+            return;
+        };
+        let total: u64 = ranges.iter().map(|(_, len)| len).sum();
+        let mut open = true;
+        let mut decision: Option<bool> = None;
+        egui::Window::new("Zero fields")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(format!(
+                    "Write {total} zero byte(s) over the selected fields in the process? \
+                     The old values are lost."
+                ));
+                for (address, len) in &ranges {
+                    ui.monospace(format!("0x{address:X} .. 0x{:X}", address + len));
+                }
+                ui.horizontal(|ui| {
+                    if ui.button("Zero").clicked() {
+                        decision = Some(true);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        decision = Some(false);
+                    }
+                });
+            });
+        if decision == Some(true) {
+            self.zero_ranges(&ranges);
+        }
+        if decision.is_some() || !open {
+            self.zero_fields_prompt = None;
+        }
+    }
+
+    /// One write per contiguous range; failures are reported in the error dialog
+    fn zero_ranges(&mut self, ranges: &[(u64, u64)]) {
+        let Some(handle) = self.app.handle.clone() else {
+            return;
+        };
+        let failed: Vec<String> = ranges
+            .iter()
+            .filter(|(address, len)| {
+                handle
+                    .write_slice(*address, &vec![0u8; *len as usize])
+                    .is_err()
+            })
+            .map(|(address, _)| format!("0x{address:X}"))
+            .collect();
+        if !failed.is_empty() {
+            self.cycle_error_text = format!("Failed to zero the bytes at {}", failed.join(", "));
+            self.cycle_error_open = true;
+        }
+    }
+}
@@ -12,24 +12,94 @@ use eframe::egui::{
 
 use super::ReClassApp;
 
+mod analyze;
+mod bookmarks;
+mod comment_editor;
+mod dump;
+mod field_search;
 mod header;
+mod hex_view;
 pub mod memory_view;
+mod navigation;
+mod offset_search;
+mod pattern_scan;
 mod process;
+mod profiles;
+mod recovery;
+mod reference_scan;
+mod refresh_hotkey;
+mod replace_types;
+mod session;
+mod settings;
 mod signatures;
+mod tabs;
 mod theme;
+mod undo;
+mod value_scan;
+
+#[cfg(test)]
+mod tests;
+
+pub use theme::TreeColors;
+
+/// Fields listed in the tooltip of a class in the Definitions panel
+const CLASS_TOOLTIP_FIELDS: usize = 8;
 
 pub struct ReClassGui {
     app: ReClassApp,
+    // Open projects; the shown one lives in `app` and the fields below, see `tabs::ProjectTab`
+    tabs: Vec<tabs::ProjectTab>,
+    active_tab: usize,
+    // Id the next opened tab gets; ids are never reused, so they can key recovery files
+    next_tab_id: u64,
+    // Id of the tab with unsaved changes waiting for confirmation before it is closed
+    tab_close_prompt: Option<u64>,
+    // Byte ranges of selected fields waiting for confirmation before they are zeroed
+    zero_fields_prompt: Option<Vec<(u64, u64)>>,
+    // Fields (owner class, field ids) waiting for confirmation because they hold nested classes
+    remove_fields_prompt: Option<(u64, HashSet<u64>)>,
+    // "Replace field types" window of a class
+    replace_types: Option<replace_types::ReplaceTypesDialog>,
     attach_window_open: bool,
     process_filter: String,
+    dump_load: dump::DumpLoadState,
     modules_window_open: bool,
     modules_filter: String,
     signatures_window_open: bool,
+    pattern_scan: pattern_scan::PatternScanState,
+    value_scan_window: value_scan::ValueScanWindowState,
+    reference_scan: reference_scan::ReferenceScanState,
+    // Field types proposed by "Analyze region", shown until accepted or rejected
+    analyze_proposal: Option<analyze::AnalyzeProposal>,
+    // Array stride found by "Auto-detect", shown until applied or cancelled
+    stride_proposal: Option<analyze::StrideProposal>,
+    bookmarks: bookmarks::BookmarksPanelState,
+    profiles: profiles::ProfilesWindowState,
+    // Raw hex dump panel: start address, byte count and selected byte range (anchor, cursor)
+    hex_view_open: bool,
+    hex_view_address_buffer: String,
+    hex_view_address: u64,
+    hex_view_length: u64,
+    hex_view_selection: Option<(u64, u64)>,
+    // Instance shown side by side with the main tree ("Pin to compare")
+    compare_pin: Option<memory_view::ComparePin>,
+    // Address peeked at from the header without moving the root
+    quick_view: memory_view::QuickView,
     needs_rebuild: bool,
     field_name_buffers: std::collections::HashMap<memory_view::FieldKey, String>,
     class_type_buffers: std::collections::HashMap<memory_view::FieldKey, u64>,
     root_class_type_buffer: Option<String>,
     root_address_buffer: Option<String>,
+    // Last committed root address expression; re-resolved when attaching to a process
+    root_address_expression: Option<String>,
+    // Root address navigation: visited addresses, the current entry and the Goto (Ctrl+G) box
+    address_history: Vec<u64>,
+    address_history_cursor: usize,
+    // Roots left by "Follow pointer as root", oldest first
+    root_breadcrumbs: Vec<navigation::RootBreadcrumb>,
+    goto_open: bool,
+    goto_buffer: String,
+    goto_focus_pending: bool,
     cycle_error_open: bool,
     cycle_error_text: String,
     rename_dialog_open: bool,
@@ -37,33 +107,146 @@ pub struct ReClassGui {
     rename_buffer: String,
     rename_is_enum: bool,
     rename_error_text: Option<String>,
+    // Definition last clicked in the Definitions panel (id, is enum); F2 renames it
+    focused_definition: Option<(u64, bool)>,
     theme_applied: bool,
     ui_scale: f32,
+    // Session restore: state as last handed to storage, the user's opt-in and the current file
+    session: crate::re_class_app::session::SessionState,
+    restore_last_session: bool,
+    last_structure_path: Option<std::path::PathBuf>,
+    // Structure as last loaded or saved, to ask before closing a tab with edits
+    saved_snapshot: Option<String>,
+    // Auto-save: edits since the last save, when the shown tab's recovery file is next written
+    // and the recovery files of all tabs
+    structure_dirty: bool,
+    recovery_due: Option<std::time::Instant>,
+    recovery: recovery::RecoveryFiles,
+    // Recovery files from earlier runs are waiting to be restored or discarded
+    recovery_prompt: bool,
+    recent_files: Vec<std::path::PathBuf>,
+    // Disables layout edits; reading and value editing stay available
+    structure_locked: bool,
+    // Global key flipping reads_paused, polled even while another window has focus
+    refresh_hotkey_enabled: bool,
+    refresh_hotkey: u16,
+    refresh_hotkey_down: bool,
+    // Hex bytes a newly created class starts with
+    new_class_bytes: u64,
+    // New hex fields are named after their offset, e.g. field_0x40
+    auto_name_fields: bool,
+    // Type label and reference colors of the memory tree
+    tree_colors: TreeColors,
+    // Nested instances deeper than this show a stub instead of being read and rendered
+    max_render_depth: usize,
+    // Decimals of float, vector and matrix values; None shows them exactly
+    float_precision: Option<u8>,
+    settings_window_open: bool,
     class_filter: String,
+    field_search: field_search::FieldSearchState,
+    offset_search: offset_search::OffsetSearchState,
     enum_window_open: bool,
     enum_window_target: Option<u64>,
     enum_value_buffers: std::collections::HashMap<(String, usize), String>,
+    // "Paste from C++" source in the enum editor and its last parse error
+    enum_paste_open: bool,
+    enum_paste_buffer: String,
+    enum_paste_error: Option<String>,
     bytes_custom_buffer: String,
     // Selection state: limited to a single class instance at a time
     selected_instance_address: Option<u64>,
     selected_fields: std::collections::HashSet<memory_view::FieldKey>,
     selection_anchor: Option<(u64, usize)>,
+    // Field row currently being dragged to reorder it
+    field_drag: Option<memory_view::FieldDrag>,
+    // Field definition under the mouse, outlined in every rendered instance of its class. Rows
+    // paint the previous frame's value and report this frame's into `hovered_def_id_next`.
+    hovered_def_id: Option<u64>,
+    hovered_def_id_next: Option<u64>,
+    // Inline value editing: buffers for fields currently being edited
+    value_edit_buffers: std::collections::HashMap<memory_view::FieldKey, String>,
+    // Offsets being typed in the pointer chain editor, by (field definition id, index)
+    chain_offset_buffers: std::collections::HashMap<(u64, usize), String>,
+    value_edit_focus_pending: bool,
+    // Frozen fields: bytes captured at freeze time are written back every frame
+    frozen_fields: std::collections::HashSet<memory_view::FieldKey>,
+    frozen_values: std::collections::HashMap<memory_view::FieldKey, Vec<u8>>,
+    // "Plot value" windows: recent values of each plotted field, sampled at the refresh rate
+    value_plots: std::collections::HashMap<memory_view::FieldKey, memory_view::ValuePlot>,
+    // Root instance bytes captured by "Snapshot"; Compare tints fields that changed since
+    snapshot: Option<crate::memory::snapshot::Snapshot>,
+    snapshot_compare: bool,
+    // Expand all / Collapse all: forced open state of the tree headers and frames left to apply it
+    tree_open_override: Option<(bool, u8)>,
+    // Ids of the open tree headers with the field or class each shows; they start open again
+    // when egui has no state for them
+    open_tree_headers: std::collections::HashMap<u64, u64>,
+    // Offset column: relative to the owning instance, or to the start of the enclosing struct
+    offsets_from_root: bool,
+    offset_base: u64,
+    // Embedded and pointer hops from the tree root to the instance being rendered
+    offset_hops: Vec<memory_view::OffsetHop>,
+    // Instances nested around the one being rendered, checked against max_render_depth
+    render_depth: usize,
+    // Field whose comment is open in the multiline editor
+    comment_editor: Option<comment_editor::CommentEditor>,
+    // Fields copied via the context menu, pasted into any class
+    field_clipboard: Option<crate::memory::clipboard::FieldClipboard>,
+    // Virtualized rendering: measured row heights per list and the height of a simple field row
+    virtual_row_heights: std::collections::HashMap<egui::Id, Vec<f32>>,
+    field_row_height: f32,
+    // Number of pointers followed when previewing a pointer to a pointer
+    pointer_preview_depth: u8,
+    // Undo/redo: snapshot of the structure as of the last recorded edit
+    undo_stack: crate::memory::undo::UndoStack,
+    undo_baseline: Option<String>,
+    undo_toast: Option<(String, std::time::Instant)>,
 }
 
 impl ReClassGui {
-    pub fn new() -> anyhow::Result<Self> {
-        Ok(Self {
+    pub fn new(storage: Option<&dyn eframe::Storage>) -> anyhow::Result<Self> {
+        let session = crate::re_class_app::session::SessionState::load(storage);
+        let mut gui = Self {
             app: ReClassApp::new()?,
+            tabs: vec![tabs::ProjectTab::new(1)],
+            active_tab: 0,
+            next_tab_id: 2,
+            tab_close_prompt: None,
+            zero_fields_prompt: None,
+            remove_fields_prompt: None,
+            replace_types: None,
             attach_window_open: false,
             process_filter: String::new(),
+            dump_load: dump::DumpLoadState::default(),
             modules_window_open: false,
             modules_filter: String::new(),
             signatures_window_open: false,
+            pattern_scan: pattern_scan::PatternScanState::default(),
+            value_scan_window: value_scan::ValueScanWindowState::default(),
+            reference_scan: reference_scan::ReferenceScanState::default(),
+            analyze_proposal: None,
+            stride_proposal: None,
+            bookmarks: bookmarks::BookmarksPanelState::default(),
+            profiles: profiles::ProfilesWindowState::default(),
+            hex_view_open: false,
+            hex_view_address_buffer: String::new(),
+            hex_view_address: 0,
+            hex_view_length: 0x1000,
+            hex_view_selection: None,
+            compare_pin: None,
+            quick_view: memory_view::QuickView::default(),
             needs_rebuild: false,
             field_name_buffers: std::collections::HashMap::new(),
             class_type_buffers: std::collections::HashMap::new(),
             root_class_type_buffer: None,
             root_address_buffer: None,
+            root_address_expression: None,
+            address_history: Vec::new(),
+            address_history_cursor: 0,
+            root_breadcrumbs: Vec::new(),
+            goto_open: false,
+            goto_buffer: String::new(),
+            goto_focus_pending: false,
             cycle_error_open: false,
             cycle_error_text: String::new(),
             rename_dialog_open: false,
@@ -71,22 +254,116 @@ impl ReClassGui {
             rename_buffer: String::new(),
             rename_is_enum: false,
             rename_error_text: None,
+            focused_definition: None,
             theme_applied: false,
             ui_scale: 1.0,
+            restore_last_session: session.restore,
+            recent_files: session.recent_files.clone(),
+            structure_locked: session.structure_locked,
+            refresh_hotkey_enabled: session.refresh_hotkey_enabled,
+            refresh_hotkey: session.refresh_hotkey,
+            refresh_hotkey_down: false,
+            new_class_bytes: session
+                .new_class_bytes
+                .clamp(1, settings::NEW_CLASS_BYTES_MAX),
+            auto_name_fields: session.auto_name_fields,
+            tree_colors: session.tree_colors,
+            max_render_depth: session
+                .max_render_depth
+                .clamp(1, settings::MAX_RENDER_DEPTH_LIMIT),
+            float_precision: session
+                .float_precision
+                .map(|decimals| decimals.min(settings::FLOAT_DECIMALS_MAX)),
+            settings_window_open: false,
+            open_tree_headers: session.open_tree_header_owners.iter().copied().collect(),
+            recovery: recovery::RecoveryFiles::new(
+                crate::re_class_app::session::recovery_dir(),
+                session.recovery_files.clone(),
+            ),
+            session,
+            last_structure_path: None,
+            saved_snapshot: None,
+            structure_dirty: false,
+            recovery_due: None,
+            recovery_prompt: false,
             class_filter: String::new(),
+            field_search: field_search::FieldSearchState::default(),
+            offset_search: offset_search::OffsetSearchState::default(),
             enum_window_open: false,
             enum_window_target: None,
             enum_value_buffers: std::collections::HashMap::new(),
+            enum_paste_open: false,
+            enum_paste_buffer: String::new(),
+            enum_paste_error: None,
             bytes_custom_buffer: String::new(),
             selected_instance_address: None,
             selected_fields: std::collections::HashSet::new(),
             selection_anchor: None,
-        })
+            field_drag: None,
+            hovered_def_id: None,
+            hovered_def_id_next: None,
+            value_edit_buffers: std::collections::HashMap::new(),
+            chain_offset_buffers: std::collections::HashMap::new(),
+            value_edit_focus_pending: false,
+            frozen_fields: std::collections::HashSet::new(),
+            frozen_values: std::collections::HashMap::new(),
+            value_plots: std::collections::HashMap::new(),
+            snapshot: None,
+            snapshot_compare: false,
+            tree_open_override: None,
+            offsets_from_root: false,
+            offset_base: 0,
+            offset_hops: Vec::new(),
+            render_depth: 0,
+            comment_editor: None,
+            field_clipboard: None,
+            virtual_row_heights: std::collections::HashMap::new(),
+            pointer_preview_depth: 1,
+            field_row_height: 0.0,
+            undo_stack: crate::memory::undo::UndoStack::new(undo::UNDO_CAPACITY),
+            undo_baseline: None,
+            undo_toast: None,
+        };
+        gui.restore_session();
+        gui.check_recovery_files();
+        Ok(gui)
     }
 
     fn schedule_rebuild(&mut self) {
         self.needs_rebuild = true;
     }
+
+    /// Open the rename dialog for a class or enum, prefilled with its current name
+    fn open_rename_dialog(&mut self, id: u64, is_enum: bool) {
+        let name = self.app.get_memory_structure().and_then(|ms| {
+            if is_enum {
+                ms.enum_registry.get(id).map(|d| d.name.clone())
+            } else {
+                ms.class_registry.get(id).map(|d| d.name.clone())
+            }
+        });
+        let Some(name) = name else {
+            return;
+        };
+        self.rename_dialog_open = true;
+        self.rename_target_id = id;
+        self.rename_is_enum = is_enum;
+        self.rename_buffer = name;
+        self.rename_error_text = None;
+    }
+
+    /// F2 renames the focused definition. Ignored while a text field has focus.
+    fn handle_definition_shortcuts(&mut self, ctx: &Context) {
+        if ctx.memory(|m| m.focused().is_some()) || self.structure_locked {
+            return;
+        }
+        let Some((id, is_enum)) = self.focused_definition else {
+            return;
+        };
+        if ctx.input_mut(|i| i.consume_key(egui::Modifiers::NONE, egui::Key::F2)) {
+            self.open_rename_dialog(id, is_enum);
+        }
+    }
 }
 
 impl eframe::App for ReClassGui {
@@ -94,6 +371,27 @@ impl eframe::App for ReClassGui {
         // Apply theme & style once
         self.apply_theme_once(ctx);
 
+        self.handle_undo_shortcuts(ctx);
+        self.handle_navigation_shortcuts(ctx);
+        self.handle_field_shortcuts(ctx);
+        self.handle_definition_shortcuts(ctx);
+        self.poll_refresh_hotkey(ctx);
+
+        // Keep frozen values pinned; repaint continuously while anything is frozen
+        self.apply_frozen_values();
+        if !self.frozen_fields.is_empty() {
+            ctx.request_repaint();
+        }
+        self.sample_value_plots();
+        // A process that went away shows as exited instead of a tree of failed reads
+        if self.app.check_process_exited() {
+            self.detach_from_process();
+        }
+        // Keep live values updating at the refresh rate even while the mouse is idle
+        if self.app.handle.is_some() && !self.app.reads_paused {
+            ctx.request_repaint_after(std::time::Duration::from_millis(self.app.read_interval_ms));
+        }
+
         // Top bar
         let top_fill = ctx.style().visuals.faint_bg_color;
         let top_stroke = egui::Stroke::new(1.0, Color32::from_black_alpha(60));
@@ -106,6 +404,8 @@ impl eframe::App for ReClassGui {
             )
             .show(ctx, |ui| {
                 self.header_bar(ui);
+                ui.separator();
+                self.tab_bar(ui);
             });
 
         // Left: class and enum definitions
@@ -121,6 +421,8 @@ impl eframe::App for ReClassGui {
                     self.class_filter.clear();
                 }
             });
+            self.field_search_section(ui);
+            self.offset_search_section(ui);
             ui.separator();
             let snapshot = self.app.get_memory_structure().map(|ms| {
                 let ids = ms.class_registry.get_class_ids();
@@ -128,6 +430,8 @@ impl eframe::App for ReClassGui {
                 let mut referenced: HashSet<u64> = HashSet::new();
                 for cid in &ids {
                     if let Some(def) = ms.class_registry.get(*cid) {
+                        // Removing a base class would shift every field of the derived class
+                        if let Some(base) = def.base_class_id { referenced.insert(base); }
                         for f in &def.fields {
                             if f.field_type == crate::memory::FieldType::ClassInstance {
                                 if let Some(cid) = f.class_id { if let Some(d) = ms.class_registry.get_by_id(cid) { referenced.insert(d.id); } }
@@ -150,13 +454,7 @@ impl eframe::App for ReClassGui {
                     .filter(|cid| {
                         if **cid == root_id { return false; }
                         if referenced.contains(cid) { return false; }
-                        if let Some(def) = ms.class_registry.get(**cid) {
-                            if def.fields.len() == 1 {
-                                let f = &def.fields[0];
-                                return f.field_type == crate::memory::FieldType::Hex64 && f.name.is_none();
-                            }
-                        }
-                        false
+                        ms.class_registry.get(**cid).is_some_and(|def| def.is_placeholder())
                     })
                     .cloned()
                     .collect();
@@ -174,7 +472,7 @@ impl eframe::App for ReClassGui {
                         .unwrap_or(false));
                 }
                 if ui
-                    .add_enabled(!unused.is_empty(), egui::Button::new("Delete unused"))
+                    .add_enabled(!unused.is_empty() && !self.structure_locked, egui::Button::new("Delete unused"))
                     .on_hover_text("Delete class definitions that have only the default field and are not referenced anywhere (excluding current root)")
                     .clicked()
                 {
@@ -197,7 +495,21 @@ impl eframe::App for ReClassGui {
                         if active == cid {
                             button = button.fill(egui::Color32::from_rgb(40, 80, 160));
                         }
-                        let resp = ui.add(button);
+                        if self.focused_definition == Some((cid, false)) {
+                            button = button.stroke(ui.visuals().selection.stroke);
+                        }
+                        let ms = self.app.get_memory_structure();
+                        let resp = ui.add(button).on_hover_ui(|ui| {
+                            // Built only while hovered
+                            if let Some(summary) =
+                                ms.and_then(|ms| ms.class_summary(cid, CLASS_TOOLTIP_FIELDS))
+                            {
+                                ui.label(summary);
+                            }
+                        });
+                        if resp.clicked() {
+                            self.focused_definition = Some((cid, false));
+                        }
                         if resp.double_clicked() {
                             if let Some(ms_mut) = self.app.get_memory_structure_mut() {
                                 if ms_mut.set_root_class_by_id(cid) {
@@ -206,17 +518,10 @@ impl eframe::App for ReClassGui {
                             }
                         }
                         let can_remove = cid != root_id && !referenced.contains(&cid);
+                        let locked = self.structure_locked;
                         resp.context_menu(|ui| {
-                            if ui.button("Rename").clicked() {
-                                self.rename_dialog_open = true;
-                                self.rename_target_id = cid;
-                                self.rename_is_enum = false;
-                                self.rename_buffer = self
-                                    .app
-                                    .get_memory_structure()
-                                    .and_then(|ms| ms.class_registry.get(cid).map(|d| d.name.clone()))
-                                    .unwrap_or_default();
-                                self.rename_error_text = None;
+                            if ui.add_enabled(!locked, egui::Button::new("Rename")).on_hover_text("F2").clicked() {
+                                self.open_rename_dialog(cid, false);
                                 ui.close_menu();
                             }
                             if ui.button("Set as root").clicked() {
@@ -227,8 +532,26 @@ impl eframe::App for ReClassGui {
                                 }
                                 ui.close_menu();
                             }
+                            if ui
+                                .add_enabled(!locked, egui::Button::new("Duplicate"))
+                                .on_hover_text("Copy this class under a new name; references inside keep their targets")
+                                .clicked()
+                            {
+                                if let Some(ms_mut) = self.app.get_memory_structure_mut() {
+                                    ms_mut.class_registry.duplicate(cid);
+                                }
+                                ui.close_menu();
+                            }
+                            if ui
+                                .add_enabled(!locked, egui::Button::new("Replace field types…"))
+                                .on_hover_text("Change every field of one type to another, e.g. all Hex64 to Pointer")
+                                .clicked()
+                            {
+                                self.replace_types = Some(replace_types::ReplaceTypesDialog::new(cid));
+                                ui.close_menu();
+                            }
                             let remove_btn = ui.add_enabled(
-                                can_remove,
+                                can_remove && !locked,
                                 egui::Button::new("Remove"),
                             );
                             if remove_btn.clicked() {
@@ -244,7 +567,7 @@ impl eframe::App for ReClassGui {
                 ui.separator();
                 ui.horizontal(|ui| {
                     ui.label("Enums");
-                    if ui.button("New").clicked() {
+                    if ui.add_enabled(!self.structure_locked, egui::Button::new("New")).clicked() {
                         if let Some(ms) = self.app.get_memory_structure_mut() {
                             let base = "NewEnum";
                             let mut name = base.to_string();
@@ -260,24 +583,25 @@ impl eframe::App for ReClassGui {
                 ScrollArea::vertical().id_source("enum_defs_scroll").show(ui, |ui| {
                     for id in enum_ids {
                         let name = self.app.get_memory_structure().and_then(|ms| ms.enum_registry.get(id).map(|d| d.name.clone())).unwrap_or_default();
-                        let mut resp = ui.label(name.clone());
-                        resp = resp.on_hover_text("Right-click to edit");
+                        let focused = self.focused_definition == Some((id, true));
+                        let mut resp = ui.selectable_label(focused, name);
+                        if resp.clicked() {
+                            self.focused_definition = Some((id, true));
+                        }
+                        resp = resp.on_hover_text("Right-click to edit, F2 to rename");
+                        let locked = self.structure_locked;
                         resp.context_menu(|ui| {
-                            if ui.button("Rename").clicked() {
-                                self.rename_dialog_open = true;
-                                self.rename_target_id = id;
-                                self.rename_is_enum = true;
-                                self.rename_buffer = name.clone();
-                                self.rename_error_text = None;
+                            if ui.add_enabled(!locked, egui::Button::new("Rename")).on_hover_text("F2").clicked() {
+                                self.open_rename_dialog(id, true);
                                 ui.close_menu();
                             }
-                            if ui.button("Open editor").clicked() {
+                            if ui.add_enabled(!locked, egui::Button::new("Open editor")).clicked() {
                                 self.enum_window_open = true;
                                 self.enum_window_target = Some(id);
                                 ui.close_menu();
                             }
                             // Delete only if not referenced
-                            if ui.button("Delete").clicked() {
+                            if ui.add_enabled(!locked, egui::Button::new("Delete")).clicked() {
                                 if let Some(ms) = self.app.get_memory_structure_mut() {
                                     if !ms.is_enum_referenced(id) {
                                         ms.enum_registry.remove(id);
@@ -294,10 +618,42 @@ impl eframe::App for ReClassGui {
             }
         });
 
+        // Right: saved addresses and raw hex dump
+        if self.bookmarks.open {
+            self.bookmarks_panel(ctx);
+        }
+        if self.hex_view_open {
+            self.hex_view_panel(ctx);
+        }
+        self.compare_panel(ctx);
+
         // Center
         CentralPanel::default().show(ctx, |ui| {
             self.memory_structure_panel(ui);
         });
+        // Rows above the hovered one were painted before it was known
+        let hovered_def_id = self.hovered_def_id_next.take();
+        if hovered_def_id != self.hovered_def_id {
+            self.hovered_def_id = hovered_def_id;
+            ctx.request_repaint();
+        }
+        // Headers opened this frame only lay out their bodies on the next one, so the override
+        // is kept for a few frames to reach nested levels
+        if let Some((open, frames)) = self.tree_open_override {
+            self.tree_open_override = (frames > 1).then_some((open, frames - 1));
+            ctx.request_repaint();
+        }
+
+        self.tab_close_prompt_window(ctx);
+        self.recovery_prompt_window(ctx);
+        self.zero_fields_prompt_window(ctx);
+        self.remove_fields_prompt_window(ctx);
+        self.replace_types_window(ctx);
+        self.quick_view_window(ctx);
+        if self.profiles.open {
+            self.profiles_window(ctx);
+        }
+        self.value_plot_windows(ctx);
 
         // Error dialog for cycle prevention
         if self.cycle_error_open {
@@ -446,6 +802,8 @@ impl eframe::App for ReClassGui {
                                     ui.end_row();
 
                                     let mut delete_index: Option<usize> = None;
+                                    let mut move_request: Option<(usize, bool)> = None;
+                                    let variant_count = def.variants.len();
                                     for (idx, var) in def.variants.iter_mut().enumerate() {
                                         let key = (def.name.clone(), idx);
                                         // Auto-width name editor
@@ -477,20 +835,46 @@ impl eframe::App for ReClassGui {
                                         if resp_val.lost_focus()
                                             || ui.input(|i| i.key_pressed(egui::Key::Enter))
                                         {
-                                            if let Ok(parsed) = val_buf.parse::<u32>() {
+                                            if let Ok(parsed) = val_buf.trim().parse::<i64>() {
                                                 var.value = parsed;
                                             }
                                         }
 
-                                        if ui.button("Delete").clicked() {
-                                            delete_index = Some(idx);
-                                        }
+                                        ui.horizontal(|ui| {
+                                            if ui
+                                                .add_enabled(idx > 0, egui::Button::new("⏶"))
+                                                .on_hover_text("Move up")
+                                                .clicked()
+                                            {
+                                                move_request = Some((idx, true));
+                                            }
+                                            if ui
+                                                .add_enabled(
+                                                    idx + 1 < variant_count,
+                                                    egui::Button::new("⏷"),
+                                                )
+                                                .on_hover_text("Move down")
+                                                .clicked()
+                                            {
+                                                move_request = Some((idx, false));
+                                            }
+                                            if ui.button("Delete").clicked() {
+                                                delete_index = Some(idx);
+                                            }
+                                        });
                                         ui.end_row();
                                     }
                                     if let Some(di) = delete_index {
                                         def.variants.remove(di);
                                         self.enum_value_buffers.retain(|(n, _), _| n != &def.name);
                                     }
+                                    if let Some((idx, up)) = move_request {
+                                        if def.move_variant(idx, up) {
+                                            // Value buffers are keyed by row
+                                            self.enum_value_buffers
+                                                .retain(|(n, _), _| n != &def.name);
+                                        }
+                                    }
                                 });
                             ui.separator();
                             ui.separator();
@@ -522,7 +906,7 @@ impl eframe::App for ReClassGui {
                                     def.is_flags = flags;
                                     if def.is_flags {
                                         // Recompute to powers of two from current ordering
-                                        let mut v: u32 = 1;
+                                        let mut v: i64 = 1;
                                         for var in &mut def.variants {
                                             var.value = v;
                                             if v == 0 {
@@ -533,6 +917,11 @@ impl eframe::App for ReClassGui {
                                     }
                                 }
                             });
+                            ui.horizontal(|ui| {
+                                ui.checkbox(&mut def.is_signed, "Signed").on_hover_text(
+                                    "Read the backing integer as signed so negative values match",
+                                );
+                            });
                             if ui
                                 .button("Add value")
                                 .on_hover_text("Append a new variant with next id")
@@ -540,8 +929,8 @@ impl eframe::App for ReClassGui {
                             {
                                 let next_val = if def.is_flags {
                                     // next power of two
-                                    let mut v: u32 = 1;
-                                    let used: std::collections::HashSet<u32> =
+                                    let mut v: i64 = 1;
+                                    let used: std::collections::HashSet<i64> =
                                         def.variants.iter().map(|vv| vv.value).collect();
                                     while used.contains(&v) {
                                         if v == 0 {
@@ -567,6 +956,67 @@ impl eframe::App for ReClassGui {
                                     value: next_val,
                                 });
                             }
+                            if ui
+                                .button("Sort by value")
+                                .on_hover_text(
+                                    "Order the variants by ascending value; flags keep their bits",
+                                )
+                                .clicked()
+                            {
+                                def.sort_variants_by_value();
+                                self.enum_value_buffers.retain(|(n, _), _| n != &def.name);
+                            }
+                            if ui
+                                .button("Paste from C++")
+                                .on_hover_text(
+                                    "Replace the variants with an enum parsed from C/C++ source",
+                                )
+                                .clicked()
+                            {
+                                self.enum_paste_open = !self.enum_paste_open;
+                                self.enum_paste_error = None;
+                            }
+                            if self.enum_paste_open {
+                                ui.add(
+                                    egui::TextEdit::multiline(&mut self.enum_paste_buffer)
+                                        .code_editor()
+                                        .desired_rows(8)
+                                        .desired_width(f32::INFINITY)
+                                        .hint_text("enum Foo { A = 1, B, C = 0x10 };"),
+                                );
+                                ui.horizontal(|ui| {
+                                    if ui.button("Parse").clicked() {
+                                        // Existing variants are only replaced by a successful parse
+                                        match crate::import::cpp_enum::parse_cpp_enum(
+                                            &self.enum_paste_buffer,
+                                        ) {
+                                            Ok(parsed) => {
+                                                def.variants = parsed.variants;
+                                                def.is_signed = parsed.is_signed;
+                                                if def.default_size != parsed.default_size {
+                                                    def.default_size = parsed.default_size;
+                                                    self.needs_rebuild = true;
+                                                }
+                                                self.enum_value_buffers
+                                                    .retain(|(n, _), _| n != &def.name);
+                                                self.enum_paste_buffer.clear();
+                                                self.enum_paste_error = None;
+                                                self.enum_paste_open = false;
+                                            }
+                                            Err(err) => {
+                                                self.enum_paste_error = Some(err.to_string())
+                                            }
+                                        }
+                                    }
+                                    if ui.button("Cancel").clicked() {
+                                        self.enum_paste_error = None;
+                                        self.enum_paste_open = false;
+                                    }
+                                });
+                                if let Some(err) = &self.enum_paste_error {
+                                    ui.colored_label(ui.visuals().error_fg_color, err);
+                                }
+                            }
                         } else {
                             ui.label("Enum not found");
                         }
@@ -581,6 +1031,7 @@ impl eframe::App for ReClassGui {
         }
 
         // Apply deferred rebuilds
+        let rebuilt = self.needs_rebuild;
         if self.needs_rebuild {
             if let Some(ms) = self.app.get_memory_structure_mut() {
                 ms.rebuild_root_from_registry();
@@ -588,15 +1039,57 @@ impl eframe::App for ReClassGui {
             }
             self.needs_rebuild = false;
         }
+        // A drag released outside any field row is cancelled
+        if self.field_drag.is_some() {
+            if ctx.input(|i| i.pointer.any_down()) {
+                ctx.set_cursor_icon(egui::CursorIcon::Grabbing);
+            } else {
+                self.field_drag = None;
+            }
+        }
+        self.track_undo_state(ctx, rebuilt);
+        self.undo_toast(ctx);
+        self.autosave(ctx);
 
         if self.attach_window_open {
             self.attach_window(ctx);
         }
+        if self.dump_load.open {
+            self.dump_load_window(ctx);
+        }
         if self.modules_window_open {
             self.modules_window(ctx);
         }
         if self.signatures_window_open {
             self.signatures_window(ctx);
         }
+        if self.pattern_scan.open {
+            self.pattern_scan_window(ctx);
+        }
+        if self.value_scan_window.open {
+            self.value_scan_window(ctx);
+        }
+        if self.reference_scan.open {
+            self.reference_scan_window(ctx);
+        }
+        if self.settings_window_open {
+            self.settings_window(ctx);
+        }
+        if self.goto_open {
+            self.goto_window(ctx);
+        }
+        if self.comment_editor.is_some() {
+            self.comment_editor_window(ctx);
+        }
+        if self.analyze_proposal.is_some() {
+            self.analyze_window(ctx);
+        }
+        if self.stride_proposal.is_some() {
+            self.stride_proposal_window(ctx);
+        }
+    }
+
+    fn save(&mut self, storage: &mut dyn eframe::Storage) {
+        self.save_session(storage);
     }
 }
@@ -0,0 +1,317 @@
+use eframe::egui::{
+    self,
+    Context,
+    Key,
+    KeyboardShortcut,
+    Modifiers,
+    Ui,
+};
+
+use super::{
+    memory_view::read_pointer_value,
+    ReClassGui,
+};
+
+/// Oldest root addresses are dropped once the history holds this many
+const ADDRESS_HISTORY_CAPACITY: usize = 100;
+
+/// Root left behind by "Follow pointer as root", "Go to class definition" or a quick view
+/// promoted to root
+pub(super) struct RootBreadcrumb {
+    pub address: u64,
+    pub class_id: u64,
+}
+
+impl ReClassGui {
+    /// Make `address` the root address and record the move in the navigation history
+    pub(super) fn navigate_to_address(&mut self, address: u64, expression: String) {
+        let Some(ms) = self.app.get_memory_structure_mut() else {
+            return;
+        };
+        let previous = ms.root_class.address;
+        ms.set_root_address(address);
+        self.app.invalidate_read_cache();
+        self.root_address_buffer = None;
+        self.root_address_expression = Some(expression);
+        self.record_address_visit(previous, address);
+    }
+
+    /// Push `to` after the current history entry, dropping any forward entries like a browser
+    pub(super) fn record_address_visit(&mut self, from: u64, to: u64) {
+        if from == to {
+            return;
+        }
+        self.address_history
+            .truncate(self.address_history_cursor + 1);
+        // The root may have moved without going through the history, e.g. on attach
+        if self.address_history.last() != Some(&from) {
+            self.address_history.push(from);
+        }
+        self.address_history.push(to);
+        let excess = self
+            .address_history
+            .len()
+            .saturating_sub(ADDRESS_HISTORY_CAPACITY);
+        self.address_history.drain(..excess);
+        self.address_history_cursor = self.address_history.len() - 1;
+    }
+
+    /// Read the pointer at `pointer_address` and make its target the root, switching to the
+    /// pointee class if there is one. The current root is kept as a breadcrumb.
+    pub(super) fn follow_pointer_as_root(&mut self, pointer_address: u64, class_id: Option<u64>) {
+        let Some(handle) = self.app.handle.clone() else {
+            return;
+        };
+        let target = match read_pointer_value(&handle, pointer_address, self.byte_order()) {
+            Some(0) => {
+                self.cycle_error_text = format!("The pointer at 0x{pointer_address:X} is null.");
+                self.cycle_error_open = true;
+                return;
+            }
+            Some(target) => target,
+            None => {
+                self.cycle_error_text =
+                    format!("Could not read the pointer at 0x{pointer_address:X}.");
+                self.cycle_error_open = true;
+                return;
+            }
+        };
+        self.move_root_with_breadcrumb(target, class_id, format!("0x{target:X}"));
+    }
+
+    /// Make `address` the root, switching to `class_id` if given, and keep the current root
+    /// as a breadcrumb
+    pub(super) fn move_root_with_breadcrumb(
+        &mut self,
+        address: u64,
+        class_id: Option<u64>,
+        expression: String,
+    ) {
+        let Some(ms) = self.app.get_memory_structure_mut() else {
+            return;
+        };
+        self.root_breadcrumbs.push(RootBreadcrumb {
+            address: ms.root_class.address,
+            class_id: ms.root_class.class_id,
+        });
+        if let Some(class_id) = class_id {
+            if class_id != ms.root_class.class_id && ms.set_root_class_by_id(class_id) {
+                self.root_class_type_buffer = None;
+                self.needs_rebuild = true;
+            }
+        }
+        self.navigate_to_address(address, expression);
+    }
+
+    /// Make `class_id` the root class to edit its layout, moving the root to `address` (the
+    /// instance the user came from) when it is known. The current root is kept as a
+    /// breadcrumb.
+    pub(super) fn go_to_class_definition(&mut self, class_id: u64, address: Option<u64>) {
+        let Some(ms) = self.app.get_memory_structure_mut() else {
+            return;
+        };
+        if class_id == ms.root_class.class_id || !ms.class_registry.contains(class_id) {
+            return;
+        }
+        let crumb = RootBreadcrumb {
+            address: ms.root_class.address,
+            class_id: ms.root_class.class_id,
+        };
+        if !ms.set_root_class_by_id(class_id) {
+            return;
+        }
+        self.root_breadcrumbs.push(crumb);
+        self.root_class_type_buffer = None;
+        self.focused_definition = Some((class_id, false));
+        self.needs_rebuild = true;
+        if let Some(address) = address {
+            self.navigate_to_address(address, format!("0x{address:X}"));
+        }
+    }
+
+    /// Go back up to the breadcrumb at `index`, dropping it and everything followed after it
+    fn return_to_breadcrumb(&mut self, index: usize) {
+        let Some(crumb) = self.root_breadcrumbs.get(index) else {
+            return;
+        };
+        let (address, class_id) = (crumb.address, crumb.class_id);
+        self.root_breadcrumbs.truncate(index);
+        let Some(ms) = self.app.get_memory_structure_mut() else {
+            return;
+        };
+        if class_id != ms.root_class.class_id && ms.set_root_class_by_id(class_id) {
+            self.root_class_type_buffer = None;
+            self.needs_rebuild = true;
+        }
+        self.navigate_to_address(address, format!("0x{address:X}"));
+    }
+
+    /// Chain of roots followed through pointers; click an entry to pop back up to it
+    pub(super) fn root_breadcrumb_bar(&mut self, ui: &mut Ui) {
+        if self.root_breadcrumbs.is_empty() {
+            return;
+        }
+        let Some(ms) = self.app.get_memory_structure() else {
+            return;
+        };
+        let crumb_label = |class_id: u64, address: u64| {
+            let name = ms
+                .class_registry
+                .get(class_id)
+                .map(|def| def.name.clone())
+                .unwrap_or_else(|| format!("#{class_id}"));
+            format!("{name} @ 0x{address:X}")
+        };
+        let labels: Vec<String> = self
+            .root_breadcrumbs
+            .iter()
+            .map(|crumb| crumb_label(crumb.class_id, crumb.address))
+            .collect();
+        let current = crumb_label(ms.root_class.class_id, ms.root_class.address);
+        let mut clicked: Option<usize> = None;
+        let mut clear = false;
+        ui.horizontal_wrapped(|ui| {
+            for (index, label) in labels.iter().enumerate() {
+                if ui
+                    .link(label)
+                    .on_hover_text("Return to this root")
+                    .clicked()
+                {
+                    clicked = Some(index);
+                }
+                ui.label(">");
+            }
+            ui.strong(current);
+            if ui
+                .small_button("x")
+                .on_hover_text("Forget the followed pointers")
+                .clicked()
+            {
+                clear = true;
+            }
+        });
+        if let Some(index) = clicked {
+            self.return_to_breadcrumb(index);
+        } else if clear {
+            self.root_breadcrumbs.clear();
+        }
+    }
+
+    fn can_step_address_history(&self, back: bool) -> bool {
+        if back {
+            self.address_history_cursor > 0
+        } else {
+            self.address_history_cursor + 1 < self.address_history.len()
+        }
+    }
+
+    fn step_address_history(&mut self, back: bool) {
+        if !self.can_step_address_history(back) {
+            return;
+        }
+        let cursor = if back {
+            self.address_history_cursor - 1
+        } else {
+            self.address_history_cursor + 1
+        };
+        let address = self.address_history[cursor];
+        let Some(ms) = self.app.get_memory_structure_mut() else {
+            return;
+        };
+        ms.set_root_address(address);
+        self.app.invalidate_read_cache();
+        self.root_address_buffer = None;
+        self.root_address_expression = Some(format!("0x{address:X}"));
+        self.address_history_cursor = cursor;
+    }
+
+    pub(super) fn handle_navigation_shortcuts(&mut self, ctx: &Context) {
+        let goto = KeyboardShortcut::new(Modifiers::COMMAND, Key::G);
+        if ctx.input_mut(|i| i.consume_shortcut(&goto)) {
+            self.goto_open = true;
+            self.goto_focus_pending = true;
+        }
+        if ctx.memory(|m| m.focused().is_some()) {
+            return;
+        }
+        let back = KeyboardShortcut::new(Modifiers::ALT, Key::ArrowLeft);
+        let forward = KeyboardShortcut::new(Modifiers::ALT, Key::ArrowRight);
+        if ctx.input_mut(|i| i.consume_shortcut(&back)) {
+            self.step_address_history(true);
+        } else if ctx.input_mut(|i| i.consume_shortcut(&forward)) {
+            self.step_address_history(false);
+        }
+    }
+
+    /// Back/forward buttons and the Goto button of the header bar
+    pub(super) fn navigation_buttons(&mut self, ui: &mut Ui) {
+        if ui
+            .add_enabled(self.can_step_address_history(true), egui::Button::new("<"))
+            .on_hover_text("Back to the previous root address (Alt+Left)")
+            .clicked()
+        {
+            self.step_address_history(true);
+        }
+        if ui
+            .add_enabled(self.can_step_address_history(false), egui::Button::new(">"))
+            .on_hover_text("Forward (Alt+Right)")
+            .clicked()
+        {
+            self.step_address_history(false);
+        }
+        if ui
+            .button("Goto")
+            .on_hover_text("Set the root address from an expression (Ctrl+G)")
+            .clicked()
+        {
+            self.goto_open = true;
+            self.goto_focus_pending = true;
+        }
+    }
+
+    pub(super) fn goto_window(&mut self, ctx: &Context) {
+        let mut open = self.goto_open;
+        let mut commit = false;
+        egui::Window::new("Goto address")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    let resp = ui.add(
+                        egui::TextEdit::singleline(&mut self.goto_buffer)
+                            .hint_text("client.dll+0x1234")
+                            .desired_width(220.0),
+                    );
+                    if self.goto_focus_pending {
+                        resp.request_focus();
+                        self.goto_focus_pending = false;
+                    }
+                    let enter = resp.lost_focus() && ui.input(|i| i.key_pressed(egui::Key::Enter));
+                    if ui.button("Go").clicked() || enter {
+                        commit = true;
+                    }
+                });
+            });
+        self.goto_open = open;
+
+        if commit {
+            let input = self.goto_buffer.trim().to_string();
+            match self.eval_root_address_input(&input) {
+                Some(address) => {
+                    self.navigate_to_address(address, input);
+                    self.goto_open = false;
+                }
+                None => {
+                    self.cycle_error_text = match self.missing_module_in_expression(&input) {
+                        Some(module) => {
+                            format!("Module '{module}' is not loaded in the attached process.")
+                        }
+                        None => format!("Invalid address: {input}"),
+                    };
+                    self.cycle_error_open = true;
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,135 @@
+use eframe::egui::{
+    self,
+    RichText,
+    ScrollArea,
+    Ui,
+};
+
+use super::ReClassGui;
+use crate::memory::FieldType;
+
+/// Types offered by the offset search type filter
+const OFFSET_SEARCH_TYPES: [FieldType; 33] = [
+    FieldType::Hex8,
+    FieldType::Hex16,
+    FieldType::Hex32,
+    FieldType::Hex64,
+    FieldType::Int8,
+    FieldType::Int16,
+    FieldType::Int32,
+    FieldType::Int64,
+    FieldType::UInt8,
+    FieldType::UInt16,
+    FieldType::UInt32,
+    FieldType::UInt64,
+    FieldType::Bool,
+    FieldType::Float,
+    FieldType::Double,
+    FieldType::Vector2,
+    FieldType::Vector3,
+    FieldType::Vector4,
+    FieldType::Quaternion,
+    FieldType::Matrix4x4,
+    FieldType::Guid,
+    FieldType::UnixTime32,
+    FieldType::UnixTime64,
+    FieldType::Win32FileTime,
+    FieldType::Text,
+    FieldType::TextPointer,
+    FieldType::WideText,
+    FieldType::WideTextPointer,
+    FieldType::Pointer,
+    FieldType::Enum,
+    FieldType::Array,
+    FieldType::Bitfield,
+    FieldType::ClassInstance,
+];
+
+/// "Which classes have a field at +0xNN" search, in the definitions panel
+#[derive(Default)]
+pub(super) struct OffsetSearchState {
+    /// Hex offset, with or without `+`/`0x`
+    pub offset: String,
+    /// Only match fields of this type
+    pub field_type: Option<FieldType>,
+}
+
+fn parse_offset(text: &str) -> Option<u64> {
+    let t = text.trim().trim_start_matches('+');
+    let t = t
+        .strip_prefix("0x")
+        .or_else(|| t.strip_prefix("0X"))
+        .unwrap_or(t);
+    u64::from_str_radix(t, 16).ok()
+}
+
+impl ReClassGui {
+    pub(super) fn offset_search_section(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("Offset:");
+            ui.add(
+                egui::TextEdit::singleline(&mut self.offset_search.offset)
+                    .hint_text("+0x40")
+                    .desired_width(70.0),
+            );
+            let selected = self
+                .offset_search
+                .field_type
+                .as_ref()
+                .map(FieldType::get_display_name)
+                .unwrap_or("Any type");
+            egui::ComboBox::from_id_source("offset_search_type")
+                .selected_text(selected)
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut self.offset_search.field_type, None, "Any type");
+                    for t in OFFSET_SEARCH_TYPES {
+                        let name = t.get_display_name();
+                        ui.selectable_value(&mut self.offset_search.field_type, Some(t), name);
+                    }
+                });
+            if ui.button("Clear").clicked() {
+                self.offset_search.offset.clear();
+            }
+        });
+        if self.offset_search.offset.trim().is_empty() {
+            return;
+        }
+        let Some(offset) = parse_offset(&self.offset_search.offset) else {
+            ui.label(RichText::new("Enter a hex offset").weak());
+            return;
+        };
+        let Some(ms) = self.app.get_memory_structure() else {
+            return;
+        };
+        let results: Vec<(u64, String)> = ms
+            .classes_with_field_at(offset, self.offset_search.field_type.clone())
+            .into_iter()
+            .filter_map(|cid| Some((cid, ms.class_registry.get(cid)?.name.clone())))
+            .collect();
+        if results.is_empty() {
+            ui.label(RichText::new(format!("No class has a field at +0x{offset:X}")).weak());
+            return;
+        }
+        let mut set_root: Option<u64> = None;
+        ScrollArea::vertical()
+            .id_source("offset_search_scroll")
+            .max_height(120.0)
+            .show(ui, |ui| {
+                ui.horizontal_wrapped(|ui| {
+                    for (cid, name) in &results {
+                        if ui.button(name).on_hover_text("Set as root").clicked() {
+                            set_root = Some(*cid);
+                        }
+                    }
+                });
+            });
+        if let Some(cid) = set_root {
+            if let Some(ms) = self.app.get_memory_structure_mut() {
+                if ms.set_root_class_by_id(cid) {
+                    self.root_class_type_buffer = None;
+                    self.needs_rebuild = true;
+                }
+            }
+        }
+    }
+}
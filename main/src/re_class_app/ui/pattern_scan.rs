@@ -0,0 +1,183 @@
+use std::sync::mpsc::{
+    self,
+    Receiver,
+};
+
+use eframe::egui::{
+    self,
+    Context,
+    ScrollArea,
+};
+
+use super::ReClassGui;
+
+/// Stop collecting matches after this many results
+const PATTERN_SCAN_MAX_RESULTS: usize = 1000;
+
+/// State of the "Pattern Scan" window
+#[derive(Default)]
+pub(super) struct PatternScanState {
+    pub open: bool,
+    pub pattern: String,
+    pub module: Option<String>,
+    pub results: Vec<u64>,
+    pub status: Option<String>,
+    /// Receives the matches of the scan currently running on a background thread
+    pub job: Option<Receiver<Vec<u64>>>,
+}
+
+impl ReClassGui {
+    fn start_pattern_scan(&mut self) {
+        let scan = &mut self.pattern_scan;
+        let sanitized = scan
+            .pattern
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ");
+        let Some(pattern) = handle::ByteSequencePattern::parse(&sanitized) else {
+            scan.status = Some(String::from("Invalid pattern"));
+            return;
+        };
+        let Some(handle) = self.app.handle.clone() else {
+            scan.status = Some(String::from("Not attached to a process"));
+            return;
+        };
+        let Some(module) = scan
+            .module
+            .as_deref()
+            .and_then(|name| handle.get_module_by_name(name))
+            .map(|m| (m.base_address, m.module_size as usize))
+        else {
+            scan.status = Some(String::from("Select a module"));
+            return;
+        };
+
+        let (tx, rx) = mpsc::channel();
+        std::thread::spawn(move || {
+            let matches =
+                handle.find_all_patterns(module.0, module.1, &pattern, PATTERN_SCAN_MAX_RESULTS);
+            let _ = tx.send(matches);
+        });
+        scan.results.clear();
+        scan.status = Some(String::from("Scanning..."));
+        scan.job = Some(rx);
+    }
+
+    fn poll_pattern_scan(&mut self, ctx: &Context) {
+        let scan = &mut self.pattern_scan;
+        let Some(job) = &scan.job else {
+            return;
+        };
+        match job.try_recv() {
+            Ok(matches) => {
+                scan.status = Some(match matches.len() {
+                    0 => String::from("No match"),
+                    1 => String::from("1 match"),
+                    n if n >= PATTERN_SCAN_MAX_RESULTS => format!("{n}+ matches (list truncated)"),
+                    n => format!("{n} matches"),
+                });
+                scan.results = matches;
+                scan.job = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => ctx.request_repaint(),
+            Err(mpsc::TryRecvError::Disconnected) => {
+                scan.status = Some(String::from("Scan failed"));
+                scan.job = None;
+            }
+        }
+    }
+
+    pub(super) fn pattern_scan_window(&mut self, ctx: &Context) {
+        self.poll_pattern_scan(ctx);
+
+        let mut open = self.pattern_scan.open;
+        let mut chosen: Option<u64> = None;
+        egui::Window::new("Pattern Scan")
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let mut module_names: Vec<String> = self
+                    .app
+                    .get_modules()
+                    .iter()
+                    .filter_map(|m| m.get_base_dll_name().map(str::to_string))
+                    .collect();
+                module_names.sort_by_key(|name| name.to_ascii_lowercase());
+
+                ui.horizontal(|ui| {
+                    ui.label("Pattern:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.pattern_scan.pattern)
+                            .hint_text("48 8B 05 ?? ?? ?? ??")
+                            .desired_width(260.0),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Module:");
+                    egui::ComboBox::from_id_source("pattern_scan_module")
+                        .selected_text(
+                            self.pattern_scan
+                                .module
+                                .clone()
+                                .unwrap_or_else(|| String::from("Select module")),
+                        )
+                        .show_ui(ui, |ui| {
+                            for name in &module_names {
+                                ui.selectable_value(
+                                    &mut self.pattern_scan.module,
+                                    Some(name.clone()),
+                                    name,
+                                );
+                            }
+                        });
+                    let scanning = self.pattern_scan.job.is_some();
+                    if ui
+                        .add_enabled(!scanning, egui::Button::new("Scan"))
+                        .clicked()
+                    {
+                        self.start_pattern_scan();
+                    }
+                    if scanning {
+                        ui.spinner();
+                    }
+                });
+                if let Some(status) = &self.pattern_scan.status {
+                    ui.label(status);
+                }
+                ui.separator();
+
+                let handle = self.app.handle.clone();
+                ScrollArea::vertical().show_rows(
+                    ui,
+                    ui.text_style_height(&egui::TextStyle::Body),
+                    self.pattern_scan.results.len(),
+                    |ui, rows| {
+                        for &address in &self.pattern_scan.results[rows] {
+                            let label = match handle
+                                .as_ref()
+                                .and_then(|h| h.get_module_by_address(address))
+                            {
+                                Some(m) => format!(
+                                    "0x{address:X}  ({}+0x{:X})",
+                                    m.get_base_dll_name().unwrap_or("?"),
+                                    address - m.base_address
+                                ),
+                                None => format!("0x{address:X}"),
+                            };
+                            let resp = ui
+                                .selectable_label(false, egui::RichText::new(label).monospace())
+                                .on_hover_text("Double-click to use as root address");
+                            if resp.double_clicked() {
+                                chosen = Some(address);
+                            }
+                        }
+                    },
+                );
+            });
+        self.pattern_scan.open = open;
+
+        if let Some(address) = chosen {
+            self.navigate_to_address(address, format!("0x{address:X}"));
+        }
+    }
+}
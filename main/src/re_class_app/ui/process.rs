@@ -54,15 +54,58 @@ impl ReClassGui {
             });
 
         if let Some(pid) = clicked_pid {
-            if let Some(proc_info) = self.app.get_process_by_id(pid) {
-                self.app.select_process(*proc_info);
-            }
-            let _ = self.app.create_handle(pid);
-            let _ = self.app.fetch_modules(pid);
+            self.attach_to_process(pid);
             self.attach_window_open = false;
         }
     }
 
+    pub(super) fn attach_to_process(&mut self, pid: u32) {
+        if let Some(proc_info) = self.app.get_process_by_id(pid) {
+            self.app.select_process(*proc_info);
+        }
+        let _ = self.app.create_handle(pid);
+        let _ = self.app.fetch_modules(pid);
+        // Module bases differ per process; re-resolve the root address expression
+        if let Some(addr) = self.resolve_root_address_expression() {
+            if let Some(ms) = self.app.get_memory_structure_mut() {
+                ms.set_root_address(addr);
+            }
+        }
+        self.apply_attached_profile();
+        self.app.invalidate_read_cache();
+    }
+
+    pub(super) fn detach_from_process(&mut self) {
+        self.app.detach();
+        self.modules_window_open = false;
+        self.frozen_fields.clear();
+        self.frozen_values.clear();
+        self.value_plots.clear();
+    }
+
+    /// Attach to a running process with the same name as the one that exited, or let the user
+    /// pick one if there is none
+    pub(super) fn reattach_exited_process(&mut self) {
+        let _ = self.app.fetch_processes();
+        let name = self
+            .app
+            .process_state
+            .exited_process
+            .as_ref()
+            .and_then(|p| p.get_image_base_name().map(str::to_string));
+        let pid = name.and_then(|name| {
+            self.app
+                .get_processes()
+                .iter()
+                .find(|p| p.get_image_base_name() == Some(name.as_str()))
+                .map(|p| p.process_id)
+        });
+        match pid {
+            Some(pid) => self.attach_to_process(pid),
+            None => self.attach_window_open = true,
+        }
+    }
+
     pub(super) fn modules_window(&mut self, ctx: &Context) {
         let selected_pid = self
             .app
@@ -0,0 +1,233 @@
+use eframe::egui::{
+    self,
+    Context,
+    RichText,
+    ScrollArea,
+};
+
+use super::ReClassGui;
+use crate::memory::{
+    Bookmark,
+    Profile,
+};
+
+/// Inputs of the "Profiles" window; the profiles themselves are saved with the structure
+#[derive(Default)]
+pub(super) struct ProfilesWindowState {
+    pub open: bool,
+    pub name: String,
+    pub image: String,
+    /// Save the current root with the new profile so attaching opens it
+    pub with_root: bool,
+}
+
+impl ReClassGui {
+    /// Image base name of the attached process
+    fn attached_image(&self) -> Option<String> {
+        self.app
+            .process_state
+            .selected_process
+            .as_ref()
+            .and_then(|p| p.get_image_base_name())
+            .map(str::to_string)
+    }
+
+    /// The current root as a bookmark pinning the root class
+    fn current_root_bookmark(&self) -> Option<Bookmark> {
+        let ms = self.app.get_memory_structure()?;
+        Some(Bookmark {
+            name: String::from("Root"),
+            expr: self
+                .root_address_expression
+                .clone()
+                .unwrap_or_else(|| format!("0x{:X}", ms.root_class.address)),
+            class_id: Some(ms.root_class.class_id),
+        })
+    }
+
+    /// Add the profile's bookmarks and open its root, if it has one
+    fn apply_profile(&mut self, profile: &Profile) {
+        let Some(ms) = self.app.get_memory_structure_mut() else {
+            return;
+        };
+        if ms.merge_profile_bookmarks(profile) > 0 {
+            self.bookmarks.open = true;
+        }
+        if let Some(root) = &profile.root {
+            self.open_bookmark(root);
+        }
+    }
+
+    /// Apply the first profile whose image matches the process just attached to
+    pub(super) fn apply_attached_profile(&mut self) {
+        let Some(image) = self.attached_image() else {
+            return;
+        };
+        let profile = self
+            .app
+            .get_memory_structure()
+            .and_then(|ms| ms.profile_for_image(&image))
+            .cloned();
+        if let Some(profile) = profile {
+            self.apply_profile(&profile);
+        }
+    }
+
+    pub(super) fn profiles_window(&mut self, ctx: &Context) {
+        let mut open = self.profiles.open;
+        let mut apply: Option<Profile> = None;
+        let mut update: Option<usize> = None;
+        let mut remove: Option<usize> = None;
+        let mut save = false;
+        let attached_image = self.attached_image();
+        egui::Window::new("Profiles")
+            .open(&mut open)
+            .resizable(true)
+            .default_width(420.0)
+            .show(ctx, |ui| {
+                let Some(ms) = self.app.get_memory_structure() else {
+                    ui.label("No structure loaded");
+                    return;
+                };
+                ui.label(
+                    RichText::new(
+                        "Attaching to a process with a profile's image adds its bookmarks and \
+                         opens its root",
+                    )
+                    .weak(),
+                );
+                egui::Grid::new("profile_add_grid")
+                    .num_columns(2)
+                    .show(ui, |ui| {
+                        ui.label("Name:");
+                        ui.text_edit_singleline(&mut self.profiles.name);
+                        ui.end_row();
+                        ui.label("Image:");
+                        ui.add(
+                            egui::TextEdit::singleline(&mut self.profiles.image)
+                                .hint_text(attached_image.as_deref().unwrap_or("game.exe")),
+                        );
+                        ui.end_row();
+                    });
+                ui.checkbox(
+                    &mut self.profiles.with_root,
+                    "Open the current root on attach",
+                );
+                let image_given =
+                    !self.profiles.image.trim().is_empty() || attached_image.is_some();
+                if ui
+                    .add_enabled(image_given, egui::Button::new("Save current bookmarks"))
+                    .on_hover_text(format!(
+                        "Create a profile with the {} bookmark(s) of this project",
+                        ms.bookmarks.len()
+                    ))
+                    .clicked()
+                {
+                    save = true;
+                }
+                ui.separator();
+
+                if ms.profiles.is_empty() {
+                    ui.label(RichText::new("No profiles yet").weak());
+                }
+                ScrollArea::vertical()
+                    .id_source("profiles_scroll")
+                    .show(ui, |ui| {
+                        for (index, profile) in ms.profiles.iter().enumerate() {
+                            let attached = attached_image
+                                .as_deref()
+                                .is_some_and(|image| profile.matches_image(image));
+                            ui.horizontal(|ui| {
+                                let title = RichText::new(&profile.name).strong();
+                                ui.label(if attached {
+                                    title.color(ui.visuals().selection.stroke.color)
+                                } else {
+                                    title
+                                });
+                                let mut detail = format!(
+                                    "{}  {} bookmark(s)",
+                                    profile.image,
+                                    profile.bookmarks.len()
+                                );
+                                if let Some(root) = &profile.root {
+                                    detail.push_str(&format!(", root {}", root.expr));
+                                }
+                                ui.label(RichText::new(detail).monospace().weak());
+                            });
+                            ui.horizontal(|ui| {
+                                if ui
+                                    .button("Apply")
+                                    .on_hover_text("Add its bookmarks and open its root now")
+                                    .clicked()
+                                {
+                                    apply = Some(profile.clone());
+                                }
+                                if ui
+                                    .button("Update")
+                                    .on_hover_text(
+                                        "Replace its bookmarks (and root, if it has one) with \
+                                         the current ones",
+                                    )
+                                    .clicked()
+                                {
+                                    update = Some(index);
+                                }
+                                if ui.button("Delete").clicked() {
+                                    remove = Some(index);
+                                }
+                            });
+                            ui.separator();
+                        }
+                    });
+            });
+        self.profiles.open = open;
+
+        if save {
+            let image = match self.profiles.image.trim() {
+                "" => attached_image.unwrap_or_default(),
+                image => image.to_string(),
+            };
+            let name = match self.profiles.name.trim() {
+                "" => image.clone(),
+                name => name.to_string(),
+            };
+            let root = if self.profiles.with_root {
+                self.current_root_bookmark()
+            } else {
+                None
+            };
+            if let Some(ms) = self.app.get_memory_structure_mut() {
+                ms.profiles.push(Profile {
+                    name,
+                    image,
+                    bookmarks: ms.bookmarks.clone(),
+                    root,
+                });
+                self.profiles.name.clear();
+                self.profiles.image.clear();
+            }
+        }
+        if let Some(index) = update {
+            let root = self.current_root_bookmark();
+            if let Some(ms) = self.app.get_memory_structure_mut() {
+                let bookmarks = ms.bookmarks.clone();
+                if let Some(profile) = ms.profiles.get_mut(index) {
+                    profile.bookmarks = bookmarks;
+                    if profile.root.is_some() {
+                        profile.root = root;
+                    }
+                }
+            }
+        }
+        if let Some(index) = remove {
+            if let Some(ms) = self.app.get_memory_structure_mut() {
+                if index < ms.profiles.len() {
+                    ms.profiles.remove(index);
+                }
+            }
+        }
+        if let Some(profile) = apply {
+            self.apply_profile(&profile);
+        }
+    }
+}
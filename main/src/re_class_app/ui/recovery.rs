@@ -0,0 +1,286 @@
+use std::{
+    collections::HashMap,
+    path::{
+        Path,
+        PathBuf,
+    },
+    time::{
+        Duration,
+        Instant,
+        SystemTime,
+        UNIX_EPOCH,
+    },
+};
+
+use eframe::egui::{
+    self,
+    Context,
+};
+
+use super::ReClassGui;
+use crate::{
+    memory::MemoryStructure,
+    re_class_app::app::AppSignature,
+};
+
+/// Time between an edit and the auto-save that writes it to the recovery file
+const AUTOSAVE_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Structure file contents plus the file the edits belong to, if it was ever saved
+#[derive(serde::Serialize)]
+struct RecoverySave<'a> {
+    memory: &'a MemoryStructure,
+    signatures: &'a Vec<AppSignature>,
+    source: Option<&'a Path>,
+}
+
+#[derive(serde::Deserialize)]
+struct RecoverySource {
+    #[serde(default)]
+    source: Option<PathBuf>,
+}
+
+fn modified_time(path: &Path) -> Option<std::time::SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+fn remove_recovery_file(path: &Path) {
+    if let Err(err) = std::fs::remove_file(path) {
+        if err.kind() != std::io::ErrorKind::NotFound {
+            log::warn!("Failed to remove {}: {err}", path.display());
+        }
+    }
+}
+
+/// Whether a recovery file has nothing left to restore: it is gone, or the file its edits
+/// belong to was saved after it was written
+pub(super) fn recovery_outdated(path: &Path) -> bool {
+    let Some(written) = modified_time(path) else {
+        return true;
+    };
+    std::fs::read_to_string(path)
+        .ok()
+        .and_then(|text| serde_json::from_str::<RecoverySource>(&text).ok())
+        .and_then(|r| r.source)
+        .as_deref()
+        .and_then(modified_time)
+        .is_some_and(|saved| saved >= written)
+}
+
+/// Recovery files of the open tabs, keyed by tab id, and the ones earlier runs left behind.
+/// Each run names its files after its launch time, so they never replace a file that is
+/// still waiting to be restored.
+pub(super) struct RecoveryFiles {
+    dir: Option<PathBuf>,
+    launch: u64,
+    tabs: HashMap<u64, PathBuf>,
+    /// Left by earlier runs, neither restored nor discarded yet
+    pub pending: Vec<PathBuf>,
+}
+
+impl RecoveryFiles {
+    pub fn new(dir: Option<PathBuf>, pending: Vec<PathBuf>) -> Self {
+        let launch = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|since| since.as_millis() as u64)
+            .unwrap_or_default();
+        Self {
+            dir,
+            launch,
+            tabs: HashMap::new(),
+            pending,
+        }
+    }
+
+    /// Write the recovery file of a tab, creating it on its first auto-save
+    pub fn write(&mut self, tab_id: u64, text: &str) -> anyhow::Result<()> {
+        let path = match self.tabs.get(&tab_id) {
+            Some(path) => path.clone(),
+            None => {
+                let dir = self
+                    .dir
+                    .as_ref()
+                    .ok_or_else(|| anyhow::anyhow!("no config directory"))?;
+                dir.join(format!("recovery-{}-{tab_id}.json", self.launch))
+            }
+        };
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)?;
+        }
+        std::fs::write(&path, text)?;
+        self.tabs.insert(tab_id, path);
+        Ok(())
+    }
+
+    /// Let a tab keep auto-saving into a file restored from an earlier run
+    pub fn adopt(&mut self, tab_id: u64, path: PathBuf) {
+        self.pending.retain(|p| *p != path);
+        if let Some(previous) = self.tabs.insert(tab_id, path) {
+            remove_recovery_file(&previous);
+        }
+    }
+
+    /// Delete the recovery file of one tab, e.g. after it was saved or closed
+    pub fn discard(&mut self, tab_id: u64) {
+        if let Some(path) = self.tabs.remove(&tab_id) {
+            remove_recovery_file(&path);
+        }
+    }
+
+    pub fn discard_pending(&mut self) {
+        for path in self.pending.drain(..) {
+            remove_recovery_file(&path);
+        }
+    }
+
+    /// Every file to offer on the next launch, stored with the session
+    pub fn stored(&self) -> Vec<PathBuf> {
+        let mut files = self.pending.clone();
+        let mut tab_files: Vec<(&u64, &PathBuf)> = self.tabs.iter().collect();
+        tab_files.sort_unstable();
+        files.extend(tab_files.into_iter().map(|(_, path)| path.clone()));
+        files
+    }
+}
+
+impl ReClassGui {
+    /// Note an edit of the shown structure; the recovery file is rewritten once the auto-save
+    /// interval has passed
+    pub(super) fn mark_structure_dirty(&mut self) {
+        self.structure_dirty = true;
+        self.recovery_due
+            .get_or_insert_with(|| Instant::now() + AUTOSAVE_INTERVAL);
+    }
+
+    /// Write the shown structure to the recovery file when an auto-save is due
+    pub(super) fn autosave(&mut self, ctx: &Context) {
+        let Some(due) = self.recovery_due else {
+            return;
+        };
+        let now = Instant::now();
+        if now < due {
+            // Wake up for the write even if nothing else repaints in between
+            ctx.request_repaint_after(due - now);
+            return;
+        }
+        self.recovery_due = None;
+        if !self.structure_dirty {
+            return;
+        }
+        if let Err(err) = self.write_recovery_file() {
+            log::warn!("Auto-save failed: {err}");
+        }
+    }
+
+    /// Write the shown tab's recovery file now if an auto-save is pending, e.g. before the tab
+    /// is hidden
+    pub(super) fn flush_autosave(&mut self) {
+        if self.recovery_due.take().is_some() && self.structure_dirty {
+            if let Err(err) = self.write_recovery_file() {
+                log::warn!("Auto-save failed: {err}");
+            }
+        }
+    }
+
+    fn write_recovery_file(&mut self) -> anyhow::Result<()> {
+        let Some(ms) = self.app.get_memory_structure() else {
+            return Ok(());
+        };
+        let wrapper = RecoverySave {
+            memory: ms,
+            signatures: &self.app.signatures,
+            source: self.last_structure_path.as_deref(),
+        };
+        let text = serde_json::to_string(&wrapper)?;
+        let tab_id = self.active_tab_id();
+        self.recovery.write(tab_id, &text)
+    }
+
+    /// Delete the shown tab's recovery file, e.g. after its edits were saved
+    pub(super) fn discard_recovery_file(&mut self) {
+        let tab_id = self.active_tab_id();
+        self.recovery.discard(tab_id);
+    }
+
+    /// On launch, offer to restore the recovery files left behind by earlier runs, except the
+    /// ones whose structure file was saved after they were written
+    pub(super) fn check_recovery_files(&mut self) {
+        let (outdated, pending): (Vec<PathBuf>, Vec<PathBuf>) = self
+            .recovery
+            .pending
+            .drain(..)
+            .partition(|path| recovery_outdated(path));
+        for path in &outdated {
+            remove_recovery_file(path);
+        }
+        self.recovery_prompt = !pending.is_empty();
+        self.recovery.pending = pending;
+    }
+
+    /// Open a recovery file in a tab of its own, unsaved and named after its original file.
+    /// The tab keeps auto-saving into the same file.
+    fn restore_recovery_file(&mut self, path: &Path) -> anyhow::Result<()> {
+        let text = std::fs::read_to_string(path)?;
+        let source = serde_json::from_str::<RecoverySource>(&text)?.source;
+        self.open_tab_for_project();
+        let repairs = self.install_structure_json(&text)?;
+        self.last_structure_path = source;
+        self.saved_snapshot = None;
+        self.mark_structure_dirty();
+        let tab_id = self.active_tab_id();
+        self.recovery.adopt(tab_id, path.to_path_buf());
+        self.report_reference_repairs(&repairs);
+        Ok(())
+    }
+
+    /// Ask whether to restore the edits auto-saved before the last run ended
+    pub(super) fn recovery_prompt_window(&mut self, ctx: &Context) {
+        if !self.recovery_prompt {
+            return;
+        }
+        let mut open = true;
+        let mut decision: Option<bool> = None;
+        let count = self.recovery.pending.len();
+        egui::Window::new("Recover unsaved work")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(match count {
+                    1 => String::from("The last session ended with unsaved changes. Restore them?"),
+                    n => format!(
+                        "The last session ended with unsaved changes in {n} tabs. Restore them?"
+                    ),
+                });
+                ui.horizontal(|ui| {
+                    if ui.button("Restore").clicked() {
+                        decision = Some(true);
+                    }
+                    if ui
+                        .button("Discard")
+                        .on_hover_text("Delete the auto-saved changes")
+                        .clicked()
+                    {
+                        decision = Some(false);
+                    }
+                });
+            });
+        match decision {
+            Some(true) => {
+                for path in self.recovery.pending.clone() {
+                    if let Err(err) = self.restore_recovery_file(&path) {
+                        self.cycle_error_text =
+                            format!("Failed to restore {}: {err}", path.display());
+                        self.cycle_error_open = true;
+                    }
+                }
+            }
+            Some(false) => self.recovery.discard_pending(),
+            None => {}
+        }
+        // Closing the window keeps the files for the next launch
+        if decision.is_some() || !open {
+            self.recovery_prompt = false;
+        }
+    }
+}
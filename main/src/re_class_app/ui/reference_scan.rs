@@ -0,0 +1,254 @@
+use std::sync::{
+    atomic::{
+        AtomicBool,
+        AtomicU64,
+        Ordering,
+    },
+    mpsc::{
+        self,
+        Receiver,
+    },
+    Arc,
+};
+
+use eframe::egui::{
+    self,
+    Context,
+    ScrollArea,
+};
+
+use super::ReClassGui;
+use crate::scan::find_references;
+
+/// Stop collecting references after this many results
+const REFERENCE_SCAN_MAX_RESULTS: usize = 1000;
+
+/// Reference scan running on a background thread
+pub(super) struct ReferenceScanJob {
+    progress: Arc<AtomicU64>,
+    total: u64,
+    cancel: Arc<AtomicBool>,
+    result: Receiver<Vec<u64>>,
+}
+
+/// State of the "Find References" window
+#[derive(Default)]
+pub(super) struct ReferenceScanState {
+    pub open: bool,
+    /// Module to search; all readable memory when unset
+    pub module: Option<String>,
+    pub results: Vec<u64>,
+    pub status: Option<String>,
+    pub job: Option<ReferenceScanJob>,
+}
+
+impl ReferenceScanState {
+    fn cancel(&mut self) {
+        if let Some(job) = self.job.take() {
+            job.cancel.store(true, Ordering::Relaxed);
+        }
+    }
+}
+
+impl ReClassGui {
+    fn start_reference_scan(&mut self) {
+        let Some(target) = self
+            .app
+            .get_memory_structure()
+            .map(|ms| ms.root_class.address)
+        else {
+            self.reference_scan.status = Some(String::from("No structure loaded"));
+            return;
+        };
+        let scan = &mut self.reference_scan;
+        let Some(handle) = self.app.handle.clone() else {
+            scan.status = Some(String::from("Not attached to a process"));
+            return;
+        };
+        let ranges: Vec<(u64, u64)> = match scan.module.as_deref() {
+            Some(name) => handle
+                .get_module_by_name(name)
+                .map(|m| (m.base_address, m.module_size))
+                .into_iter()
+                .collect(),
+            None => handle.readable_regions(),
+        };
+        if ranges.is_empty() {
+            scan.status = Some(String::from("Module not found"));
+            return;
+        }
+        scan.cancel();
+        let total = ranges.iter().map(|(_, len)| len).sum();
+        let pointer_size = handle.pointer_size();
+        let progress = Arc::new(AtomicU64::new(0));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = mpsc::channel();
+        {
+            let progress = progress.clone();
+            let cancel = cancel.clone();
+            std::thread::spawn(move || {
+                let found = find_references(
+                    &ranges,
+                    target,
+                    pointer_size,
+                    REFERENCE_SCAN_MAX_RESULTS,
+                    &cancel,
+                    &progress,
+                    |address, buffer| handle.read_uncached(address, buffer).is_ok(),
+                );
+                let _ = tx.send(found);
+            });
+        }
+        scan.results.clear();
+        scan.status = Some(String::from("Scanning..."));
+        scan.job = Some(ReferenceScanJob {
+            progress,
+            total,
+            cancel,
+            result: rx,
+        });
+    }
+
+    fn poll_reference_scan(&mut self, ctx: &Context) {
+        let scan = &mut self.reference_scan;
+        let Some(job) = &scan.job else {
+            return;
+        };
+        match job.result.try_recv() {
+            Ok(found) => {
+                scan.status = Some(match found.len() {
+                    0 => String::from("No reference"),
+                    1 => String::from("1 reference"),
+                    n if n >= REFERENCE_SCAN_MAX_RESULTS => {
+                        format!("{n}+ references (list truncated)")
+                    }
+                    n => format!("{n} references"),
+                });
+                scan.results = found;
+                scan.job = None;
+            }
+            Err(mpsc::TryRecvError::Empty) => ctx.request_repaint(),
+            Err(mpsc::TryRecvError::Disconnected) => {
+                scan.status = Some(String::from("Scan failed"));
+                scan.job = None;
+            }
+        }
+    }
+
+    /// Addresses in the process's memory holding a pointer to the current root
+    pub(super) fn reference_scan_window(&mut self, ctx: &Context) {
+        self.poll_reference_scan(ctx);
+
+        let mut open = self.reference_scan.open;
+        let mut chosen: Option<u64> = None;
+        egui::Window::new("Find References")
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let mut module_names: Vec<String> = self
+                    .app
+                    .get_modules()
+                    .iter()
+                    .filter_map(|m| m.get_base_dll_name().map(str::to_string))
+                    .collect();
+                module_names.sort_by_key(|name| name.to_ascii_lowercase());
+                let root = self
+                    .app
+                    .get_memory_structure()
+                    .map(|ms| ms.root_class.address);
+
+                ui.label(match root {
+                    Some(address) => format!("Pointers to the root at 0x{address:X}"),
+                    None => String::from("No structure loaded"),
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Module:");
+                    egui::ComboBox::from_id_source("reference_scan_module")
+                        .selected_text(
+                            self.reference_scan
+                                .module
+                                .clone()
+                                .unwrap_or_else(|| String::from("All memory")),
+                        )
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(
+                                &mut self.reference_scan.module,
+                                None,
+                                "All memory",
+                            );
+                            for name in &module_names {
+                                ui.selectable_value(
+                                    &mut self.reference_scan.module,
+                                    Some(name.clone()),
+                                    name,
+                                );
+                            }
+                        });
+                    let running = self.reference_scan.job.is_some();
+                    if ui
+                        .add_enabled(!running && root.is_some(), egui::Button::new("Scan"))
+                        .on_hover_text("Search for pointer-sized values equal to the root address")
+                        .clicked()
+                    {
+                        self.start_reference_scan();
+                    }
+                    if running && ui.button("Cancel").clicked() {
+                        self.reference_scan.cancel();
+                        self.reference_scan.status = Some(String::from("Cancelled"));
+                    }
+                });
+                if let Some(job) = &self.reference_scan.job {
+                    let done = job.progress.load(Ordering::Relaxed);
+                    let fraction = (done as f64 / job.total.max(1) as f64).min(1.0) as f32;
+                    ui.add(egui::ProgressBar::new(fraction).show_percentage());
+                } else if let Some(status) = &self.reference_scan.status {
+                    ui.label(status);
+                }
+                ui.separator();
+
+                let handle = self.app.handle.clone();
+                ScrollArea::vertical().show_rows(
+                    ui,
+                    ui.text_style_height(&egui::TextStyle::Body),
+                    self.reference_scan.results.len(),
+                    |ui, rows| {
+                        for &address in &self.reference_scan.results[rows] {
+                            let label = match handle
+                                .as_ref()
+                                .and_then(|h| h.get_module_by_address(address))
+                            {
+                                Some(m) => format!(
+                                    "0x{address:X}  ({}+0x{:X})",
+                                    m.get_base_dll_name().unwrap_or("?"),
+                                    address - m.base_address
+                                ),
+                                None => format!("0x{address:X}"),
+                            };
+                            let resp = ui
+                                .selectable_label(false, egui::RichText::new(label).monospace())
+                                .on_hover_text("Double-click to use as root address");
+                            if resp.double_clicked() {
+                                chosen = Some(address);
+                            }
+                            resp.context_menu(|ui| {
+                                if ui.button("Use as root").clicked() {
+                                    chosen = Some(address);
+                                    ui.close_menu();
+                                }
+                                if ui.button("Copy address").clicked() {
+                                    let _ = arboard::Clipboard::new()
+                                        .and_then(|mut cb| cb.set_text(format!("0x{address:X}")));
+                                    ui.close_menu();
+                                }
+                            });
+                        }
+                    },
+                );
+            });
+        self.reference_scan.open = open;
+
+        if let Some(address) = chosen {
+            self.navigate_to_address(address, format!("0x{address:X}"));
+        }
+    }
+}
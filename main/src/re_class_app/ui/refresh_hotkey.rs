@@ -0,0 +1,96 @@
+use std::time::Duration;
+
+use eframe::egui::{
+    self,
+    Context,
+    Ui,
+};
+
+use super::ReClassGui;
+
+/// Virtual-key codes offered for the pause hotkey
+const HOTKEY_CHOICES: &[(u16, &str)] = &[
+    (0x70, "F1"),
+    (0x71, "F2"),
+    (0x72, "F3"),
+    (0x73, "F4"),
+    (0x74, "F5"),
+    (0x75, "F6"),
+    (0x76, "F7"),
+    (0x77, "F8"),
+    (0x78, "F9"),
+    (0x79, "F10"),
+    (0x7A, "F11"),
+    (0x7B, "F12"),
+    (0x13, "Pause"),
+    (0x91, "Scroll Lock"),
+    (0x2D, "Insert"),
+    (0x24, "Home"),
+    (0x23, "End"),
+];
+
+/// How often the key is polled while the window may be in the background
+const HOTKEY_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+fn hotkey_name(key: u16) -> &'static str {
+    HOTKEY_CHOICES
+        .iter()
+        .find(|(code, _)| *code == key)
+        .map_or("?", |(_, name)| name)
+}
+
+/// Physical state of a key regardless of which window has focus. The driver interface only
+/// injects keyboard input, so the state comes from the OS.
+#[cfg(windows)]
+fn key_down(key: u16) -> bool {
+    // SAFETY: GetAsyncKeyState only reads the key state
+    let state = unsafe { winapi::um::winuser::GetAsyncKeyState(i32::from(key)) };
+    (state as u16 & 0x8000) != 0
+}
+
+#[cfg(not(windows))]
+fn key_down(_key: u16) -> bool {
+    false
+}
+
+impl ReClassGui {
+    /// Flip the refresh pause when the hotkey goes down, even while a game has focus
+    pub(super) fn poll_refresh_hotkey(&mut self, ctx: &Context) {
+        if !self.refresh_hotkey_enabled {
+            return;
+        }
+        let down = key_down(self.refresh_hotkey);
+        if down && !self.refresh_hotkey_down {
+            self.app.reads_paused = !self.app.reads_paused;
+            self.app.apply_read_settings();
+        }
+        self.refresh_hotkey_down = down;
+        // Keep polling without input events reaching the window
+        ctx.request_repaint_after(HOTKEY_POLL_INTERVAL);
+    }
+
+    /// Header menu to enable the global pause hotkey and pick its key
+    pub(super) fn refresh_hotkey_menu(&mut self, ui: &mut Ui) {
+        let title = if self.refresh_hotkey_enabled {
+            format!("Hotkey: {}", hotkey_name(self.refresh_hotkey))
+        } else {
+            String::from("Hotkey")
+        };
+        ui.menu_button(title, |ui| {
+            ui.checkbox(
+                &mut self.refresh_hotkey_enabled,
+                "Toggle Pause with a global key",
+            )
+            .on_hover_text("Works while another window, e.g. a fullscreen game, has focus");
+            ui.add_enabled_ui(self.refresh_hotkey_enabled, |ui| {
+                egui::ComboBox::from_id_source("refresh_hotkey_key")
+                    .selected_text(hotkey_name(self.refresh_hotkey))
+                    .show_ui(ui, |ui| {
+                        for (code, name) in HOTKEY_CHOICES {
+                            ui.selectable_value(&mut self.refresh_hotkey, *code, *name);
+                        }
+                    });
+            });
+        });
+    }
+}
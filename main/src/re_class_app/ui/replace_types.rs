@@ -0,0 +1,143 @@
+use eframe::egui::{
+    self,
+    Context,
+};
+
+use super::{
+    memory_view::CHANGE_TYPE_CHOICES,
+    ReClassGui,
+};
+use crate::memory::FieldType;
+
+/// State of the "Replace field types" window opened from a class's context menu
+pub(super) struct ReplaceTypesDialog {
+    class_id: u64,
+    all_classes: bool,
+    from: FieldType,
+    to: FieldType,
+    // Set by "Replace…" to ask for confirmation with the number of fields
+    confirming: bool,
+}
+
+impl ReplaceTypesDialog {
+    pub(super) fn new(class_id: u64) -> Self {
+        Self {
+            class_id,
+            all_classes: false,
+            from: FieldType::Hex64,
+            to: FieldType::Pointer,
+            confirming: false,
+        }
+    }
+}
+
+fn field_type_combo(ui: &mut egui::Ui, id: &str, value: &mut FieldType) -> bool {
+    let mut changed = false;
+    egui::ComboBox::from_id_source(id)
+        .selected_text(format!("{value:?}"))
+        .show_ui(ui, |ui| {
+            for t in CHANGE_TYPE_CHOICES {
+                let label = format!("{t:?}");
+                changed |= ui.selectable_value(value, t, label).changed();
+            }
+        });
+    changed
+}
+
+impl ReClassGui {
+    /// Change every field of one type to another in a class or in all classes, after
+    /// confirming how many fields match
+    pub(super) fn replace_types_window(&mut self, ctx: &Context) {
+        let Some(dialog) = self.replace_types.as_mut() else {
+            return;
+        };
+        let Some(ms) = self.app.memory_structure.as_mut() else {
+            self.replace_types = None;
+            return;
+        };
+        let Some(class_name) = ms
+            .class_registry
+            .get(dialog.class_id)
+            .map(|d| d.name.clone())
+        else {
+            self.replace_types = None;
+            return;
+        };
+        let scope = (!dialog.all_classes).then_some(dialog.class_id);
+        let count = ms.count_fields_of_type(scope, &dialog.from);
+        let mut open = true;
+        let mut apply = false;
+        let mut close = false;
+        egui::Window::new("Replace field types")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let mut changed = false;
+                ui.add_enabled_ui(!dialog.confirming, |ui| {
+                    ui.horizontal(|ui| {
+                        changed |= ui
+                            .radio_value(&mut dialog.all_classes, false, format!("In {class_name}"))
+                            .changed();
+                        changed |= ui
+                            .radio_value(&mut dialog.all_classes, true, "In all classes")
+                            .changed();
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label("Replace");
+                        changed |= field_type_combo(ui, "replace_types_from", &mut dialog.from);
+                        ui.label("with");
+                        changed |= field_type_combo(ui, "replace_types_to", &mut dialog.to);
+                    });
+                });
+                if changed {
+                    dialog.confirming = false;
+                }
+                ui.separator();
+                if dialog.confirming {
+                    ui.label(format!(
+                        "Change {count} field(s) from {:?} to {:?}? Fields after a type of a \
+                         different size move.",
+                        dialog.from, dialog.to
+                    ));
+                    ui.horizontal(|ui| {
+                        if ui.button("Apply").clicked() {
+                            apply = true;
+                        }
+                        if ui.button("Back").clicked() {
+                            dialog.confirming = false;
+                        }
+                    });
+                } else {
+                    ui.label(match count {
+                        1 => String::from("1 field matches"),
+                        n => format!("{n} fields match"),
+                    });
+                    ui.horizontal(|ui| {
+                        if ui
+                            .add_enabled(
+                                count > 0 && dialog.from != dialog.to,
+                                egui::Button::new("Replace…"),
+                            )
+                            .clicked()
+                        {
+                            dialog.confirming = true;
+                        }
+                        if ui.button("Cancel").clicked() {
+                            close = true;
+                        }
+                    });
+                }
+            });
+        if apply {
+            let (from, to) = (dialog.from.clone(), dialog.to.clone());
+            if ms.replace_field_types(scope, &from, &to) > 0 {
+                self.schedule_rebuild();
+            }
+            close = true;
+        }
+        if close || !open {
+            self.replace_types = None;
+        }
+    }
+}
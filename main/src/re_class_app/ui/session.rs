@@ -0,0 +1,93 @@
+use std::path::Path;
+
+use super::ReClassGui;
+use crate::re_class_app::session::{
+    SessionState,
+    RECENT_FILES_MAX,
+};
+
+impl ReClassGui {
+    fn current_session(&self) -> SessionState {
+        SessionState {
+            restore: self.restore_last_session,
+            last_file: self.last_structure_path.clone(),
+            last_process: self
+                .app
+                .process_state
+                .selected_process
+                .as_ref()
+                .and_then(|p| p.get_image_base_name())
+                .map(str::to_string)
+                .or_else(|| self.session.last_process.clone()),
+            ui_scale: self.ui_scale,
+            hex_view_open: self.hex_view_open,
+            signatures_window_open: self.signatures_window_open,
+            recent_files: self.recent_files.clone(),
+            structure_locked: self.structure_locked,
+            refresh_hotkey_enabled: self.refresh_hotkey_enabled,
+            refresh_hotkey: self.refresh_hotkey,
+            open_tree_header_owners: {
+                let mut ids: Vec<(u64, u64)> = self
+                    .open_tree_headers
+                    .iter()
+                    .map(|(&id, &owner)| (id, owner))
+                    .collect();
+                ids.sort_unstable();
+                ids
+            },
+            new_class_bytes: self.new_class_bytes,
+            auto_name_fields: self.auto_name_fields,
+            tree_colors: self.tree_colors,
+            max_render_depth: self.max_render_depth,
+            float_precision: self.float_precision,
+            recovery_files: self.recovery.stored(),
+        }
+    }
+
+    /// Move `path` to the top of the Recent menu; called after a successful load or save
+    pub(super) fn remember_recent_file(&mut self, path: &Path) {
+        self.recent_files.retain(|p| p != path);
+        self.recent_files.insert(0, path.to_path_buf());
+        self.recent_files.truncate(RECENT_FILES_MAX);
+    }
+
+    /// Hand the session to eframe's storage, which writes it periodically and on exit
+    pub(super) fn save_session(&mut self, storage: &mut dyn eframe::Storage) {
+        let current = self.current_session();
+        current.save(storage);
+        self.session = current;
+    }
+
+    /// Reopen the last structure file, restore the layout and reattach to a process with the
+    /// same image name as last time
+    pub(super) fn restore_session(&mut self) {
+        let session = self.session.clone();
+        if !session.restore {
+            return;
+        }
+        self.ui_scale = session.ui_scale.clamp(0.8, 1.8);
+        self.hex_view_open = session.hex_view_open;
+        self.signatures_window_open = session.signatures_window_open;
+        if let Some(path) = session.last_file.as_deref().filter(|p| p.exists()) {
+            if let Err(err) = self.load_structure_file(path) {
+                log::warn!("Failed to restore {}: {err}", path.display());
+            }
+        }
+        if let Some(name) = session.last_process.as_deref() {
+            if self.app.fetch_processes().is_ok() {
+                let pid = self
+                    .app
+                    .get_processes()
+                    .iter()
+                    .find(|p| {
+                        p.get_image_base_name()
+                            .is_some_and(|n| n.eq_ignore_ascii_case(name))
+                    })
+                    .map(|p| p.process_id);
+                if let Some(pid) = pid {
+                    self.attach_to_process(pid);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,84 @@
+use eframe::egui::{
+    self,
+    Context,
+};
+
+use super::ReClassGui;
+
+/// Upper bound of the hex bytes a new class starts with
+pub(crate) const NEW_CLASS_BYTES_MAX: u64 = 0x1000;
+
+/// Upper bound of the "Max render depth" setting
+pub(crate) const MAX_RENDER_DEPTH_LIMIT: usize = 64;
+
+/// Most decimals the float precision setting offers
+pub(crate) const FLOAT_DECIMALS_MAX: u8 = 9;
+
+impl ReClassGui {
+    /// Small window with preferences that apply to all tabs and are kept between launches
+    pub(super) fn settings_window(&mut self, ctx: &Context) {
+        let mut open = self.settings_window_open;
+        egui::Window::new("Settings")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label("New class size:");
+                    ui.add(
+                        egui::DragValue::new(&mut self.new_class_bytes)
+                            .clamp_range(1..=NEW_CLASS_BYTES_MAX)
+                            .hexadecimal(1, false, true)
+                            .prefix("0x"),
+                    )
+                    .on_hover_text(
+                        "Hex bytes a class gets when created by New, Create class or a new \
+                         class type; 0x8 is a single Hex64",
+                    );
+                    if ui.small_button("Reset").clicked() {
+                        self.new_class_bytes = 8;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Max render depth:");
+                    ui.add(
+                        egui::DragValue::new(&mut self.max_render_depth)
+                            .clamp_range(1..=MAX_RENDER_DEPTH_LIMIT),
+                    )
+                    .on_hover_text(
+                        "How deep opened pointers and arrays nest before the tree stops reading \
+                         and shows a stub; keeps linked lists and trees from dereferencing \
+                         thousands of pointers per frame",
+                    );
+                    if ui.small_button("Reset").clicked() {
+                        self.max_render_depth = 8;
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label("Float decimals:");
+                    let mut full = self.float_precision.is_none();
+                    let mut decimals = self.float_precision.unwrap_or(3);
+                    ui.add_enabled(
+                        !full,
+                        egui::DragValue::new(&mut decimals).clamp_range(0..=FLOAT_DECIMALS_MAX),
+                    )
+                    .on_hover_text(
+                        "Decimals of Float, Double, vector, quaternion and matrix values",
+                    );
+                    ui.checkbox(&mut full, "Full").on_hover_text(
+                        "Show the shortest form that reads back as the exact same value",
+                    );
+                    self.float_precision = (!full).then_some(decimals);
+                });
+                ui.checkbox(&mut self.auto_name_fields, "Auto-name new fields")
+                    .on_hover_text(
+                        "Name hex fields added by New, Add bytes, Insert or padding after their \
+                         offset, e.g. field_0x40, instead of leaving them unnamed",
+                    );
+                ui.separator();
+                ui.label("Theme");
+                self.theme_settings(ui);
+            });
+        self.settings_window_open = open;
+    }
+}
@@ -0,0 +1,363 @@
+use std::{
+    collections::{
+        HashMap,
+        HashSet,
+    },
+    mem,
+    path::PathBuf,
+};
+
+use eframe::egui::{
+    self,
+    Context,
+    Ui,
+};
+
+use super::{
+    memory_view::FieldKey,
+    navigation::RootBreadcrumb,
+    undo::UNDO_CAPACITY,
+    ReClassGui,
+};
+use crate::{
+    memory::{
+        snapshot::Snapshot,
+        undo::{
+            snapshot_structure,
+            UndoStack,
+        },
+        MemoryStructure,
+    },
+    re_class_app::app::AppSignature,
+};
+
+/// Project of a tab that is not shown. The shown tab lives in `ReClassApp` and the GUI fields;
+/// its slot in `ReClassGui::tabs` is an empty placeholder until another tab is activated.
+pub(super) struct ProjectTab {
+    // Stays the same while tabs before it are closed, unlike its index
+    id: u64,
+    memory_structure: Option<MemoryStructure>,
+    signatures: Vec<AppSignature>,
+    last_structure_path: Option<PathBuf>,
+    saved_snapshot: Option<String>,
+    structure_dirty: bool,
+    root_address_expression: Option<String>,
+    address_history: Vec<u64>,
+    address_history_cursor: usize,
+    root_breadcrumbs: Vec<RootBreadcrumb>,
+    undo_stack: UndoStack,
+    undo_baseline: Option<String>,
+    frozen_fields: HashSet<FieldKey>,
+    frozen_values: HashMap<FieldKey, Vec<u8>>,
+    snapshot: Option<Snapshot>,
+}
+
+impl ProjectTab {
+    pub(super) fn new(id: u64) -> Self {
+        Self {
+            id,
+            memory_structure: None,
+            signatures: Vec::new(),
+            last_structure_path: None,
+            saved_snapshot: None,
+            structure_dirty: false,
+            root_address_expression: None,
+            address_history: Vec::new(),
+            address_history_cursor: 0,
+            root_breadcrumbs: Vec::new(),
+            undo_stack: UndoStack::new(UNDO_CAPACITY),
+            undo_baseline: None,
+            frozen_fields: HashSet::new(),
+            frozen_values: HashMap::new(),
+            snapshot: None,
+        }
+    }
+
+    fn label(&self) -> String {
+        tab_label(
+            self.last_structure_path.as_ref(),
+            self.memory_structure.as_ref(),
+        )
+    }
+}
+
+/// File name of the tab's structure file, else the root class name
+fn tab_label(path: Option<&PathBuf>, ms: Option<&MemoryStructure>) -> String {
+    path.and_then(|p| p.file_name())
+        .map(|name| name.to_string_lossy().into_owned())
+        .or_else(|| {
+            ms.and_then(|ms| ms.class_registry.get(ms.root_class.class_id))
+                .map(|def| def.name.clone())
+        })
+        .unwrap_or_else(|| String::from("Untitled"))
+}
+
+impl ReClassGui {
+    /// Move the shown project out of the app and GUI state, leaving an empty project behind
+    fn take_active_tab(&mut self) -> ProjectTab {
+        let tab = ProjectTab {
+            id: self.tabs[self.active_tab].id,
+            memory_structure: self.app.memory_structure.take(),
+            signatures: mem::take(&mut self.app.signatures),
+            last_structure_path: self.last_structure_path.take(),
+            saved_snapshot: self.saved_snapshot.take(),
+            structure_dirty: mem::take(&mut self.structure_dirty),
+            root_address_expression: self.root_address_expression.take(),
+            address_history: mem::take(&mut self.address_history),
+            address_history_cursor: mem::take(&mut self.address_history_cursor),
+            root_breadcrumbs: mem::take(&mut self.root_breadcrumbs),
+            undo_stack: mem::replace(&mut self.undo_stack, UndoStack::new(UNDO_CAPACITY)),
+            undo_baseline: self.undo_baseline.take(),
+            frozen_fields: mem::take(&mut self.frozen_fields),
+            frozen_values: mem::take(&mut self.frozen_values),
+            snapshot: self.snapshot.take(),
+        };
+        // Buffers and selections refer to fields of the project that was shown
+        self.snapshot_compare = false;
+        self.root_address_buffer = None;
+        self.root_class_type_buffer = None;
+        self.field_name_buffers.clear();
+        self.class_type_buffers.clear();
+        self.value_edit_buffers.clear();
+        self.chain_offset_buffers.clear();
+        self.selected_fields.clear();
+        self.selected_instance_address = None;
+        self.selection_anchor = None;
+        self.field_drag = None;
+        self.hovered_def_id = None;
+        self.comment_editor = None;
+        self.analyze_proposal = None;
+        self.stride_proposal = None;
+        self.enum_window_target = None;
+        self.focused_definition = None;
+        self.compare_pin = None;
+        self.quick_view.open = false;
+        self.replace_types = None;
+        self.remove_fields_prompt = None;
+        self.value_plots.clear();
+        self.app.invalidate_read_cache();
+        tab
+    }
+
+    fn show_tab(&mut self, mut tab: ProjectTab) {
+        // The pointer size may have changed by attaching while this tab was hidden
+        if let Some(ms) = tab.memory_structure.as_mut() {
+            ms.set_pointer_size(self.app.pointer_size);
+        }
+        self.app.memory_structure = tab.memory_structure;
+        self.app.signatures = tab.signatures;
+        self.last_structure_path = tab.last_structure_path;
+        self.saved_snapshot = tab.saved_snapshot;
+        // Auto-save follows the shown tab
+        self.recovery_due = None;
+        if tab.structure_dirty {
+            self.mark_structure_dirty();
+        }
+        self.root_address_expression = tab.root_address_expression;
+        self.address_history = tab.address_history;
+        self.address_history_cursor = tab.address_history_cursor;
+        self.root_breadcrumbs = tab.root_breadcrumbs;
+        self.undo_stack = tab.undo_stack;
+        self.undo_baseline = tab.undo_baseline;
+        self.frozen_fields = tab.frozen_fields;
+        self.frozen_values = tab.frozen_values;
+        self.snapshot_compare = tab.snapshot.is_some();
+        self.snapshot = tab.snapshot;
+        self.needs_rebuild = true;
+    }
+
+    pub(super) fn active_tab_id(&self) -> u64 {
+        self.tabs[self.active_tab].id
+    }
+
+    /// Structures of every tab, the shown one included
+    pub(super) fn tab_structures(&self) -> impl Iterator<Item = &MemoryStructure> {
+        self.app.memory_structure.iter().chain(
+            self.tabs
+                .iter()
+                .filter_map(|tab| tab.memory_structure.as_ref()),
+        )
+    }
+
+    /// Move a hidden tab out of its slot, leaving an empty placeholder with the same id
+    fn take_hidden_tab(&mut self, index: usize) -> ProjectTab {
+        let id = self.tabs[index].id;
+        mem::replace(&mut self.tabs[index], ProjectTab::new(id))
+    }
+
+    fn switch_tab(&mut self, index: usize) {
+        if index == self.active_tab || index >= self.tabs.len() {
+            return;
+        }
+        // Hidden tabs are not auto-saved; write pending edits before this one is hidden
+        self.flush_autosave();
+        let current = self.take_active_tab();
+        self.tabs[self.active_tab] = current;
+        let target = self.take_hidden_tab(index);
+        self.show_tab(target);
+        self.active_tab = index;
+    }
+
+    /// Empty project with an id no other tab of this run has had
+    fn new_tab(&mut self) -> ProjectTab {
+        let id = self.next_tab_id;
+        self.next_tab_id += 1;
+        ProjectTab::new(id)
+    }
+
+    /// Add an empty tab after the others and show it
+    pub(super) fn open_new_tab(&mut self) {
+        let tab = self.new_tab();
+        self.tabs.push(tab);
+        self.switch_tab(self.tabs.len() - 1);
+    }
+
+    /// Open a new tab for a project about to be created or loaded, unless the shown one is empty
+    pub(super) fn open_tab_for_project(&mut self) {
+        if self.app.get_memory_structure().is_some() {
+            self.open_new_tab();
+        }
+    }
+
+    /// Load a structure file into a new tab, or into the shown one if it is empty. A tab
+    /// opened for a file that fails to load is closed again.
+    pub(super) fn open_structure_file(&mut self, path: &std::path::Path) -> anyhow::Result<()> {
+        let previous = self.active_tab;
+        let opened = self.app.get_memory_structure().is_some();
+        self.open_tab_for_project();
+        let result = self.load_structure_file(path);
+        if result.is_err() && opened {
+            self.close_tab(self.active_tab);
+            self.switch_tab(previous);
+        }
+        result
+    }
+
+    /// Remember the shown structure as saved, so closing its tab does not ask
+    pub(super) fn mark_structure_saved(&mut self) {
+        self.saved_snapshot = self.app.get_memory_structure().map(snapshot_structure);
+        self.structure_dirty = false;
+        self.recovery_due = None;
+    }
+
+    fn active_tab_unsaved(&self) -> bool {
+        match self.app.get_memory_structure() {
+            Some(ms) => self.saved_snapshot.as_deref() != Some(snapshot_structure(ms).as_str()),
+            None => false,
+        }
+    }
+
+    fn close_tab(&mut self, index: usize) {
+        if index >= self.tabs.len() {
+            return;
+        }
+        // Its edits were saved or deliberately dropped
+        self.recovery.discard(self.tabs[index].id);
+        if self.tabs.len() == 1 {
+            // The last tab stays open, empty
+            let _ = self.take_active_tab();
+            self.tabs[0] = self.new_tab();
+            return;
+        }
+        if index == self.active_tab {
+            let _ = self.take_active_tab();
+            self.tabs.remove(index);
+            let next = index.min(self.tabs.len() - 1);
+            let target = self.take_hidden_tab(next);
+            self.show_tab(target);
+            self.active_tab = next;
+        } else {
+            self.tabs.remove(index);
+            if index < self.active_tab {
+                self.active_tab -= 1;
+            }
+        }
+    }
+
+    /// One button per open project, a close button on each and "+" for a new empty tab
+    pub(super) fn tab_bar(&mut self, ui: &mut Ui) {
+        let mut activate: Option<usize> = None;
+        let mut close: Option<usize> = None;
+        let mut add = false;
+        ui.horizontal_wrapped(|ui| {
+            for index in 0..self.tabs.len() {
+                let label = if index == self.active_tab {
+                    tab_label(
+                        self.last_structure_path.as_ref(),
+                        self.app.get_memory_structure(),
+                    )
+                } else {
+                    self.tabs[index].label()
+                };
+                if ui
+                    .selectable_label(index == self.active_tab, label)
+                    .clicked()
+                {
+                    activate = Some(index);
+                }
+                if ui.small_button("x").on_hover_text("Close tab").clicked() {
+                    close = Some(index);
+                }
+                ui.separator();
+            }
+            if ui.button("+").on_hover_text("New empty tab").clicked() {
+                add = true;
+            }
+        });
+        if let Some(index) = activate {
+            self.switch_tab(index);
+        }
+        if let Some(index) = close {
+            let unsaved = if index == self.active_tab {
+                self.active_tab_unsaved()
+            } else {
+                let tab = &self.tabs[index];
+                tab.memory_structure.as_ref().is_some_and(|ms| {
+                    tab.saved_snapshot.as_deref() != Some(snapshot_structure(ms).as_str())
+                })
+            };
+            if unsaved {
+                self.tab_close_prompt = Some(self.tabs[index].id);
+            } else {
+                self.close_tab(index);
+            }
+        }
+        if add {
+            self.open_new_tab();
+        }
+    }
+
+    /// Confirmation before closing a tab whose structure changed since it was loaded or saved
+    pub(super) fn tab_close_prompt_window(&mut self, ctx: &Context) {
+        let Some(tab_id) = self.tab_close_prompt else {
+            return;
+        };
+        let Some(index) = self.tabs.iter().position(|tab| tab.id == tab_id) else {
+            self.tab_close_prompt = None;
+            return;
+        };
+        let mut open = true;
+        let mut decision: Option<bool> = None;
+        egui::Window::new("Close tab")
+            .open(&mut open)
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label("This tab has unsaved changes. Close it anyway?");
+                ui.horizontal(|ui| {
+                    if ui.button("Close").clicked() {
+                        decision = Some(true);
+                    }
+                    if ui.button("Cancel").clicked() {
+                        decision = Some(false);
+                    }
+                });
+            });
+        if decision == Some(true) {
+            self.close_tab(index);
+        }
+        if decision.is_some() || !open {
+            self.tab_close_prompt = None;
+        }
+    }
+}
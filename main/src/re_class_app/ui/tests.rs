@@ -0,0 +1,107 @@
+use std::{
+    path::PathBuf,
+    time::{
+        Duration,
+        SystemTime,
+    },
+};
+
+use super::recovery::{
+    recovery_outdated,
+    RecoveryFiles,
+};
+
+/// Empty directory of its own for each test
+fn test_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("reclass-recovery-{name}-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+fn set_modified(path: &PathBuf, time: SystemTime) {
+    std::fs::File::options()
+        .write(true)
+        .open(path)
+        .unwrap()
+        .set_modified(time)
+        .unwrap();
+}
+
+#[cfg(test)]
+mod recovery_files_tests {
+    use super::*;
+
+    #[test]
+    fn test_each_tab_writes_its_own_file() {
+        let dir = test_dir("tabs");
+        let mut files = RecoveryFiles::new(Some(dir.clone()), Vec::new());
+        files.write(1, "first").unwrap();
+        files.write(2, "second").unwrap();
+        files.write(1, "first again").unwrap();
+        let stored = files.stored();
+        assert_eq!(stored.len(), 2);
+        assert_eq!(std::fs::read_to_string(&stored[0]).unwrap(), "first again");
+        assert_eq!(std::fs::read_to_string(&stored[1]).unwrap(), "second");
+
+        // Saving one tab leaves the other tab's edits alone
+        files.discard(1);
+        assert!(!stored[0].exists());
+        assert!(stored[1].exists());
+        assert_eq!(files.stored(), vec![stored[1].clone()]);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_pending_files_are_not_overwritten() {
+        let dir = test_dir("pending");
+        let earlier = dir.join("recovery-1-1.json");
+        std::fs::write(&earlier, "earlier run").unwrap();
+        let mut files = RecoveryFiles::new(Some(dir.clone()), vec![earlier.clone()]);
+        // Tab ids start over every launch
+        files.write(1, "this run").unwrap();
+        assert_eq!(std::fs::read_to_string(&earlier).unwrap(), "earlier run");
+        assert_eq!(files.stored().len(), 2);
+
+        // A restored tab keeps writing into the file it came from
+        files.adopt(2, earlier.clone());
+        assert!(files.pending.is_empty());
+        files.write(2, "restored").unwrap();
+        assert_eq!(std::fs::read_to_string(&earlier).unwrap(), "restored");
+
+        files.discard_pending();
+        assert!(earlier.exists());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_recovery_outdated_once_its_source_is_saved() {
+        let dir = test_dir("saved-since");
+        let source = dir.join("game.json");
+        std::fs::write(&source, "{}").unwrap();
+        let recovery = dir.join("recovery.json");
+        let text = serde_json::json!({ "source": source }).to_string();
+        std::fs::write(&recovery, text).unwrap();
+
+        let written = SystemTime::now();
+        set_modified(&recovery, written);
+        set_modified(&source, written - Duration::from_secs(60));
+        assert!(!recovery_outdated(&recovery));
+
+        set_modified(&source, written + Duration::from_secs(60));
+        assert!(recovery_outdated(&recovery));
+
+        std::fs::remove_file(&recovery).unwrap();
+        assert!(recovery_outdated(&recovery));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+
+    #[test]
+    fn test_recovery_without_source_stays_until_restored() {
+        let dir = test_dir("no-source");
+        let recovery = dir.join("recovery.json");
+        std::fs::write(&recovery, r#"{"source":null}"#).unwrap();
+        assert!(!recovery_outdated(&recovery));
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -6,11 +6,89 @@ use eframe::egui::{
     FontFamily,
     FontId,
     TextStyle,
+    Ui,
     Visuals,
 };
+use serde::{
+    Deserialize,
+    Serialize,
+};
 
 use super::ReClassGui;
 
+/// Colors of type labels, reference headers and row backgrounds in the memory tree, kept in
+/// the session
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct TreeColors {
+    /// Type label of plain fields
+    pub field: [u8; 3],
+    /// Pointer type labels and headers
+    pub pointer: [u8; 3],
+    /// Type label of enum fields
+    pub enumeration: [u8; 3],
+    /// References to a class or enum that no longer exists, shown as `#id` or `<enum?>`
+    pub broken: [u8; 3],
+    /// Background of every other field row, RGBA
+    pub row_stripe: [u8; 4],
+    /// Background of selected field rows, RGBA
+    pub selection_fill: [u8; 4],
+    /// Outline of selected field rows, and faintly of the hovered field in other instances
+    pub selection_stroke: [u8; 3],
+}
+
+impl Default for TreeColors {
+    fn default() -> Self {
+        Self {
+            field: [170, 190, 255],
+            pointer: [110, 200, 220],
+            enumeration: [200, 160, 240],
+            broken: [235, 95, 95],
+            row_stripe: [0, 0, 0, 12],
+            selection_fill: [255, 255, 255, 18],
+            selection_stroke: [100, 160, 255],
+        }
+    }
+}
+
+impl TreeColors {
+    pub fn field_color(&self) -> Color32 {
+        rgb(self.field)
+    }
+
+    pub fn pointer_color(&self) -> Color32 {
+        rgb(self.pointer)
+    }
+
+    pub fn enum_color(&self) -> Color32 {
+        rgb(self.enumeration)
+    }
+
+    pub fn broken_color(&self) -> Color32 {
+        rgb(self.broken)
+    }
+
+    pub fn row_stripe_color(&self) -> Color32 {
+        rgba(self.row_stripe)
+    }
+
+    pub fn selection_fill_color(&self) -> Color32 {
+        rgba(self.selection_fill)
+    }
+
+    pub fn selection_stroke_color(&self) -> Color32 {
+        rgb(self.selection_stroke)
+    }
+}
+
+fn rgb([r, g, b]: [u8; 3]) -> Color32 {
+    Color32::from_rgb(r, g, b)
+}
+
+fn rgba([r, g, b, a]: [u8; 4]) -> Color32 {
+    Color32::from_rgba_unmultiplied(r, g, b, a)
+}
+
 impl ReClassGui {
     pub(super) fn apply_theme_once(&mut self, ctx: &Context) {
         if self.theme_applied {
@@ -72,6 +150,51 @@ impl ReClassGui {
         );
 
         ctx.set_style(style);
+        // The scale may have been restored from the last session
+        ctx.set_pixels_per_point(self.ui_scale);
         self.theme_applied = true;
     }
+
+    /// UI scale and color pickers for the memory tree colors, shown in the Settings window
+    pub(super) fn theme_settings(&mut self, ui: &mut Ui) {
+        ui.horizontal(|ui| {
+            ui.label("UI scale:");
+            let resp = ui.add(
+                egui::Slider::new(&mut self.ui_scale, 0.8..=1.8)
+                    .step_by(0.05)
+                    .custom_formatter(|v, _| format!("{:.0}%", v * 100.0)),
+            );
+            // Rescaling while dragging would move the slider under the mouse
+            if resp.drag_stopped() || (resp.changed() && !resp.dragged()) {
+                ui.ctx().set_pixels_per_point(self.ui_scale);
+            }
+        });
+        let colors = &mut self.tree_colors;
+        egui::Grid::new("tree_colors_grid")
+            .num_columns(2)
+            .show(ui, |ui| {
+                for (label, color) in [
+                    ("Field type", &mut colors.field),
+                    ("Pointer", &mut colors.pointer),
+                    ("Enum", &mut colors.enumeration),
+                    ("Broken reference", &mut colors.broken),
+                    ("Selection outline", &mut colors.selection_stroke),
+                ] {
+                    ui.label(label);
+                    ui.color_edit_button_srgb(color);
+                    ui.end_row();
+                }
+                for (label, color) in [
+                    ("Row stripe", &mut colors.row_stripe),
+                    ("Selection", &mut colors.selection_fill),
+                ] {
+                    ui.label(label);
+                    ui.color_edit_button_srgba_unmultiplied(color);
+                    ui.end_row();
+                }
+            });
+        if ui.small_button("Reset colors").clicked() {
+            *colors = TreeColors::default();
+        }
+    }
 }
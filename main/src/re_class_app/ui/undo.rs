@@ -0,0 +1,139 @@
+use std::time::{
+    Duration,
+    Instant,
+};
+
+use eframe::egui::{
+    self,
+    Context,
+    Key,
+    KeyboardShortcut,
+    Modifiers,
+};
+
+use crate::{
+    memory::undo::{
+        describe_change,
+        restore_structure,
+        snapshot_structure,
+    },
+    re_class_app::ReClassGui,
+};
+
+/// Maximum number of undo steps kept in memory
+pub(super) const UNDO_CAPACITY: usize = 50;
+const UNDO_TOAST_DURATION: Duration = Duration::from_millis(2000);
+
+impl ReClassGui {
+    /// Forget all undo/redo history, e.g. after loading or creating a new structure
+    pub(super) fn reset_undo_history(&mut self) {
+        self.undo_stack.clear();
+        self.undo_baseline = None;
+    }
+
+    /// Ctrl+Z undoes, Ctrl+Y / Ctrl+Shift+Z redoes. Ignored while a text field has focus so
+    /// text editing keeps its own undo.
+    pub(super) fn handle_undo_shortcuts(&mut self, ctx: &Context) {
+        if ctx.memory(|m| m.focused().is_some()) || self.structure_locked {
+            return;
+        }
+        let redo_shift = KeyboardShortcut::new(Modifiers::COMMAND | Modifiers::SHIFT, Key::Z);
+        let redo = KeyboardShortcut::new(Modifiers::COMMAND, Key::Y);
+        let undo = KeyboardShortcut::new(Modifiers::COMMAND, Key::Z);
+        // Shift+Z first: the plain Ctrl+Z shortcut also matches when Shift is held
+        if ctx.input_mut(|i| i.consume_shortcut(&redo_shift) || i.consume_shortcut(&redo)) {
+            self.step_history(false);
+        } else if ctx.input_mut(|i| i.consume_shortcut(&undo)) {
+            self.step_history(true);
+        }
+    }
+
+    fn step_history(&mut self, undo: bool) {
+        let Some(ms) = self.app.get_memory_structure() else {
+            return;
+        };
+        let current = snapshot_structure(ms);
+        let step = if undo {
+            self.undo_stack.undo(current)
+        } else {
+            self.undo_stack.redo(current)
+        };
+        let Some((label, snapshot)) = step else {
+            return;
+        };
+        let Some(restored) = restore_structure(&snapshot) else {
+            return;
+        };
+        self.undo_baseline = Some(snapshot_structure(&restored));
+        self.app.set_memory_structure(restored);
+        self.mark_structure_dirty();
+        self.field_name_buffers.clear();
+        self.class_type_buffers.clear();
+        let verb = if undo { "Undo" } else { "Redo" };
+        self.undo_toast = Some((format!("{verb}: {label}"), Instant::now()));
+    }
+
+    /// Compare the structure against the last known state and push an undo entry when an edit
+    /// happened this frame. Runs after deferred rebuilds so edits are fully applied; `rebuilt`
+    /// tells whether one was applied this frame.
+    pub(super) fn track_undo_state(&mut self, ctx: &Context, rebuilt: bool) {
+        let Some(ms) = self.app.get_memory_structure() else {
+            self.undo_baseline = None;
+            return;
+        };
+        // Edits come from rebuilds or from clicks and typing; skip the snapshot on idle
+        // repaints and while the pointer only moves or scrolls
+        if self.undo_baseline.is_some() && !rebuilt && !ctx.input(|i| i.events.iter().any(may_edit))
+        {
+            return;
+        }
+        let current = snapshot_structure(ms);
+        let Some(baseline) = self.undo_baseline.take() else {
+            self.undo_baseline = Some(current);
+            return;
+        };
+        if baseline != current {
+            let label = restore_structure(&baseline)
+                .map(|before| describe_change(&before, ms))
+                .unwrap_or_else(|| String::from("Edit"));
+            self.undo_stack.record(label, baseline, Instant::now());
+            self.mark_structure_dirty();
+        }
+        self.undo_baseline = Some(current);
+    }
+
+    /// Briefly show which edit was undone or redone
+    pub(super) fn undo_toast(&mut self, ctx: &Context) {
+        let Some((text, shown_at)) = &self.undo_toast else {
+            return;
+        };
+        let elapsed = shown_at.elapsed();
+        if elapsed >= UNDO_TOAST_DURATION {
+            self.undo_toast = None;
+            return;
+        }
+        egui::Area::new(egui::Id::new("undo_toast"))
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-16.0, -16.0))
+            .interactable(false)
+            .show(ctx, |ui| {
+                egui::Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.label(text.as_str());
+                });
+            });
+        ctx.request_repaint_after(UNDO_TOAST_DURATION - elapsed);
+    }
+}
+
+/// Whether an input event can change the structure: a click, a key or text input
+fn may_edit(event: &egui::Event) -> bool {
+    match event {
+        egui::Event::PointerButton { pressed, .. } => !pressed,
+        egui::Event::Key { pressed, .. } => *pressed,
+        egui::Event::Text(_)
+        | egui::Event::Paste(_)
+        | egui::Event::Cut
+        | egui::Event::CompositionEnd(_)
+        | egui::Event::AccessKitActionRequest(_) => true,
+        _ => false,
+    }
+}
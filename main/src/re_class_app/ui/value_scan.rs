@@ -0,0 +1,272 @@
+use eframe::egui::{
+    self,
+    Context,
+    ScrollArea,
+};
+
+use super::ReClassGui;
+use crate::scan::{
+    ScanFilter,
+    ScanValueType,
+    MAX_SCAN_CANDIDATES,
+};
+
+/// Only this many candidates are listed; the rest still take part in next scans
+const VALUE_SCAN_MAX_LISTED: usize = 500;
+
+#[derive(Clone, Copy, PartialEq, Eq, Default)]
+pub(super) enum NextScanKind {
+    #[default]
+    Exact,
+    Changed,
+    Unchanged,
+    Increased,
+    Decreased,
+}
+
+impl NextScanKind {
+    const ALL: [NextScanKind; 5] = [
+        NextScanKind::Exact,
+        NextScanKind::Changed,
+        NextScanKind::Unchanged,
+        NextScanKind::Increased,
+        NextScanKind::Decreased,
+    ];
+
+    fn label(&self) -> &'static str {
+        match self {
+            NextScanKind::Exact => "Exact value",
+            NextScanKind::Changed => "Changed",
+            NextScanKind::Unchanged => "Unchanged",
+            NextScanKind::Increased => "Increased",
+            NextScanKind::Decreased => "Decreased",
+        }
+    }
+}
+
+/// Inputs of the "Scan Value" window; the candidates themselves live in `ReClassApp`
+#[derive(Default)]
+pub(super) struct ValueScanWindowState {
+    pub open: bool,
+    pub value_type: ScanValueType,
+    pub value: String,
+    pub next_kind: NextScanKind,
+    pub status: Option<String>,
+}
+
+impl ReClassGui {
+    fn start_first_value_scan(&mut self) {
+        let window = &mut self.value_scan_window;
+        let Some(value) = window.value_type.parse(&window.value) else {
+            window.status = Some(format!("Not a valid {} value", window.value_type));
+            return;
+        };
+        let Some(handle) = self.app.handle.clone() else {
+            window.status = Some(String::from("Not attached to a process"));
+            return;
+        };
+        self.app
+            .value_scan
+            .start_first_scan(handle, window.value_type, value);
+        window.status = Some(String::from("Scanning..."));
+    }
+
+    fn start_next_value_scan(&mut self) {
+        let window = &mut self.value_scan_window;
+        let filter = match window.next_kind {
+            NextScanKind::Exact => {
+                // Next scans compare against the type of the first scan
+                let value_type = self.app.value_scan.value_type;
+                match value_type.parse(&window.value) {
+                    Some(value) => ScanFilter::Exact(value),
+                    None => {
+                        window.status = Some(format!("Not a valid {value_type} value"));
+                        return;
+                    }
+                }
+            }
+            NextScanKind::Changed => ScanFilter::Changed,
+            NextScanKind::Unchanged => ScanFilter::Unchanged,
+            NextScanKind::Increased => ScanFilter::Increased,
+            NextScanKind::Decreased => ScanFilter::Decreased,
+        };
+        let Some(handle) = self.app.handle.clone() else {
+            window.status = Some(String::from("Not attached to a process"));
+            return;
+        };
+        self.app.value_scan.start_next_scan(handle, filter);
+        window.status = Some(String::from("Scanning..."));
+    }
+
+    fn poll_value_scan(&mut self, ctx: &Context) {
+        let scan = &mut self.app.value_scan;
+        if scan.poll() {
+            self.value_scan_window.status = Some(match scan.candidates.len() {
+                0 => String::from("No match"),
+                1 => String::from("1 match"),
+                n if n >= MAX_SCAN_CANDIDATES => format!("{n}+ matches (scan stopped early)"),
+                n => format!("{n} matches"),
+            });
+        } else if scan.is_running() {
+            ctx.request_repaint();
+        }
+    }
+
+    pub(super) fn value_scan_window(&mut self, ctx: &Context) {
+        self.poll_value_scan(ctx);
+
+        let mut open = self.value_scan_window.open;
+        let mut chosen: Option<u64> = None;
+        egui::Window::new("Scan Value")
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let running = self.app.value_scan.is_running();
+                let has_results = self.app.value_scan.has_results;
+                ui.horizontal(|ui| {
+                    ui.label("Type:");
+                    // The type is fixed once candidates exist
+                    ui.add_enabled_ui(!has_results && !running, |ui| {
+                        egui::ComboBox::from_id_source("value_scan_type")
+                            .selected_text(self.value_scan_window.value_type.to_string())
+                            .show_ui(ui, |ui| {
+                                for value_type in ScanValueType::ALL {
+                                    ui.selectable_value(
+                                        &mut self.value_scan_window.value_type,
+                                        value_type,
+                                        value_type.to_string(),
+                                    );
+                                }
+                            });
+                    });
+                    ui.label("Value:");
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.value_scan_window.value)
+                            .hint_text("100")
+                            .desired_width(140.0),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!running, egui::Button::new("First Scan"))
+                        .on_hover_text("Search all modules of the process for the value")
+                        .clicked()
+                    {
+                        self.app.value_scan.reset();
+                        self.start_first_value_scan();
+                    }
+                    egui::ComboBox::from_id_source("value_scan_next_kind")
+                        .selected_text(self.value_scan_window.next_kind.label())
+                        .show_ui(ui, |ui| {
+                            for kind in NextScanKind::ALL {
+                                ui.selectable_value(
+                                    &mut self.value_scan_window.next_kind,
+                                    kind,
+                                    kind.label(),
+                                );
+                            }
+                        });
+                    if ui
+                        .add_enabled(!running && has_results, egui::Button::new("Next Scan"))
+                        .on_hover_text("Keep only the candidates matching the filter")
+                        .clicked()
+                    {
+                        self.start_next_value_scan();
+                    }
+                    if running {
+                        if ui.button("Cancel").clicked() {
+                            self.app.value_scan.cancel();
+                            self.value_scan_window.status = Some(String::from("Cancelled"));
+                        }
+                    } else if ui
+                        .add_enabled(has_results, egui::Button::new("Reset"))
+                        .clicked()
+                    {
+                        self.app.value_scan.reset();
+                        self.value_scan_window.status = None;
+                    }
+                });
+                if let Some(progress) = self.app.value_scan.progress() {
+                    ui.add(egui::ProgressBar::new(progress).show_percentage());
+                } else if let Some(status) = &self.value_scan_window.status {
+                    ui.label(status);
+                }
+                ui.separator();
+
+                let handle = self.app.handle.clone();
+                let scan = &self.app.value_scan;
+                let listed = scan.candidates.len().min(VALUE_SCAN_MAX_LISTED);
+                if scan.candidates.len() > listed {
+                    ui.label(
+                        egui::RichText::new(format!(
+                            "Showing the first {listed} of {} candidates",
+                            scan.candidates.len()
+                        ))
+                        .weak(),
+                    );
+                }
+                ScrollArea::vertical().show_rows(
+                    ui,
+                    ui.text_style_height(&egui::TextStyle::Body),
+                    listed,
+                    |ui, rows| {
+                        for candidate in &scan.candidates[rows] {
+                            let address = candidate.address;
+                            let location = match handle
+                                .as_ref()
+                                .and_then(|h| h.get_module_by_address(address))
+                            {
+                                Some(m) => format!(
+                                    "0x{address:X}  ({}+0x{:X})",
+                                    m.get_base_dll_name().unwrap_or("?"),
+                                    address - m.base_address
+                                ),
+                                None => format!("0x{address:X}"),
+                            };
+                            let mut current = [0u8; 8];
+                            let current = handle
+                                .as_ref()
+                                .and_then(|h| {
+                                    h.read_slice(address, &mut current[..scan.value_type.size()])
+                                        .ok()
+                                })
+                                .map(|_| scan.value_type.decode(&current).to_string())
+                                .unwrap_or_else(|| String::from("??"));
+                            ui.horizontal(|ui| {
+                                let resp = ui
+                                    .selectable_label(
+                                        false,
+                                        egui::RichText::new(format!(
+                                            "{location}  =  {current}  (was {})",
+                                            candidate.value
+                                        ))
+                                        .monospace(),
+                                    )
+                                    .on_hover_text("Double-click to use as root address");
+                                if resp.double_clicked() {
+                                    chosen = Some(address);
+                                }
+                                resp.context_menu(|ui| {
+                                    if ui.button("Use as root").clicked() {
+                                        chosen = Some(address);
+                                        ui.close_menu();
+                                    }
+                                    if ui.button("Copy address").clicked() {
+                                        let _ = arboard::Clipboard::new().and_then(|mut cb| {
+                                            cb.set_text(format!("0x{address:X}"))
+                                        });
+                                        ui.close_menu();
+                                    }
+                                });
+                            });
+                        }
+                    },
+                );
+            });
+        self.value_scan_window.open = open;
+
+        if let Some(address) = chosen {
+            self.navigate_to_address(address, format!("0x{address:X}"));
+        }
+    }
+}
@@ -0,0 +1,8 @@
+pub mod scanner;
+pub mod value;
+
+pub use scanner::*;
+pub use value::*;
+
+#[cfg(test)]
+mod tests;
@@ -0,0 +1,264 @@
+use std::sync::{
+    atomic::{
+        AtomicBool,
+        AtomicU64,
+        Ordering,
+    },
+    mpsc::{
+        self,
+        Receiver,
+    },
+    Arc,
+};
+
+use handle::AppHandle;
+
+use super::value::{
+    scan_buffer,
+    ScanCandidate,
+    ScanFilter,
+    ScanValue,
+    ScanValueType,
+};
+
+/// A first scan stops collecting once this many addresses matched
+pub const MAX_SCAN_CANDIDATES: usize = 100_000;
+
+const SCAN_CHUNK_SIZE: u64 = 0x10_0000;
+const SCAN_PAGE_SIZE: u64 = 0x1000;
+
+/// Search `[start, start + len)` ranges for aligned occurrences of `value`.
+/// `read` fills a buffer from process memory and returns false if the range is unreadable,
+/// which only skips that chunk. `progress` is advanced by the number of bytes visited.
+pub fn scan_ranges(
+    ranges: &[(u64, u64)],
+    value_type: ScanValueType,
+    value: &ScanValue,
+    limit: usize,
+    cancel: &AtomicBool,
+    progress: &AtomicU64,
+    mut read: impl FnMut(u64, &mut [u8]) -> bool,
+) -> Vec<ScanCandidate> {
+    let mut out = Vec::new();
+    let mut buffer = Vec::new();
+    for &(start, len) in ranges {
+        let mut offset = 0;
+        while offset < len && out.len() < limit {
+            if cancel.load(Ordering::Relaxed) {
+                break;
+            }
+            // The chunk size is a multiple of every value size, so aligned values never
+            // straddle two chunks
+            let chunk_len = SCAN_CHUNK_SIZE.min(len - offset);
+            buffer.resize(chunk_len as usize, 0);
+            if read(start + offset, &mut buffer) {
+                scan_buffer(&buffer, start + offset, value_type, value, &mut out, limit);
+            }
+            progress.fetch_add(chunk_len, Ordering::Relaxed);
+            offset += chunk_len;
+        }
+    }
+    out
+}
+
+/// Addresses in `ranges` holding a pointer-aligned pointer of `pointer_size` bytes to `target`
+pub fn find_references(
+    ranges: &[(u64, u64)],
+    target: u64,
+    pointer_size: u64,
+    limit: usize,
+    cancel: &AtomicBool,
+    progress: &AtomicU64,
+    read: impl FnMut(u64, &mut [u8]) -> bool,
+) -> Vec<u64> {
+    let value_type = if pointer_size == 4 {
+        ScanValueType::UInt32
+    } else {
+        ScanValueType::UInt64
+    };
+    scan_ranges(
+        ranges,
+        value_type,
+        &ScanValue::Unsigned(target),
+        limit,
+        cancel,
+        progress,
+        read,
+    )
+    .into_iter()
+    .map(|candidate| candidate.address)
+    .collect()
+}
+
+/// Re-read every candidate and keep those passing `filter`, storing their current value.
+/// Candidates are read one page at a time; unreadable pages drop their candidates.
+pub fn filter_candidates(
+    candidates: &[ScanCandidate],
+    value_type: ScanValueType,
+    filter: &ScanFilter,
+    cancel: &AtomicBool,
+    progress: &AtomicU64,
+    mut read: impl FnMut(u64, &mut [u8]) -> bool,
+) -> Vec<ScanCandidate> {
+    let size = value_type.size() as u64;
+    let mut out = Vec::new();
+    let mut buffer = Vec::new();
+    let mut index = 0;
+    while index < candidates.len() {
+        if cancel.load(Ordering::Relaxed) {
+            break;
+        }
+        let first = candidates[index].address;
+        let page = first / SCAN_PAGE_SIZE;
+        let group_len = candidates[index..]
+            .iter()
+            .take_while(|c| c.address / SCAN_PAGE_SIZE == page)
+            .count();
+        let group = &candidates[index..index + group_len];
+        let last = group[group_len - 1].address;
+        buffer.resize((last - first + size) as usize, 0);
+        if read(first, &mut buffer) {
+            for candidate in group {
+                let offset = (candidate.address - first) as usize;
+                let current = value_type.decode(&buffer[offset..]);
+                if filter.keeps(&candidate.value, &current) {
+                    out.push(ScanCandidate {
+                        address: candidate.address,
+                        value: current,
+                    });
+                }
+            }
+        }
+        progress.fetch_add(group_len as u64, Ordering::Relaxed);
+        index += group_len;
+    }
+    out
+}
+
+struct ScanJob {
+    progress: Arc<AtomicU64>,
+    total: u64,
+    cancel: Arc<AtomicBool>,
+    result: Receiver<Vec<ScanCandidate>>,
+}
+
+/// Candidate addresses of an ongoing value scan, narrowed down by successive scans
+#[derive(Default)]
+pub struct ValueScan {
+    pub value_type: ScanValueType,
+    pub candidates: Vec<ScanCandidate>,
+    /// Whether a first scan has completed, so next scans can filter its candidates
+    pub has_results: bool,
+    job: Option<ScanJob>,
+}
+
+impl ValueScan {
+    pub fn is_running(&self) -> bool {
+        self.job.is_some()
+    }
+
+    /// Fraction of the running scan that is done
+    pub fn progress(&self) -> Option<f32> {
+        let job = self.job.as_ref()?;
+        let done = job.progress.load(Ordering::Relaxed);
+        Some((done as f64 / job.total.max(1) as f64).min(1.0) as f32)
+    }
+
+    /// Scan every module of the attached process, or every range of a dump, for `value` on a
+    /// background thread
+    pub fn start_first_scan(
+        &mut self,
+        handle: Arc<AppHandle>,
+        value_type: ScanValueType,
+        value: ScanValue,
+    ) {
+        let ranges = handle.scan_regions();
+        let total = ranges.iter().map(|(_, len)| len).sum();
+        self.value_type = value_type;
+        self.spawn(total, move |cancel, progress| {
+            scan_ranges(
+                &ranges,
+                value_type,
+                &value,
+                MAX_SCAN_CANDIDATES,
+                cancel,
+                progress,
+                |address, buffer| handle.read_uncached(address, buffer).is_ok(),
+            )
+        });
+    }
+
+    /// Re-read the current candidates on a background thread and keep those passing `filter`
+    pub fn start_next_scan(&mut self, handle: Arc<AppHandle>, filter: ScanFilter) {
+        let candidates = self.candidates.clone();
+        let value_type = self.value_type;
+        self.spawn(candidates.len() as u64, move |cancel, progress| {
+            filter_candidates(
+                &candidates,
+                value_type,
+                &filter,
+                cancel,
+                progress,
+                |address, buffer| handle.read_uncached(address, buffer).is_ok(),
+            )
+        });
+    }
+
+    fn spawn(
+        &mut self,
+        total: u64,
+        work: impl FnOnce(&AtomicBool, &AtomicU64) -> Vec<ScanCandidate> + Send + 'static,
+    ) {
+        self.cancel();
+        let progress = Arc::new(AtomicU64::new(0));
+        let cancel = Arc::new(AtomicBool::new(false));
+        let (tx, rx) = mpsc::channel();
+        {
+            let progress = progress.clone();
+            let cancel = cancel.clone();
+            std::thread::spawn(move || {
+                let _ = tx.send(work(&cancel, &progress));
+            });
+        }
+        self.job = Some(ScanJob {
+            progress,
+            total,
+            cancel,
+            result: rx,
+        });
+    }
+
+    /// Pick up the result of a finished scan. Returns true once when the scan completes.
+    pub fn poll(&mut self) -> bool {
+        let Some(job) = &self.job else {
+            return false;
+        };
+        match job.result.try_recv() {
+            Ok(candidates) => {
+                self.candidates = candidates;
+                self.has_results = true;
+                self.job = None;
+                true
+            }
+            Err(mpsc::TryRecvError::Empty) => false,
+            Err(mpsc::TryRecvError::Disconnected) => {
+                self.job = None;
+                true
+            }
+        }
+    }
+
+    /// Stop the running scan, keeping the previous candidates
+    pub fn cancel(&mut self) {
+        if let Some(job) = self.job.take() {
+            job.cancel.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Forget all candidates so the next scan starts from scratch
+    pub fn reset(&mut self) {
+        self.cancel();
+        self.candidates.clear();
+        self.has_results = false;
+    }
+}
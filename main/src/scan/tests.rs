@@ -0,0 +1,231 @@
+use std::sync::atomic::{
+    AtomicBool,
+    AtomicU64,
+};
+
+use crate::scan::{
+    scanner::{
+        filter_candidates,
+        find_references,
+        scan_ranges,
+    },
+    value::{
+        scan_buffer,
+        ScanCandidate,
+        ScanFilter,
+        ScanValue,
+        ScanValueType,
+    },
+};
+
+/// Fake process memory: `memory` is mapped at `base`, everything else is unreadable
+fn reader(base: u64, memory: &[u8]) -> impl FnMut(u64, &mut [u8]) -> bool + '_ {
+    move |address, buffer| {
+        let Some(offset) = address.checked_sub(base) else {
+            return false;
+        };
+        let offset = offset as usize;
+        match memory.get(offset..offset + buffer.len()) {
+            Some(bytes) => {
+                buffer.copy_from_slice(bytes);
+                true
+            }
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod scan_value_tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_respects_type_range() {
+        assert_eq!(
+            ScanValueType::Int8.parse("-128"),
+            Some(ScanValue::Signed(-128))
+        );
+        assert_eq!(ScanValueType::Int8.parse("128"), None);
+        assert_eq!(
+            ScanValueType::UInt16.parse("0xFFFF"),
+            Some(ScanValue::Unsigned(0xFFFF))
+        );
+        assert_eq!(ScanValueType::UInt16.parse("0x10000"), None);
+        assert_eq!(ScanValueType::UInt32.parse("-1"), None);
+        assert_eq!(
+            ScanValueType::Float.parse(" 1.5 "),
+            Some(ScanValue::Float(1.5))
+        );
+        assert_eq!(ScanValueType::Double.parse("abc"), None);
+    }
+
+    #[test]
+    fn test_decode_sign_extends() {
+        assert_eq!(
+            ScanValueType::Int16.decode(&[0xFE, 0xFF]),
+            ScanValue::Signed(-2)
+        );
+        assert_eq!(
+            ScanValueType::UInt16.decode(&[0xFE, 0xFF]),
+            ScanValue::Unsigned(0xFFFE)
+        );
+        assert_eq!(
+            ScanValueType::Float.decode(&2.5f32.to_le_bytes()),
+            ScanValue::Float(2.5)
+        );
+    }
+
+    #[test]
+    fn test_filters() {
+        let old = ScanValue::Signed(10);
+        let up = ScanValue::Signed(11);
+        assert!(ScanFilter::Changed.keeps(&old, &up));
+        assert!(!ScanFilter::Unchanged.keeps(&old, &up));
+        assert!(ScanFilter::Increased.keeps(&old, &up));
+        assert!(!ScanFilter::Decreased.keeps(&old, &up));
+        assert!(ScanFilter::Exact(ScanValue::Signed(11)).keeps(&old, &up));
+        assert!(!ScanFilter::Exact(ScanValue::Signed(10)).keeps(&old, &up));
+    }
+
+    #[test]
+    fn test_float_compare_uses_tolerance() {
+        let target = ScanValue::Float(100.0);
+        assert!(ScanFilter::Exact(target).keeps(&target, &ScanValue::Float(100.0004)));
+        assert!(!ScanFilter::Exact(target).keeps(&target, &ScanValue::Float(100.1)));
+        assert!(
+            !ScanFilter::Unchanged.keeps(&ScanValue::Float(f64::NAN), &ScanValue::Float(f64::NAN))
+        );
+    }
+
+    #[test]
+    fn test_scan_buffer_is_aligned() {
+        let mut buffer = vec![0u8; 16];
+        buffer[2..6].copy_from_slice(&7i32.to_le_bytes());
+        buffer[8..12].copy_from_slice(&7i32.to_le_bytes());
+        let mut out = Vec::new();
+        scan_buffer(
+            &buffer,
+            0x1000,
+            ScanValueType::Int32,
+            &ScanValue::Signed(7),
+            &mut out,
+            10,
+        );
+        assert_eq!(
+            out,
+            vec![ScanCandidate {
+                address: 0x1008,
+                value: ScanValue::Signed(7),
+            }]
+        );
+    }
+}
+
+#[cfg(test)]
+mod scanner_tests {
+    use super::*;
+
+    #[test]
+    fn test_scan_ranges_skips_unreadable_and_caps() {
+        let mut memory = vec![0u8; 0x40];
+        for offset in [0x00, 0x10, 0x20, 0x30] {
+            memory[offset..offset + 4].copy_from_slice(&42u32.to_le_bytes());
+        }
+        let cancel = AtomicBool::new(false);
+        let progress = AtomicU64::new(0);
+        let ranges = [(0x5000, 0x40), (0x9000, 0x100)];
+        let found = scan_ranges(
+            &ranges,
+            ScanValueType::UInt32,
+            &ScanValue::Unsigned(42),
+            3,
+            &cancel,
+            &progress,
+            reader(0x5000, &memory),
+        );
+        let addresses: Vec<u64> = found.iter().map(|c| c.address).collect();
+        assert_eq!(addresses, vec![0x5000, 0x5010, 0x5020]);
+    }
+
+    #[test]
+    fn test_find_references_matches_pointer_width() {
+        let mut memory = vec![0u8; 0x20];
+        memory[0x08..0x10].copy_from_slice(&0x1234_5678u64.to_le_bytes());
+        // Unaligned copy is not a pointer slot
+        memory[0x11..0x19].copy_from_slice(&0x1234_5678u64.to_le_bytes());
+        let cancel = AtomicBool::new(false);
+        let progress = AtomicU64::new(0);
+        let found = find_references(
+            &[(0x7000, 0x20)],
+            0x1234_5678,
+            8,
+            10,
+            &cancel,
+            &progress,
+            reader(0x7000, &memory),
+        );
+        assert_eq!(found, vec![0x7008]);
+
+        // A 32-bit process is searched in 4-byte slots
+        let found = find_references(
+            &[(0x7000, 0x20)],
+            0x1234_5678,
+            4,
+            10,
+            &cancel,
+            &progress,
+            reader(0x7000, &memory),
+        );
+        assert_eq!(found, vec![0x7008]);
+    }
+
+    #[test]
+    fn test_filter_candidates_rereads_values() {
+        let mut memory = vec![0u8; 0x2000];
+        memory[0x10..0x14].copy_from_slice(&5i32.to_le_bytes());
+        memory[0x20..0x24].copy_from_slice(&9i32.to_le_bytes());
+        memory[0x1800..0x1804].copy_from_slice(&3i32.to_le_bytes());
+        let candidates = [
+            ScanCandidate {
+                address: 0x10010,
+                value: ScanValue::Signed(5),
+            },
+            ScanCandidate {
+                address: 0x10020,
+                value: ScanValue::Signed(5),
+            },
+            ScanCandidate {
+                address: 0x11800,
+                value: ScanValue::Signed(5),
+            },
+            ScanCandidate {
+                address: 0x50000,
+                value: ScanValue::Signed(5),
+            },
+        ];
+        let cancel = AtomicBool::new(false);
+        let progress = AtomicU64::new(0);
+        let kept = filter_candidates(
+            &candidates,
+            ScanValueType::Int32,
+            &ScanFilter::Changed,
+            &cancel,
+            &progress,
+            reader(0x10000, &memory),
+        );
+        assert_eq!(
+            kept,
+            vec![
+                ScanCandidate {
+                    address: 0x10020,
+                    value: ScanValue::Signed(9),
+                },
+                ScanCandidate {
+                    address: 0x11800,
+                    value: ScanValue::Signed(3),
+                },
+            ]
+        );
+        assert_eq!(progress.load(std::sync::atomic::Ordering::Relaxed), 4);
+    }
+}
@@ -0,0 +1,216 @@
+use std::{
+    cmp::Ordering,
+    fmt,
+};
+
+/// Floats closer than this to the searched value count as equal
+const FLOAT_TOLERANCE: f64 = 1e-3;
+
+/// Numeric types the value scanner can search for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ScanValueType {
+    Int8,
+    Int16,
+    #[default]
+    Int32,
+    Int64,
+    UInt8,
+    UInt16,
+    UInt32,
+    UInt64,
+    Float,
+    Double,
+}
+
+/// A decoded value, kept in the widest representation of its kind
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScanValue {
+    Signed(i64),
+    Unsigned(u64),
+    Float(f64),
+}
+
+/// How a follow-up scan decides which candidates to keep
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScanFilter {
+    Exact(ScanValue),
+    Changed,
+    Unchanged,
+    Increased,
+    Decreased,
+}
+
+impl ScanValueType {
+    pub const ALL: [ScanValueType; 10] = [
+        ScanValueType::Int8,
+        ScanValueType::Int16,
+        ScanValueType::Int32,
+        ScanValueType::Int64,
+        ScanValueType::UInt8,
+        ScanValueType::UInt16,
+        ScanValueType::UInt32,
+        ScanValueType::UInt64,
+        ScanValueType::Float,
+        ScanValueType::Double,
+    ];
+
+    pub fn size(&self) -> usize {
+        match self {
+            ScanValueType::Int8 | ScanValueType::UInt8 => 1,
+            ScanValueType::Int16 | ScanValueType::UInt16 => 2,
+            ScanValueType::Int32 | ScanValueType::UInt32 | ScanValueType::Float => 4,
+            ScanValueType::Int64 | ScanValueType::UInt64 | ScanValueType::Double => 8,
+        }
+    }
+
+    /// Parse user input; integers accept decimal or `0x` hex
+    pub fn parse(&self, input: &str) -> Option<ScanValue> {
+        let input = input.trim();
+        let hex = input
+            .strip_prefix("0x")
+            .or_else(|| input.strip_prefix("0X"));
+        let value = match self {
+            ScanValueType::Float | ScanValueType::Double => {
+                ScanValue::Float(input.parse::<f64>().ok()?)
+            }
+            ScanValueType::Int8
+            | ScanValueType::Int16
+            | ScanValueType::Int32
+            | ScanValueType::Int64 => {
+                let v = match hex {
+                    Some(h) => u64::from_str_radix(h, 16).ok()? as i64,
+                    None => input.parse::<i64>().ok()?,
+                };
+                ScanValue::Signed(v)
+            }
+            _ => ScanValue::Unsigned(match hex {
+                Some(h) => u64::from_str_radix(h, 16).ok()?,
+                None => input.parse::<u64>().ok()?,
+            }),
+        };
+        self.fits(value).then_some(value)
+    }
+
+    fn fits(&self, value: ScanValue) -> bool {
+        match (self, value) {
+            (ScanValueType::Int8, ScanValue::Signed(v)) => i8::try_from(v).is_ok(),
+            (ScanValueType::Int16, ScanValue::Signed(v)) => i16::try_from(v).is_ok(),
+            (ScanValueType::Int32, ScanValue::Signed(v)) => i32::try_from(v).is_ok(),
+            (ScanValueType::UInt8, ScanValue::Unsigned(v)) => u8::try_from(v).is_ok(),
+            (ScanValueType::UInt16, ScanValue::Unsigned(v)) => u16::try_from(v).is_ok(),
+            (ScanValueType::UInt32, ScanValue::Unsigned(v)) => u32::try_from(v).is_ok(),
+            _ => true,
+        }
+    }
+
+    /// Decode a little-endian value; `bytes` must hold at least `size()` bytes
+    pub fn decode(&self, bytes: &[u8]) -> ScanValue {
+        let mut raw = [0u8; 8];
+        raw[..self.size()].copy_from_slice(&bytes[..self.size()]);
+        let u = u64::from_le_bytes(raw);
+        match self {
+            ScanValueType::Int8 => ScanValue::Signed(u as u8 as i8 as i64),
+            ScanValueType::Int16 => ScanValue::Signed(u as u16 as i16 as i64),
+            ScanValueType::Int32 => ScanValue::Signed(u as u32 as i32 as i64),
+            ScanValueType::Int64 => ScanValue::Signed(u as i64),
+            ScanValueType::UInt8
+            | ScanValueType::UInt16
+            | ScanValueType::UInt32
+            | ScanValueType::UInt64 => ScanValue::Unsigned(u),
+            ScanValueType::Float => ScanValue::Float(f32::from_bits(u as u32) as f64),
+            ScanValueType::Double => ScanValue::Float(f64::from_bits(u)),
+        }
+    }
+}
+
+impl fmt::Display for ScanValueType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ScanValueType::Int8 => "Int8",
+            ScanValueType::Int16 => "Int16",
+            ScanValueType::Int32 => "Int32",
+            ScanValueType::Int64 => "Int64",
+            ScanValueType::UInt8 => "UInt8",
+            ScanValueType::UInt16 => "UInt16",
+            ScanValueType::UInt32 => "UInt32",
+            ScanValueType::UInt64 => "UInt64",
+            ScanValueType::Float => "Float",
+            ScanValueType::Double => "Double",
+        };
+        write!(f, "{name}")
+    }
+}
+
+impl ScanValue {
+    /// Compare two values of the same kind; floats within tolerance are equal and NaN never
+    /// compares
+    pub fn compare(&self, other: &ScanValue) -> Option<Ordering> {
+        match (self, other) {
+            (ScanValue::Signed(a), ScanValue::Signed(b)) => Some(a.cmp(b)),
+            (ScanValue::Unsigned(a), ScanValue::Unsigned(b)) => Some(a.cmp(b)),
+            (ScanValue::Float(a), ScanValue::Float(b)) => {
+                if (a - b).abs() <= FLOAT_TOLERANCE {
+                    Some(Ordering::Equal)
+                } else {
+                    a.partial_cmp(b)
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ScanValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ScanValue::Signed(v) => write!(f, "{v}"),
+            ScanValue::Unsigned(v) => write!(f, "{v}"),
+            ScanValue::Float(v) => write!(f, "{v}"),
+        }
+    }
+}
+
+impl ScanFilter {
+    /// Whether a candidate that held `previous` and now holds `current` survives the filter
+    pub fn keeps(&self, previous: &ScanValue, current: &ScanValue) -> bool {
+        let vs_previous = current.compare(previous);
+        match self {
+            ScanFilter::Exact(value) => current.compare(value) == Some(Ordering::Equal),
+            ScanFilter::Changed => vs_previous != Some(Ordering::Equal),
+            ScanFilter::Unchanged => vs_previous == Some(Ordering::Equal),
+            ScanFilter::Increased => vs_previous == Some(Ordering::Greater),
+            ScanFilter::Decreased => vs_previous == Some(Ordering::Less),
+        }
+    }
+}
+
+/// An address that matched so far, with the value it held at the last scan
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ScanCandidate {
+    pub address: u64,
+    pub value: ScanValue,
+}
+
+/// Collect aligned matches of `value` in `buffer`, which was read from `base`.
+/// Stops once `out` holds `limit` candidates.
+pub fn scan_buffer(
+    buffer: &[u8],
+    base: u64,
+    value_type: ScanValueType,
+    value: &ScanValue,
+    out: &mut Vec<ScanCandidate>,
+    limit: usize,
+) {
+    let size = value_type.size();
+    let mut offset = ((size as u64 - base % size as u64) % size as u64) as usize;
+    while offset + size <= buffer.len() && out.len() < limit {
+        let current = value_type.decode(&buffer[offset..]);
+        if current.compare(value) == Some(Ordering::Equal) {
+            out.push(ScanCandidate {
+                address: base + offset as u64,
+                value: current,
+            });
+        }
+        offset += size;
+    }
+}